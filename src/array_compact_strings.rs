@@ -0,0 +1,387 @@
+use core::{fmt::Debug, ops::Index};
+
+use crate::{metadata::Metadata, utf8, CapacityError};
+
+/// A list of strings with a fixed capacity, stored in the same packed layout as
+/// [`CompactStrings`](crate::CompactStrings) but in inline arrays instead of vectors.
+///
+/// The bytes of the strings share a single array of `DATA` bytes, and each of the at most `N`
+/// strings takes up one starting index and one length. An [`ArrayCompactStrings`] never
+/// allocates, and can be constructed in a `const` context to live in static memory. It is also
+/// the one type that remains available when the default `alloc` feature is disabled, for targets
+/// without an allocator.
+///
+/// # Examples
+/// ```
+/// # use compact_strings::ArrayCompactStrings;
+/// let mut cmpstrs = ArrayCompactStrings::<16, 4>::new();
+/// cmpstrs.push("One").unwrap();
+/// cmpstrs.push("Two").unwrap();
+///
+/// assert_eq!(cmpstrs.get(1), Some("Two"));
+/// assert_eq!(cmpstrs.data_len(), 6);
+/// assert!(cmpstrs.iter().eq(["One", "Two"]));
+/// ```
+#[derive(Clone)]
+pub struct ArrayCompactStrings<const DATA: usize, const N: usize> {
+    data: [u8; DATA],
+    data_len: usize,
+    meta: [Metadata; N],
+    len: usize,
+}
+
+impl<const DATA: usize, const N: usize> ArrayCompactStrings<DATA, N> {
+    /// Constructs a new, empty [`ArrayCompactStrings`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::ArrayCompactStrings;
+    /// static EMPTY: ArrayCompactStrings<64, 8> = ArrayCompactStrings::new();
+    ///
+    /// assert!(EMPTY.is_empty());
+    /// ```
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            data: [0; DATA],
+            data_len: 0,
+            meta: [Metadata::new(0, 0); N],
+            len: 0,
+        }
+    }
+
+    /// Appends a string to the back of the [`ArrayCompactStrings`].
+    ///
+    /// # Errors
+    /// Returns [`CapacityError::Full`] if all `N` strings are taken, or
+    /// [`CapacityError::DataFull`] if the bytes of `string` do not fit in the rest of the data
+    /// array, leaving the [`ArrayCompactStrings`] unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{ArrayCompactStrings, CapacityError};
+    /// let mut cmpstrs = ArrayCompactStrings::<6, 4>::new();
+    ///
+    /// assert_eq!(cmpstrs.push("One"), Ok(()));
+    /// assert_eq!(cmpstrs.push("Three"), Err(CapacityError::DataFull));
+    /// assert_eq!(cmpstrs.push("Two"), Ok(()));
+    /// ```
+    pub fn push(&mut self, string: &str) -> Result<(), CapacityError> {
+        if self.len == N {
+            return Err(CapacityError::Full);
+        }
+
+        let start = self.data_len;
+        let end = start + string.len();
+        let bytes = self
+            .data
+            .get_mut(start..end)
+            .ok_or(CapacityError::DataFull)?;
+        bytes.copy_from_slice(string.as_bytes());
+
+        self.meta[self.len] = Metadata::new(start, string.len());
+        self.data_len = end;
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Removes the last string, returning true if there was one.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::ArrayCompactStrings;
+    /// let mut cmpstrs = ArrayCompactStrings::<16, 4>::new();
+    /// cmpstrs.push("One").unwrap();
+    ///
+    /// assert!(cmpstrs.pop());
+    /// assert!(!cmpstrs.pop());
+    /// assert_eq!(cmpstrs.data_len(), 0);
+    /// ```
+    pub fn pop(&mut self) -> bool {
+        if self.len == 0 {
+            return false;
+        }
+
+        self.len -= 1;
+        self.data_len = self.meta[self.len].start;
+        true
+    }
+
+    /// Returns a reference to the string stored in the [`ArrayCompactStrings`] at that position.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::ArrayCompactStrings;
+    /// let mut cmpstrs = ArrayCompactStrings::<16, 4>::new();
+    /// cmpstrs.push("One").unwrap();
+    ///
+    /// assert_eq!(cmpstrs.get(0), Some("One"));
+    /// assert_eq!(cmpstrs.get(1), None);
+    /// ```
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&str> {
+        let (start, len) = self.meta[..self.len].get(index)?.as_tuple();
+        utf8::from_utf8_maybe_checked(&self.data[start..start + len])
+    }
+
+    /// Returns the number of strings in the [`ArrayCompactStrings`], also referred to as its 'length'.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::ArrayCompactStrings;
+    /// let mut cmpstrs = ArrayCompactStrings::<16, 4>::new();
+    /// cmpstrs.push("One").unwrap();
+    ///
+    /// assert_eq!(cmpstrs.len(), 1);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the [`ArrayCompactStrings`] contains no strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::ArrayCompactStrings;
+    /// let cmpstrs = ArrayCompactStrings::<16, 4>::new();
+    ///
+    /// assert!(cmpstrs.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns true if all `N` strings are taken.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::ArrayCompactStrings;
+    /// let mut cmpstrs = ArrayCompactStrings::<16, 1>::new();
+    /// cmpstrs.push("One").unwrap();
+    ///
+    /// assert!(cmpstrs.is_full());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Returns the maximum number of strings, `N`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::ArrayCompactStrings;
+    /// let cmpstrs = ArrayCompactStrings::<16, 4>::new();
+    ///
+    /// assert_eq!(cmpstrs.capacity(), 4);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of bytes taken up by the strings in the data array.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::ArrayCompactStrings;
+    /// let mut cmpstrs = ArrayCompactStrings::<16, 4>::new();
+    /// cmpstrs.push("One").unwrap();
+    ///
+    /// assert_eq!(cmpstrs.data_len(), 3);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn data_len(&self) -> usize {
+        self.data_len
+    }
+
+    /// Returns the size of the data array, `DATA`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::ArrayCompactStrings;
+    /// let cmpstrs = ArrayCompactStrings::<16, 4>::new();
+    ///
+    /// assert_eq!(cmpstrs.data_capacity(), 16);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn data_capacity(&self) -> usize {
+        DATA
+    }
+
+    /// Clears the [`ArrayCompactStrings`], removing all strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::ArrayCompactStrings;
+    /// let mut cmpstrs = ArrayCompactStrings::<16, 4>::new();
+    /// cmpstrs.push("One").unwrap();
+    /// cmpstrs.clear();
+    ///
+    /// assert!(cmpstrs.is_empty());
+    /// assert_eq!(cmpstrs.data_len(), 0);
+    /// ```
+    #[inline]
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.data_len = 0;
+    }
+
+    /// Returns an iterator over the strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::ArrayCompactStrings;
+    /// let mut cmpstrs = ArrayCompactStrings::<16, 4>::new();
+    /// cmpstrs.push("One").unwrap();
+    /// cmpstrs.push("Two").unwrap();
+    ///
+    /// assert!(cmpstrs.iter().eq(["One", "Two"]));
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            data: &self.data[..self.data_len],
+            meta: self.meta[..self.len].iter(),
+        }
+    }
+}
+
+impl<const DATA: usize, const N: usize> Default for ArrayCompactStrings<DATA, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const DATA: usize, const N: usize> PartialEq for ArrayCompactStrings<DATA, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<const DATA: usize, const N: usize> Debug for ArrayCompactStrings<DATA, N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<const DATA: usize, const N: usize> Index<usize> for ArrayCompactStrings<DATA, N> {
+    type Output = str;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+
+impl<'a, const DATA: usize, const N: usize> IntoIterator for &'a ArrayCompactStrings<DATA, N> {
+    type Item = &'a str;
+
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over the strings of an [`ArrayCompactStrings`].
+///
+/// This does not reuse the iterator of [`CompactStrings`](crate::CompactStrings), so that it is
+/// available without the `alloc` feature.
+///
+/// # Examples
+/// ```
+/// # use compact_strings::ArrayCompactStrings;
+/// let mut cmpstrs = ArrayCompactStrings::<16, 4>::new();
+/// cmpstrs.push("One").unwrap();
+/// cmpstrs.push("Two").unwrap();
+///
+/// let mut iter = cmpstrs.iter();
+/// assert_eq!(iter.len(), 2);
+/// assert_eq!(iter.next_back(), Some("Two"));
+/// assert_eq!(iter.next(), Some("One"));
+/// assert_eq!(iter.next(), None);
+/// ```
+#[must_use = "Iterators are lazy and do nothing unless consumed"]
+pub struct Iter<'a> {
+    data: &'a [u8],
+    meta: core::slice::Iter<'a, Metadata>,
+}
+
+impl<'a> Iter<'a> {
+    #[inline]
+    fn str(&self, meta: &Metadata) -> Option<&'a str> {
+        let (start, len) = meta.as_tuple();
+        utf8::from_utf8_maybe_checked(&self.data[start..start + len])
+    }
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a str;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let meta = self.meta.next()?;
+        self.str(meta)
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let meta = self.meta.nth(n)?;
+        self.str(meta)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.meta.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Iter<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let meta = self.meta.next_back()?;
+        self.str(meta)
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.meta.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ArrayCompactStrings, CapacityError};
+
+    #[test]
+    fn fills_up_and_pops() {
+        let mut cmpstrs = ArrayCompactStrings::<8, 3>::new();
+        assert_eq!(cmpstrs.push("ab"), Ok(()));
+        assert_eq!(cmpstrs.push(""), Ok(()));
+        assert_eq!(cmpstrs.push("cdefgh"), Ok(()));
+        assert_eq!(cmpstrs.data_len(), 8);
+        assert_eq!(cmpstrs.push(""), Err(CapacityError::Full));
+
+        assert!(cmpstrs.pop());
+        assert_eq!(cmpstrs.push("cdefghi"), Err(CapacityError::DataFull));
+        assert_eq!(cmpstrs.push("é"), Ok(()));
+        assert!(cmpstrs.iter().rev().eq(["é", "", "ab"]));
+
+        let copy = cmpstrs.clone();
+        cmpstrs.clear();
+        assert!(cmpstrs.is_empty());
+        assert_eq!(copy[2], *"é");
+    }
+}
@@ -0,0 +1,334 @@
+use alloc::{format, sync::Arc, vec, vec::Vec};
+use std::io::Cursor;
+
+use arrow_array::{
+    cast::AsArray, Array, ArrayRef, GenericBinaryArray, LargeBinaryArray, LargeStringArray,
+    OffsetSizeTrait, RecordBatch,
+};
+use arrow_buffer::{Buffer, OffsetBuffer, ScalarBuffer};
+use arrow_ipc::{reader::FileReader, writer::FileWriter};
+use arrow_schema::{ArrowError, DataType, Field, Schema};
+
+use crate::{metadata::Metadata, CompactBytestrings, CompactStrings};
+
+impl CompactBytestrings {
+    /// Returns the Arrow offsets and values of the bytestrings.
+    ///
+    /// The data vector is copied as is when the bytestrings lie back to back in it, which holds
+    /// unless [`swap_ignore`](CompactBytestrings::swap_ignore) has left unused bytes behind.
+    #[allow(clippy::cast_possible_wrap)]
+    fn arrow_buffers(&self) -> (OffsetBuffer<i64>, Buffer) {
+        let mut offsets = Vec::with_capacity(self.len() + 1);
+        let mut end = 0;
+        let mut contiguous = true;
+        offsets.push(0);
+        for meta in &self.meta {
+            contiguous &= meta.start == end;
+            end += meta.len;
+            // A vector never holds more than `isize::MAX` bytes.
+            offsets.push(end as i64);
+        }
+
+        let values = if contiguous && end == self.data.len() {
+            Buffer::from_slice_ref(&self.data)
+        } else {
+            let mut values = Vec::with_capacity(end);
+            for bytestring in self {
+                values.extend_from_slice(bytestring);
+            }
+            Buffer::from_vec(values)
+        };
+
+        (OffsetBuffer::new(ScalarBuffer::from(offsets)), values)
+    }
+
+    /// Appends every element of an Arrow binary array, copying its values in one go and turning
+    /// its offsets into starting indices.
+    fn extend_from_arrow<O: OffsetSizeTrait>(
+        &mut self,
+        array: &GenericBinaryArray<O>,
+    ) -> Result<(), ArrowError> {
+        if array.null_count() != 0 {
+            return Err(ArrowError::InvalidArgumentError(
+                "array contains null values".into(),
+            ));
+        }
+
+        let offsets = array.value_offsets();
+        let first = offsets[0].as_usize();
+        let last = offsets[offsets.len() - 1].as_usize();
+        let base = self.data.len();
+
+        self.data
+            .extend_from_slice(&array.value_data()[first..last]);
+        self.meta.extend(offsets.windows(2).map(|w| {
+            let start = w[0].as_usize();
+            Metadata::new(base + start - first, w[1].as_usize() - start)
+        }));
+
+        Ok(())
+    }
+
+    /// Copies the bytestrings into an Arrow `LargeBinary` array, whose offsets are the starting
+    /// indices of the bytestrings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([b"One", b"Two"]);
+    ///
+    /// let array = cmpbytes.to_arrow_array();
+    /// assert_eq!(array.value_offsets(), [0, 3, 6]);
+    /// assert_eq!(array.value(1), b"Two");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "arrow")))]
+    #[must_use]
+    pub fn to_arrow_array(&self) -> LargeBinaryArray {
+        let (offsets, values) = self.arrow_buffers();
+        LargeBinaryArray::new(offsets, values, None)
+    }
+
+    /// Copies the elements of an Arrow `Binary` or `LargeBinary` array into a new
+    /// [`CompactBytestrings`].
+    ///
+    /// # Errors
+    /// Returns [`ArrowError::InvalidArgumentError`] if the array contains nulls.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// use arrow_array::BinaryArray;
+    ///
+    /// let array = BinaryArray::from_vec(vec![b"One", b"Two"]);
+    ///
+    /// let cmpbytes = CompactBytestrings::from_arrow_array(&array).unwrap();
+    /// assert!(cmpbytes.iter().eq([b"One", b"Two"]));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "arrow")))]
+    pub fn from_arrow_array<O: OffsetSizeTrait>(
+        array: &GenericBinaryArray<O>,
+    ) -> Result<Self, ArrowError> {
+        let mut cmpbytes = Self::new();
+        cmpbytes.extend_from_arrow(array)?;
+        Ok(cmpbytes)
+    }
+
+    /// Writes the bytestrings as the only column of an Arrow IPC file, which tools such as
+    /// `DuckDB` and pandas can read.
+    ///
+    /// # Errors
+    /// Returns an error if writing the file fails.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([b"One", b"Two"]);
+    ///
+    /// let file = cmpbytes.to_arrow_ipc("values").unwrap();
+    /// assert!(file.starts_with(b"ARROW1"));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "arrow")))]
+    pub fn to_arrow_ipc(&self, column: &str) -> Result<Vec<u8>, ArrowError> {
+        write_ipc(column, Arc::new(self.to_arrow_array()))
+    }
+
+    /// Reads the first column of an Arrow IPC file, such as one written by
+    /// [`to_arrow_ipc`](CompactBytestrings::to_arrow_ipc), appending the values of every record
+    /// batch in turn.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read, if the column is not of a binary or string
+    /// type, or if it contains nulls.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let file = CompactBytestrings::from([b"One", b"Two"]).to_arrow_ipc("values").unwrap();
+    ///
+    /// let cmpbytes = CompactBytestrings::from_arrow_ipc(&file).unwrap();
+    /// assert!(cmpbytes.iter().eq([b"One", b"Two"]));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "arrow")))]
+    pub fn from_arrow_ipc(buf: &[u8]) -> Result<Self, ArrowError> {
+        let mut cmpbytes = Self::new();
+        for column in read_ipc(buf)? {
+            match column.data_type() {
+                DataType::Binary => cmpbytes.extend_from_arrow(column.as_binary::<i32>())?,
+                DataType::LargeBinary => cmpbytes.extend_from_arrow(column.as_binary::<i64>())?,
+                DataType::Utf8 => cmpbytes.extend_from_arrow(&GenericBinaryArray::from(
+                    column.as_string::<i32>().clone(),
+                ))?,
+                DataType::LargeUtf8 => cmpbytes.extend_from_arrow(&GenericBinaryArray::from(
+                    column.as_string::<i64>().clone(),
+                ))?,
+                data_type => return Err(unexpected_type("binary", data_type)),
+            }
+        }
+
+        Ok(cmpbytes)
+    }
+}
+
+impl CompactStrings {
+    /// Copies the strings into an Arrow `LargeUtf8` array, whose offsets are the starting indices
+    /// of the strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let cmpstrs = CompactStrings::from(["One", "Two"]);
+    ///
+    /// let array = cmpstrs.to_arrow_array();
+    /// assert_eq!(array.value_offsets(), [0, 3, 6]);
+    /// assert_eq!(array.value(1), "Two");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "arrow")))]
+    #[must_use]
+    pub fn to_arrow_array(&self) -> LargeStringArray {
+        let (offsets, values) = self.0.arrow_buffers();
+        if cfg!(feature = "no_unsafe") {
+            LargeStringArray::new(offsets, values, None)
+        } else {
+            unsafe { LargeStringArray::new_unchecked(offsets, values, None) }
+        }
+    }
+
+    /// Copies the elements of an Arrow `Utf8` or `LargeUtf8` array into a new [`CompactStrings`].
+    ///
+    /// # Errors
+    /// Returns [`ArrowError::InvalidArgumentError`] if the array contains nulls.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// use arrow_array::StringArray;
+    ///
+    /// let array = StringArray::from(vec!["One", "Two"]);
+    ///
+    /// let cmpstrs = CompactStrings::from_arrow_array(&array).unwrap();
+    /// assert!(cmpstrs.iter().eq(["One", "Two"]));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "arrow")))]
+    pub fn from_arrow_array<O: OffsetSizeTrait>(
+        array: &arrow_array::GenericStringArray<O>,
+    ) -> Result<Self, ArrowError> {
+        // Arrow has already checked that every string is valid UTF-8.
+        CompactBytestrings::from_arrow_array(&GenericBinaryArray::from(array.clone()))
+            .map(CompactStrings)
+    }
+
+    /// Writes the strings as the only column of an Arrow IPC file, as described in
+    /// [`CompactBytestrings::to_arrow_ipc`].
+    ///
+    /// # Errors
+    /// Returns an error if writing the file fails.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let cmpstrs = CompactStrings::from(["One", "Two"]);
+    ///
+    /// let file = cmpstrs.to_arrow_ipc("values").unwrap();
+    /// assert!(file.starts_with(b"ARROW1"));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "arrow")))]
+    pub fn to_arrow_ipc(&self, column: &str) -> Result<Vec<u8>, ArrowError> {
+        write_ipc(column, Arc::new(self.to_arrow_array()))
+    }
+
+    /// Reads the first column of an Arrow IPC file, such as one written by
+    /// [`to_arrow_ipc`](CompactStrings::to_arrow_ipc), appending the values of every record batch
+    /// in turn.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read, if the column is not of a string type, or if
+    /// it contains nulls.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let file = CompactStrings::from(["One", "Two"]).to_arrow_ipc("values").unwrap();
+    ///
+    /// let cmpstrs = CompactStrings::from_arrow_ipc(&file).unwrap();
+    /// assert!(cmpstrs.iter().eq(["One", "Two"]));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "arrow")))]
+    pub fn from_arrow_ipc(buf: &[u8]) -> Result<Self, ArrowError> {
+        let mut cmpbytes = CompactBytestrings::new();
+        for column in read_ipc(buf)? {
+            match column.data_type() {
+                DataType::Utf8 => cmpbytes.extend_from_arrow(&GenericBinaryArray::from(
+                    column.as_string::<i32>().clone(),
+                ))?,
+                DataType::LargeUtf8 => cmpbytes.extend_from_arrow(&GenericBinaryArray::from(
+                    column.as_string::<i64>().clone(),
+                ))?,
+                data_type => return Err(unexpected_type("string", data_type)),
+            }
+        }
+
+        Ok(CompactStrings(cmpbytes))
+    }
+}
+
+fn unexpected_type(expected: &str, found: &DataType) -> ArrowError {
+    ArrowError::SchemaError(format!("expected a {expected} column, found {found}"))
+}
+
+/// Writes `array` as the only column, named `column`, of an Arrow IPC file.
+fn write_ipc(column: &str, array: ArrayRef) -> Result<Vec<u8>, ArrowError> {
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        column,
+        array.data_type().clone(),
+        false,
+    )]));
+    let batch = RecordBatch::try_new(schema.clone(), vec![array])?;
+
+    let mut writer = FileWriter::try_new(Vec::new(), &schema)?;
+    writer.write(&batch)?;
+    writer.into_inner()
+}
+
+/// Reads the first column of every record batch of an Arrow IPC file.
+fn read_ipc(buf: &[u8]) -> Result<Vec<ArrayRef>, ArrowError> {
+    FileReader::try_new(Cursor::new(buf), Some(vec![0]))?
+        .map(|batch| Ok(batch?.column(0).clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use arrow_array::{LargeStringArray, StringArray};
+
+    use crate::{CompactBytestrings, CompactStrings};
+
+    #[test]
+    fn arrow_round_trip_after_swap_ignore() {
+        let mut cmpbytes = CompactBytestrings::from([b"One", b"Two", b"Six"]);
+        cmpbytes.swap_ignore(0);
+
+        let array = cmpbytes.to_arrow_array();
+        assert_eq!(array.value_offsets(), [0, 3, 6]);
+        assert_eq!(array.value_data(), b"SixTwo");
+
+        let file = cmpbytes.to_arrow_ipc("values").unwrap();
+        assert_eq!(CompactBytestrings::from_arrow_ipc(&file).unwrap(), cmpbytes);
+        assert!(CompactStrings::from_arrow_ipc(&file).is_err());
+    }
+
+    #[test]
+    fn from_sliced_and_null_arrays() {
+        let array = StringArray::from(vec!["One", "Two", "Three", "Four"]).slice(1, 2);
+        let cmpstrs = CompactStrings::from_arrow_array(&array).unwrap();
+        assert!(cmpstrs.iter().eq(["Two", "Three"]));
+        assert_eq!(
+            cmpstrs.to_arrow_array(),
+            LargeStringArray::from(vec!["Two", "Three"])
+        );
+
+        let array = StringArray::from(vec![Some("One"), None]);
+        assert!(CompactStrings::from_arrow_array(&array).is_err());
+    }
+}
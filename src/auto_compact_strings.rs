@@ -0,0 +1,655 @@
+use core::{
+    fmt::Debug,
+    ops::{Deref, Index},
+};
+
+use alloc::{vec, vec::Vec};
+
+use crate::{compact_strings, CompactStrings};
+
+/// The fraction of garbage used by [`AutoCompactStrings::new`].
+const DEFAULT_MAX_GARBAGE: f64 = 0.5;
+
+/// A [`CompactStrings`] whose removals only mark strings as removed, compacting once the removed
+/// strings exceed a fraction of it.
+///
+/// [`remove`] takes *O*(1) time: it leaves a tombstone in the slot of the removed string and its
+/// bytes behind as garbage, so neither the metadata nor the bytes after it are shifted over.
+/// Removed strings are skipped by [`get`] and [`iter`], and the indices of the other strings stay
+/// the same. Once the bytes or the slots of removed strings exceed the configured fraction of the
+/// collection, [`compact`] is run, which drops the tombstones and the garbage and so shifts the
+/// remaining strings down to fill the gaps. This amortizes the cost of compacting over the
+/// removals that caused it.
+///
+/// [`remove`]: AutoCompactStrings::remove
+/// [`get`]: AutoCompactStrings::get
+/// [`iter`]: AutoCompactStrings::iter
+/// [`compact`]: AutoCompactStrings::compact
+///
+/// # Examples
+/// ```
+/// # use compact_strings::AutoCompactStrings;
+/// let mut cmpstrs = AutoCompactStrings::with_max_garbage(0.5);
+/// cmpstrs.extend(["One", "Two", "Six", "Ten"]);
+///
+/// cmpstrs.remove(0);
+/// cmpstrs.remove(1);
+/// assert_eq!(cmpstrs.garbage_len(), 6);
+/// assert_eq!(cmpstrs.get(1), None);
+/// assert_eq!(cmpstrs.get(3), Some("Ten"));
+///
+/// cmpstrs.remove(2);
+/// assert_eq!(cmpstrs.garbage_len(), 0);
+/// assert_eq!(cmpstrs.get(0), Some("Ten"));
+/// ```
+#[derive(Clone)]
+pub struct AutoCompactStrings {
+    strings: CompactStrings,
+    removed: Vec<bool>,
+    removed_count: usize,
+    garbage: usize,
+    max_garbage: f64,
+}
+
+impl AutoCompactStrings {
+    /// Constructs a new, empty [`AutoCompactStrings`] that compacts once more than half of the
+    /// data vector or of the slots belong to removed strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::AutoCompactStrings;
+    /// let cmpstrs = AutoCompactStrings::new();
+    ///
+    /// assert!(cmpstrs.is_empty());
+    /// assert_eq!(cmpstrs.max_garbage(), 0.5);
+    /// ```
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            strings: CompactStrings::new(),
+            removed: Vec::new(),
+            removed_count: 0,
+            garbage: 0,
+            max_garbage: DEFAULT_MAX_GARBAGE,
+        }
+    }
+
+    /// Constructs a new, empty [`AutoCompactStrings`] that compacts once more than `max_garbage`
+    /// of the data vector or of the slots belong to removed strings.
+    ///
+    /// # Panics
+    /// Panics if `max_garbage` is not between `0.0` and `1.0`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::AutoCompactStrings;
+    /// let mut cmpstrs = AutoCompactStrings::with_max_garbage(0.0);
+    /// cmpstrs.extend(["One", "Two"]);
+    ///
+    /// cmpstrs.remove(0);
+    /// assert_eq!(cmpstrs.garbage_len(), 0);
+    /// ```
+    #[track_caller]
+    #[must_use]
+    pub fn with_max_garbage(max_garbage: f64) -> Self {
+        let mut cmpstrs = Self::new();
+        cmpstrs.set_max_garbage(max_garbage);
+        cmpstrs
+    }
+
+    /// Returns the fraction of the data vector, and of the slots, that may belong to removed
+    /// strings before the [`AutoCompactStrings`] is compacted.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::AutoCompactStrings;
+    /// let cmpstrs = AutoCompactStrings::with_max_garbage(0.25);
+    ///
+    /// assert_eq!(cmpstrs.max_garbage(), 0.25);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn max_garbage(&self) -> f64 {
+        self.max_garbage
+    }
+
+    /// Sets the fraction of the data vector, and of the slots, that may belong to removed strings
+    /// before the [`AutoCompactStrings`] is compacted, compacting right away if there are already
+    /// more than that.
+    ///
+    /// # Panics
+    /// Panics if `max_garbage` is not between `0.0` and `1.0`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::AutoCompactStrings;
+    /// let mut cmpstrs = AutoCompactStrings::new();
+    /// cmpstrs.extend(["One", "Two"]);
+    ///
+    /// cmpstrs.remove(0);
+    /// assert_eq!(cmpstrs.garbage_len(), 3);
+    ///
+    /// cmpstrs.set_max_garbage(0.1);
+    /// assert_eq!(cmpstrs.garbage_len(), 0);
+    /// ```
+    #[track_caller]
+    pub fn set_max_garbage(&mut self, max_garbage: f64) {
+        #[cold]
+        #[inline(never)]
+        #[track_caller]
+        fn assert_failed(max_garbage: f64) -> ! {
+            panic!("max garbage (is {max_garbage}) should be between 0 and 1");
+        }
+
+        if !(0.0..=1.0).contains(&max_garbage) {
+            assert_failed(max_garbage);
+        }
+
+        self.max_garbage = max_garbage;
+        self.compact_if_needed();
+    }
+
+    /// Appends a string to the back of the [`AutoCompactStrings`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::AutoCompactStrings;
+    /// let mut cmpstrs = AutoCompactStrings::new();
+    /// cmpstrs.push("One");
+    ///
+    /// assert_eq!(cmpstrs.get(0), Some("One"));
+    /// ```
+    #[inline]
+    pub fn push<S>(&mut self, string: S)
+    where
+        S: Deref<Target = str>,
+    {
+        self.strings.push(string);
+        self.removed.push(false);
+    }
+
+    /// Removes the string at the specified index in *O*(1) time, leaving a tombstone in its slot
+    /// and its bytes behind as garbage.
+    ///
+    /// The indices of the other strings stay the same, unless this removal makes the
+    /// [`AutoCompactStrings`] compact itself.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds or the string at `index` was already removed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::AutoCompactStrings;
+    /// let mut cmpstrs = AutoCompactStrings::new();
+    /// cmpstrs.extend(["One", "Two", "Three"]);
+    ///
+    /// cmpstrs.remove(1);
+    ///
+    /// assert!(cmpstrs.iter().eq(["One", "Three"]));
+    /// assert_eq!(cmpstrs.get(1), None);
+    /// assert_eq!(cmpstrs.get(2), Some("Three"));
+    /// assert_eq!(cmpstrs.garbage_len(), 3);
+    /// ```
+    #[track_caller]
+    pub fn remove(&mut self, index: usize) {
+        let len = self.take(index);
+        self.removed[index] = true;
+        self.removed_count += 1;
+        self.garbage += len;
+        self.compact_if_needed();
+    }
+
+    /// Removes the string at the specified index in *O*(1) time, moving the last slot into its
+    /// place and leaving its bytes behind as garbage.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds or the string at `index` was already removed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::AutoCompactStrings;
+    /// let mut cmpstrs = AutoCompactStrings::new();
+    /// cmpstrs.extend(["One", "Two", "Three"]);
+    ///
+    /// cmpstrs.swap_remove(0);
+    ///
+    /// assert!(cmpstrs.iter().eq(["Three", "Two"]));
+    /// assert_eq!(cmpstrs.garbage_len(), 3);
+    /// ```
+    #[track_caller]
+    pub fn swap_remove(&mut self, index: usize) {
+        let len = self.take(index);
+        self.strings.swap_ignore(index);
+        self.removed.swap_remove(index);
+        self.garbage += len;
+        self.compact_if_needed();
+    }
+
+    /// Drops the tombstones and the garbage, shifting the remaining strings down to fill the
+    /// slots of removed strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::AutoCompactStrings;
+    /// let mut cmpstrs = AutoCompactStrings::new();
+    /// cmpstrs.extend(["One", "Two", "Three"]);
+    ///
+    /// cmpstrs.remove(0);
+    /// assert_eq!(cmpstrs.get(1), Some("Two"));
+    ///
+    /// cmpstrs.compact();
+    /// assert_eq!(cmpstrs.garbage_len(), 0);
+    /// assert_eq!(cmpstrs.get(0), Some("Two"));
+    /// assert_eq!(cmpstrs.slot_count(), 2);
+    /// ```
+    pub fn compact(&mut self) {
+        if self.removed_count > 0 {
+            let mut removed = self.removed.iter();
+            self.strings
+                .0
+                .meta
+                .retain(|_| removed.next() == Some(&false));
+            self.removed.truncate(self.strings.len());
+            self.removed.fill(false);
+            self.removed_count = 0;
+        }
+
+        self.strings.compact();
+        self.garbage = 0;
+    }
+
+    /// Removes all strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::AutoCompactStrings;
+    /// let mut cmpstrs = AutoCompactStrings::new();
+    /// cmpstrs.extend(["One", "Two"]);
+    /// cmpstrs.clear();
+    ///
+    /// assert!(cmpstrs.is_empty());
+    /// assert_eq!(cmpstrs.garbage_len(), 0);
+    /// ```
+    #[inline]
+    pub fn clear(&mut self) {
+        self.strings.clear();
+        self.removed.clear();
+        self.removed_count = 0;
+        self.garbage = 0;
+    }
+
+    /// Returns the number of bytes in the data vector that belong to removed strings.
+    ///
+    /// Unlike [`CompactStrings::garbage_len`], this is tracked as strings are removed and takes
+    /// *O*(1) time.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::AutoCompactStrings;
+    /// let mut cmpstrs = AutoCompactStrings::new();
+    /// cmpstrs.extend(["One", "Two", "Three"]);
+    /// cmpstrs.remove(2);
+    ///
+    /// assert_eq!(cmpstrs.garbage_len(), 5);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn garbage_len(&self) -> usize {
+        self.garbage
+    }
+
+    /// Returns a reference to the string stored in the [`AutoCompactStrings`] at that position,
+    /// or [`None`] if it is out of bounds or the string was removed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::AutoCompactStrings;
+    /// let mut cmpstrs = AutoCompactStrings::new();
+    /// cmpstrs.extend(["One", "Two", "Three"]);
+    /// cmpstrs.remove(0);
+    ///
+    /// assert_eq!(cmpstrs.get(0), None);
+    /// assert_eq!(cmpstrs.get(1), Some("Two"));
+    /// assert_eq!(cmpstrs.get(3), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&str> {
+        if self.removed.get(index) == Some(&false) {
+            self.strings.get(index)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the number of strings in the [`AutoCompactStrings`], also referred to as its 'length'.
+    ///
+    /// Removed strings are not counted.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::AutoCompactStrings;
+    /// let mut cmpstrs = AutoCompactStrings::new();
+    /// cmpstrs.extend(["One", "Two", "Three"]);
+    /// cmpstrs.remove(0);
+    ///
+    /// assert_eq!(cmpstrs.len(), 2);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.strings.len() - self.removed_count
+    }
+
+    /// Returns the number of slots, which is one past the highest index a string can be at.
+    ///
+    /// Removed strings keep their slot until the [`AutoCompactStrings`] is compacted.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::AutoCompactStrings;
+    /// let mut cmpstrs = AutoCompactStrings::new();
+    /// cmpstrs.extend(["One", "Two", "Three"]);
+    /// cmpstrs.remove(0);
+    ///
+    /// assert_eq!(cmpstrs.slot_count(), 3);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn slot_count(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns true if the [`AutoCompactStrings`] contains no strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::AutoCompactStrings;
+    /// let cmpstrs = AutoCompactStrings::new();
+    ///
+    /// assert!(cmpstrs.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the strings, skipping removed ones.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::AutoCompactStrings;
+    /// let mut cmpstrs = AutoCompactStrings::new();
+    /// cmpstrs.extend(["One", "Two", "Three"]);
+    /// cmpstrs.remove(1);
+    ///
+    /// assert!(cmpstrs.iter().eq(["One", "Three"]));
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            strings: self.strings.iter(),
+            removed: self.removed.iter(),
+            remaining: self.len(),
+        }
+    }
+
+    /// Compacts the [`AutoCompactStrings`] and returns the underlying [`CompactStrings`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{AutoCompactStrings, CompactStrings};
+    /// let mut cmpstrs = AutoCompactStrings::new();
+    /// cmpstrs.extend(["One", "Two", "Three"]);
+    /// cmpstrs.remove(1);
+    ///
+    /// assert_eq!(cmpstrs.into_inner(), CompactStrings::from(["One", "Three"]));
+    /// ```
+    #[must_use]
+    pub fn into_inner(mut self) -> CompactStrings {
+        self.compact();
+        self.strings
+    }
+
+    /// Returns the length of the string at `index`, checking that it is in bounds and was not
+    /// removed.
+    #[track_caller]
+    fn take(&self, index: usize) -> usize {
+        #[cold]
+        #[inline(never)]
+        #[track_caller]
+        fn assert_failed(index: usize, len: usize) -> ! {
+            panic!("removal index (is {index}) should be < len (is {len}) and not removed");
+        }
+
+        match self.removed.get(index) {
+            Some(false) => self.strings.0.meta[index].len,
+            _ => assert_failed(index, self.strings.len()),
+        }
+    }
+
+    /// Compacts if more than the allowed fraction of the data vector or of the slots belongs to
+    /// removed strings.
+    #[allow(clippy::cast_precision_loss)]
+    fn compact_if_needed(&mut self) {
+        if self.garbage as f64 > self.strings.data_len() as f64 * self.max_garbage
+            || self.removed_count as f64 > self.strings.len() as f64 * self.max_garbage
+        {
+            self.compact();
+        }
+    }
+}
+
+impl Default for AutoCompactStrings {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialEq for AutoCompactStrings {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl Debug for AutoCompactStrings {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl Index<usize> for AutoCompactStrings {
+    type Output = str;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+
+impl<S> Extend<S> for AutoCompactStrings
+where
+    S: Deref<Target = str>,
+{
+    #[inline]
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        self.strings.extend(iter);
+        self.removed.resize(self.strings.len(), false);
+    }
+}
+
+impl<'a> IntoIterator for &'a AutoCompactStrings {
+    type Item = &'a str;
+
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl From<CompactStrings> for AutoCompactStrings {
+    /// Wraps a [`CompactStrings`], compacting it right away if more than half of its data vector
+    /// is garbage.
+    fn from(value: CompactStrings) -> Self {
+        let mut cmpstrs = Self {
+            removed: vec![false; value.len()],
+            removed_count: 0,
+            garbage: value.garbage_len(),
+            strings: value,
+            max_garbage: DEFAULT_MAX_GARBAGE,
+        };
+        cmpstrs.compact_if_needed();
+        cmpstrs
+    }
+}
+
+/// Iterator over the strings of an [`AutoCompactStrings`], skipping removed ones.
+///
+/// # Examples
+/// ```
+/// # use compact_strings::AutoCompactStrings;
+/// let mut cmpstrs = AutoCompactStrings::new();
+/// cmpstrs.extend(["One", "Two", "Three"]);
+/// cmpstrs.remove(1);
+///
+/// let mut iter = cmpstrs.iter();
+/// assert_eq!(iter.len(), 2);
+/// assert_eq!(iter.next(), Some("One"));
+/// assert_eq!(iter.next_back(), Some("Three"));
+/// assert_eq!(iter.next(), None);
+/// ```
+#[must_use = "Iterators are lazy and do nothing unless consumed"]
+pub struct Iter<'a> {
+    strings: compact_strings::Iter<'a>,
+    removed: core::slice::Iter<'a, bool>,
+    remaining: usize,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a str;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        for string in self.strings.by_ref() {
+            if self.removed.next() == Some(&false) {
+                self.remaining -= 1;
+                return Some(string);
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl DoubleEndedIterator for Iter<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        while let Some(string) = self.strings.next_back() {
+            if self.removed.next_back() == Some(&false) {
+                self.remaining -= 1;
+                return Some(string);
+            }
+        }
+        None
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.remaining
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, vec::Vec};
+
+    use crate::{AutoCompactStrings, CompactStrings};
+
+    #[test]
+    fn garbage_is_tracked_exactly() {
+        let mut cmpstrs = AutoCompactStrings::with_max_garbage(0.3);
+        for i in 0..100 {
+            cmpstrs.push(format!("{i}").as_str());
+        }
+
+        for i in 0..50 {
+            let live: Vec<usize> = (0..cmpstrs.slot_count())
+                .filter(|&index| cmpstrs.get(index).is_some())
+                .collect();
+            let index = live[i * 31 % live.len()];
+            if i % 2 == 0 {
+                cmpstrs.remove(index);
+            } else {
+                cmpstrs.swap_remove(index);
+            }
+
+            let live_len: usize = cmpstrs.iter().map(str::len).sum();
+            let data_len = cmpstrs.strings.data_len();
+            assert_eq!(cmpstrs.garbage_len(), data_len - live_len);
+            assert!(live_len * 10 >= data_len * 7);
+            assert!(cmpstrs.len() * 10 >= cmpstrs.slot_count() * 7);
+            assert_eq!(cmpstrs.iter().len(), cmpstrs.len());
+        }
+    }
+
+    #[test]
+    fn tombstones_are_dropped_on_compaction() {
+        let mut cmpstrs = AutoCompactStrings::with_max_garbage(1.0);
+        cmpstrs.extend(["One", "", "Three", "Four"]);
+
+        cmpstrs.remove(1);
+        cmpstrs.remove(3);
+        assert_eq!(cmpstrs.len(), 2);
+        assert_eq!(cmpstrs.slot_count(), 4);
+        assert!(cmpstrs.iter().rev().eq(["Three", "One"]));
+        assert_eq!(
+            cmpstrs,
+            AutoCompactStrings::from(CompactStrings::from(["One", "Three"]))
+        );
+
+        cmpstrs.push("Five");
+        assert_eq!(cmpstrs.get(4), Some("Five"));
+
+        cmpstrs.compact();
+        assert_eq!(cmpstrs.slot_count(), 3);
+        assert!(cmpstrs.iter().eq(["One", "Three", "Five"]));
+        assert_eq!(cmpstrs.strings.data_len(), 12);
+    }
+
+    #[test]
+    #[should_panic = "removal index (is 0) should be < len (is 2) and not removed"]
+    fn rejects_removing_twice() {
+        let mut cmpstrs = AutoCompactStrings::with_max_garbage(1.0);
+        cmpstrs.extend(["One", "Two"]);
+        cmpstrs.remove(0);
+        cmpstrs.remove(0);
+    }
+
+    #[test]
+    #[should_panic = "max garbage (is 1.5) should be between 0 and 1"]
+    fn rejects_max_garbage_above_one() {
+        let _ = AutoCompactStrings::with_max_garbage(1.5);
+    }
+
+    #[test]
+    fn compacts_wrapped_strings() {
+        let mut cmpstrs = CompactStrings::from(["One", "Two", "Six"]);
+        cmpstrs.ignore(0);
+        cmpstrs.ignore(0);
+
+        let wrapped = AutoCompactStrings::from(cmpstrs);
+        assert_eq!(wrapped.garbage_len(), 0);
+        assert_eq!(wrapped.strings.data_len(), 3);
+    }
+}
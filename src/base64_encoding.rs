@@ -0,0 +1,153 @@
+use alloc::{vec, vec::Vec};
+
+use base64::{prelude::BASE64_STANDARD, DecodeError, DecodeSliceError, Engine};
+
+use crate::{metadata::Metadata, CompactBytestrings, CompactStrings};
+
+impl CompactBytestrings {
+    /// Encodes every bytestring as standard padded base64 into a new [`CompactStrings`].
+    ///
+    /// The exact length of the encoded data is computed up front, so the bytestrings are encoded
+    /// straight into a single allocation.
+    ///
+    /// # Panics
+    /// Panics if the length of the encoded data overflows a [`usize`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([&b"One"[..], b"", b"\xff\xfe"]);
+    /// let encoded = cmpbytes.encode_base64();
+    ///
+    /// assert!(encoded.iter().eq(["T25l", "", "//4="]));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "base64")))]
+    #[track_caller]
+    #[must_use]
+    pub fn encode_base64(&self) -> CompactStrings {
+        #[cold]
+        #[inline(never)]
+        #[track_caller]
+        fn capacity_overflow() -> ! {
+            panic!("capacity overflow");
+        }
+
+        let data_len = self
+            .meta
+            .iter()
+            .try_fold(0usize, |total, meta| {
+                total.checked_add(base64::encoded_len(meta.len, true)?)
+            })
+            .unwrap_or_else(|| capacity_overflow());
+
+        let mut data = vec![0; data_len];
+        let mut meta = Vec::with_capacity(self.meta.len());
+        let mut start = 0;
+        for bytes in self {
+            // The output was sized from the same lengths, so it can never be too small.
+            let len = BASE64_STANDARD
+                .encode_slice(bytes, &mut data[start..])
+                .unwrap_or_default();
+            meta.push(Metadata::new(start, len));
+            start += len;
+        }
+
+        CompactStrings(Self { data, meta })
+    }
+
+    /// Decodes every string of `strings` from standard padded base64 into a new
+    /// [`CompactBytestrings`].
+    ///
+    /// The exact length of the decoded data is computed up front from the lengths and padding of
+    /// the strings, so they are decoded straight into a single allocation.
+    ///
+    /// # Errors
+    /// Returns the error of the first string that is not valid base64.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactBytestrings, CompactStrings};
+    /// let encoded = CompactStrings::from(["T25l", "", "//4="]);
+    /// let cmpbytes = CompactBytestrings::from_base64(&encoded).unwrap();
+    ///
+    /// assert!(cmpbytes.iter().eq([&b"One"[..], b"", b"\xff\xfe"]));
+    /// assert!(CompactBytestrings::from_base64(&CompactStrings::from(["T25l!"])).is_err());
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "base64")))]
+    pub fn from_base64(strings: &CompactStrings) -> Result<Self, DecodeError> {
+        let data_len = strings
+            .iter()
+            .map(|string| decoded_len(string.as_bytes()))
+            .sum();
+
+        let mut data = vec![0; data_len];
+        let mut meta = Vec::with_capacity(strings.len());
+        let mut start = 0;
+        for string in strings {
+            let len = BASE64_STANDARD
+                .decode_slice(string, &mut data[start..])
+                .map_err(|err| match err {
+                    DecodeSliceError::DecodeError(err) => err,
+                    // Only strings whose length cannot be valid base64 can be decoded into more
+                    // bytes than they were counted for.
+                    DecodeSliceError::OutputSliceTooSmall => {
+                        DecodeError::InvalidLength(string.len())
+                    }
+                })?;
+            meta.push(Metadata::new(start, len));
+            start += len;
+        }
+
+        data.truncate(start);
+        Ok(Self { data, meta })
+    }
+}
+
+/// Returns the number of bytes that the valid padded base64 `encoded` decodes to.
+fn decoded_len(encoded: &[u8]) -> usize {
+    let padding = encoded
+        .iter()
+        .rev()
+        .take(2)
+        .take_while(|&&b| b == b'=')
+        .count();
+    let unpadded = encoded.len() - padding;
+    unpadded / 4 * 3 + [0, 0, 1, 2][unpadded % 4]
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::{CompactBytestrings, CompactStrings};
+
+    #[test]
+    fn round_trips_every_remainder() {
+        let bytes = (0..=255).collect::<Vec<u8>>();
+        let mut cmpbytes = CompactBytestrings::new();
+        for len in 0..10 {
+            cmpbytes.push(&bytes[len * 20..len * 21]);
+        }
+
+        let encoded = cmpbytes.encode_base64();
+        assert_eq!(CompactBytestrings::from_base64(&encoded).unwrap(), cmpbytes);
+    }
+
+    #[test]
+    fn skips_garbage() {
+        let mut cmpbytes = CompactBytestrings::from([b"One", b"Two"]);
+        cmpbytes.ignore(0);
+
+        let encoded = cmpbytes.encode_base64();
+        assert_eq!(encoded.data_len(), 4);
+        assert!(encoded.iter().eq(["VHdv"]));
+    }
+
+    #[test]
+    fn rejects_invalid_lengths() {
+        for string in ["T", "T25", "T25lV", "T25l="] {
+            let encoded = CompactStrings::from([string]);
+            assert!(CompactBytestrings::from_base64(&encoded).is_err());
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use core::{fmt::Debug, ops::Index};
+
+use alloc::sync::Arc;
+
+use bytes::Bytes;
+
+use crate::{
+    compact_bytestrings::Iter, metadata::Metadata, CompactBytesRef, CompactBytestrings, LayoutError,
+};
+
+/// An immutable list of bytestrings whose data buffer is a [`Bytes`].
+///
+/// Cloning a [`BytesCompactBytestrings`] only increments reference counts, and
+/// [`get_bytes`] hands out bytestrings as [`Bytes`] that share the data buffer instead of copying
+/// it, so they can outlive the [`BytesCompactBytestrings`] and be passed to other tasks.
+///
+/// [`get_bytes`]: BytesCompactBytestrings::get_bytes
+///
+/// # Examples
+/// ```
+/// # use compact_strings::{BytesCompactBytestrings, CompactBytestrings};
+/// let cmpbytes = CompactBytestrings::from([b"One", b"Two"]);
+/// let bytes = BytesCompactBytestrings::from(cmpbytes);
+///
+/// let two = bytes.get_bytes(1).unwrap();
+/// drop(bytes);
+///
+/// assert_eq!(two, b"Two".as_slice());
+/// ```
+#[derive(Clone)]
+pub struct BytesCompactBytestrings {
+    data: Bytes,
+    meta: Arc<[Metadata]>,
+}
+
+impl BytesCompactBytestrings {
+    /// Parses a buffer holding the binary layout written by [`CompactBytestrings::to_bytes`],
+    /// such as one received over the network, without copying its data.
+    ///
+    /// # Errors
+    /// Returns a [`LayoutError`] if the buffer does not hold a valid layout.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{BytesCompactBytestrings, CompactBytestrings};
+    /// use bytes::Bytes;
+    ///
+    /// let buf = Bytes::from(CompactBytestrings::from([b"One", b"Two"]).to_bytes());
+    /// let bytes = BytesCompactBytestrings::parse(buf).unwrap();
+    ///
+    /// assert_eq!(bytes.get(1), Some(b"Two".as_slice()));
+    /// ```
+    pub fn parse(mut buf: Bytes) -> Result<Self, LayoutError> {
+        let view = CompactBytesRef::parse(&buf)?;
+        let mut start = 0;
+        let meta = view
+            .iter()
+            .map(|bytes| {
+                let meta = Metadata::new(start, bytes.len());
+                start += bytes.len();
+                meta
+            })
+            .collect();
+        let data = buf.split_off(buf.len() - start);
+
+        Ok(Self { data, meta })
+    }
+
+    /// Returns a reference to the bytestring stored in the [`BytesCompactBytestrings`] at that position.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::BytesCompactBytestrings;
+    /// let bytes = BytesCompactBytestrings::from([b"One", b"Two"]);
+    ///
+    /// assert_eq!(bytes.get(0), Some(b"One".as_slice()));
+    /// assert_eq!(bytes.get(1), Some(b"Two".as_slice()));
+    /// assert_eq!(bytes.get(2), None);
+    /// ```
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&[u8]> {
+        let (start, len) = self.meta.get(index)?.as_tuple();
+        if cfg!(feature = "no_unsafe") {
+            self.data.get(start..start + len)
+        } else {
+            unsafe { Some(self.data.get_unchecked(start..start + len)) }
+        }
+    }
+
+    /// Returns the bytestring stored in the [`BytesCompactBytestrings`] at that position as a
+    /// [`Bytes`] sharing the data buffer, without copying it.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::BytesCompactBytestrings;
+    /// let bytes = BytesCompactBytestrings::from([b"One", b"Two"]);
+    ///
+    /// assert_eq!(bytes.get_bytes(0).unwrap(), b"One".as_slice());
+    /// assert_eq!(bytes.get_bytes(2), None);
+    /// ```
+    #[must_use]
+    pub fn get_bytes(&self, index: usize) -> Option<Bytes> {
+        let (start, len) = self.meta.get(index)?.as_tuple();
+        Some(self.data.slice(start..start + len))
+    }
+
+    /// Returns the number of bytestrings in the [`BytesCompactBytestrings`], also referred to as its 'length'.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::BytesCompactBytestrings;
+    /// let bytes = BytesCompactBytestrings::from([b"One", b"Two"]);
+    ///
+    /// assert_eq!(bytes.len(), 2);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.meta.len()
+    }
+
+    /// Returns true if the [`BytesCompactBytestrings`] contains no bytestrings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{BytesCompactBytestrings, CompactBytestrings};
+    /// let bytes = BytesCompactBytestrings::from(CompactBytestrings::new());
+    ///
+    /// assert!(bytes.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the bytestrings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::BytesCompactBytestrings;
+    /// let bytes = BytesCompactBytestrings::from([b"One", b"Two"]);
+    /// let mut iterator = bytes.iter();
+    ///
+    /// assert_eq!(iterator.next(), Some(b"One".as_slice()));
+    /// assert_eq!(iterator.next(), Some(b"Two".as_slice()));
+    /// assert_eq!(iterator.next(), None);
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter::from_parts(&self.data, &self.meta)
+    }
+}
+
+impl PartialEq for BytesCompactBytestrings {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl Debug for BytesCompactBytestrings {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl Index<usize> for BytesCompactBytestrings {
+    type Output = [u8];
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+
+impl<'a> IntoIterator for &'a BytesCompactBytestrings {
+    type Item = &'a [u8];
+
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<S> FromIterator<S> for BytesCompactBytestrings
+where
+    S: AsRef<[u8]>,
+{
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        CompactBytestrings::from_iter(iter).into()
+    }
+}
+
+impl<S, I> From<I> for BytesCompactBytestrings
+where
+    S: AsRef<[u8]>,
+    I: IntoIterator<Item = S>,
+{
+    #[inline]
+    fn from(value: I) -> Self {
+        FromIterator::from_iter(value)
+    }
+}
+
+impl From<CompactBytestrings> for BytesCompactBytestrings {
+    /// Moves the data vector into a [`Bytes`] without copying it.
+    fn from(value: CompactBytestrings) -> Self {
+        Self {
+            data: value.data.into(),
+            meta: value.meta.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use crate::{BytesCompactBytestrings, CompactBytestrings, LayoutError};
+
+    #[test]
+    fn slices_share_the_buffer() {
+        let mut cmpbytes = CompactBytestrings::from([&b"One"[..], b"", b"Three"]);
+        cmpbytes.ignore(0);
+        let bytes = BytesCompactBytestrings::from(cmpbytes);
+
+        let three = bytes.get_bytes(1).unwrap();
+        assert_eq!(three, b"Three".as_slice());
+        assert_eq!(three.as_ptr(), bytes[1].as_ptr());
+        assert_eq!(bytes.get_bytes(0).unwrap(), b"".as_slice());
+    }
+
+    #[test]
+    fn parse_rejects_invalid_layouts() {
+        let buf = Bytes::from(CompactBytestrings::from([b"One", b"Two"]).to_bytes());
+        let bytes = BytesCompactBytestrings::parse(buf.clone()).unwrap();
+        assert!(bytes.iter().eq([b"One", b"Two"]));
+        assert_eq!(
+            bytes.get_bytes(1).unwrap().as_ptr(),
+            buf[buf.len() - 3..].as_ptr()
+        );
+
+        assert_eq!(
+            BytesCompactBytestrings::parse(buf.slice(1..)),
+            Err(LayoutError::LengthMismatch)
+        );
+    }
+}
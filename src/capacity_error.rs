@@ -0,0 +1,33 @@
+use core::fmt::{self, Display};
+
+/// The reason a string could not be pushed onto an [`ArrayCompactStrings`](crate::ArrayCompactStrings).
+///
+/// # Examples
+/// ```
+/// # use compact_strings::{ArrayCompactStrings, CapacityError};
+/// let mut cmpstrs = ArrayCompactStrings::<4, 1>::new();
+///
+/// assert_eq!(cmpstrs.push("Three"), Err(CapacityError::DataFull));
+/// assert_eq!(cmpstrs.push("One"), Ok(()));
+/// assert_eq!(cmpstrs.push(""), Err(CapacityError::Full));
+/// ```
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CapacityError {
+    /// Every element slot is taken.
+    Full,
+    /// There is not enough room left in the data array for the bytes of the string.
+    DataFull,
+}
+
+impl Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CapacityError::Full => "every element slot is taken",
+            CapacityError::DataFull => "not enough room left in the data array",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CapacityError {}
@@ -0,0 +1,447 @@
+use core::{
+    cell::RefCell,
+    fmt::Debug,
+    iter::FusedIterator,
+    ops::{Deref, Index},
+    ptr::NonNull,
+};
+
+use alloc::vec::Vec;
+
+/// The capacity of each chunk used by [`ChunkedCompactStrings::new`].
+const DEFAULT_CHUNK_SIZE: usize = 4096;
+
+/// An append-only list of strings whose bytes are stored in fixed-size chunks.
+///
+/// A full chunk is never grown. A new chunk is allocated instead, so pushing a string never moves
+/// the bytes of the strings already stored. This avoids the reallocation and copy of the whole
+/// data vector that growing a [`CompactStrings`](crate::CompactStrings) can cause, and it lets
+/// [`push`] take `&self` and return a reference that stays valid across later pushes.
+///
+/// A string longer than the chunk size gets a chunk of its own.
+///
+/// Handing out references into chunks that are still being written to cannot be done without
+/// `unsafe` code, so this type sits behind its own `chunked` feature and still uses `unsafe` code
+/// when the `no_unsafe` feature is enabled.
+///
+/// [`push`]: ChunkedCompactStrings::push
+///
+/// # Examples
+/// ```
+/// # use compact_strings::ChunkedCompactStrings;
+/// let cmpstrs = ChunkedCompactStrings::new();
+///
+/// let one = cmpstrs.push("One");
+/// let two = cmpstrs.push("Two");
+///
+/// assert_eq!(one, "One");
+/// assert_eq!(two, "Two");
+/// assert_eq!(cmpstrs.get(1), Some("Two"));
+/// ```
+pub struct ChunkedCompactStrings {
+    chunks: RefCell<Vec<Vec<u8>>>,
+    entries: RefCell<Vec<Entry>>,
+    chunk_size: usize,
+}
+
+/// Points at the bytes of a string in one of the chunks.
+#[derive(Clone, Copy)]
+struct Entry {
+    ptr: NonNull<u8>,
+    len: usize,
+}
+
+// SAFETY: The entries only point into chunks owned by the `ChunkedCompactStrings`.
+unsafe impl Send for ChunkedCompactStrings {}
+
+impl ChunkedCompactStrings {
+    /// Constructs a new, empty [`ChunkedCompactStrings`] with chunks of 4 KiB.
+    ///
+    /// No chunk is allocated until a non-empty string is pushed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::ChunkedCompactStrings;
+    /// let cmpstrs = ChunkedCompactStrings::new();
+    ///
+    /// assert!(cmpstrs.is_empty());
+    /// ```
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_chunk_size(DEFAULT_CHUNK_SIZE)
+    }
+
+    /// Constructs a new, empty [`ChunkedCompactStrings`] whose chunks can each hold
+    /// `chunk_size` bytes.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::ChunkedCompactStrings;
+    /// let cmpstrs = ChunkedCompactStrings::with_chunk_size(4);
+    /// cmpstrs.push("One");
+    /// cmpstrs.push("Two");
+    ///
+    /// assert_eq!(cmpstrs.chunk_count(), 2);
+    /// ```
+    #[must_use]
+    pub const fn with_chunk_size(chunk_size: usize) -> Self {
+        Self {
+            chunks: RefCell::new(Vec::new()),
+            entries: RefCell::new(Vec::new()),
+            chunk_size,
+        }
+    }
+
+    /// Appends a string to the back of the [`ChunkedCompactStrings`], returning a reference to
+    /// the stored copy.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::ChunkedCompactStrings;
+    /// let cmpstrs = ChunkedCompactStrings::with_chunk_size(8);
+    ///
+    /// let first = cmpstrs.push("One");
+    /// for _ in 0..100 {
+    ///     cmpstrs.push("Two");
+    /// }
+    ///
+    /// assert_eq!(first, "One");
+    /// assert_eq!(cmpstrs.len(), 101);
+    /// ```
+    pub fn push<S>(&self, string: S) -> &str
+    where
+        S: Deref<Target = str>,
+    {
+        let bytes = string.as_bytes();
+        let ptr = if bytes.is_empty() {
+            NonNull::dangling()
+        } else {
+            let mut chunks = self.chunks.borrow_mut();
+            let fits = chunks
+                .last()
+                .map_or(false, |chunk| chunk.capacity() - chunk.len() >= bytes.len());
+            if !fits {
+                chunks.push(Vec::with_capacity(self.chunk_size.max(bytes.len())));
+            }
+
+            // The chunk has room for the bytes, so extending it does not move the bytes already
+            // stored in it.
+            let last = chunks.len() - 1;
+            let chunk = &mut chunks[last];
+            let ptr = unsafe { NonNull::new_unchecked(chunk.as_mut_ptr().add(chunk.len())) };
+            chunk.extend_from_slice(bytes);
+            ptr
+        };
+
+        let entry = Entry {
+            ptr,
+            len: bytes.len(),
+        };
+        self.entries.borrow_mut().push(entry);
+
+        unsafe { Self::resolve(entry) }
+    }
+
+    /// Returns a reference to the string stored in the [`ChunkedCompactStrings`] at that position.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::ChunkedCompactStrings;
+    /// let cmpstrs = ChunkedCompactStrings::from(["One", "Two"]);
+    ///
+    /// assert_eq!(cmpstrs.get(0), Some("One"));
+    /// assert_eq!(cmpstrs.get(1), Some("Two"));
+    /// assert_eq!(cmpstrs.get(2), None);
+    /// ```
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&str> {
+        let entry = *self.entries.borrow().get(index)?;
+        Some(unsafe { Self::resolve(entry) })
+    }
+
+    /// Returns the number of strings in the [`ChunkedCompactStrings`], also referred to as its 'length'.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::ChunkedCompactStrings;
+    /// let cmpstrs = ChunkedCompactStrings::from(["One", "Two"]);
+    ///
+    /// assert_eq!(cmpstrs.len(), 2);
+    /// ```
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.borrow().len()
+    }
+
+    /// Returns true if the [`ChunkedCompactStrings`] contains no strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::ChunkedCompactStrings;
+    /// let cmpstrs = ChunkedCompactStrings::new();
+    /// assert!(cmpstrs.is_empty());
+    ///
+    /// cmpstrs.push("One");
+    /// assert!(!cmpstrs.is_empty());
+    /// ```
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of chunks allocated by the [`ChunkedCompactStrings`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::ChunkedCompactStrings;
+    /// let cmpstrs = ChunkedCompactStrings::with_chunk_size(8);
+    /// cmpstrs.push("One");
+    /// cmpstrs.push("Two");
+    /// assert_eq!(cmpstrs.chunk_count(), 1);
+    ///
+    /// cmpstrs.push("Three");
+    /// assert_eq!(cmpstrs.chunk_count(), 2);
+    /// ```
+    #[must_use]
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.borrow().len()
+    }
+
+    /// Returns the number of bytes stored across all chunks.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::ChunkedCompactStrings;
+    /// let cmpstrs = ChunkedCompactStrings::from(["One", "Two"]);
+    ///
+    /// assert_eq!(cmpstrs.data_len(), 6);
+    /// ```
+    #[must_use]
+    pub fn data_len(&self) -> usize {
+        self.chunks.borrow().iter().map(Vec::len).sum()
+    }
+
+    /// Removes all strings and frees every chunk.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::ChunkedCompactStrings;
+    /// let mut cmpstrs = ChunkedCompactStrings::from(["One", "Two"]);
+    /// cmpstrs.clear();
+    ///
+    /// assert!(cmpstrs.is_empty());
+    /// assert_eq!(cmpstrs.chunk_count(), 0);
+    /// ```
+    pub fn clear(&mut self) {
+        self.entries.get_mut().clear();
+        self.chunks.get_mut().clear();
+    }
+
+    /// Returns an iterator over the strings stored when it was created.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::ChunkedCompactStrings;
+    /// let cmpstrs = ChunkedCompactStrings::from(["One", "Two"]);
+    ///
+    /// let iter = cmpstrs.iter();
+    /// cmpstrs.push("Three");
+    ///
+    /// assert!(iter.eq(["One", "Two"]));
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            inner: self,
+            start: 0,
+            end: self.len(),
+        }
+    }
+
+    /// Turns an entry into the string it points at.
+    ///
+    /// # Safety
+    /// The entry must have been pushed into this [`ChunkedCompactStrings`], which must not have
+    /// been cleared since.
+    unsafe fn resolve<'a>(entry: Entry) -> &'a str {
+        let bytes = core::slice::from_raw_parts(entry.ptr.as_ptr(), entry.len);
+        core::str::from_utf8_unchecked(bytes)
+    }
+}
+
+impl Default for ChunkedCompactStrings {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clone for ChunkedCompactStrings {
+    fn clone(&self) -> Self {
+        let cloned = Self::with_chunk_size(self.chunk_size);
+        for string in self {
+            cloned.push(string);
+        }
+
+        cloned
+    }
+}
+
+impl PartialEq for ChunkedCompactStrings {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl Debug for ChunkedCompactStrings {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl Index<usize> for ChunkedCompactStrings {
+    type Output = str;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+
+/// Iterator over strings in a [`ChunkedCompactStrings`]
+///
+/// # Examples
+/// ```
+/// # use compact_strings::ChunkedCompactStrings;
+/// let cmpstrs = ChunkedCompactStrings::from(["One", "Two"]);
+///
+/// let mut iter = cmpstrs.into_iter();
+/// assert_eq!(iter.next(), Some("One"));
+/// assert_eq!(iter.next(), Some("Two"));
+/// assert_eq!(iter.next(), None);
+/// ```
+#[must_use = "Iterators are lazy and do nothing unless consumed"]
+pub struct Iter<'a> {
+    inner: &'a ChunkedCompactStrings,
+    start: usize,
+    end: usize,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            return None;
+        }
+
+        self.start += 1;
+        self.inner.get(self.start - 1)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Iter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            return None;
+        }
+
+        self.end -= 1;
+        self.inner.get(self.end)
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+impl FusedIterator for Iter<'_> {}
+
+impl<'a> IntoIterator for &'a ChunkedCompactStrings {
+    type Item = &'a str;
+
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<S> Extend<S> for ChunkedCompactStrings
+where
+    S: Deref<Target = str>,
+{
+    #[inline]
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        for s in iter {
+            self.push(s);
+        }
+    }
+}
+
+impl<S> FromIterator<S> for ChunkedCompactStrings
+where
+    S: Deref<Target = str>,
+{
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        let mut cmpstrs = Self::new();
+        cmpstrs.extend(iter);
+        cmpstrs
+    }
+}
+
+impl<S, I> From<I> for ChunkedCompactStrings
+where
+    S: Deref<Target = str>,
+    I: IntoIterator<Item = S>,
+{
+    #[inline]
+    fn from(value: I) -> Self {
+        FromIterator::from_iter(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, string::String, vec::Vec};
+
+    use crate::ChunkedCompactStrings;
+
+    #[test]
+    fn references_survive_pushes() {
+        let cmpstrs = ChunkedCompactStrings::with_chunk_size(16);
+
+        let pushed = (0..200)
+            .map(|i| cmpstrs.push(format!("{i}").as_str()))
+            .collect::<Vec<_>>();
+        let long = cmpstrs.push("a string longer than the chunk size");
+        cmpstrs.push("");
+
+        for (i, string) in pushed.iter().enumerate() {
+            assert_eq!(*string, format!("{i}"));
+        }
+        assert_eq!(long, "a string longer than the chunk size");
+        assert_eq!(cmpstrs.get(201), Some(""));
+        assert_eq!(cmpstrs.iter().map(String::from).count(), 202);
+    }
+
+    #[test]
+    fn chunks_are_never_grown() {
+        let cmpstrs = ChunkedCompactStrings::with_chunk_size(4);
+        cmpstrs.push("ab");
+        cmpstrs.push("cd");
+        cmpstrs.push("e");
+
+        assert_eq!(cmpstrs.chunk_count(), 2);
+        assert_eq!(cmpstrs.data_len(), 5);
+        assert!(core::ptr::eq(
+            cmpstrs.get(0).unwrap().as_ptr().wrapping_add(2),
+            cmpstrs.get(1).unwrap().as_ptr(),
+        ));
+    }
+}
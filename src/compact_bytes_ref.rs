@@ -0,0 +1,358 @@
+use core::{fmt::Debug, ops::Index};
+
+use alloc::vec::Vec;
+
+use crate::layout::{self, LayoutError, START_LEN};
+
+/// A read-only view of bytestrings borrowed from a buffer in the binary layout written by
+/// [`CompactBytestrings::to_bytes`](crate::CompactBytestrings::to_bytes).
+///
+/// Both the starting indices and the bytes are read straight out of the buffer, so parsing it
+/// validates the starting indices but does not allocate.
+///
+/// # Examples
+/// ```
+/// # use compact_strings::{CompactBytesRef, CompactBytestrings};
+/// let cmpbytes = CompactBytestrings::from([b"One", b"Two"]);
+///
+/// let buf = cmpbytes.to_bytes();
+///
+/// let view = CompactBytesRef::parse(&buf).unwrap();
+/// assert_eq!(view.get(0), Some(b"One".as_slice()));
+/// assert_eq!(view.get(1), Some(b"Two".as_slice()));
+/// assert_eq!(view.get(2), None);
+/// ```
+#[derive(Clone, Copy)]
+pub struct CompactBytesRef<'a> {
+    starts: &'a [u8],
+    data: &'a [u8],
+}
+
+impl<'a> CompactBytesRef<'a> {
+    /// Parses a buffer in the binary layout written by
+    /// [`CompactBytestrings::to_bytes`](crate::CompactBytestrings::to_bytes), borrowing the starting
+    /// indices and bytes from it.
+    ///
+    /// # Errors
+    /// Returns an error if the length of the buffer does not match its header, or if the starting
+    /// indices are out of order or out of bounds.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactBytesRef, CompactBytestrings, LayoutError};
+    /// let buf = CompactBytestrings::from([b"One", b"Two"]).to_bytes();
+    ///
+    /// assert_eq!(CompactBytesRef::parse(&buf).unwrap().len(), 2);
+    /// assert_eq!(
+    ///     CompactBytesRef::parse(&buf[..buf.len() - 1]),
+    ///     Err(LayoutError::LengthMismatch),
+    /// );
+    /// ```
+    pub fn parse(buf: &'a [u8]) -> Result<Self, LayoutError> {
+        let (starts, data) = layout::parse(buf)?;
+        Ok(Self { starts, data })
+    }
+
+    /// Returns the starting indices and bytes, which have already been validated.
+    pub(crate) fn parts(self) -> (&'a [u8], &'a [u8]) {
+        (self.starts, self.data)
+    }
+
+    /// Creates a view over parts that have already been validated by [`layout::parse`].
+    #[cfg(feature = "mmap")]
+    pub(crate) fn from_parts(starts: &'a [u8], data: &'a [u8]) -> Self {
+        Self { starts, data }
+    }
+
+    fn bounds(self, index: usize) -> (usize, usize) {
+        let start = layout::read_start(self.starts, index);
+        let end = if index + 1 < self.len() {
+            layout::read_start(self.starts, index + 1)
+        } else {
+            self.data.len()
+        };
+        (start, end)
+    }
+
+    /// Returns a reference to the bytestring stored in the [`CompactBytesRef`] at that position.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactBytesRef, CompactBytestrings};
+    /// # let buf = CompactBytestrings::from([b"One", b"Two"]).to_bytes();
+    /// let view = CompactBytesRef::parse(&buf).unwrap();
+    ///
+    /// assert_eq!(view.get(0), Some(b"One".as_slice()));
+    /// assert_eq!(view.get(1), Some(b"Two".as_slice()));
+    /// assert_eq!(view.get(2), None);
+    /// ```
+    #[must_use]
+    pub fn get(self, index: usize) -> Option<&'a [u8]> {
+        if index >= self.len() {
+            return None;
+        }
+
+        let (start, end) = self.bounds(index);
+        if cfg!(feature = "no_unsafe") {
+            self.data.get(start..end)
+        } else {
+            unsafe { Some(self.data.get_unchecked(start..end)) }
+        }
+    }
+
+    /// Returns a reference to the bytestring stored in the [`CompactBytesRef`] at that position, without
+    /// doing bounds checking.
+    ///
+    /// # Safety
+    /// Calling this method with an out-of-bounds index is undefined behavior even if the resulting reference is not used.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactBytesRef, CompactBytestrings};
+    /// # let buf = CompactBytestrings::from([b"One", b"Two"]).to_bytes();
+    /// let view = CompactBytesRef::parse(&buf).unwrap();
+    ///
+    /// unsafe {
+    ///     assert_eq!(view.get_unchecked(0), b"One".as_slice());
+    ///     assert_eq!(view.get_unchecked(1), b"Two".as_slice());
+    /// }
+    /// ```
+    #[must_use]
+    #[cfg(not(feature = "no_unsafe"))]
+    pub unsafe fn get_unchecked(self, index: usize) -> &'a [u8] {
+        let (start, end) = self.bounds(index);
+        self.data.get_unchecked(start..end)
+    }
+
+    /// Returns the number of bytestrings in the [`CompactBytesRef`], also referred to as its 'length'.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactBytesRef, CompactBytestrings};
+    /// # let buf = CompactBytestrings::from([b"One", b"Two"]).to_bytes();
+    /// let view = CompactBytesRef::parse(&buf).unwrap();
+    ///
+    /// assert_eq!(view.len(), 2);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn len(self) -> usize {
+        self.starts.len() / START_LEN
+    }
+
+    /// Returns true if the [`CompactBytesRef`] contains no bytestrings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactBytesRef, CompactBytestrings};
+    /// # let buf = CompactBytestrings::new().to_bytes();
+    /// let view = CompactBytesRef::parse(&buf).unwrap();
+    ///
+    /// assert!(view.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_empty(self) -> bool {
+        self.starts.is_empty()
+    }
+
+    /// Copies each bytestring into an owned `Vec<u8>`, returning them in a vector.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactBytesRef, CompactBytestrings};
+    /// # let buf = CompactBytestrings::from([b"One", b"Two"]).to_bytes();
+    /// let view = CompactBytesRef::parse(&buf).unwrap();
+    ///
+    /// assert_eq!(view.to_vec(), [b"One".to_vec(), b"Two".to_vec()]);
+    /// ```
+    #[must_use]
+    pub fn to_vec(self) -> Vec<Vec<u8>> {
+        self.iter().map(<[u8]>::to_vec).collect()
+    }
+
+    /// Returns an iterator over the bytestrings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactBytesRef, CompactBytestrings};
+    /// # let buf = CompactBytestrings::from([b"One", b"Two"]).to_bytes();
+    /// let view = CompactBytesRef::parse(&buf).unwrap();
+    /// let mut iterator = view.iter();
+    ///
+    /// assert_eq!(iterator.next(), Some(b"One".as_slice()));
+    /// assert_eq!(iterator.next(), Some(b"Two".as_slice()));
+    /// assert_eq!(iterator.next(), None);
+    /// ```
+    #[inline]
+    pub fn iter(self) -> Iter<'a> {
+        Iter {
+            inner: self,
+            index: 0,
+            end: self.len(),
+        }
+    }
+}
+
+impl PartialEq for CompactBytesRef<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl Debug for CompactBytesRef<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl Index<usize> for CompactBytesRef<'_> {
+    type Output = [u8];
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for CompactBytesRef<'a> {
+    type Error = LayoutError;
+
+    #[inline]
+    fn try_from(value: &'a [u8]) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+/// Iterator over bytestrings in a [`CompactBytesRef`]
+///
+/// # Examples
+/// ```
+/// # use compact_strings::{CompactBytesRef, CompactBytestrings};
+/// # let buf = CompactBytestrings::from([b"One", b"Two", b"Three".as_slice()]).to_bytes();
+/// let view = CompactBytesRef::parse(&buf).unwrap();
+/// let mut iter = view.into_iter();
+///
+/// assert_eq!(iter.next(), Some(b"One".as_slice()));
+/// assert_eq!(iter.next_back(), Some(b"Three".as_slice()));
+/// assert_eq!(iter.next(), Some(b"Two".as_slice()));
+/// assert_eq!(iter.next(), None);
+/// ```
+#[must_use = "Iterators are lazy and do nothing unless consumed"]
+pub struct Iter<'a> {
+    inner: CompactBytesRef<'a>,
+    index: usize,
+    end: usize,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.end {
+            return None;
+        }
+
+        let item = self.inner.get(self.index);
+        self.index += 1;
+        item
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.index = self.index.saturating_add(n).min(self.end);
+        self.next()
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Iter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index >= self.end {
+            return None;
+        }
+
+        self.end -= 1;
+        self.inner.get(self.end)
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.end - self.index
+    }
+}
+
+impl<'a> IntoIterator for CompactBytesRef<'a> {
+    type Item = &'a [u8];
+
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &CompactBytesRef<'a> {
+    type Item = &'a [u8];
+
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CompactBytesRef, LayoutError};
+
+    fn encode(starts: &[u64], data: &[u8]) -> alloc::vec::Vec<u8> {
+        let mut buf = alloc::vec::Vec::new();
+        buf.extend_from_slice(&(starts.len() as u64).to_le_bytes());
+        buf.extend_from_slice(&(data.len() as u64).to_le_bytes());
+        for start in starts {
+            buf.extend_from_slice(&start.to_le_bytes());
+        }
+        buf.extend_from_slice(data);
+        buf
+    }
+
+    #[test]
+    fn borrows_from_buffer() {
+        let buf = encode(&[0, 3, 3], b"OneSix");
+        let view = CompactBytesRef::parse(&buf).unwrap();
+
+        assert!(view.iter().eq([&b"One"[..], b"", b"Six"]));
+        assert!(core::ptr::eq(
+            view[2].as_ptr(),
+            buf[buf.len() - 3..].as_ptr()
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_starts() {
+        for starts in [&[1, 3][..], &[0, 4, 3], &[0, 7], &[]] {
+            let buf = encode(starts, b"OneSix");
+            assert_eq!(CompactBytesRef::parse(&buf), Err(LayoutError::InvalidStart));
+        }
+    }
+}
@@ -0,0 +1,849 @@
+use core::{fmt::Debug, ops::Index};
+
+use alloc::{collections::VecDeque, vec, vec::Vec};
+
+use crate::{metadata::Metadata, CompactBytestrings, MemoryUsage};
+
+/// A double-ended queue of bytestrings stored compactly.
+///
+/// Bytestrings are stored in a ring buffer of bytes, with their lengths and starting indices being
+/// stored separately in a second ring buffer.
+///
+/// Bytestrings pushed to the back are written after the last one and bytestrings pushed to the
+/// front are written before the first one, wrapping around the end of the data buffer. Each
+/// bytestring is kept in one piece, so one that does not fit before the end of the buffer is
+/// written at its start instead, skipping the bytes in between. Popping from either end only moves
+/// the boundaries of the ring and never moves any data, so the bytes of popped bytestrings are
+/// reused by later pushes. The data buffer is only reallocated, copying the bytestrings in order,
+/// once a bytestring does not fit in the free part of it, so pushing is amortized *O*(1) in the
+/// length of the bytestring and popping is *O*(1).
+///
+/// # Examples
+/// ```
+/// # use compact_strings::CompactBytestringDeque;
+/// let mut deque = CompactBytestringDeque::new();
+///
+/// deque.push_back(b"Two");
+/// deque.push_back(b"Three");
+/// deque.push_front(b"One");
+///
+/// assert_eq!(deque.pop_front(), Some(b"One".as_slice()));
+/// assert_eq!(deque.pop_back(), Some(b"Three".as_slice()));
+/// assert_eq!(deque.get(0), Some(b"Two".as_slice()));
+/// assert_eq!(deque.len(), 1);
+/// ```
+pub struct CompactBytestringDeque {
+    /// The ring buffer of bytes, whose length is the size of the ring.
+    pub(crate) data: Vec<u8>,
+    pub(crate) meta: VecDeque<Metadata>,
+    /// Where the first bytestring starts in the data ring buffer.
+    pub(crate) head: usize,
+    /// How many bytes of the data ring buffer are taken from `head` onwards, including any bytes
+    /// skipped when wrapping around. Always less than the length of the data ring buffer unless
+    /// both are zero, so that a full ring can be told apart from an empty one.
+    pub(crate) used: usize,
+}
+
+impl CompactBytestringDeque {
+    /// Constructs a new, empty [`CompactBytestringDeque`].
+    ///
+    /// The [`CompactBytestringDeque`] will not allocate until bytestrings are pushed into it.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestringDeque;
+    /// let mut deque = CompactBytestringDeque::new();
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            data: Vec::new(),
+            meta: VecDeque::new(),
+            head: 0,
+            used: 0,
+        }
+    }
+
+    /// Constructs a new, empty [`CompactBytestringDeque`] with at least the specified capacities in
+    /// each buffer.
+    ///
+    /// - `data_capacity`: The capacity of the data ring buffer where the bytes of the bytestrings are
+    ///   stored.
+    /// - `capacity_meta`: The capacity of the meta ring buffer where the starting indices and lengths
+    ///   of the bytestrings are stored.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestringDeque;
+    /// let mut deque = CompactBytestringDeque::with_capacity(20, 3);
+    ///
+    /// assert_eq!(deque.len(), 0);
+    /// assert!(deque.capacity() >= 20);
+    /// assert!(deque.capacity_meta() >= 3);
+    /// ```
+    #[must_use]
+    pub fn with_capacity(data_capacity: usize, capacity_meta: usize) -> Self {
+        Self {
+            data: ring(data_capacity),
+            meta: VecDeque::with_capacity(capacity_meta),
+            head: 0,
+            used: 0,
+        }
+    }
+
+    /// Appends a bytestring to the back of the [`CompactBytestringDeque`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestringDeque;
+    /// let mut deque = CompactBytestringDeque::new();
+    /// deque.push_back(b"One");
+    /// deque.push_back(b"Two");
+    ///
+    /// assert_eq!(deque.get(0), Some(b"One".as_slice()));
+    /// assert_eq!(deque.get(1), Some(b"Two".as_slice()));
+    /// ```
+    pub fn push_back<S>(&mut self, bytestring: S)
+    where
+        S: AsRef<[u8]>,
+    {
+        let bytestr = bytestring.as_ref();
+        let start = if let Some(start) = self.back_slot(bytestr.len()) {
+            start
+        } else {
+            self.grow(bytestr.len(), 0);
+            let start = self.used;
+            self.used += bytestr.len();
+            start
+        };
+
+        self.data[start..start + bytestr.len()].copy_from_slice(bytestr);
+        self.meta.push_back(Metadata::new(start, bytestr.len()));
+    }
+
+    /// Prepends a bytestring to the front of the [`CompactBytestringDeque`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestringDeque;
+    /// let mut deque = CompactBytestringDeque::new();
+    /// deque.push_front(b"Two");
+    /// deque.push_front(b"One");
+    ///
+    /// assert_eq!(deque.get(0), Some(b"One".as_slice()));
+    /// assert_eq!(deque.get(1), Some(b"Two".as_slice()));
+    /// ```
+    pub fn push_front<S>(&mut self, bytestring: S)
+    where
+        S: AsRef<[u8]>,
+    {
+        let bytestr = bytestring.as_ref();
+        let start = if let Some(start) = self.front_slot(bytestr.len()) {
+            start
+        } else {
+            self.grow(bytestr.len(), bytestr.len());
+            self.head = 0;
+            self.used += bytestr.len();
+            0
+        };
+
+        self.data[start..start + bytestr.len()].copy_from_slice(bytestr);
+        self.meta.push_front(Metadata::new(start, bytestr.len()));
+    }
+
+    /// Removes the first bytestring and returns a reference to its bytes, or [`None`] if the
+    /// [`CompactBytestringDeque`] is empty.
+    ///
+    /// The bytes stay in the data ring buffer until a later push writes over them, so this never
+    /// moves any data.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestringDeque;
+    /// let mut deque = CompactBytestringDeque::new();
+    /// deque.push_back(b"One");
+    /// deque.push_back(b"Two");
+    ///
+    /// assert_eq!(deque.pop_front(), Some(b"One".as_slice()));
+    /// assert_eq!(deque.pop_front(), Some(b"Two".as_slice()));
+    /// assert_eq!(deque.pop_front(), None);
+    /// ```
+    pub fn pop_front(&mut self) -> Option<&[u8]> {
+        let (start, len) = self.meta.pop_front()?.as_tuple();
+        if let Some(front) = self.meta.front() {
+            self.used -= self.distance(self.head, front.start);
+            self.head = front.start;
+        } else {
+            self.head = 0;
+            self.used = 0;
+        }
+
+        self.data.get(start..start + len)
+    }
+
+    /// Removes the last bytestring and returns a reference to its bytes, or [`None`] if the
+    /// [`CompactBytestringDeque`] is empty.
+    ///
+    /// The bytes stay in the data ring buffer until a later push writes over them, so this never
+    /// moves any data.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestringDeque;
+    /// let mut deque = CompactBytestringDeque::new();
+    /// deque.push_back(b"One");
+    /// deque.push_back(b"Two");
+    ///
+    /// assert_eq!(deque.pop_back(), Some(b"Two".as_slice()));
+    /// assert_eq!(deque.pop_back(), Some(b"One".as_slice()));
+    /// assert_eq!(deque.pop_back(), None);
+    /// ```
+    pub fn pop_back(&mut self) -> Option<&[u8]> {
+        let (start, len) = self.meta.pop_back()?.as_tuple();
+        if let Some(back) = self.meta.back() {
+            self.used = self.distance(self.head, back.start + back.len);
+        } else {
+            self.head = 0;
+            self.used = 0;
+        }
+
+        self.data.get(start..start + len)
+    }
+
+    /// Returns a reference to the bytestring stored in the [`CompactBytestringDeque`] at that position.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestringDeque;
+    /// let mut deque = CompactBytestringDeque::new();
+    /// deque.push_back(b"Two");
+    /// deque.push_front(b"One");
+    ///
+    /// assert_eq!(deque.get(0), Some(b"One".as_slice()));
+    /// assert_eq!(deque.get(1), Some(b"Two".as_slice()));
+    /// assert_eq!(deque.get(2), None);
+    /// ```
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&[u8]> {
+        let (start, len) = self.meta.get(index)?.as_tuple();
+        if cfg!(feature = "no_unsafe") {
+            self.data.get(start..start + len)
+        } else {
+            unsafe { Some(self.data.get_unchecked(start..start + len)) }
+        }
+    }
+
+    /// Returns a reference to the first bytestring, or [`None`] if the [`CompactBytestringDeque`] is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestringDeque;
+    /// let mut deque = CompactBytestringDeque::new();
+    /// assert_eq!(deque.front(), None);
+    ///
+    /// deque.push_back(b"One");
+    /// deque.push_back(b"Two");
+    /// assert_eq!(deque.front(), Some(b"One".as_slice()));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn front(&self) -> Option<&[u8]> {
+        self.get(0)
+    }
+
+    /// Returns a reference to the last bytestring, or [`None`] if the [`CompactBytestringDeque`] is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestringDeque;
+    /// let mut deque = CompactBytestringDeque::new();
+    /// assert_eq!(deque.back(), None);
+    ///
+    /// deque.push_back(b"One");
+    /// deque.push_back(b"Two");
+    /// assert_eq!(deque.back(), Some(b"Two".as_slice()));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn back(&self) -> Option<&[u8]> {
+        self.get(self.len().checked_sub(1)?)
+    }
+
+    /// Returns the number of bytestrings in the [`CompactBytestringDeque`], also referred to as its 'length'.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestringDeque;
+    /// let mut deque = CompactBytestringDeque::new();
+    ///
+    /// deque.push_back(b"One");
+    /// deque.push_front(b"Two");
+    ///
+    /// assert_eq!(deque.len(), 2);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.meta.len()
+    }
+
+    /// Returns true if the [`CompactBytestringDeque`] contains no bytestrings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestringDeque;
+    /// let mut deque = CompactBytestringDeque::new();
+    /// assert!(deque.is_empty());
+    ///
+    /// deque.push_back(b"One");
+    ///
+    /// assert!(!deque.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of bytes taken up in the data ring buffer, as opposed to its
+    /// [`capacity`].
+    ///
+    /// This includes any bytes skipped when a bytestring did not fit before the end of the buffer.
+    ///
+    /// [`capacity`]: CompactBytestringDeque::capacity
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestringDeque;
+    /// let mut deque = CompactBytestringDeque::with_capacity(20, 3);
+    ///
+    /// deque.push_back(b"One");
+    /// deque.push_back(b"Three");
+    ///
+    /// assert_eq!(deque.data_len(), 8);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn data_len(&self) -> usize {
+        self.used
+    }
+
+    /// Returns the number of bytes used and allocated by the data and meta ring buffers.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestringDeque;
+    /// let mut deque = CompactBytestringDeque::with_capacity(20, 3);
+    /// deque.push_back(b"One");
+    ///
+    /// let usage = deque.memory_usage();
+    /// assert_eq!(usage.data_used, 3);
+    /// assert!(usage.data_reserved >= 20);
+    /// assert!(usage.meta_reserved >= usage.meta_used);
+    /// ```
+    #[must_use]
+    pub fn memory_usage(&self) -> MemoryUsage {
+        MemoryUsage::new::<Metadata>(
+            self.used,
+            self.data.capacity(),
+            self.meta.len(),
+            self.meta.capacity(),
+        )
+    }
+
+    /// Returns the number of bytes the data ring buffer can store without reallocating.
+    ///
+    /// Bytes skipped when a bytestring does not fit before the end of the buffer count against
+    /// this as well.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestringDeque;
+    /// let mut deque = CompactBytestringDeque::with_capacity(20, 3);
+    ///
+    /// deque.push_back(b"One");
+    ///
+    /// assert!(deque.capacity() >= 20);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.data.len().saturating_sub(1)
+    }
+
+    /// Returns the number of starting indices and lengths can store without reallocating.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestringDeque;
+    /// let mut deque = CompactBytestringDeque::with_capacity(20, 3);
+    ///
+    /// deque.push_back(b"One");
+    ///
+    /// assert!(deque.capacity_meta() >= 3);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn capacity_meta(&self) -> usize {
+        self.meta.capacity()
+    }
+
+    /// Clears the [`CompactBytestringDeque`], removing all bytestrings.
+    ///
+    /// Note that this method has no effect on the allocated capacity of the buffers.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestringDeque;
+    /// let mut deque = CompactBytestringDeque::new();
+    ///
+    /// deque.push_back(b"One");
+    /// deque.push_back(b"Two");
+    /// deque.clear();
+    ///
+    /// assert!(deque.is_empty());
+    /// ```
+    pub fn clear(&mut self) {
+        self.meta.clear();
+        self.head = 0;
+        self.used = 0;
+    }
+
+    /// Returns an iterator over the bytestrings from front to back.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestringDeque;
+    /// let mut deque = CompactBytestringDeque::new();
+    /// deque.push_back(b"Two");
+    /// deque.push_front(b"One");
+    /// let mut iterator = deque.iter();
+    ///
+    /// assert_eq!(iterator.next(), Some(b"One".as_slice()));
+    /// assert_eq!(iterator.next(), Some(b"Two".as_slice()));
+    /// assert_eq!(iterator.next(), None);
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter::new(self)
+    }
+
+    /// Returns where a bytestring of `len` bytes pushed to the back would start, marking those
+    /// bytes as taken, or [`None`] if it does not fit.
+    fn back_slot(&mut self, len: usize) -> Option<usize> {
+        let size = self.data.len();
+        let end = self.head + self.used;
+        let (start, taken) = if end > size {
+            (end - size, len)
+        } else if len <= size - end {
+            (end, len)
+        } else {
+            // Skip the rest of the buffer and wrap around to its start.
+            (0, size - end + len)
+        };
+
+        if self.used + taken < size {
+            self.used += taken;
+            Some(start)
+        } else {
+            None
+        }
+    }
+
+    /// Returns where a bytestring of `len` bytes pushed to the front would start, marking those
+    /// bytes as taken, or [`None`] if it does not fit.
+    fn front_slot(&mut self, len: usize) -> Option<usize> {
+        let size = self.data.len();
+        let (start, taken) = if len <= self.head {
+            (self.head - len, len)
+        } else {
+            // Wrap around to the end of the buffer, skipping the bytes before the head.
+            (size.checked_sub(len)?, self.head + len)
+        };
+
+        if self.used + taken < size {
+            self.head = start;
+            self.used += taken;
+            Some(start)
+        } else {
+            None
+        }
+    }
+
+    /// Returns how many bytes lie between `from` and `to` going forwards around the ring.
+    fn distance(&self, from: usize, to: usize) -> usize {
+        if to >= from {
+            to - from
+        } else {
+            to + self.data.len() - from
+        }
+    }
+
+    /// Copies the bytestrings in order into a new data ring buffer with room for at least
+    /// `additional` more bytes, starting `offset` bytes into it.
+    fn grow(&mut self, additional: usize, offset: usize) {
+        let len: usize = self.meta.iter().map(|meta| meta.len).sum();
+        let size = (self.data.len() * 2).max(len + additional + 1);
+
+        let mut data = vec![0; size];
+        let mut end = offset;
+        for meta in &mut self.meta {
+            let (start, len) = meta.as_tuple();
+            data[end..end + len].copy_from_slice(&self.data[start..start + len]);
+            meta.start = end;
+            end += len;
+        }
+
+        self.data = data;
+        self.head = offset;
+        self.used = end - offset;
+    }
+}
+
+/// Allocates a data ring buffer that can hold `capacity` bytes.
+///
+/// One byte more than that is allocated, as the ring is never filled up completely.
+fn ring(capacity: usize) -> Vec<u8> {
+    if capacity == 0 {
+        Vec::new()
+    } else {
+        vec![0; capacity + 1]
+    }
+}
+
+impl Clone for CompactBytestringDeque {
+    fn clone(&self) -> Self {
+        let mut data = ring(self.meta.iter().map(|meta| meta.len).sum());
+        let mut meta = VecDeque::with_capacity(self.meta.len());
+
+        let mut end = 0;
+        for bytes in self {
+            meta.push_back(Metadata::new(end, bytes.len()));
+            data[end..end + bytes.len()].copy_from_slice(bytes);
+            end += bytes.len();
+        }
+
+        Self {
+            data,
+            meta,
+            head: 0,
+            used: end,
+        }
+    }
+}
+
+impl Default for CompactBytestringDeque {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialEq for CompactBytestringDeque {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl Debug for CompactBytestringDeque {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<S> Extend<S> for CompactBytestringDeque
+where
+    S: AsRef<[u8]>,
+{
+    #[inline]
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        for s in iter {
+            self.push_back(s);
+        }
+    }
+}
+
+impl Index<usize> for CompactBytestringDeque {
+    type Output = [u8];
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+
+/// Iterator over bytestrings in a [`CompactBytestringDeque`]
+///
+/// # Examples
+/// ```
+/// # use compact_strings::CompactBytestringDeque;
+/// let mut deque = CompactBytestringDeque::new();
+/// deque.push_back(b"Two");
+/// deque.push_front(b"One");
+///
+/// let mut iter = deque.into_iter();
+/// assert_eq!(iter.next(), Some(b"One".as_slice()));
+/// assert_eq!(iter.next(), Some(b"Two".as_slice()));
+/// assert_eq!(iter.next(), None);
+/// ```
+#[must_use = "Iterators are lazy and do nothing unless consumed"]
+pub struct Iter<'a> {
+    data: &'a [u8],
+    iter: alloc::collections::vec_deque::Iter<'a, Metadata>,
+}
+
+impl<'a> Iter<'a> {
+    #[inline]
+    pub fn new(inner: &'a CompactBytestringDeque) -> Self {
+        Self {
+            data: &inner.data,
+            iter: inner.meta.iter(),
+        }
+    }
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (start, len) = self.iter.next()?.as_tuple();
+
+        if cfg!(feature = "no_unsafe") {
+            self.data.get(start..start + len)
+        } else {
+            unsafe { Some(self.data.get_unchecked(start..start + len)) }
+        }
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let (start, len) = self.iter.nth(n)?.as_tuple();
+
+        if cfg!(feature = "no_unsafe") {
+            self.data.get(start..start + len)
+        } else {
+            unsafe { Some(self.data.get_unchecked(start..start + len)) }
+        }
+    }
+
+    #[inline]
+    fn count(self) -> usize
+    where
+        Self: Sized,
+    {
+        self.len()
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<Self::Item>
+    where
+        Self: Sized,
+    {
+        self.next_back()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Iter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (start, len) = self.iter.next_back()?.as_tuple();
+
+        if cfg!(feature = "no_unsafe") {
+            self.data.get(start..start + len)
+        } else {
+            unsafe { Some(self.data.get_unchecked(start..start + len)) }
+        }
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        let (start, len) = self.iter.nth_back(n)?.as_tuple();
+
+        if cfg!(feature = "no_unsafe") {
+            self.data.get(start..start + len)
+        } else {
+            unsafe { Some(self.data.get_unchecked(start..start + len)) }
+        }
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+impl<'a> IntoIterator for &'a CompactBytestringDeque {
+    type Item = &'a [u8];
+
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<S> FromIterator<S> for CompactBytestringDeque
+where
+    S: AsRef<[u8]>,
+{
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let meta_capacity = match iter.size_hint() {
+            (a, Some(b)) if a == b => a,
+            _ => 0,
+        };
+
+        let mut out = CompactBytestringDeque::with_capacity(0, meta_capacity);
+        for s in iter {
+            out.push_back(s);
+        }
+
+        out
+    }
+}
+
+impl From<CompactBytestrings> for CompactBytestringDeque {
+    /// Reuses the data vector of the [`CompactBytestrings`] as the data ring buffer, copying the
+    /// bytestrings only if they are not laid out in order.
+    ///
+    /// Bytes left behind by [`ignore`](CompactBytestrings::ignore) before the last bytestring
+    /// stay in the ring until the data buffer is next reallocated.
+    fn from(mut value: CompactBytestrings) -> Self {
+        value.restore_order();
+
+        let (head, end) = match (value.meta.first(), value.meta.last()) {
+            (Some(first), Some(last)) => (first.start, last.start + last.len),
+            _ => (0, 0),
+        };
+        let mut data = value.data;
+        if end != 0 && end >= data.len() {
+            data.push(0);
+        }
+
+        Self {
+            data,
+            meta: value.meta.into(),
+            head,
+            used: end - head,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{collections::VecDeque, vec::Vec};
+
+    use crate::{CompactBytestringDeque, CompactBytestrings};
+
+    #[test]
+    fn reuses_popped_bytes() {
+        let mut deque = CompactBytestringDeque::with_capacity(16, 4);
+        let data = deque.data.as_ptr();
+
+        for _ in 0..64 {
+            deque.push_back(b"Stream");
+            deque.push_front(b"Head");
+            assert_eq!(deque.pop_back(), Some(b"Stream".as_slice()));
+            assert_eq!(deque.pop_front(), Some(b"Head".as_slice()));
+            deque.push_back(b"Tail");
+            assert_eq!(deque.pop_front(), Some(b"Tail".as_slice()));
+        }
+
+        assert!(deque.is_empty());
+        assert_eq!(deque.data.as_ptr(), data);
+        assert_eq!(deque.capacity(), 16);
+    }
+
+    #[test]
+    fn wraps_around_the_data_buffer() {
+        let mut deque = CompactBytestringDeque::with_capacity(7, 4);
+        deque.push_back(b"One");
+        deque.push_back(b"Two");
+        assert_eq!(deque.pop_front(), Some(b"One".as_slice()));
+
+        deque.push_back(b"Xy");
+        assert_eq!(deque.meta[1].start, 6);
+        // Nothing is left before the end, so this starts over at the front of the buffer.
+        deque.push_back(b"Z");
+        assert_eq!(deque.meta[2].start, 0);
+        // 'Two' starts at 3, so this fits right before it.
+        deque.push_front(b"A");
+        assert_eq!(deque.meta[0].start, 2);
+        assert_eq!(deque.data_len(), 7);
+        assert_eq!(deque.capacity(), 7);
+        assert!(deque.iter().eq([&b"A"[..], b"Two", b"Xy", b"Z"]));
+
+        // The data buffer is full, so this copies the bytestrings into a bigger one.
+        deque.push_front(b"Tens");
+        assert!(deque.iter().eq([&b"Tens"[..], b"A", b"Two", b"Xy", b"Z"]));
+        assert_eq!(deque.meta[0].start, 0);
+        assert_eq!(deque.meta[1].start, 4);
+    }
+
+    #[test]
+    fn matches_vec_deque() {
+        let mut deque = CompactBytestringDeque::new();
+        let mut model = VecDeque::new();
+
+        for i in 0..2000_usize {
+            let bytes: Vec<u8> = (0..i * 7 % 13).map(|j| (i + j).to_le_bytes()[0]).collect();
+            match i * 31 % 11 {
+                0..=2 => {
+                    deque.push_back(&bytes);
+                    model.push_back(bytes);
+                }
+                3..=5 => {
+                    deque.push_front(&bytes);
+                    model.push_front(bytes);
+                }
+                6 | 7 => assert_eq!(deque.pop_back(), model.pop_back().as_deref()),
+                _ => assert_eq!(deque.pop_front(), model.pop_front().as_deref()),
+            }
+
+            assert!(deque.used < deque.data.len() || deque.data.is_empty());
+            assert!(deque.iter().eq(model.iter().map(Vec::as_slice)));
+        }
+    }
+
+    #[test]
+    fn reuses_compact_bytestrings_data() {
+        let mut cmpbytes = CompactBytestrings::from([b"One", b"Two", b"Six", b"Ten"]);
+        cmpbytes.ignore(0);
+        cmpbytes.ignore(0);
+        cmpbytes.ignore(0);
+
+        let mut deque = CompactBytestringDeque::from(cmpbytes);
+        assert_eq!((deque.head, deque.used), (9, 3));
+
+        let clone = deque.clone();
+        assert_eq!(&clone.data[..3], b"Ten");
+
+        deque.push_back(b"Five");
+        deque.push_front(b"Four");
+        assert!(deque.iter().eq([&b"Four"[..], b"Ten", b"Five"]));
+        assert_eq!(deque.meta[0].start, 5);
+    }
+
+    #[test]
+    fn double_ended_iterator() {
+        let mut deque = CompactBytestringDeque::new();
+
+        deque.push_back(b"Two");
+        deque.push_back(b"Three");
+        deque.push_front(b"One");
+        deque.push_back(b"Four");
+
+        let mut iter = deque.iter();
+        assert_eq!(iter.next(), Some(b"One".as_slice()));
+        assert_eq!(iter.next_back(), Some(b"Four".as_slice()));
+        assert_eq!(iter.next(), Some(b"Two".as_slice()));
+        assert_eq!(iter.next_back(), Some(b"Three".as_slice()));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+}
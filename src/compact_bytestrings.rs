@@ -1,8 +1,22 @@
-use core::{fmt::Debug, ops::Index};
-
-use alloc::vec::Vec;
-
-use crate::{metadata::Metadata, CompactStrings};
+use core::{
+    cmp::Ordering,
+    fmt::Debug,
+    hash::Hasher,
+    ops::{Index, Range, RangeBounds},
+    str::Utf8Error,
+};
+
+use alloc::{borrow::Cow, boxed::Box, collections::BTreeMap, vec::Vec};
+
+use crate::{
+    layout,
+    memory_usage::with_slack,
+    metadata::Metadata,
+    permutation::check_permutation,
+    radix::radix_sort,
+    range::{check_sorted_indices, to_range},
+    search, utf8, CompactStrings, Diff, LengthStats, MemoryUsage, PartsError, SetError,
+};
 
 /// A more compact but limited representation of a list of bytestrings.
 ///
@@ -54,7 +68,7 @@ impl CompactBytestrings {
     ///
     /// - `data_capacity`: The capacity of the data vector where the bytes of the bytestrings are stored.
     /// - `capacity_meta`: The capacity of the meta vector where the starting indices and lengths
-    /// of the bytestrings are stored.
+    ///   of the bytestrings are stored.
     ///
     /// The [`CompactBytestrings`] will be able to hold at least *`data_capacity`* bytes worth of bytestrings
     /// without reallocating the data vector, and at least *`capacity_meta`* of starting indices and
@@ -86,6 +100,164 @@ impl CompactBytestrings {
         }
     }
 
+    /// Constructs a new [`CompactBytestrings`] holding copies of the bytestrings in `bytestrings`.
+    ///
+    /// Unlike collecting from an iterator, this sums up the lengths of the bytestrings first so that
+    /// both vectors are allocated exactly once, which makes it the fastest way to convert
+    /// an existing `Vec<Vec<u8>>`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let vec = vec![b"One".to_vec(), b"Two".to_vec(), b"Three".to_vec()];
+    /// let cmpbytes = CompactBytestrings::from_slice(&vec);
+    ///
+    /// assert_eq!(cmpbytes.len(), 3);
+    /// assert!(cmpbytes.capacity() >= 11);
+    /// assert_eq!(cmpbytes.get(0), Some(b"One".as_slice()));
+    /// assert_eq!(cmpbytes.get(2), Some(b"Three".as_slice()));
+    /// ```
+    #[must_use]
+    pub fn from_slice<S>(bytestrings: &[S]) -> Self
+    where
+        S: AsRef<[u8]>,
+    {
+        let data_capacity = bytestrings.iter().map(|b| b.as_ref().len()).sum();
+        let mut out = Self::with_capacity(data_capacity, bytestrings.len());
+        for bytestring in bytestrings {
+            out.push(bytestring);
+        }
+
+        out
+    }
+
+    /// Constructs a new [`CompactBytestrings`] holding the pieces of `buf` separated by
+    /// `delimiter`, like [`slice::split`].
+    ///
+    /// The pieces are copied into the data vector without their delimiters, which are found
+    /// using a SIMD-accelerated search when the `memchr` feature is enabled.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from_split_bytes(b"One\0Two\0", 0);
+    ///
+    /// assert!(cmpbytes.iter().eq([&b"One"[..], b"Two", b""]));
+    /// ```
+    #[must_use]
+    pub fn from_split_bytes(buf: &[u8], delimiter: u8) -> Self {
+        let mut cmpbytes = Self::with_capacity(buf.len(), 0);
+        for range in search::split(buf, delimiter) {
+            cmpbytes.push(&buf[range]);
+        }
+
+        cmpbytes
+    }
+
+    /// Constructs a new [`CompactBytestrings`] holding the pieces of `buf` separated by
+    /// `delimiter` like [`from_split_bytes`], taking ownership of `buf` instead of copying it.
+    ///
+    /// The delimiters are left in the data vector as bytes no bytestring refers to, which
+    /// [`compact`] removes.
+    ///
+    /// [`from_split_bytes`]: CompactBytestrings::from_split_bytes
+    /// [`compact`]: CompactBytestrings::compact
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from_owned_split(b"One\0Two".to_vec(), 0);
+    ///
+    /// assert!(cmpbytes.iter().eq([b"One", b"Two"]));
+    /// assert_eq!(cmpbytes.garbage_len(), 1);
+    /// ```
+    #[must_use]
+    pub fn from_owned_split(buf: Vec<u8>, delimiter: u8) -> Self {
+        let meta = search::split(&buf, delimiter)
+            .map(|range| Metadata::new(range.start, range.len()))
+            .collect();
+
+        Self { data: buf, meta }
+    }
+
+    /// Constructs a new [`CompactBytestrings`] holding the pieces of `buf` terminated by
+    /// `delimiter`, taking ownership of `buf` like [`from_owned_split`] so that no bytes are copied.
+    ///
+    /// Unlike [`from_owned_split`], a delimiter at the end of `buf` ends the last bytestring
+    /// instead of starting an empty one, which matches newline- or NUL-terminated dumps such as
+    /// the output of `find -print0`. A last piece without a delimiter is still included.
+    ///
+    /// [`from_owned_split`]: CompactBytestrings::from_owned_split
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from_delimited(b"One\nTwo\n\n".to_vec(), b'\n');
+    /// assert!(cmpbytes.iter().eq([&b"One"[..], b"Two", b""]));
+    ///
+    /// let cmpbytes = CompactBytestrings::from_delimited(b"One\0Two".to_vec(), 0);
+    /// assert!(cmpbytes.iter().eq([b"One", b"Two"]));
+    ///
+    /// assert!(CompactBytestrings::from_delimited(Vec::new(), 0).is_empty());
+    /// ```
+    #[must_use]
+    pub fn from_delimited(buf: Vec<u8>, delimiter: u8) -> Self {
+        let mut cmpbytes = Self::from_owned_split(buf, delimiter);
+        let data_len = cmpbytes.data.len();
+        if cmpbytes
+            .meta
+            .last()
+            .map_or(false, |meta| meta.len == 0 && meta.start == data_len)
+        {
+            cmpbytes.meta.pop();
+        }
+
+        cmpbytes
+    }
+
+    /// Constructs a new [`CompactBytestrings`] from a data vector and the `(start, len)` of each
+    /// bytestring in it, checking once that every bytestring is in bounds and then taking
+    /// ownership of both vectors.
+    ///
+    /// If every bytestring starts at or after the end of the one before it, the data is not
+    /// copied, and bytes no bytestring refers to are kept like those left behind by [`ignore`].
+    /// Otherwise the bytestrings are out of order or share bytes, so the data vector is rewritten
+    /// once in the order of `meta`, as removing and counting bytes relies on them being neither.
+    ///
+    /// [`ignore`]: CompactBytestrings::ignore
+    ///
+    /// # Errors
+    /// Returns [`PartsError::OutOfBounds`] if a bytestring extends past the end of `data`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactBytestrings, PartsError};
+    /// let cmpbytes = CompactBytestrings::try_from_parts(b"OneTwo".to_vec(), vec![(3, 3), (0, 3)]);
+    /// assert!(cmpbytes.unwrap().iter().eq([b"Two", b"One"]));
+    ///
+    /// let cmpbytes = CompactBytestrings::try_from_parts(b"OneTwo".to_vec(), vec![(4, 3)]);
+    /// assert_eq!(cmpbytes, Err(PartsError::OutOfBounds));
+    /// ```
+    pub fn try_from_parts(data: Vec<u8>, meta: Vec<(usize, usize)>) -> Result<Self, PartsError> {
+        let in_bounds = meta.iter().all(|&(start, len)| {
+            start
+                .checked_add(len)
+                .map_or(false, |end| end <= data.len())
+        });
+        if !in_bounds {
+            return Err(PartsError::OutOfBounds);
+        }
+
+        let meta = meta
+            .into_iter()
+            .map(|(start, len)| Metadata::new(start, len))
+            .collect();
+
+        let mut cmpbytes = Self { data, meta };
+        cmpbytes.restore_order();
+        Ok(cmpbytes)
+    }
+
     /// Appends a bytestring to the back of the [`CompactBytestrings`].
     ///
     /// # Examples
@@ -111,6 +283,82 @@ impl CompactBytestrings {
         self.data.extend_from_slice(bytestr);
     }
 
+    /// Appends every bytestring in `bytestrings` to the back of the [`CompactBytestrings`].
+    ///
+    /// The lengths of the bytestrings are summed up first so that each vector is reserved at most
+    /// once, rather than potentially reallocating on every [`push`](CompactBytestrings::push).
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::from([b"One"]);
+    /// cmpbytes.push_many(&[&b"Two"[..], b"Three"]);
+    ///
+    /// assert!(cmpbytes.capacity() >= 11);
+    /// assert!(cmpbytes.iter().eq([&b"One"[..], b"Two", b"Three"]));
+    /// ```
+    pub fn push_many<S>(&mut self, bytestrings: &[S])
+    where
+        S: AsRef<[u8]>,
+    {
+        let data_len = bytestrings.iter().map(|b| b.as_ref().len()).sum();
+        self.data.reserve(data_len);
+        self.meta.reserve(bytestrings.len());
+        for bytestring in bytestrings {
+            self.push(bytestring);
+        }
+    }
+
+    /// Appends bytes to the end of the last bytestring in the [`CompactBytestrings`].
+    ///
+    /// If bytes of [`ignore`]d bytestrings come after the last bytestring, it is first copied to
+    /// the end of the data vector so that it can grow in place.
+    ///
+    /// [`ignore`]: CompactBytestrings::ignore
+    ///
+    /// # Panics
+    /// Panics if the [`CompactBytestrings`] is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::new();
+    /// cmpbytes.push(b"One");
+    /// cmpbytes.push(b"Tw");
+    /// cmpbytes.push_bytes_to_last(b"o");
+    ///
+    /// assert_eq!(cmpbytes.get(0), Some(b"One".as_slice()));
+    /// assert_eq!(cmpbytes.get(1), Some(b"Two".as_slice()));
+    /// ```
+    #[track_caller]
+    pub fn push_bytes_to_last<S>(&mut self, bytestring: S)
+    where
+        S: AsRef<[u8]>,
+    {
+        #[cold]
+        #[inline(never)]
+        #[track_caller]
+        fn assert_failed() -> ! {
+            panic!("cannot push to the last bytestring of an empty collection");
+        }
+
+        let bytestr = bytestring.as_ref();
+        let data_len = self.data.len();
+        let last = match self.meta.last_mut() {
+            Some(last) => last,
+            None => assert_failed(),
+        };
+
+        if last.start + last.len != data_len {
+            self.data
+                .extend_from_within(last.start..last.start + last.len);
+            last.start = data_len;
+        }
+
+        self.data.extend_from_slice(bytestr);
+        last.len += bytestr.len();
+    }
+
     /// Returns a reference to the bytestring stored in the [`CompactBytestrings`] at that position.
     ///
     /// # Examples
@@ -136,6 +384,68 @@ impl CompactBytestrings {
         }
     }
 
+    /// Returns a mutable reference to the bytestring stored in the [`CompactBytestrings`] at that position.
+    ///
+    /// The bytes can be changed in place, but the length of the bytestring cannot.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::new();
+    /// cmpbytes.push(b"One");
+    /// cmpbytes.push(b"Two");
+    ///
+    /// if let Some(bytes) = cmpbytes.get_mut(1) {
+    ///     bytes.make_ascii_uppercase();
+    /// }
+    ///
+    /// assert_eq!(cmpbytes.get(1), Some(b"TWO".as_slice()));
+    /// assert_eq!(cmpbytes.get_mut(2), None);
+    /// ```
+    #[must_use]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut [u8]> {
+        let (start, len) = self.meta.get(index)?.as_tuple();
+        if cfg!(feature = "no_unsafe") {
+            self.data.get_mut(start..start + len)
+        } else {
+            unsafe { Some(self.data.get_unchecked_mut(start..start + len)) }
+        }
+    }
+
+    /// Overwrites the bytestring at the specified index with `bytestring`, which must have the same
+    /// length in bytes.
+    ///
+    /// As the length does not change, no bytes have to be shifted over.
+    ///
+    /// # Errors
+    /// Returns [`SetError::OutOfBounds`] if `index` is out of bounds, or
+    /// [`SetError::LengthMismatch`] if the lengths differ, leaving the [`CompactBytestrings`] unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactBytestrings, SetError};
+    /// let mut cmpbytes = CompactBytestrings::from([&b"One"[..], b"Two"]);
+    ///
+    /// assert_eq!(cmpbytes.try_set(1, b"Six"), Ok(()));
+    /// assert_eq!(cmpbytes.try_set(1, b"Three"), Err(SetError::LengthMismatch));
+    /// assert_eq!(cmpbytes.try_set(2, b"Ten"), Err(SetError::OutOfBounds));
+    ///
+    /// assert!(cmpbytes.iter().eq([&b"One"[..], b"Six"]));
+    /// ```
+    pub fn try_set<S>(&mut self, index: usize, bytestring: S) -> Result<(), SetError>
+    where
+        S: AsRef<[u8]>,
+    {
+        let bytestr = bytestring.as_ref();
+        let bytes = self.get_mut(index).ok_or(SetError::OutOfBounds)?;
+        if bytes.len() != bytestr.len() {
+            return Err(SetError::LengthMismatch);
+        }
+
+        bytes.copy_from_slice(bytestr);
+        Ok(())
+    }
+
     /// Returns a reference to the bytestring stored in the [`CompactBytestrings`] at that position, without
     /// doing bounds checking.
     ///
@@ -163,6 +473,66 @@ impl CompactBytestrings {
         self.data.get_unchecked(start..start + len)
     }
 
+    /// Returns references to the bytestrings at each of `indices`, or [`None`] if any index is out of
+    /// bounds.
+    ///
+    /// Every index is bounds checked up front, after which the bytestrings are read without any
+    /// further checks. Indices may repeat.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([&b"One"[..], b"Two", b"Three"]);
+    ///
+    /// assert_eq!(cmpbytes.get_many([2, 0]), Some([&b"Three"[..], b"One"]));
+    /// assert_eq!(cmpbytes.get_many([0, 3]), None);
+    /// ```
+    #[must_use]
+    pub fn get_many<const N: usize>(&self, indices: [usize; N]) -> Option<[&[u8]; N]> {
+        if indices.iter().any(|&index| index >= self.len()) {
+            return None;
+        }
+
+        #[cfg(feature = "no_unsafe")]
+        let bytestrings = indices.map(|index| &self[index]);
+        #[cfg(not(feature = "no_unsafe"))]
+        let bytestrings = indices.map(|index| unsafe { self.get_unchecked(index) });
+
+        Some(bytestrings)
+    }
+
+    /// Returns references to the bytestrings at each index yielded by `indices`, or [`None`] if any
+    /// index is out of bounds, as described in [`get_many`](CompactBytestrings::get_many).
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([&b"One"[..], b"Two", b"Three"]);
+    ///
+    /// assert_eq!(cmpbytes.get_many_vec((0..3).rev().step_by(2)), Some(vec![&b"Three"[..], b"One"]));
+    /// assert_eq!(cmpbytes.get_many_vec([3]), None);
+    /// ```
+    #[must_use]
+    pub fn get_many_vec<I>(&self, indices: I) -> Option<Vec<&[u8]>>
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        let indices: Vec<usize> = indices.into_iter().collect();
+        if indices.iter().any(|&index| index >= self.len()) {
+            return None;
+        }
+
+        #[cfg(feature = "no_unsafe")]
+        let bytestrings = indices.into_iter().map(|index| &self[index]).collect();
+        #[cfg(not(feature = "no_unsafe"))]
+        let bytestrings = indices
+            .into_iter()
+            .map(|index| unsafe { self.get_unchecked(index) })
+            .collect();
+
+        Some(bytestrings)
+    }
+
     /// Returns the number of bytestrings in the [`CompactBytestrings`], also referred to as its 'length'.
     ///
     /// # Examples
@@ -200,6 +570,72 @@ impl CompactBytestrings {
         self.len() == 0
     }
 
+    /// Returns the number of bytes in the data vector, as opposed to its [`capacity`].
+    ///
+    /// This includes the bytes of bytestrings that were [`ignore`]d, which stay in the data vector.
+    ///
+    /// [`capacity`]: CompactBytestrings::capacity
+    /// [`ignore`]: CompactBytestrings::ignore
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::with_capacity(20, 3);
+    ///
+    /// cmpbytes.push(b"One");
+    /// cmpbytes.push(b"Three");
+    ///
+    /// assert_eq!(cmpbytes.data_len(), 8);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn data_len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns the number of bytes used and allocated by the data and meta vectors.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::with_capacity(20, 3);
+    /// cmpbytes.push(b"One");
+    ///
+    /// let usage = cmpbytes.memory_usage();
+    /// assert_eq!(usage.data_used, 3);
+    /// assert!(usage.data_reserved >= 20);
+    /// assert!(usage.meta_reserved >= usage.meta_used);
+    /// ```
+    #[must_use]
+    pub fn memory_usage(&self) -> MemoryUsage {
+        MemoryUsage::new::<Metadata>(
+            self.data.len(),
+            self.data.capacity(),
+            self.meta.len(),
+            self.meta.capacity(),
+        )
+    }
+
+    /// Returns statistics about the lengths of the bytestrings, computed in one pass over the
+    /// metadata.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::from([&b"One"[..], b"Two", b"Three"]);
+    /// cmpbytes.ignore(1);
+    ///
+    /// let stats = cmpbytes.stats();
+    /// assert_eq!(stats.count, 2);
+    /// assert_eq!((stats.min_len, stats.max_len), (3, 5));
+    /// assert_eq!(stats.mean_len(), 4.0);
+    /// assert_eq!(stats.garbage_len, 3);
+    /// ```
+    #[must_use]
+    pub fn stats(&self) -> LengthStats {
+        LengthStats::new(self.meta.iter().map(|meta| meta.len), self.data.len())
+    }
+
     /// Returns the number of bytes the data vector can store without reallocating.
     ///
     /// # Examples
@@ -355,6 +791,77 @@ impl CompactBytestrings {
         self.meta.shrink_to(min_capacity);
     }
 
+    /// Shrinks the capacities of both the data vector and the meta vector as much as possible.
+    ///
+    /// This is equivalent to calling both [`shrink_to_fit`] and [`shrink_meta_to_fit`].
+    ///
+    /// [`shrink_to_fit`]: CompactBytestrings::shrink_to_fit
+    /// [`shrink_meta_to_fit`]: CompactBytestrings::shrink_meta_to_fit
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::with_capacity(20, 10);
+    ///
+    /// cmpbytes.push(b"One");
+    /// cmpbytes.push(b"Two");
+    ///
+    /// cmpbytes.shrink_all_to_fit();
+    /// assert!(cmpbytes.capacity() >= 6);
+    /// assert!(cmpbytes.capacity_meta() >= 2);
+    /// ```
+    #[inline]
+    pub fn shrink_all_to_fit(&mut self) {
+        self.shrink_to_fit();
+        self.shrink_meta_to_fit();
+    }
+
+    /// Shrinks the capacities of both the data vector and the meta vector, leaving room for a
+    /// fraction `slack` of their lengths to be added without reallocating.
+    ///
+    /// For example, a `slack` of `0.1` keeps 10% of spare capacity in each vector. A negative or
+    /// NaN `slack` is treated as zero. If a capacity is already below its target, it is left
+    /// unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::with_capacity(100, 100);
+    ///
+    /// for _ in 0..10 {
+    ///     cmpbytes.push(b"Ten");
+    /// }
+    ///
+    /// cmpbytes.shrink_all_to_fit_with_slack(0.5);
+    /// assert!(cmpbytes.capacity() >= 45 && cmpbytes.capacity() < 100);
+    /// assert!(cmpbytes.capacity_meta() >= 15 && cmpbytes.capacity_meta() < 100);
+    /// ```
+    pub fn shrink_all_to_fit_with_slack(&mut self, slack: f64) {
+        self.data.shrink_to(with_slack(self.data.len(), slack));
+        self.meta.shrink_to(with_slack(self.meta.len(), slack));
+    }
+
+    /// Consumes and leaks the [`CompactBytestrings`], returning a reference that lives for the rest of the
+    /// program, like [`Vec::leak`].
+    ///
+    /// The buffers are shrunk to fit before being leaked. Since the reference can be `'static`,
+    /// the bytestrings it hands out can be `'static` as well, which suits tables that are built once
+    /// and kept for the lifetime of the process.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let table: &'static CompactBytestrings = CompactBytestrings::from([b"One", b"Two"]).leak();
+    /// let first: &'static [u8] = table.get(0).unwrap();
+    ///
+    /// assert_eq!(first, b"One");
+    /// ```
+    #[must_use]
+    pub fn leak<'a>(mut self) -> &'a Self {
+        self.shrink_all_to_fit();
+        Box::leak(Box::new(self))
+    }
+
     /// Removes the data pointing to where the bytestring at the specified index is stored.
     ///
     /// Note: This does not remove the bytes of the bytestring from memory, you may want to use
@@ -397,6 +904,97 @@ impl CompactBytestrings {
         self.meta.remove(index);
     }
 
+    /// Removes the data pointing to where the bytestrings at the specified indices are stored, which
+    /// must be sorted and unique.
+    ///
+    /// Note: This does not remove the bytes of the bytestrings from memory, you may want to use
+    /// [`remove_many`] if you desire that behavior, or call [`compact`] later.
+    ///
+    /// The meta vector is shifted over in a single pass, rather than once per bytestring as repeated
+    /// calls to [`ignore`] would.
+    ///
+    /// [`remove_many`]: CompactBytestrings::remove_many
+    /// [`compact`]: CompactBytestrings::compact
+    /// [`ignore`]: CompactBytestrings::ignore
+    ///
+    /// # Panics
+    /// Panics if `indices` is not strictly increasing or if an index is out of bounds.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::from([&b"One"[..], b"Two", b"Three", b"Four"]);
+    /// cmpbytes.ignore_many(&[0, 2]);
+    ///
+    /// assert!(cmpbytes.iter().eq([&b"Two"[..], b"Four"]));
+    /// assert_eq!(cmpbytes.data_len(), 15);
+    /// ```
+    #[track_caller]
+    pub fn ignore_many(&mut self, indices: &[usize]) {
+        check_sorted_indices(indices, self.len());
+        if indices.is_empty() {
+            return;
+        }
+
+        let mut ignored = indices.iter().peekable();
+        let mut index = 0;
+        self.meta.retain(|_| {
+            let keep = ignored.next_if_eq(&&index).is_none();
+            index += 1;
+            keep
+        });
+    }
+
+    /// Removes the data pointing to where the bytestring at the specified index is stored,
+    /// replacing it with the last bytestring.
+    ///
+    /// Note: This does not remove the bytes of the bytestring from memory, you may want to use
+    /// [`remove`] if you desire that behavior.
+    ///
+    /// Note: This does not preserve ordering, but is *O*(1). If you need to preserve the ordering,
+    /// use [`ignore`] instead.
+    ///
+    /// Because the moved bytestring no longer follows the bytestrings before it in the data
+    /// vector, the next call to [`remove`], [`remove_range`] or [`compact`] copies the bytes into
+    /// a new data vector in order first.
+    ///
+    /// [`remove`]: CompactBytestrings::remove
+    /// [`ignore`]: CompactBytestrings::ignore
+    /// [`remove_range`]: CompactBytestrings::remove_range
+    /// [`compact`]: CompactBytestrings::compact
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::with_capacity(20, 3);
+    ///
+    /// cmpbytes.push(b"One");
+    /// cmpbytes.push(b"Two");
+    /// cmpbytes.push(b"Three");
+    ///
+    /// cmpbytes.swap_ignore(0);
+    ///
+    /// assert_eq!(cmpbytes.get(0), Some(b"Three".as_slice()));
+    /// assert_eq!(cmpbytes.get(1), Some(b"Two".as_slice()));
+    /// assert_eq!(cmpbytes.get(2), None);
+    /// ```
+    #[track_caller]
+    pub fn swap_ignore(&mut self, index: usize) {
+        #[cold]
+        #[inline(never)]
+        #[track_caller]
+        fn assert_failed(index: usize, len: usize) -> ! {
+            panic!("swap_ignore index (is {index}) should be < len (is {len})");
+        }
+
+        let len = self.len();
+        if index >= len {
+            assert_failed(index, len);
+        }
+
+        self.meta.swap_remove(index);
+    }
+
     /// Removes the bytes of the bytestring and data pointing to the bytestring is stored.
     ///
     /// Note: This does not shrink the vectors where the bytes of the bytestring and data to the bytestring
@@ -442,15 +1040,17 @@ impl CompactBytestrings {
             assert_failed(index, len);
         }
 
+        self.restore_order();
         let (start, len) = self.meta.remove(index).as_tuple();
         let inner_len = self.data.len();
 
         for meta in self.meta.iter_mut().skip(index) {
-            meta.start -= start;
+            meta.start -= len;
         }
 
         if cfg!(feature = "no_unsafe") {
             self.data.copy_within(start + len..inner_len, start);
+            self.data.truncate(inner_len - len);
         } else {
             unsafe {
                 let ptr = self.data.as_mut_ptr().add(start);
@@ -462,6 +1062,1338 @@ impl CompactBytestrings {
         }
     }
 
+    /// Removes the bytestring at the specified index and returns it, shifting the bytestrings after it
+    /// to the left.
+    ///
+    /// The bytes of the bytestring are copied out before the remaining bytes are shifted over, so
+    /// this has the same worst-case performance of *O*(*n*) as [`remove`].
+    ///
+    /// [`remove`]: CompactBytestrings::remove
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::from([&b"One"[..], b"Two", b"Three"]);
+    ///
+    /// assert_eq!(cmpbytes.take(1), b"Two");
+    /// assert!(cmpbytes.iter().eq([&b"One"[..], b"Three"]));
+    /// ```
+    #[track_caller]
+    pub fn take(&mut self, index: usize) -> Vec<u8> {
+        #[cold]
+        #[inline(never)]
+        #[track_caller]
+        fn assert_failed(index: usize, len: usize) -> ! {
+            panic!("removal index (is {index}) should be < len (is {len})");
+        }
+
+        let len = self.len();
+        if index >= len {
+            assert_failed(index, len);
+        }
+
+        let taken = self[index].to_vec();
+        self.remove(index);
+        taken
+    }
+
+    /// Removes the bytestrings at the specified indices, which must be sorted and unique.
+    ///
+    /// Every remaining bytestring is shifted over at most once, so this takes *O*(*n*) time in total
+    /// rather than *O*(*k* \* *n*) for *k* separate calls to [`remove`].
+    ///
+    /// [`remove`]: CompactBytestrings::remove
+    ///
+    /// # Panics
+    /// Panics if `indices` is not strictly increasing or if an index is out of bounds.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::from([&b"One"[..], b"Two", b"Three", b"Four"]);
+    /// cmpbytes.remove_many(&[0, 2]);
+    ///
+    /// assert!(cmpbytes.iter().eq([&b"Two"[..], b"Four"]));
+    /// ```
+    #[track_caller]
+    pub fn remove_many(&mut self, indices: &[usize]) {
+        check_sorted_indices(indices, self.len());
+        if indices.is_empty() {
+            return;
+        }
+
+        self.restore_order();
+        let mut removed = indices.iter().peekable();
+        let mut kept = 0;
+        let mut write = 0;
+        for read in 0..self.meta.len() {
+            if removed.next_if_eq(&&read).is_some() {
+                continue;
+            }
+
+            let (start, len) = self.meta[read].as_tuple();
+            self.data.copy_within(start..start + len, write);
+            self.meta[kept] = Metadata::new(write, len);
+            kept += 1;
+            write += len;
+        }
+
+        self.meta.truncate(kept);
+        self.data.truncate(write);
+    }
+
+    /// Removes the bytes of the bytestrings in the specified range and the data pointing to them.
+    ///
+    /// This shifts the remaining bytes in the data vector and the remaining elements in the meta
+    /// vector only once, rather than once per bytestring as repeated calls to [`remove`] would.
+    ///
+    /// [`remove`]: CompactBytestrings::remove
+    ///
+    /// # Panics
+    /// Panics if the starting point is greater than the end point or if the end point is greater
+    /// than the length of the [`CompactBytestrings`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::with_capacity(20, 4);
+    ///
+    /// cmpbytes.push(b"One");
+    /// cmpbytes.push(b"Two");
+    /// cmpbytes.push(b"Three");
+    /// cmpbytes.push(b"Four");
+    ///
+    /// cmpbytes.remove_range(1..3);
+    ///
+    /// assert_eq!(cmpbytes.get(0), Some(b"One".as_slice()));
+    /// assert_eq!(cmpbytes.get(1), Some(b"Four".as_slice()));
+    /// assert_eq!(cmpbytes.get(2), None);
+    /// ```
+    #[track_caller]
+    pub fn remove_range<R>(&mut self, range: R)
+    where
+        R: RangeBounds<usize>,
+    {
+        let Range { start, end } = to_range(range, self.len());
+        if start == end {
+            return;
+        }
+
+        self.restore_order();
+        let lo = self.meta[start].start;
+        let hi = {
+            let last = &self.meta[end - 1];
+            last.start + last.len
+        };
+
+        self.meta.drain(start..end);
+        for meta in self.meta.iter_mut().skip(start) {
+            meta.start -= hi - lo;
+        }
+
+        self.data.drain(lo..hi);
+    }
+
+    /// Replaces the bytestrings in the specified range with the bytestrings of `replace_with`, returning
+    /// the removed bytestrings in a new [`CompactBytestrings`].
+    ///
+    /// Like [`Vec::splice`], the tail of the data vector and of the starting indices is only
+    /// shifted once, regardless of how many bytestrings are removed or inserted.
+    ///
+    /// # Panics
+    /// Panics if the starting point is greater than the end point or if the end point is greater
+    /// than the length of the [`CompactBytestrings`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::from([&b"One"[..], b"Two", b"Three", b"Four"]);
+    /// let removed = cmpbytes.splice(1..3, [&b"2"[..], b"3", b"3.5"]);
+    ///
+    /// assert!(cmpbytes.iter().eq([&b"One"[..], b"2", b"3", b"3.5", b"Four"]));
+    /// assert!(removed.iter().eq([b"Two".as_slice(), b"Three"]));
+    /// ```
+    #[track_caller]
+    #[must_use = "the removed bytestrings are returned in a new collection"]
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Self
+    where
+        R: RangeBounds<usize>,
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        let range = to_range(range, self.len());
+        let replacement = replace_with.into_iter().collect();
+        self.splice_from(range, replacement)
+    }
+
+    /// Replaces the bytestrings in `range`, which must be in bounds, with those of `replacement`.
+    pub(crate) fn splice_from(&mut self, range: Range<usize>, replacement: Self) -> Self {
+        let removed = self.gather(range.clone());
+
+        self.restore_order();
+        let lo = self
+            .meta
+            .get(range.start)
+            .map_or(self.data.len(), |meta| meta.start);
+        let hi = match range.end.checked_sub(1).filter(|&last| last >= range.start) {
+            Some(last) => self.meta[last].start + self.meta[last].len,
+            None => lo,
+        };
+
+        let inserted = replacement.data.len();
+        let inserted_count = replacement.meta.len();
+        self.data.splice(lo..hi, replacement.data);
+        self.meta.splice(
+            range.clone(),
+            replacement
+                .meta
+                .into_iter()
+                .map(|meta| Metadata::new(lo + meta.start, meta.len)),
+        );
+        for meta in self.meta.iter_mut().skip(range.start + inserted_count) {
+            meta.start = meta.start - (hi - lo) + inserted;
+        }
+
+        removed
+    }
+
+    /// Removes all but the first of consecutive bytestrings that `same_bucket` considers equal.
+    ///
+    /// `same_bucket` is passed each bytestring followed by the last bytestring that was kept before
+    /// it, like [`Vec::dedup_by`]. The bytes of the remaining bytestrings are then moved together
+    /// in a single pass over the data vector.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::from([b"One", b"ONE", b"Two", b"one"]);
+    /// cmpbytes.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    ///
+    /// assert!(cmpbytes.iter().eq([b"One", b"Two", b"one"]));
+    /// ```
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+    where
+        F: FnMut(&[u8], &[u8]) -> bool,
+    {
+        let data = &self.data;
+        self.meta.dedup_by(|a, b| {
+            same_bucket(
+                &data[a.start..a.start + a.len],
+                &data[b.start..b.start + b.len],
+            )
+        });
+
+        self.compact();
+    }
+
+    /// Removes every bytestring for which `pred` returns true, returning how many were removed.
+    ///
+    /// The bytes of the remaining bytestrings are moved together in a single pass over the data
+    /// vector, rather than once per bytestring as repeated calls to [`remove`] would.
+    ///
+    /// [`remove`]: CompactBytestrings::remove
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::from([&b"One"[..], b"Two", b"Three", b"Four"]);
+    ///
+    /// assert_eq!(cmpbytes.remove_matching(|bytes| bytes.starts_with(b"T")), 2);
+    /// assert!(cmpbytes.iter().eq([&b"One"[..], b"Four"]));
+    /// ```
+    pub fn remove_matching<F>(&mut self, mut pred: F) -> usize
+    where
+        F: FnMut(&[u8]) -> bool,
+    {
+        let len = self.meta.len();
+        let data = &self.data;
+        self.meta
+            .retain(|meta| !pred(&data[meta.start..meta.start + meta.len]));
+
+        self.compact();
+        len - self.meta.len()
+    }
+
+    /// Removes all but the first of consecutive bytestrings that map to the same key.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::from([&b"One"[..], b"Two", b"Three", b"Four"]);
+    /// cmpbytes.dedup_by_key(|bytes| bytes.len());
+    ///
+    /// assert!(cmpbytes.iter().eq([b"One".as_slice(), b"Three", b"Four"]));
+    /// ```
+    #[inline]
+    pub fn dedup_by_key<K, F>(&mut self, mut key: F)
+    where
+        K: PartialEq,
+        F: FnMut(&[u8]) -> K,
+    {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    /// Returns a new [`CompactBytestrings`] holding copies of the bytestrings at `indices`, in that order.
+    ///
+    /// Indices may repeat. The lengths of the selected bytestrings are summed first, so the data
+    /// vector of the new [`CompactBytestrings`] is allocated exactly once and with no spare capacity.
+    ///
+    /// # Panics
+    /// Panics if any index is out of bounds.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([&b"One"[..], b"Two", b"Three"]);
+    /// let gathered = cmpbytes.gather([2, 0, 2]);
+    ///
+    /// assert!(gathered.iter().eq([&b"Three"[..], b"One", b"Three"]));
+    /// ```
+    #[track_caller]
+    #[must_use]
+    pub fn gather<I>(&self, indices: I) -> Self
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        #[cold]
+        #[inline(never)]
+        #[track_caller]
+        fn assert_failed(index: usize, len: usize) -> ! {
+            panic!("gather index (is {index}) should be < len (is {len})");
+        }
+
+        let indices: Vec<usize> = indices.into_iter().collect();
+        let mut data_len = 0;
+        for &index in &indices {
+            match self.get(index) {
+                Some(bytestring) => data_len += bytestring.len(),
+                None => assert_failed(index, self.len()),
+            }
+        }
+
+        let mut gathered = Self::with_capacity(data_len, indices.len());
+        for index in indices {
+            gathered.push(&self[index]);
+        }
+
+        gathered
+    }
+
+    /// Returns a new [`CompactBytestrings`] holding copies of the bytestrings for which `pred` returns true.
+    ///
+    /// Unlike collecting a filtered iterator, this finds the matching bytestrings and the total
+    /// length of their bytes first, so the data vector of the new [`CompactBytestrings`] is allocated only once.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([&b"One"[..], b"Two", b"Three"]);
+    /// let filtered = cmpbytes.filtered(|bytes| bytes.len() == 3);
+    ///
+    /// assert!(filtered.iter().eq([b"One", b"Two"]));
+    /// ```
+    #[must_use]
+    pub fn filtered<F>(&self, mut pred: F) -> Self
+    where
+        F: FnMut(&[u8]) -> bool,
+    {
+        let indices: Vec<usize> = (0..self.len()).filter(|&i| pred(&self[i])).collect();
+        self.gather(indices)
+    }
+
+    /// Returns a new [`CompactBytestrings`] holding the result of `f` for each bytestring.
+    ///
+    /// Borrowed results are copied straight into the data vector of the new [`CompactBytestrings`], so bytestrings
+    /// that `f` leaves unchanged or only slices never need an allocation of their own.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// use std::borrow::Cow;
+    ///
+    /// let cmpbytes = CompactBytestrings::from([&b"One"[..], b"Two!", b"Three"]);
+    /// let mapped = cmpbytes.map_bytestrings(|bytes| match bytes.strip_suffix(b"!") {
+    ///     Some(bytes) => Cow::Owned(bytes.repeat(2)),
+    ///     None => Cow::Borrowed(bytes),
+    /// });
+    ///
+    /// assert!(mapped.iter().eq([&b"One"[..], b"TwoTwo", b"Three"]));
+    /// ```
+    #[must_use]
+    pub fn map_bytestrings<'a, F>(&'a self, mut f: F) -> Self
+    where
+        F: FnMut(&'a [u8]) -> Cow<'a, [u8]>,
+    {
+        let mut mapped = Self::with_capacity(self.data_len(), self.len());
+        for item in self {
+            mapped.push(f(item));
+        }
+
+        mapped
+    }
+
+    /// Groups the bytestrings by the key that `key` returns for each of them, returning a map from
+    /// each key to a new [`CompactBytestrings`] holding copies of its bytestrings in their original
+    /// order.
+    ///
+    /// The keys are computed once per bytestring, and the number and total length of the
+    /// bytestrings in each group are counted before copying them, so the buffers of every group
+    /// are allocated exactly once.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([&b"One"[..], b"Three", b"Two", b"Four"]);
+    /// let groups = cmpbytes.group_by_key(<[u8]>::len);
+    ///
+    /// assert_eq!(groups.len(), 3);
+    /// assert!(groups[&3].iter().eq([b"One", b"Two"]));
+    /// assert!(groups[&5].iter().eq([b"Three"]));
+    /// ```
+    #[must_use]
+    pub fn group_by_key<K, F>(&self, mut key: F) -> BTreeMap<K, Self>
+    where
+        K: Ord,
+        F: FnMut(&[u8]) -> K,
+    {
+        let mut ids = BTreeMap::new();
+        let mut sizes: Vec<(usize, usize)> = Vec::new();
+        let mut group_of = Vec::with_capacity(self.len());
+        for bytestring in self {
+            let next_id = ids.len();
+            let id = *ids.entry(key(bytestring)).or_insert(next_id);
+            if id == sizes.len() {
+                sizes.push((0, 0));
+            }
+            sizes[id].0 += bytestring.len();
+            sizes[id].1 += 1;
+            group_of.push(id);
+        }
+
+        let mut groups: Vec<Self> = sizes
+            .into_iter()
+            .map(|(data_len, len)| Self::with_capacity(data_len, len))
+            .collect();
+        for (bytestring, id) in self.iter().zip(group_of) {
+            groups[id].push(bytestring);
+        }
+
+        ids.into_iter()
+            .map(|(key, id)| (key, core::mem::take(&mut groups[id])))
+            .collect()
+    }
+
+    /// Returns the number of bytes in the data vector that are no longer referenced by any
+    /// bytestring, such as those left behind by [`ignore`].
+    ///
+    /// This sums the lengths of all bytestrings, so it runs in *O*(*n*) time.
+    ///
+    /// [`ignore`]: CompactBytestrings::ignore
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::from([b"One", b"Two", b"Six"]);
+    /// assert_eq!(cmpbytes.garbage_len(), 0);
+    ///
+    /// cmpbytes.ignore(1);
+    /// assert_eq!(cmpbytes.garbage_len(), 3);
+    /// ```
+    #[must_use]
+    pub fn garbage_len(&self) -> usize {
+        self.data.len() - self.meta.iter().map(|m| m.len).sum::<usize>()
+    }
+
+    /// Returns the fraction of bytes in the data vector that are referenced by a bytestring, from
+    /// `0.0` to `1.0`.
+    ///
+    /// An empty data vector is considered fully utilized. Together with [`garbage_len`], this
+    /// helps decide when running [`compact`] is worth it.
+    ///
+    /// [`garbage_len`]: CompactBytestrings::garbage_len
+    /// [`compact`]: CompactBytestrings::compact
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::from([b"One", b"Two", b"Six", b"Ten"]);
+    /// assert_eq!(cmpbytes.utilization(), 1.0);
+    ///
+    /// cmpbytes.ignore(1);
+    /// assert_eq!(cmpbytes.utilization(), 0.75);
+    /// ```
+    #[allow(clippy::cast_precision_loss)]
+    #[must_use]
+    pub fn utilization(&self) -> f64 {
+        if self.data.is_empty() {
+            return 1.0;
+        }
+
+        (self.data.len() - self.garbage_len()) as f64 / self.data.len() as f64
+    }
+
+    /// Reclaims the bytes left behind in the data vector by [`ignore`], moving the bytes of every
+    /// bytestring to the front of the data vector and truncating it.
+    ///
+    /// This runs in a single pass over the data vector. The capacity is left unchanged, so call
+    /// [`shrink_to_fit`] afterwards to release the memory.
+    ///
+    /// [`ignore`]: CompactBytestrings::ignore
+    /// [`shrink_to_fit`]: CompactBytestrings::shrink_to_fit
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::from([b"One", b"Two", b"Six"]);
+    /// cmpbytes.ignore(1);
+    /// assert_eq!(cmpbytes.data_len(), 9);
+    ///
+    /// cmpbytes.compact();
+    /// assert_eq!(cmpbytes.data_len(), 6);
+    /// assert!(cmpbytes.iter().eq([b"One", b"Six"]));
+    /// ```
+    pub fn compact(&mut self) {
+        if !self.starts_in_order() {
+            self.rewrite_in_order();
+            return;
+        }
+
+        // Each bytestring starts at or after the end of the one before it, so moving each
+        // bytestring to the front in order never overwrites one that has yet to be moved.
+        let mut write = 0;
+        for meta in &mut self.meta {
+            self.data
+                .copy_within(meta.start..meta.start + meta.len, write);
+            meta.start = write;
+            write += meta.len;
+        }
+
+        self.data.truncate(write);
+    }
+
+    /// Sorts the bytestrings.
+    ///
+    /// This sort is stable. The meta vector is sorted first, after which the bytes are copied into
+    /// a new data vector in their sorted order, dropping the bytes of [`ignore`]d bytestrings.
+    ///
+    /// [`ignore`]: CompactBytestrings::ignore
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::from([b"Two", b"One", b"Six"]);
+    /// cmpbytes.sort();
+    ///
+    /// assert!(cmpbytes.iter().eq([b"One", b"Six", b"Two"]));
+    /// ```
+    #[inline]
+    pub fn sort(&mut self) {
+        self.sort_by(Ord::cmp);
+    }
+
+    /// Sorts the bytestrings with a comparator function.
+    ///
+    /// This sort is stable, and rewrites the data vector once like [`sort`].
+    ///
+    /// [`sort`]: CompactBytestrings::sort
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::from([&b"Three"[..], b"One", b"Four"]);
+    /// cmpbytes.sort_by(|a, b| b.cmp(a));
+    ///
+    /// assert!(cmpbytes.iter().eq([&b"Three"[..], b"One", b"Four"]));
+    /// ```
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&[u8], &[u8]) -> Ordering,
+    {
+        /// Rewrites the data vector even if `compare` panics, as the meta vector may have been
+        /// partially reordered.
+        struct Guard<'a>(&'a mut CompactBytestrings);
+
+        impl Drop for Guard<'_> {
+            fn drop(&mut self) {
+                self.0.rewrite_in_order();
+            }
+        }
+
+        let guard = Guard(self);
+        let inner = &mut *guard.0;
+        let data = &inner.data;
+        inner.meta.sort_by(|a, b| {
+            compare(
+                &data[a.start..a.start + a.len],
+                &data[b.start..b.start + b.len],
+            )
+        });
+    }
+
+    /// Sorts the bytestrings with a key extraction function.
+    ///
+    /// This sort is stable, and rewrites the data vector once like [`sort`].
+    ///
+    /// [`sort`]: CompactBytestrings::sort
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::from([&b"Three"[..], b"One", b"Four"]);
+    /// cmpbytes.sort_by_key(<[u8]>::len);
+    ///
+    /// assert!(cmpbytes.iter().eq([&b"One"[..], b"Four", b"Three"]));
+    /// ```
+    #[inline]
+    pub fn sort_by_key<K, F>(&mut self, mut key: F)
+    where
+        K: Ord,
+        F: FnMut(&[u8]) -> K,
+    {
+        self.sort_by(|a, b| key(a).cmp(&key(b)));
+    }
+
+    /// Returns true if the bytestrings are sorted in ascending order.
+    ///
+    /// Adjacent bytestrings are compared straight out of the data vector, without allocating. This
+    /// checks the precondition of binary-search-based methods such as [`iter_sorted_with_prefix`].
+    ///
+    /// [`iter_sorted_with_prefix`]: CompactBytestrings::iter_sorted_with_prefix
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::from([b"Two", b"One", b"Six"]);
+    /// assert!(!cmpbytes.is_sorted());
+    ///
+    /// cmpbytes.sort();
+    /// assert!(cmpbytes.is_sorted());
+    /// ```
+    #[must_use]
+    pub fn is_sorted(&self) -> bool {
+        self.is_sorted_by(Ord::cmp)
+    }
+
+    /// Returns true if the bytestrings are sorted by a comparator function, meaning that it never
+    /// returns [`Ordering::Greater`] for a bytestring and the one after it.
+    ///
+    /// This takes the same comparator as [`sort_by`], after which it always returns true.
+    ///
+    /// [`sort_by`]: CompactBytestrings::sort_by
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([&b"Two"[..], b"Three", b"One"]);
+    ///
+    /// assert!(cmpbytes.is_sorted_by(|a, b| b.cmp(a)));
+    /// assert!(!cmpbytes.is_sorted_by(|a, b| a.len().cmp(&b.len())));
+    /// ```
+    pub fn is_sorted_by<F>(&self, mut compare: F) -> bool
+    where
+        F: FnMut(&[u8], &[u8]) -> Ordering,
+    {
+        self.iter()
+            .zip(self.iter().skip(1))
+            .all(|(a, b)| compare(a, b) != Ordering::Greater)
+    }
+
+    /// Returns the index of the first bytestring for which `pred` returns false, assuming that it
+    /// returns true for every bytestring before that one and false for every bytestring after it, like
+    /// [`slice::partition_point`].
+    ///
+    /// This is a binary search, so it runs in *O*(log *n*) time. If the bytestrings are not
+    /// partitioned by `pred`, the index returned is unspecified.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([&b"ant"[..], b"bee", b"cat", b"dog"]);
+    ///
+    /// assert_eq!(cmpbytes.partition_point(|s| s < &b"c"[..]), 2);
+    /// assert_eq!(cmpbytes.partition_point(|_| true), 4);
+    /// ```
+    pub fn partition_point<P>(&self, mut pred: P) -> usize
+    where
+        P: FnMut(&[u8]) -> bool,
+    {
+        search::partition_point(self.len(), |i| pred(&self[i]))
+    }
+
+    /// Inserts a bytestring at the position that keeps the [`CompactBytestrings`] sorted, returning
+    /// its index.
+    ///
+    /// The position is found with a binary search, after any bytestrings equal to the new one, and
+    /// the bytes after it are shifted over once. If the [`CompactBytestrings`] is not sorted, the
+    /// position is unspecified.
+    ///
+    /// The data vector is rewritten first if [`swap_ignore`](CompactBytestrings::swap_ignore) left
+    /// the bytestrings out of order.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::from([b"One", b"Two"]);
+    ///
+    /// assert_eq!(cmpbytes.insert_sorted(b"Six"), 1);
+    /// assert_eq!(cmpbytes.insert_sorted(b"Ten"), 2);
+    /// assert!(cmpbytes.iter().eq([b"One", b"Six", b"Ten", b"Two"]));
+    /// ```
+    pub fn insert_sorted<S>(&mut self, bytestring: S) -> usize
+    where
+        S: AsRef<[u8]>,
+    {
+        let bytestr = bytestring.as_ref();
+        self.restore_order();
+        let index = self.partition_point(|s| s <= bytestr);
+        let start = self
+            .meta
+            .get(index)
+            .map_or(self.data.len(), |meta| meta.start);
+
+        self.data.splice(start..start, bytestr.iter().copied());
+        for meta in &mut self.meta[index..] {
+            meta.start += bytestr.len();
+        }
+        self.meta.insert(index, Metadata::new(start, bytestr.len()));
+
+        index
+    }
+
+    /// Merges two sorted [`CompactBytestrings`] into a new sorted [`CompactBytestrings`].
+    ///
+    /// Both the data vector and the meta vector of the result are allocated exactly once. Equal
+    /// bytestrings from `a` are placed before those from `b`. If either input is not sorted, the
+    /// order of the result is unspecified.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let a = CompactBytestrings::from([b"One", b"Ten"]);
+    /// let b = CompactBytestrings::from([b"Six", b"Two"]);
+    /// let merged = CompactBytestrings::merge_sorted(&a, &b);
+    ///
+    /// assert!(merged.iter().eq([b"One", b"Six", b"Ten", b"Two"]));
+    /// ```
+    #[must_use]
+    pub fn merge_sorted(a: &Self, b: &Self) -> Self {
+        let data_len = a.lengths().sum::<usize>() + b.lengths().sum::<usize>();
+        let mut merged = Self::with_capacity(data_len, a.len() + b.len());
+        let mut a_iter = a.iter().peekable();
+        let mut b_iter = b.iter().peekable();
+
+        while let (Some(&x), Some(&y)) = (a_iter.peek(), b_iter.peek()) {
+            if y < x {
+                merged.push(y);
+                b_iter.next();
+            } else {
+                merged.push(x);
+                a_iter.next();
+            }
+        }
+        merged.extend(a_iter);
+        merged.extend(b_iter);
+
+        merged
+    }
+
+    /// Returns the indices of the bytestrings that are only in `self` and of those that are only
+    /// in `other`, comparing them as multisets.
+    ///
+    /// Sorted collections are compared in one linear pass. Otherwise, the unsorted collection is
+    /// put in order with [`sorted_indices`](CompactBytestrings::sorted_indices) first.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let old = CompactBytestrings::from([b"One", b"Six", b"Two"]);
+    /// let new = CompactBytestrings::from([b"One", b"Ten", b"Two"]);
+    ///
+    /// let diff = old.diff(&new);
+    /// assert_eq!(diff.removed, [1]);
+    /// assert_eq!(diff.added, [1]);
+    /// ```
+    #[must_use]
+    pub fn diff(&self, other: &Self) -> Diff {
+        fn order(cmpbytes: &CompactBytestrings) -> Vec<usize> {
+            if cmpbytes.is_sorted() {
+                (0..cmpbytes.len()).collect()
+            } else {
+                cmpbytes.sorted_indices()
+            }
+        }
+
+        Diff::new(&order(self), |i| &self[i], &order(other), |i| &other[i])
+    }
+
+    /// Sorts the bytestrings with a radix sort, which distributes them by one byte at a time
+    /// instead of comparing them.
+    ///
+    /// This sort is stable and gives the same order as [`sort`], but is usually faster for large
+    /// collections of short bytestrings.
+    ///
+    /// [`sort`]: CompactBytestrings::sort
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::from([&b"Two"[..], b"One", b"On", b"Six"]);
+    /// cmpbytes.sort_radix();
+    ///
+    /// assert!(cmpbytes.iter().eq([&b"On"[..], b"One", b"Six", b"Two"]));
+    /// ```
+    pub fn sort_radix(&mut self) {
+        let data = &self.data;
+        radix_sort(&mut self.meta, |meta| {
+            &data[meta.start..meta.start + meta.len]
+        });
+        self.rewrite_in_order();
+    }
+
+    /// Returns the indices of the bytestrings in the order that would sort them, without reordering
+    /// the [`CompactBytestrings`] itself.
+    ///
+    /// Equal bytestrings keep their relative order.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([b"Two", b"One", b"Six"]);
+    ///
+    /// assert_eq!(cmpbytes.sorted_indices(), [1, 2, 0]);
+    /// ```
+    #[must_use]
+    pub fn sorted_indices(&self) -> Vec<usize> {
+        self.sorted_indices_by(Ord::cmp)
+    }
+
+    /// Returns the indices of the bytestrings in the order that would sort them with a comparator
+    /// function, without reordering the [`CompactBytestrings`] itself.
+    ///
+    /// Equal bytestrings keep their relative order.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([&b"Three"[..], b"One", b"Four"]);
+    ///
+    /// assert_eq!(cmpbytes.sorted_indices_by(|a, b| a.len().cmp(&b.len())), [1, 2, 0]);
+    /// ```
+    pub fn sorted_indices_by<F>(&self, mut compare: F) -> Vec<usize>
+    where
+        F: FnMut(&[u8], &[u8]) -> Ordering,
+    {
+        let mut indices: Vec<usize> = (0..self.len()).collect();
+        indices.sort_by(|&a, &b| compare(&self[a], &self[b]));
+        indices
+    }
+
+    /// Reorders the bytestrings so that the bytestring at each position `i` is the one that was at
+    /// `perm[i]`, rewriting the data vector once.
+    ///
+    /// Passing the result of [`sorted_indices`] sorts the [`CompactBytestrings`].
+    ///
+    /// [`sorted_indices`]: CompactBytestrings::sorted_indices
+    ///
+    /// # Panics
+    /// Panics if `perm` is not a permutation of `0..len`, where `len` is the number of bytestrings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::from([b"One", b"Two", b"Six"]);
+    /// cmpbytes.apply_permutation(&[2, 0, 1]);
+    ///
+    /// assert!(cmpbytes.iter().eq([b"Six", b"One", b"Two"]));
+    /// ```
+    #[track_caller]
+    pub fn apply_permutation(&mut self, perm: &[usize]) {
+        check_permutation(perm, self.meta.len());
+        self.meta = perm.iter().map(|&i| self.meta[i]).collect();
+        self.rewrite_in_order();
+    }
+
+    /// Returns true if any bytestring contains `needle`.
+    ///
+    /// The data vector is searched in one pass rather than bytestring by bytestring, using a
+    /// SIMD-accelerated search when the `memchr` feature is enabled. Matches spanning two
+    /// bytestrings are not counted.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([b"One", b"Two", b"Six"]);
+    ///
+    /// assert!(cmpbytes.contains_bytes(b"wo"));
+    /// assert!(!cmpbytes.contains_bytes(b"eT"));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn contains_bytes(&self, needle: &[u8]) -> bool {
+        self.find_bytes(needle).is_some()
+    }
+
+    /// Returns the index of the first bytestring that contains `needle`.
+    ///
+    /// Like [`contains_bytes`], this searches the data vector in one pass.
+    ///
+    /// [`contains_bytes`]: CompactBytestrings::contains_bytes
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([b"One", b"Two", b"Six"]);
+    ///
+    /// assert_eq!(cmpbytes.find_bytes(b"i"), Some(2));
+    /// assert_eq!(cmpbytes.find_bytes(b"eT"), None);
+    /// ```
+    #[must_use]
+    pub fn find_bytes(&self, needle: &[u8]) -> Option<usize> {
+        self.indices_containing(needle).next()
+    }
+
+    /// Returns an iterator over the indices of the bytestrings that contain `pattern`, in order.
+    ///
+    /// The data vector is searched in one pass like [`contains_bytes`], and each match is resolved
+    /// to the bytestring it falls into. Matches spanning two bytestrings are not counted, and each index
+    /// is returned at most once.
+    ///
+    /// [`contains_bytes`]: CompactBytestrings::contains_bytes
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([&b"error: disk"[..], b"ok", b"error: net"]);
+    ///
+    /// assert!(cmpbytes.indices_containing(b"error").eq([0, 2]));
+    /// ```
+    pub fn indices_containing<'a>(&'a self, pattern: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+        // Matches can only be mapped back to bytestrings in one pass while the bytestrings are laid
+        // out in order, so fall back to searching each one after `swap_ignore`.
+        let (len, data, unordered) = if self.starts_in_order() {
+            (self.len(), &self.data[..], 0..0)
+        } else {
+            (0, &[][..], 0..self.len())
+        };
+
+        let matches = search::matches(len, data, pattern, move |pos| {
+            let index = self
+                .meta
+                .partition_point(|m| m.start <= pos)
+                .checked_sub(1)?;
+            let meta = self.meta[index];
+            Some((index, meta.start..meta.start + meta.len))
+        });
+
+        matches.chain(unordered.filter(move |&i| search::contains(&self[i], pattern)))
+    }
+
+    /// Returns the index of the first bytestring that is equal to `bytestring` when ignoring ASCII case.
+    ///
+    /// Bytes are compared in place with ASCII case folding, without allocating lowercased copies.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([&b"Host"[..], b"Content-Type"]);
+    ///
+    /// assert_eq!(cmpbytes.position_ignore_ascii_case(b"content-type"), Some(1));
+    /// assert_eq!(cmpbytes.position_ignore_ascii_case(b"Accept"), None);
+    /// ```
+    #[must_use]
+    pub fn position_ignore_ascii_case(&self, bytestring: &[u8]) -> Option<usize> {
+        self.iter().position(|s| s.eq_ignore_ascii_case(bytestring))
+    }
+
+    /// Returns true if any bytestring is equal to `bytestring` when ignoring ASCII case.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([&b"Host"[..], b"Content-Type"]);
+    ///
+    /// assert!(cmpbytes.contains_ignore_ascii_case(b"content-type"));
+    /// assert!(!cmpbytes.contains_ignore_ascii_case(b"Accept"));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn contains_ignore_ascii_case(&self, bytestring: &[u8]) -> bool {
+        self.position_ignore_ascii_case(bytestring).is_some()
+    }
+
+    /// Returns a copy of the [`CompactBytestrings`] with every bytestring converted to ASCII lowercase.
+    ///
+    /// As lengths do not change, the whole data vector is converted in one pass instead of bytestring
+    /// by bytestring.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([&b"Host"[..], b"Content-Type"]);
+    ///
+    /// assert!(cmpbytes.to_ascii_lowercase().iter().eq([&b"host"[..], b"content-type"]));
+    /// ```
+    #[must_use]
+    pub fn to_ascii_lowercase(&self) -> Self {
+        let mut converted = self.clone();
+        converted.data.make_ascii_lowercase();
+        converted
+    }
+
+    /// Returns a copy of the [`CompactBytestrings`] with every bytestring converted to ASCII uppercase.
+    ///
+    /// As lengths do not change, the whole data vector is converted in one pass instead of bytestring
+    /// by bytestring.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([&b"Host"[..], b"Content-Type"]);
+    ///
+    /// assert!(cmpbytes.to_ascii_uppercase().iter().eq([&b"HOST"[..], b"CONTENT-TYPE"]));
+    /// ```
+    #[must_use]
+    pub fn to_ascii_uppercase(&self) -> Self {
+        let mut converted = self.clone();
+        converted.data.make_ascii_uppercase();
+        converted
+    }
+
+    /// Returns a new [`CompactBytestrings`] holding the bytestrings repeated `n` times.
+    ///
+    /// Both vectors of the new [`CompactBytestrings`] are allocated once with exactly the capacity they need.
+    ///
+    /// # Panics
+    /// Panics if the capacity would overflow.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([b"One", b"Two"]);
+    ///
+    /// assert!(cmpbytes.repeat(3).iter().eq([b"One", b"Two", b"One", b"Two", b"One", b"Two"]));
+    /// assert!(cmpbytes.repeat(0).is_empty());
+    /// ```
+    #[track_caller]
+    #[must_use]
+    pub fn repeat(&self, n: usize) -> Self {
+        #[cold]
+        #[inline(never)]
+        #[track_caller]
+        fn capacity_overflow() -> ! {
+            panic!("capacity overflow");
+        }
+
+        let once = self.meta.iter().map(|meta| meta.len).sum::<usize>();
+        let count = self.meta.len();
+        let (data_len, meta_len) = match (once.checked_mul(n), count.checked_mul(n)) {
+            (Some(data_len), Some(meta_len)) => (data_len, meta_len),
+            _ => capacity_overflow(),
+        };
+
+        let mut repeated = Self::with_capacity(data_len, meta_len);
+        if n == 0 {
+            return repeated;
+        }
+
+        for bytes in self {
+            repeated.push(bytes);
+        }
+        for copy in 1..n {
+            repeated.data.extend_from_within(..once);
+            for i in 0..count {
+                let meta = repeated.meta[i];
+                repeated
+                    .meta
+                    .push(Metadata::new(meta.start + copy * once, meta.len));
+            }
+        }
+
+        repeated
+    }
+
+    /// Returns an iterator over the bytestrings that start with `prefix`.
+    ///
+    /// Every bytestring is checked. If the [`CompactBytestrings`] is sorted, [`iter_sorted_with_prefix`] finds
+    /// the matching bytestrings with a binary search instead.
+    ///
+    /// [`iter_sorted_with_prefix`]: CompactBytestrings::iter_sorted_with_prefix
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([&b"car"[..], b"dog", b"cart"]);
+    ///
+    /// assert!(cmpbytes.iter_with_prefix(b"car").eq([&b"car"[..], b"cart"]));
+    /// ```
+    #[must_use]
+    pub fn iter_with_prefix<'a>(
+        &'a self,
+        prefix: &'a [u8],
+    ) -> impl DoubleEndedIterator<Item = &'a [u8]> + 'a {
+        self.iter().filter(move |s| s.starts_with(prefix))
+    }
+
+    /// Returns an iterator over the bytestrings that start with `prefix`, assuming the [`CompactBytestrings`] is
+    /// sorted.
+    ///
+    /// The bytestrings sharing a prefix are adjacent in a sorted [`CompactBytestrings`], so their range is found
+    /// with two binary searches. If the [`CompactBytestrings`] is not sorted, the bytestrings returned are
+    /// unspecified.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::from([&b"cart"[..], b"dog", b"cab", b"car"]);
+    /// cmpbytes.sort();
+    ///
+    /// let mut iter = cmpbytes.iter_sorted_with_prefix(b"car");
+    /// assert_eq!(iter.len(), 2);
+    /// assert!(iter.eq([&b"car"[..], b"cart"]));
+    /// ```
+    pub fn iter_sorted_with_prefix(&self, prefix: &[u8]) -> Iter<'_> {
+        let range = search::sorted_prefix_range(self.meta.len(), prefix, |i| {
+            let meta = self.meta[i];
+            &self.data[meta.start..meta.start + meta.len]
+        });
+        Iter::from_parts(&self.data, &self.meta[range])
+    }
+
+    /// Returns true if every bytestring starts at or after the end of the one before it, which
+    /// only stops holding after [`swap_ignore`](CompactBytestrings::swap_ignore) or when
+    /// bytestrings share bytes, as those built by an [`OverlappingBytesBuilder`] can.
+    ///
+    /// [`OverlappingBytesBuilder`]: crate::OverlappingBytesBuilder
+    fn starts_in_order(&self) -> bool {
+        self.meta
+            .windows(2)
+            .all(|w| w[0].start + w[0].len <= w[1].start)
+    }
+
+    /// Rewrites the data vector if [`starts_in_order`](CompactBytestrings::starts_in_order) does
+    /// not hold, as removing bytes from the data vector relies on it.
+    pub(crate) fn restore_order(&mut self) {
+        if !self.starts_in_order() {
+            self.rewrite_in_order();
+        }
+    }
+
+    /// Copies the bytes of every bytestring into a new data vector in the order of the meta vector,
+    /// restoring the invariant that every bytestring starts at or after the end of the one before
+    /// it, so that no two bytestrings share bytes.
+    pub(crate) fn rewrite_in_order(&mut self) {
+        let mut data = Vec::with_capacity(self.meta.iter().map(|m| m.len).sum());
+        for meta in &mut self.meta {
+            let start = data.len();
+            data.extend_from_slice(&self.data[meta.start..meta.start + meta.len]);
+            meta.start = start;
+        }
+
+        self.data = data;
+    }
+
+    /// Checks that every bytestring is valid UTF-8, validating the data vector in one pass where
+    /// possible.
+    pub(crate) fn validate_utf8(&self) -> Result<(), Utf8Error> {
+        let boundaries = self.meta.iter().flat_map(|m| [m.start, m.start + m.len]);
+        utf8::validate(&self.data, boundaries, self)
+    }
+
+    /// Returns an iterator over the starting index in the data vector and the length of each bytestring.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::new();
+    /// cmpbytes.push(b"One");
+    /// cmpbytes.push(b"Two");
+    /// cmpbytes.push(b"Three");
+    ///
+    /// let mut offsets = cmpbytes.offsets();
+    /// assert_eq!(offsets.next(), Some((0, 3)));
+    /// assert_eq!(offsets.next(), Some((3, 3)));
+    /// assert_eq!(offsets.next(), Some((6, 5)));
+    /// assert_eq!(offsets.next(), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn offsets(
+        &self,
+    ) -> impl ExactSizeIterator<Item = (usize, usize)> + DoubleEndedIterator + '_ {
+        self.meta.iter().map(Metadata::as_tuple)
+    }
+
+    /// Returns the range of bytes in the data vector holding the bytestring at that position, or
+    /// [`None`] if `index` is out of bounds.
+    ///
+    /// This translates between element indices and byte positions in the data vector, such as
+    /// those found by searching it directly.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([b"One", b"Two"]);
+    ///
+    /// assert_eq!(cmpbytes.byte_range(1), Some(3..6));
+    /// assert_eq!(cmpbytes.byte_range(2), None);
+    /// ```
+    #[must_use]
+    pub fn byte_range(&self, index: usize) -> Option<Range<usize>> {
+        let meta = self.meta.get(index)?;
+        Some(meta.start..meta.start + meta.len)
+    }
+
+    /// Returns the index of the bytestring that `bytes` was borrowed from, or [`None`] if it does
+    /// not refer to a whole bytestring in the [`CompactBytestrings`].
+    ///
+    /// Unlike searching for an equal bytestring, this tells apart bytestrings with the same
+    /// contents, so a reference handed out earlier can be mapped back to its index. If several
+    /// empty bytestrings share a position in the data vector, the first of them is returned.
+    ///
+    /// The reference is located with pointer arithmetic against the data vector, so this runs in
+    /// *O*(*n*) time but never compares any bytes.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([&b"One"[..], b"Two", b"Two"]);
+    /// let last = &cmpbytes[2];
+    ///
+    /// assert_eq!(cmpbytes.index_of_ref(last), Some(2));
+    /// assert_eq!(cmpbytes.index_of_ref(&b"Two"[..]), None);
+    /// ```
+    #[must_use]
+    pub fn index_of_ref(&self, bytes: &[u8]) -> Option<usize> {
+        let offset = (bytes.as_ptr() as usize).checked_sub(self.data.as_ptr() as usize)?;
+        if offset + bytes.len() > self.data.len() {
+            return None;
+        }
+
+        self.meta
+            .iter()
+            .position(|meta| meta.start == offset && meta.len == bytes.len())
+    }
+
+    /// Returns an iterator over the length in bytes of each bytestring.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::new();
+    /// cmpbytes.push(b"One");
+    /// cmpbytes.push(b"Two");
+    /// cmpbytes.push(b"Three");
+    ///
+    /// assert!(cmpbytes.lengths().eq([3, 3, 5]));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn lengths(&self) -> impl ExactSizeIterator<Item = usize> + DoubleEndedIterator + '_ {
+        self.meta.iter().map(|m| m.len)
+    }
+
+    /// Returns the length in bytes of the bytestring at that position, or [`None`] if `index` is out
+    /// of bounds, reading only its metadata.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([&b"One"[..], b"Three"]);
+    ///
+    /// assert_eq!(cmpbytes.len_at(1), Some(5));
+    /// assert_eq!(cmpbytes.len_at(2), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn len_at(&self, index: usize) -> Option<usize> {
+        self.meta.get(index).map(|meta| meta.len)
+    }
+
+    /// Feeds the number of bytestrings, the length of each of them and then their bytes into
+    /// `state`, in order.
+    ///
+    /// Collections holding the same bytestrings in the same order feed the same values however
+    /// their data vectors are laid out, and so do the other collection types of this crate, which
+    /// lets collections be compared or cache-keyed without serializing them first.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactBytestrings, FixedCompactBytestrings};
+    /// use std::collections::hash_map::DefaultHasher;
+    /// use std::hash::Hasher;
+    ///
+    /// let mut a = DefaultHasher::new();
+    /// CompactBytestrings::from([&b"One"[..], b"Two"]).digest(&mut a);
+    ///
+    /// let mut b = DefaultHasher::new();
+    /// FixedCompactBytestrings::from([&b"One"[..], b"Two"]).digest(&mut b);
+    /// assert_eq!(a.finish(), b.finish());
+    ///
+    /// let mut c = DefaultHasher::new();
+    /// CompactBytestrings::from([&b"OneT"[..], b"wo"]).digest(&mut c);
+    /// assert_ne!(a.finish(), c.finish());
+    /// ```
+    pub fn digest<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.len() as u64);
+        for len in self.lengths() {
+            state.write_u64(len as u64);
+        }
+        for bytestring in self {
+            state.write(bytestring);
+        }
+    }
+
+    /// Copies each bytestring into an owned `Vec<u8>`, returning them in a vector.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::new();
+    /// cmpbytes.push(b"One");
+    /// cmpbytes.push(b"Two");
+    ///
+    /// let vec: Vec<Vec<u8>> = cmpbytes.to_vec();
+    /// assert_eq!(vec, [b"One".to_vec(), b"Two".to_vec()]);
+    /// ```
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<Vec<u8>> {
+        self.iter().map(<[u8]>::to_vec).collect()
+    }
+
+    /// Encodes the [`CompactBytestrings`] in a binary layout that can be read back without
+    /// copying through [`CompactBytesRef::parse`](crate::CompactBytesRef::parse).
+    ///
+    /// The layout consists of little-endian `u64`s, followed by the bytes themselves:
+    /// 1. The number of bytestrings, `n`.
+    /// 2. The total number of bytes, `m`.
+    /// 3. `n` starting indices into the bytes, with the bytestring at `i` ending at the start of
+    ///    the bytestring at `i + 1`, or at `m` for the last bytestring.
+    /// 4. `m` bytes.
+    ///
+    /// Bytes of removed bytestrings are not encoded. Every type in this crate that can be encoded
+    /// uses the same layout, so the output of one can be read by any other.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactBytesRef, CompactBytestrings};
+    /// let mut cmpbytes = CompactBytestrings::new();
+    /// cmpbytes.push(b"One");
+    /// cmpbytes.push(b"Two");
+    ///
+    /// let buf = cmpbytes.to_bytes();
+    /// assert_eq!(buf.len(), 8 * 4 + 6);
+    ///
+    /// let view = CompactBytesRef::parse(&buf).unwrap();
+    /// assert!(view.iter().eq(cmpbytes.iter()));
+    /// ```
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        layout::encode(self.len(), || self.iter())
+    }
+
     /// Returns an iterator over the slice.
     ///
     /// The iterator yields all items from start to end.
@@ -485,6 +2417,80 @@ impl CompactBytestrings {
     pub fn iter(&self) -> Iter<'_> {
         Iter::new(self)
     }
+
+    /// Returns an iterator over the bytestrings in `range`.
+    ///
+    /// The iterator starts at the first bytestrings in `range` without stepping over the ones before
+    /// it, unlike calling [`skip`](Iterator::skip) on [`iter`](CompactBytestrings::iter).
+    ///
+    /// # Panics
+    /// Panics if the starting point is greater than the end point or if the end point is greater
+    /// than the length of the [`CompactBytestrings`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([&b"One"[..], b"Two", b"Three", b"Four"]);
+    ///
+    /// assert!(cmpbytes.iter_range(1..3).eq([&b"Two"[..], b"Three"]));
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn iter_range<R>(&self, range: R) -> Iter<'_>
+    where
+        R: RangeBounds<usize>,
+    {
+        let range = to_range(range, self.meta.len());
+        Iter::from_parts(&self.data, &self.meta[range])
+    }
+
+    /// Returns an iterator over the bytestrings from `start` onwards, as described in
+    /// [`iter_range`](CompactBytestrings::iter_range).
+    ///
+    /// # Panics
+    /// Panics if `start` is greater than the length of the [`CompactBytestrings`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([&b"One"[..], b"Two", b"Three", b"Four"]);
+    ///
+    /// assert!(cmpbytes.iter_from(2).eq([&b"Three"[..], b"Four"]));
+    /// assert_eq!(cmpbytes.iter_from(4).next(), None);
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn iter_from(&self, start: usize) -> Iter<'_> {
+        self.iter_range(start..)
+    }
+
+    /// Returns an iterator that allows modifying the bytes of each bytestring in place.
+    ///
+    /// Note: If [`swap_ignore`] left the bytestrings out of order in the data vector, the data
+    /// vector is rewritten in order first.
+    ///
+    /// [`swap_ignore`]: CompactBytestrings::swap_ignore
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::from([&b"One"[..], b"Two"]);
+    ///
+    /// for bytes in cmpbytes.iter_mut() {
+    ///     bytes.reverse();
+    /// }
+    ///
+    /// assert!(cmpbytes.iter().eq([&b"enO"[..], b"owT"]));
+    /// ```
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_> {
+        self.restore_order();
+        IterMut {
+            data: &mut self.data,
+            offset: 0,
+            iter: self.meta.iter(),
+        }
+    }
 }
 
 impl Clone for CompactBytestrings {
@@ -504,6 +2510,13 @@ impl Clone for CompactBytestrings {
     }
 }
 
+impl Default for CompactBytestrings {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl PartialEq for CompactBytestrings {
     fn eq(&self, other: &Self) -> bool {
         let len = self.len();
@@ -511,6 +2524,12 @@ impl PartialEq for CompactBytestrings {
             return false;
         }
 
+        // With the same layout, equal data vectors mean equal bytestrings. Different data vectors
+        // may still only differ in bytes that are not referenced by any bytestring.
+        if self.meta == other.meta && self.data == other.data {
+            return true;
+        }
+
         for idx in 0..len {
             if self[idx] != other[idx] {
                 return false;
@@ -578,6 +2597,14 @@ impl<'a> Iter<'a> {
             iter: inner.meta.iter(),
         }
     }
+
+    #[inline]
+    pub(crate) fn from_parts(data: &'a [u8], meta: &'a [Metadata]) -> Self {
+        Self {
+            data,
+            iter: meta.iter(),
+        }
+    }
 }
 
 impl<'a> Iterator for Iter<'a> {
@@ -625,7 +2652,7 @@ impl<'a> Iterator for Iter<'a> {
     }
 }
 
-impl<'a> DoubleEndedIterator for Iter<'a> {
+impl DoubleEndedIterator for Iter<'_> {
     fn next_back(&mut self) -> Option<Self::Item> {
         let (start, len) = self.iter.next_back()?.as_tuple();
 
@@ -665,6 +2692,94 @@ impl<'a> IntoIterator for &'a CompactBytestrings {
     }
 }
 
+/// Mutable iterator over bytestrings in a [`CompactBytestrings`]
+///
+/// # Examples
+/// ```
+/// # use compact_strings::CompactBytestrings;
+/// let mut cmpbytes = CompactBytestrings::from([&b"One"[..], b"Two"]);
+///
+/// let mut iter = cmpbytes.iter_mut();
+/// iter.next().unwrap().fill(b'-');
+/// assert_eq!(iter.next_back().as_deref(), Some(b"Two".as_slice()));
+/// assert_eq!(iter.next(), None);
+///
+/// assert_eq!(cmpbytes.get(0), Some(b"---".as_slice()));
+/// ```
+#[must_use = "Iterators are lazy and do nothing unless consumed"]
+pub struct IterMut<'a> {
+    /// The part of the data vector not yet yielded, which starts at `offset` in the data vector.
+    data: &'a mut [u8],
+    offset: usize,
+    iter: core::slice::Iter<'a, Metadata>,
+}
+
+impl<'a> Iterator for IterMut<'a> {
+    type Item = &'a mut [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (start, len) = self.iter.next()?.as_tuple();
+
+        let data = core::mem::take(&mut self.data);
+        let (bytes, rest) = data[start - self.offset..].split_at_mut(len);
+        self.data = rest;
+        self.offset = start + len;
+
+        Some(bytes)
+    }
+
+    #[inline]
+    fn count(self) -> usize
+    where
+        Self: Sized,
+    {
+        self.len()
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<Self::Item>
+    where
+        Self: Sized,
+    {
+        self.next_back()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for IterMut<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (start, len) = self.iter.next_back()?.as_tuple();
+
+        let data = core::mem::take(&mut self.data);
+        let (rest, bytes) = data.split_at_mut(start - self.offset);
+        self.data = rest;
+
+        Some(&mut bytes[..len])
+    }
+}
+
+impl ExactSizeIterator for IterMut<'_> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut CompactBytestrings {
+    type Item = &'a mut [u8];
+
+    type IntoIter = IterMut<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
 impl<S> FromIterator<S> for CompactBytestrings
 where
     S: AsRef<[u8]>,
@@ -706,6 +2821,35 @@ impl From<CompactStrings> for CompactBytestrings {
 mod tests {
     use crate::CompactBytestrings;
 
+    #[test]
+    fn splice_matches_vec() {
+        let strings: [&[u8]; 5] = [b"One", b"Two", b"", b"Three", b"Four"];
+        let mut cmpbytes = CompactBytestrings::from(strings);
+        cmpbytes.ignore(0);
+        cmpbytes.swap_ignore(0);
+        let mut model = strings[2..].to_vec();
+        model.insert(0, strings[4]);
+        model.pop();
+
+        for (range, replacement) in [
+            (0..0, &[&b"A"[..], b"Bb"][..]),
+            (1..3, &[b"Long replacement"]),
+            (2..2, &[]),
+            (0..1, &[b"", b""]),
+            (3..5, &[b"C"]),
+        ] {
+            let removed = cmpbytes.splice(range.clone(), replacement);
+            let expected: alloc::vec::Vec<_> =
+                model.splice(range, replacement.iter().copied()).collect();
+
+            assert!(removed.iter().eq(expected));
+            assert!(cmpbytes.iter().eq(model.iter().copied()));
+        }
+        let end = cmpbytes.len();
+        let _ = cmpbytes.splice(end.., [b"End"]);
+        assert_eq!(cmpbytes.get(end), Some(&b"End"[..]));
+    }
+
     #[test]
     fn exact_size_iterator() {
         let mut cmpbytes = CompactBytestrings::new();
@@ -743,4 +2887,473 @@ mod tests {
         assert_eq!(iter.next(), None);
         assert_eq!(iter.next_back(), None);
     }
+
+    #[test]
+    fn remove_rebases_following() {
+        let mut cmpbytes = CompactBytestrings::new();
+
+        cmpbytes.push(b"A");
+        cmpbytes.push(b"Bb");
+        cmpbytes.push(b"Ccc");
+        cmpbytes.push(b"Dddd");
+
+        cmpbytes.remove(0);
+        assert!(cmpbytes.iter().eq([b"Bb".as_slice(), b"Ccc", b"Dddd"]));
+
+        cmpbytes.remove(1);
+        assert!(cmpbytes.iter().eq([b"Bb".as_slice(), b"Dddd"]));
+        assert_eq!(cmpbytes.data, b"BbDddd");
+    }
+
+    #[test]
+    fn remove_after_ignore() {
+        let mut cmpbytes = CompactBytestrings::new();
+
+        cmpbytes.push(b"A");
+        cmpbytes.push(b"Bb");
+        cmpbytes.push(b"Ccc");
+        cmpbytes.push(b"Dddd");
+
+        // The bytes of "Bb" stay in the data vector, so the starting indices no longer match the
+        // total length of the bytestrings before them.
+        cmpbytes.ignore(1);
+
+        cmpbytes.remove(1);
+        assert!(cmpbytes.iter().eq([b"A".as_slice(), b"Dddd"]));
+        assert_eq!(cmpbytes.data, b"ABbDddd");
+
+        cmpbytes.remove(0);
+        assert!(cmpbytes.iter().eq([b"Dddd"]));
+        assert_eq!(cmpbytes.data, b"BbDddd");
+    }
+
+    #[test]
+    fn push_bytes_to_last_after_ignore() {
+        let mut cmpbytes = CompactBytestrings::new();
+        cmpbytes.push(b"One");
+        cmpbytes.push(b"Two");
+        cmpbytes.ignore(1);
+        cmpbytes.push_bytes_to_last(b"!");
+        cmpbytes.push(b"Three");
+
+        assert!(cmpbytes.iter().eq([&b"One!"[..], b"Three"]));
+    }
+
+    #[test]
+    fn sort_by_panic_keeps_starts_in_order() {
+        extern crate std;
+
+        let mut cmpbytes = CompactBytestrings::from([&b"Three"[..], b"One", b"Four", b"Two"]);
+        cmpbytes.ignore(1);
+        let result = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| {
+            let mut calls = 0;
+            cmpbytes.sort_by(|a, b| {
+                calls += 1;
+                assert!(calls < 2);
+                a.cmp(b)
+            });
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(cmpbytes.data.len(), 12);
+        assert!(cmpbytes.meta.windows(2).all(|w| w[0].start <= w[1].start));
+
+        cmpbytes.remove(0);
+        assert_eq!(
+            cmpbytes.data.len(),
+            cmpbytes.iter().map(<[u8]>::len).sum::<usize>()
+        );
+    }
+
+    #[test]
+    fn find_bytes_skips_unreferenced_and_spanning_matches() {
+        let mut cmpbytes = CompactBytestrings::from([&b"abc"[..], b"needle", b"ne", b"edle"]);
+        cmpbytes.ignore(1);
+
+        assert_eq!(cmpbytes.find_bytes(b"needle"), None);
+        assert_eq!(cmpbytes.find_bytes(b"edle"), Some(2));
+        assert_eq!(cmpbytes.find_bytes(b""), Some(0));
+        assert_eq!(CompactBytestrings::new().find_bytes(b""), None);
+    }
+
+    #[test]
+    fn indices_containing_reports_each_index_once() {
+        let cmpbytes = CompactBytestrings::from([&b"aaaa"[..], b"", b"a", b"ba", b"ab"]);
+
+        assert!(cmpbytes.indices_containing(b"a").eq([0, 2, 3, 4]));
+        assert!(cmpbytes.indices_containing(b"aa").eq([0]));
+        assert!(cmpbytes.indices_containing(b"").eq(0..5));
+    }
+
+    #[test]
+    fn swap_ignore_then_remove() {
+        let mut cmpbytes = CompactBytestrings::from([&b"One"[..], b"Two", b"", b"Three", b"Four"]);
+        cmpbytes.swap_ignore(0);
+        assert!(cmpbytes.indices_containing(b"o").eq([0, 1]));
+        assert_eq!(cmpbytes.find_bytes(b"Fo"), Some(0));
+
+        cmpbytes.remove(3);
+        assert!(cmpbytes.iter().eq([&b"Four"[..], b"Two", b""]));
+        assert!(cmpbytes.meta.windows(2).all(|w| w[0].start <= w[1].start));
+
+        cmpbytes.swap_ignore(0);
+        cmpbytes.remove_range(..1);
+        assert!(cmpbytes.iter().eq([&b"Two"[..]]));
+
+        cmpbytes.push(b"Six");
+        cmpbytes.swap_ignore(0);
+        cmpbytes.compact();
+        assert!(cmpbytes.iter().eq([&b"Six"[..]]));
+        assert_eq!(cmpbytes.data, b"Six");
+    }
+
+    #[test]
+    fn remove_matching_after_swap_ignore() {
+        let mut cmpbytes = CompactBytestrings::from([&b"One"[..], b"Two", b"Three", b"Four"]);
+        cmpbytes.swap_ignore(0);
+
+        assert_eq!(cmpbytes.remove_matching(|bytes| bytes.len() == 3), 1);
+        assert!(cmpbytes.iter().eq([&b"Four"[..], b"Three"]));
+        assert_eq!(cmpbytes.data, b"FourThree");
+    }
+
+    #[test]
+    fn take_after_swap_ignore() {
+        let mut cmpbytes = CompactBytestrings::from([&b"One"[..], b"Two", b"Three"]);
+        cmpbytes.swap_ignore(0);
+
+        assert_eq!(cmpbytes.take(0), b"Three");
+        assert!(cmpbytes.iter().eq([b"Two"]));
+        assert_eq!(cmpbytes.data, b"Two");
+    }
+
+    #[test]
+    fn is_sorted_follows_meta_order() {
+        let mut cmpbytes = CompactBytestrings::from([&b"Zero"[..], b"One", b"Two"]);
+        assert!(!cmpbytes.is_sorted());
+
+        cmpbytes.swap_ignore(0);
+        assert!(cmpbytes.iter().eq([b"Two", b"One"]));
+        assert!(!cmpbytes.is_sorted());
+
+        cmpbytes.swap_ignore(0);
+        assert!(cmpbytes.is_sorted());
+        assert!(CompactBytestrings::new().is_sorted());
+    }
+
+    #[test]
+    fn insert_sorted_after_swap_ignore() {
+        let mut cmpbytes = CompactBytestrings::from([b"zzz", b"bee", b"ant"]);
+        cmpbytes.swap_ignore(0);
+        assert!(cmpbytes.is_sorted());
+
+        assert_eq!(cmpbytes.insert_sorted(b"cat"), 2);
+        assert_eq!(cmpbytes.insert_sorted(b"ant"), 1);
+        assert_eq!(cmpbytes.insert_sorted(b"aaa"), 0);
+        assert!(cmpbytes.iter().eq([b"aaa", b"ant", b"ant", b"bee", b"cat"]));
+        assert_eq!(cmpbytes.garbage_len(), 0);
+    }
+
+    #[test]
+    fn merge_sorted_exact_capacity() {
+        let mut a = CompactBytestrings::from([b"ant", b"cat", b"eel"]);
+        a.ignore(1);
+        let b = CompactBytestrings::from([b"ant", b"bee", b"fox", b"gnu"]);
+
+        let merged = CompactBytestrings::merge_sorted(&a, &b);
+        assert!(merged
+            .iter()
+            .eq([b"ant", b"ant", b"bee", b"eel", b"fox", b"gnu"]));
+        assert_eq!(merged.capacity(), merged.data_len());
+        assert_eq!(merged.capacity_meta(), merged.len());
+    }
+
+    #[test]
+    fn group_by_key_exact_capacity() {
+        let mut cmpbytes = CompactBytestrings::from([&b"apple"[..], b"bee", b"avocado", b"bat"]);
+        cmpbytes.ignore(1);
+
+        let groups = cmpbytes.group_by_key(|bytes| bytes[0]);
+        assert_eq!(groups.len(), 2);
+        for group in groups.values() {
+            assert_eq!(group.capacity(), group.data_len());
+            assert_eq!(group.capacity_meta(), group.len());
+        }
+        assert!(groups[&b'a'].iter().eq([&b"apple"[..], b"avocado"]));
+        assert!(groups[&b'b'].iter().eq([b"bat"]));
+    }
+
+    #[test]
+    fn iter_mut_after_swap_ignore() {
+        let mut cmpbytes = CompactBytestrings::from([&b"One"[..], b"Two", b"Three", b"Four"]);
+        cmpbytes.swap_ignore(0);
+        cmpbytes.ignore(1);
+
+        let mut iter = cmpbytes.iter_mut();
+        iter.next_back().unwrap().make_ascii_uppercase();
+        iter.next().unwrap().make_ascii_lowercase();
+        assert!(iter.next().is_none());
+
+        assert!(cmpbytes.iter().eq([&b"four"[..], b"THREE"]));
+    }
+
+    #[test]
+    fn remove_many_after_swap_ignore() {
+        let mut cmpbytes =
+            CompactBytestrings::from([&b"One"[..], b"Two", b"Three", b"Four", b"Five"]);
+        cmpbytes.swap_ignore(0);
+        cmpbytes.remove_many(&[1, 3]);
+
+        assert!(cmpbytes.iter().eq([&b"Five"[..], b"Three"]));
+        assert_eq!(cmpbytes.data, b"FiveThree");
+    }
+
+    #[test]
+    fn eq_ignores_garbage() {
+        let mut a = CompactBytestrings::from([b"One", b"Two", b"Six"]);
+        let mut b = CompactBytestrings::from([b"One", b"Ten", b"Six"]);
+        assert_ne!(a, b);
+
+        a.ignore(1);
+        b.ignore(1);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn repeat_skips_garbage() {
+        let mut cmpbytes = CompactBytestrings::from([&b"One"[..], b"Two", b"Three"]);
+        cmpbytes.swap_ignore(0);
+
+        let repeated = cmpbytes.repeat(2);
+        assert!(repeated
+            .iter()
+            .eq([&b"Three"[..], b"Two", b"Three", b"Two"]));
+        assert_eq!(repeated.data.capacity(), 16);
+        assert_eq!(repeated.meta.capacity(), 4);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_uses_byte_arrays() {
+        use serde_test::{assert_tokens, Configure, Token};
+
+        let cmpbytes = CompactBytestrings::from([&b"One"[..], b""]);
+        assert_tokens(
+            &cmpbytes.clone().readable(),
+            &[
+                Token::Seq { len: Some(2) },
+                Token::Bytes(b"One"),
+                Token::Bytes(b""),
+                Token::SeqEnd,
+            ],
+        );
+
+        let encoded = bincode::serialize(&cmpbytes).unwrap();
+        let decoded: CompactBytestrings = bincode::deserialize(&encoded).unwrap();
+        assert_eq!(decoded, cmpbytes);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_flat_skips_removed_bytes() {
+        use crate::Flat;
+
+        let mut cmpbytes = CompactBytestrings::from([&b"One"[..], b"Two", b"\xff"]);
+        cmpbytes.remove(1);
+        let encoded = bincode::serialize(&Flat(&cmpbytes)).unwrap();
+        assert_eq!(encoded.len(), 8 + 2 * 8 + 8 + 4);
+        let Flat(decoded): Flat<CompactBytestrings> = bincode::deserialize(&encoded).unwrap();
+        assert_eq!(decoded, cmpbytes);
+
+        assert_eq!(
+            encoded,
+            bincode::serialize(&(&[0_u64, 3][..], &b"One\xff"[..])).unwrap()
+        );
+    }
+
+    #[test]
+    fn remove_range_bounds() {
+        let mut cmpbytes = CompactBytestrings::new();
+        cmpbytes.extend([b"A".as_slice(), b"Bb", b"Ccc", b"Dddd", b"Eeeee"]);
+
+        cmpbytes.remove_range(2..2);
+        assert_eq!(cmpbytes.len(), 5);
+
+        cmpbytes.remove_range(..=1);
+        assert!(cmpbytes.iter().eq([b"Ccc".as_slice(), b"Dddd", b"Eeeee"]));
+
+        cmpbytes.remove_range(1..);
+        assert!(cmpbytes.iter().eq([b"Ccc".as_slice()]));
+        assert_eq!(cmpbytes.data, b"Ccc");
+
+        cmpbytes.remove_range(..);
+        assert!(cmpbytes.is_empty());
+        assert!(cmpbytes.data.is_empty());
+    }
+    #[test]
+    fn digest_ignores_layout() {
+        use alloc::vec::Vec;
+        use core::hash::Hasher;
+
+        /// Records everything written to it, so digests can be compared exactly.
+        #[derive(Default)]
+        struct Recorder(Vec<u8>);
+
+        impl Hasher for Recorder {
+            fn finish(&self) -> u64 {
+                0
+            }
+
+            fn write(&mut self, bytes: &[u8]) {
+                self.0.extend_from_slice(bytes);
+            }
+        }
+
+        let digest = |cmpbytes: &CompactBytestrings| {
+            let mut recorder = Recorder::default();
+            cmpbytes.digest(&mut recorder);
+            recorder.0
+        };
+
+        let mut cmpbytes = CompactBytestrings::from([b"One", b"Two", b"Six"]);
+        cmpbytes.swap_ignore(0);
+        assert_eq!(
+            digest(&cmpbytes),
+            digest(&CompactBytestrings::from([b"Six", b"Two"]))
+        );
+        assert_ne!(
+            digest(&CompactBytestrings::from([&b""[..], b"One"])),
+            digest(&CompactBytestrings::from([&b"One"[..], b""]))
+        );
+    }
+
+    #[test]
+    fn overlapping_parts_are_rewritten() {
+        let cmpbytes =
+            CompactBytestrings::try_from_parts(b"One".to_vec(), alloc::vec![(0, 3), (0, 3)]);
+        let mut cmpbytes = cmpbytes.unwrap();
+        assert_eq!(cmpbytes.data, b"OneOne");
+        assert_eq!(cmpbytes.garbage_len(), 0);
+        assert_eq!(cmpbytes.stats().garbage_len, 0);
+        assert!((cmpbytes.utilization() - 1.0).abs() < f64::EPSILON);
+
+        cmpbytes.remove(0);
+        assert!(cmpbytes.iter().eq([b"One"]));
+
+        let cmpbytes =
+            CompactBytestrings::try_from_parts(b"OneTwo".to_vec(), alloc::vec![(3, 3), (1, 3)]);
+        let cmpbytes = cmpbytes.unwrap();
+        assert_eq!(cmpbytes.data, b"TwoneT");
+        assert_eq!(cmpbytes.garbage_len(), 0);
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde {
+    use serde::{
+        de::{SeqAccess, Visitor},
+        Deserialize, Deserializer, Serialize,
+    };
+
+    use crate::{flat, metadata::Metadata, CompactBytestrings};
+
+    impl Serialize for CompactBytestrings {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(self.iter().map(flat::Bytes))
+        }
+    }
+
+    impl<'de> Deserialize<'de> for CompactBytestrings {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_seq(CompactBytestringsVisitor)
+        }
+    }
+
+    impl flat::Encode for CompactBytestrings {
+        fn serialize_flat<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let starts = self.meta.iter().scan(0, |offset, meta| {
+                let start = *offset;
+                *offset += meta.len;
+                Some(start)
+            });
+            let len = self.meta.iter().map(|meta| meta.len).sum();
+
+            if len == self.data.len()
+                && self
+                    .meta
+                    .iter()
+                    .zip(starts.clone())
+                    .all(|(meta, start)| meta.start == start)
+            {
+                flat::serialize(
+                    serializer,
+                    starts,
+                    self.meta.len(),
+                    core::iter::once(&self.data[..]),
+                    len,
+                )
+            } else {
+                // Removed bytes and out-of-order ranges are skipped by writing every bytestring
+                // in turn.
+                let chunks = self
+                    .meta
+                    .iter()
+                    .map(move |meta| &self.data[meta.start..meta.start + meta.len]);
+                flat::serialize(serializer, starts, self.meta.len(), chunks, len)
+            }
+        }
+    }
+
+    impl flat::Decode for CompactBytestrings {
+        fn deserialize_flat<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let (starts, data) = flat::deserialize(deserializer)?;
+
+            let ends = starts.iter().skip(1).copied().chain(Some(data.len()));
+            let meta = starts
+                .iter()
+                .zip(ends)
+                .map(|(&start, end)| Metadata::new(start, end - start))
+                .collect();
+
+            Ok(CompactBytestrings { data, meta })
+        }
+    }
+
+    struct CompactBytestringsVisitor;
+
+    impl<'de> Visitor<'de> for CompactBytestringsVisitor {
+        type Value = CompactBytestrings;
+
+        fn expecting(&self, formatter: &mut alloc::fmt::Formatter) -> alloc::fmt::Result {
+            formatter.write_str("an array of byte arrays")
+        }
+
+        #[inline]
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut out = CompactBytestrings::with_capacity(0, seq.size_hint().unwrap_or_default());
+            loop {
+                let start = out.data.len();
+                if seq
+                    .next_element_seed(flat::AppendBytes(&mut out.data))?
+                    .is_none()
+                {
+                    break;
+                }
+                out.meta.push(Metadata::new(start, out.data.len() - start));
+            }
+
+            Ok(out)
+        }
+    }
 }
+
+#[cfg(feature = "serde")]
+#[cfg_attr(feature = "serde", allow(unused_imports))]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub use self::serde::*;
@@ -0,0 +1,398 @@
+use core::{
+    fmt::Debug,
+    ops::{Deref, Index},
+};
+
+use alloc::vec::Vec;
+
+use crate::{compact_strings::Iter as StrIter, metadata::Metadata};
+
+/// A handle to a string stored in a [`CompactStringArena`].
+///
+/// A key stays valid until its own string is removed, no matter which other strings are inserted
+/// or removed. Once removed, the key never resolves again, even if its slot is reused.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ArenaKey {
+    index: usize,
+    generation: u32,
+}
+
+/// A list of strings addressed by generational [`ArenaKey`]s rather than by position.
+///
+/// The bytes of the strings are stored contiguously like in a [`CompactStrings`], and each key
+/// names a slot holding the starting index and length of its string. Removing a string only
+/// frees its slot, and the data vector is compacted once more than half of it belongs to removed
+/// strings, which does not change any keys.
+///
+/// [`CompactStrings`]: crate::CompactStrings
+///
+/// # Examples
+/// ```
+/// # use compact_strings::CompactStringArena;
+/// let mut arena = CompactStringArena::new();
+///
+/// let one = arena.insert("One");
+/// let two = arena.insert("Two");
+/// arena.remove(one);
+///
+/// assert_eq!(arena.get(one), None);
+/// assert_eq!(arena.get(two), Some("Two"));
+/// ```
+#[derive(Clone, Default)]
+pub struct CompactStringArena {
+    data: Vec<u8>,
+    slots: Vec<Slot>,
+    free: Vec<usize>,
+    len: usize,
+    garbage: usize,
+}
+
+#[derive(Clone)]
+struct Slot {
+    generation: u32,
+    meta: Option<Metadata>,
+}
+
+impl CompactStringArena {
+    /// Constructs a new, empty [`CompactStringArena`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStringArena;
+    /// let arena = CompactStringArena::new();
+    ///
+    /// assert!(arena.is_empty());
+    /// ```
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            data: Vec::new(),
+            slots: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+            garbage: 0,
+        }
+    }
+
+    /// Constructs a new, empty [`CompactStringArena`] with the specified capacities.
+    ///
+    /// The [`CompactStringArena`] will be able to hold at least `data_capacity` bytes worth of
+    /// strings in at least `capacity_slots` slots without reallocating.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStringArena;
+    /// let mut arena = CompactStringArena::with_capacity(20, 3);
+    ///
+    /// let key = arena.insert("One");
+    /// assert_eq!(arena.get(key), Some("One"));
+    /// ```
+    #[must_use]
+    pub fn with_capacity(data_capacity: usize, capacity_slots: usize) -> Self {
+        Self {
+            data: Vec::with_capacity(data_capacity),
+            slots: Vec::with_capacity(capacity_slots),
+            free: Vec::new(),
+            len: 0,
+            garbage: 0,
+        }
+    }
+
+    /// Inserts a string into the [`CompactStringArena`], returning the key that names it.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStringArena;
+    /// let mut arena = CompactStringArena::new();
+    ///
+    /// let key = arena.insert("One");
+    /// assert_eq!(arena.get(key), Some("One"));
+    /// ```
+    pub fn insert<S>(&mut self, string: S) -> ArenaKey
+    where
+        S: Deref<Target = str>,
+    {
+        let meta = Metadata::new(self.data.len(), string.len());
+        self.data.extend_from_slice(string.as_bytes());
+        self.len += 1;
+
+        if let Some(index) = self.free.pop() {
+            let slot = &mut self.slots[index];
+            slot.meta = Some(meta);
+            ArenaKey {
+                index,
+                generation: slot.generation,
+            }
+        } else {
+            self.slots.push(Slot {
+                generation: 0,
+                meta: Some(meta),
+            });
+            ArenaKey {
+                index: self.slots.len() - 1,
+                generation: 0,
+            }
+        }
+    }
+
+    /// Returns a reference to the string named by `key`, or [`None`] if it has been removed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStringArena;
+    /// let mut arena = CompactStringArena::new();
+    ///
+    /// let key = arena.insert("One");
+    /// assert_eq!(arena.get(key), Some("One"));
+    ///
+    /// arena.remove(key);
+    /// assert_eq!(arena.get(key), None);
+    /// ```
+    #[must_use]
+    pub fn get(&self, key: ArenaKey) -> Option<&str> {
+        let (start, len) = self.meta(key)?.as_tuple();
+        StrIter::from_utf8_maybe_checked(self.data.get(start..start + len)?)
+    }
+
+    /// Returns true if `key` names a string in the [`CompactStringArena`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStringArena;
+    /// let mut arena = CompactStringArena::new();
+    ///
+    /// let key = arena.insert("One");
+    /// assert!(arena.contains_key(key));
+    ///
+    /// arena.remove(key);
+    /// assert!(!arena.contains_key(key));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn contains_key(&self, key: ArenaKey) -> bool {
+        self.meta(key).is_some()
+    }
+
+    /// Removes the string named by `key`, returning true if it was present.
+    ///
+    /// Other keys, including ones inserted later that reuse the freed slot, are unaffected.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStringArena;
+    /// let mut arena = CompactStringArena::new();
+    ///
+    /// let one = arena.insert("One");
+    /// assert!(arena.remove(one));
+    /// assert!(!arena.remove(one));
+    ///
+    /// let two = arena.insert("Two");
+    /// assert_eq!(arena.get(one), None);
+    /// assert_eq!(arena.get(two), Some("Two"));
+    /// ```
+    pub fn remove(&mut self, key: ArenaKey) -> bool {
+        let slot = match self.slots.get_mut(key.index) {
+            Some(slot) if slot.generation == key.generation => slot,
+            _ => return false,
+        };
+        let meta = match slot.meta.take() {
+            Some(meta) => meta,
+            None => return false,
+        };
+
+        // A slot whose generation cannot be bumped any further is retired instead of being
+        // reused, so that its old keys never resolve again.
+        if let Some(generation) = slot.generation.checked_add(1) {
+            slot.generation = generation;
+            self.free.push(key.index);
+        }
+
+        self.len -= 1;
+        self.garbage += meta.len;
+        if self.garbage > self.data.len() / 2 {
+            self.compact();
+        }
+
+        true
+    }
+
+    /// Returns the number of strings in the [`CompactStringArena`], also referred to as its 'length'.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStringArena;
+    /// let mut arena = CompactStringArena::new();
+    /// arena.insert("One");
+    /// arena.insert("Two");
+    ///
+    /// assert_eq!(arena.len(), 2);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the [`CompactStringArena`] contains no strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStringArena;
+    /// let mut arena = CompactStringArena::new();
+    /// assert!(arena.is_empty());
+    ///
+    /// arena.insert("One");
+    /// assert!(!arena.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the length of the data vector, including bytes of removed strings that have not
+    /// been compacted away yet.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStringArena;
+    /// let mut arena = CompactStringArena::new();
+    /// arena.insert("One");
+    /// arena.insert("Two");
+    ///
+    /// assert_eq!(arena.data_len(), 6);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn data_len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Removes all strings, invalidating every key.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStringArena;
+    /// let mut arena = CompactStringArena::new();
+    /// let key = arena.insert("One");
+    /// arena.clear();
+    ///
+    /// assert!(arena.is_empty());
+    /// assert_eq!(arena.get(key), None);
+    /// ```
+    pub fn clear(&mut self) {
+        for (index, slot) in self.slots.iter_mut().enumerate() {
+            if slot.meta.take().is_some() {
+                if let Some(generation) = slot.generation.checked_add(1) {
+                    slot.generation = generation;
+                    self.free.push(index);
+                }
+            }
+        }
+
+        self.data.clear();
+        self.len = 0;
+        self.garbage = 0;
+    }
+
+    /// Returns an iterator over the keys and strings in the [`CompactStringArena`], in slot order.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStringArena;
+    /// let mut arena = CompactStringArena::new();
+    /// let one = arena.insert("One");
+    /// let two = arena.insert("Two");
+    ///
+    /// assert!(arena.iter().eq([(one, "One"), (two, "Two")]));
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (ArenaKey, &str)> + '_ {
+        self.slots.iter().enumerate().filter_map(|(index, slot)| {
+            let (start, len) = slot.meta?.as_tuple();
+            let key = ArenaKey {
+                index,
+                generation: slot.generation,
+            };
+            Some((
+                key,
+                StrIter::from_utf8_maybe_checked(&self.data[start..start + len])?,
+            ))
+        })
+    }
+
+    /// Returns the metadata of the string named by `key`, if it is still present.
+    fn meta(&self, key: ArenaKey) -> Option<Metadata> {
+        self.slots
+            .get(key.index)
+            .filter(|slot| slot.generation == key.generation)?
+            .meta
+    }
+
+    /// Rewrites the data vector without the bytes of removed strings, keeping every slot and key.
+    fn compact(&mut self) {
+        let mut data = Vec::with_capacity(self.data.len() - self.garbage);
+        for meta in self.slots.iter_mut().filter_map(|slot| slot.meta.as_mut()) {
+            let start = data.len();
+            data.extend_from_slice(&self.data[meta.start..meta.start + meta.len]);
+            meta.start = start;
+        }
+
+        self.data = data;
+        self.garbage = 0;
+    }
+}
+
+impl Debug for CompactStringArena {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl Index<ArenaKey> for CompactStringArena {
+    type Output = str;
+
+    #[inline]
+    fn index(&self, key: ArenaKey) -> &Self::Output {
+        self.get(key).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, vec::Vec};
+
+    use crate::CompactStringArena;
+
+    #[test]
+    fn keys_survive_compaction() {
+        let mut arena = CompactStringArena::new();
+        let keys = (0..100)
+            .map(|i| arena.insert(format!("{i}").as_str()))
+            .collect::<Vec<_>>();
+
+        for (i, &key) in keys.iter().enumerate() {
+            if i % 4 != 3 {
+                arena.remove(key);
+            }
+        }
+
+        assert!(arena.data_len() < 100);
+        for (i, &key) in keys.iter().enumerate() {
+            let expected = format!("{i}");
+            assert_eq!(arena.get(key), (i % 4 == 3).then(|| expected.as_str()));
+        }
+    }
+
+    #[test]
+    fn stale_keys_do_not_resolve_after_reuse() {
+        let mut arena = CompactStringArena::new();
+        let one = arena.insert("One");
+        arena.remove(one);
+
+        let two = arena.insert("Two");
+        assert_eq!(arena.get(one), None);
+        assert!(!arena.remove(one));
+        assert_eq!(arena.get(two), Some("Two"));
+        assert_eq!(arena.len(), 1);
+    }
+}
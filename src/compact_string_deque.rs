@@ -0,0 +1,524 @@
+use core::{
+    fmt::Debug,
+    ops::{Deref, Index},
+};
+
+use crate::{utf8, CompactBytestringDeque, CompactStrings, MemoryUsage};
+
+/// A double-ended queue of strings stored compactly.
+///
+/// Strings are stored in a ring buffer of bytes, with their lengths and starting indices being
+/// stored separately in a second ring buffer.
+///
+/// See [`CompactBytestringDeque`] for how strings are laid out in the data ring buffer. Popping
+/// from either end is *O*(1) and pushing is amortized *O*(1) in the length of the string.
+///
+/// # Examples
+/// ```
+/// # use compact_strings::CompactStringDeque;
+/// let mut deque = CompactStringDeque::new();
+///
+/// deque.push_back("Two");
+/// deque.push_back("Three");
+/// deque.push_front("One");
+///
+/// assert_eq!(deque.pop_front(), Some("One"));
+/// assert_eq!(deque.pop_back(), Some("Three"));
+/// assert_eq!(deque.get(0), Some("Two"));
+/// assert_eq!(deque.len(), 1);
+/// ```
+#[repr(transparent)]
+#[derive(Clone, Default, PartialEq)]
+pub struct CompactStringDeque(pub(crate) CompactBytestringDeque);
+
+impl CompactStringDeque {
+    /// Constructs a new, empty [`CompactStringDeque`].
+    ///
+    /// The [`CompactStringDeque`] will not allocate until strings are pushed into it.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStringDeque;
+    /// let mut deque = CompactStringDeque::new();
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self(CompactBytestringDeque::new())
+    }
+
+    /// Constructs a new, empty [`CompactStringDeque`] with at least the specified capacities in
+    /// each buffer.
+    ///
+    /// - `data_capacity`: The capacity of the data ring buffer where the bytes of the strings are
+    ///   stored.
+    /// - `capacity_meta`: The capacity of the meta ring buffer where the starting indices and lengths
+    ///   of the strings are stored.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStringDeque;
+    /// let mut deque = CompactStringDeque::with_capacity(20, 3);
+    ///
+    /// assert_eq!(deque.len(), 0);
+    /// assert!(deque.capacity() >= 20);
+    /// assert!(deque.capacity_meta() >= 3);
+    /// ```
+    #[must_use]
+    pub fn with_capacity(data_capacity: usize, capacity_meta: usize) -> Self {
+        Self(CompactBytestringDeque::with_capacity(
+            data_capacity,
+            capacity_meta,
+        ))
+    }
+
+    /// Appends a string to the back of the [`CompactStringDeque`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStringDeque;
+    /// let mut deque = CompactStringDeque::new();
+    /// deque.push_back("One");
+    /// deque.push_back("Two");
+    ///
+    /// assert_eq!(deque.get(0), Some("One"));
+    /// assert_eq!(deque.get(1), Some("Two"));
+    /// ```
+    pub fn push_back<S>(&mut self, string: S)
+    where
+        S: Deref<Target = str>,
+    {
+        self.0.push_back(string.as_bytes());
+    }
+
+    /// Prepends a string to the front of the [`CompactStringDeque`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStringDeque;
+    /// let mut deque = CompactStringDeque::new();
+    /// deque.push_front("Two");
+    /// deque.push_front("One");
+    ///
+    /// assert_eq!(deque.get(0), Some("One"));
+    /// assert_eq!(deque.get(1), Some("Two"));
+    /// ```
+    pub fn push_front<S>(&mut self, string: S)
+    where
+        S: Deref<Target = str>,
+    {
+        self.0.push_front(string.as_bytes());
+    }
+
+    /// Removes the first string and returns a reference to it, or [`None`] if the
+    /// [`CompactStringDeque`] is empty.
+    ///
+    /// The bytes stay in the data ring buffer until a later push writes over them, so this never
+    /// moves any data.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStringDeque;
+    /// let mut deque = CompactStringDeque::new();
+    /// deque.push_back("One");
+    /// deque.push_back("Two");
+    ///
+    /// assert_eq!(deque.pop_front(), Some("One"));
+    /// assert_eq!(deque.pop_front(), Some("Two"));
+    /// assert_eq!(deque.pop_front(), None);
+    /// ```
+    pub fn pop_front(&mut self) -> Option<&str> {
+        self.0.pop_front().and_then(from_utf8_maybe_checked)
+    }
+
+    /// Removes the last string and returns a reference to it, or [`None`] if the
+    /// [`CompactStringDeque`] is empty.
+    ///
+    /// The bytes stay in the data ring buffer until a later push writes over them, so this never
+    /// moves any data.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStringDeque;
+    /// let mut deque = CompactStringDeque::new();
+    /// deque.push_back("One");
+    /// deque.push_back("Two");
+    ///
+    /// assert_eq!(deque.pop_back(), Some("Two"));
+    /// assert_eq!(deque.pop_back(), Some("One"));
+    /// assert_eq!(deque.pop_back(), None);
+    /// ```
+    pub fn pop_back(&mut self) -> Option<&str> {
+        self.0.pop_back().and_then(from_utf8_maybe_checked)
+    }
+
+    /// Returns a reference to the string stored in the [`CompactStringDeque`] at that position.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStringDeque;
+    /// let mut deque = CompactStringDeque::new();
+    /// deque.push_back("Two");
+    /// deque.push_front("One");
+    ///
+    /// assert_eq!(deque.get(0), Some("One"));
+    /// assert_eq!(deque.get(1), Some("Two"));
+    /// assert_eq!(deque.get(2), None);
+    /// ```
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.0.get(index).and_then(from_utf8_maybe_checked)
+    }
+
+    /// Returns a reference to the first string, or [`None`] if the [`CompactStringDeque`] is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStringDeque;
+    /// let mut deque = CompactStringDeque::new();
+    /// assert_eq!(deque.front(), None);
+    ///
+    /// deque.push_back("One");
+    /// deque.push_back("Two");
+    /// assert_eq!(deque.front(), Some("One"));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn front(&self) -> Option<&str> {
+        self.0.front().and_then(from_utf8_maybe_checked)
+    }
+
+    /// Returns a reference to the last string, or [`None`] if the [`CompactStringDeque`] is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStringDeque;
+    /// let mut deque = CompactStringDeque::new();
+    /// assert_eq!(deque.back(), None);
+    ///
+    /// deque.push_back("One");
+    /// deque.push_back("Two");
+    /// assert_eq!(deque.back(), Some("Two"));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn back(&self) -> Option<&str> {
+        self.0.back().and_then(from_utf8_maybe_checked)
+    }
+
+    /// Returns the number of strings in the [`CompactStringDeque`], also referred to as its 'length'.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStringDeque;
+    /// let mut deque = CompactStringDeque::new();
+    ///
+    /// deque.push_back("One");
+    /// deque.push_front("Two");
+    ///
+    /// assert_eq!(deque.len(), 2);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if the [`CompactStringDeque`] contains no strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStringDeque;
+    /// let mut deque = CompactStringDeque::new();
+    /// assert!(deque.is_empty());
+    ///
+    /// deque.push_back("One");
+    ///
+    /// assert!(!deque.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of bytes taken up in the data ring buffer, as opposed to its
+    /// [`capacity`].
+    ///
+    /// This includes any bytes skipped when a string did not fit before the end of the buffer.
+    ///
+    /// [`capacity`]: CompactStringDeque::capacity
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStringDeque;
+    /// let mut deque = CompactStringDeque::with_capacity(20, 3);
+    ///
+    /// deque.push_back("One");
+    /// deque.push_back("Three");
+    ///
+    /// assert_eq!(deque.data_len(), 8);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn data_len(&self) -> usize {
+        self.0.data_len()
+    }
+
+    /// Returns the number of bytes used and allocated by the data and meta ring buffers.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStringDeque;
+    /// let mut deque = CompactStringDeque::with_capacity(20, 3);
+    /// deque.push_back("One");
+    ///
+    /// let usage = deque.memory_usage();
+    /// assert_eq!(usage.data_used, 3);
+    /// assert!(usage.data_reserved >= 20);
+    /// assert!(usage.meta_reserved >= usage.meta_used);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn memory_usage(&self) -> MemoryUsage {
+        self.0.memory_usage()
+    }
+
+    /// Returns the number of bytes the data ring buffer can store without reallocating.
+    ///
+    /// Bytes skipped when a string does not fit before the end of the buffer count against this as
+    /// well.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStringDeque;
+    /// let mut deque = CompactStringDeque::with_capacity(20, 3);
+    ///
+    /// deque.push_back("One");
+    ///
+    /// assert!(deque.capacity() >= 20);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
+    /// Returns the number of starting indices and lengths can store without reallocating.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStringDeque;
+    /// let mut deque = CompactStringDeque::with_capacity(20, 3);
+    ///
+    /// deque.push_back("One");
+    ///
+    /// assert!(deque.capacity_meta() >= 3);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn capacity_meta(&self) -> usize {
+        self.0.capacity_meta()
+    }
+
+    /// Clears the [`CompactStringDeque`], removing all strings.
+    ///
+    /// Note that this method has no effect on the allocated capacity of the buffers.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStringDeque;
+    /// let mut deque = CompactStringDeque::new();
+    ///
+    /// deque.push_back("One");
+    /// deque.push_back("Two");
+    /// deque.clear();
+    ///
+    /// assert!(deque.is_empty());
+    /// ```
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Returns an iterator over the strings from front to back.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStringDeque;
+    /// let mut deque = CompactStringDeque::new();
+    /// deque.push_back("Two");
+    /// deque.push_front("One");
+    /// let mut iterator = deque.iter();
+    ///
+    /// assert_eq!(iterator.next(), Some("One"));
+    /// assert_eq!(iterator.next(), Some("Two"));
+    /// assert_eq!(iterator.next(), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter(self.0.iter())
+    }
+}
+
+fn from_utf8_maybe_checked(bytes: &[u8]) -> Option<&str> {
+    if cfg!(feature = "no_unsafe") {
+        utf8::from_utf8(bytes).ok()
+    } else {
+        Some(unsafe { core::str::from_utf8_unchecked(bytes) })
+    }
+}
+
+impl Debug for CompactStringDeque {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<S> Extend<S> for CompactStringDeque
+where
+    S: Deref<Target = str>,
+{
+    #[inline]
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        for s in iter {
+            self.push_back(s);
+        }
+    }
+}
+
+impl Index<usize> for CompactStringDeque {
+    type Output = str;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+
+/// Iterator over strings in a [`CompactStringDeque`]
+///
+/// # Examples
+/// ```
+/// # use compact_strings::CompactStringDeque;
+/// let mut deque = CompactStringDeque::new();
+/// deque.push_back("Two");
+/// deque.push_front("One");
+///
+/// let mut iter = deque.into_iter();
+/// assert_eq!(iter.next(), Some("One"));
+/// assert_eq!(iter.next(), Some("Two"));
+/// assert_eq!(iter.next(), None);
+/// ```
+pub struct Iter<'a>(crate::compact_bytestring_deque::Iter<'a>);
+
+impl<'a> Iter<'a> {
+    pub fn new(inner: &'a CompactStringDeque) -> Self {
+        Self(inner.0.iter())
+    }
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().and_then(from_utf8_maybe_checked)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.0.nth(n).and_then(from_utf8_maybe_checked)
+    }
+
+    #[inline]
+    fn count(self) -> usize
+    where
+        Self: Sized,
+    {
+        self.len()
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<Self::Item>
+    where
+        Self: Sized,
+    {
+        self.next_back()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Iter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().and_then(from_utf8_maybe_checked)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.0.nth_back(n).and_then(from_utf8_maybe_checked)
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl<'a> IntoIterator for &'a CompactStringDeque {
+    type Item = &'a str;
+
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<S> FromIterator<S> for CompactStringDeque
+where
+    S: Deref<Target = str>,
+{
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let meta_capacity = match iter.size_hint() {
+            (a, Some(b)) if a == b => a,
+            _ => 0,
+        };
+
+        let mut out = CompactStringDeque::with_capacity(0, meta_capacity);
+        for s in iter {
+            out.push_back(s);
+        }
+
+        out
+    }
+}
+
+impl From<CompactStrings> for CompactStringDeque {
+    fn from(value: CompactStrings) -> Self {
+        Self(value.0.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::CompactStringDeque;
+
+    #[test]
+    fn stream_through() {
+        let mut deque = CompactStringDeque::new();
+
+        for i in 0..100 {
+            deque.push_back(if i % 2 == 0 { "Even" } else { "Odd" });
+            if deque.len() > 3 {
+                let _ = deque.pop_front();
+            }
+        }
+
+        assert!(deque.iter().eq(["Odd", "Even", "Odd"]));
+    }
+}
@@ -0,0 +1,306 @@
+use core::{fmt::Debug, ops::Index};
+
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+    compact_bytes_ref,
+    compact_strings::Iter as StrIter,
+    layout::{self, LayoutError},
+    CompactBytesRef,
+};
+
+/// A read-only view of strings borrowed from a buffer in the binary layout written by
+/// [`CompactStrings::to_bytes`](crate::CompactStrings::to_bytes).
+///
+/// Both the starting indices and the strings are read straight out of the buffer, so parsing it
+/// validates the starting indices and UTF-8 but does not allocate.
+///
+/// # Examples
+/// ```
+/// # use compact_strings::{CompactStrings, CompactStrsRef};
+/// let cmpstrs = CompactStrings::from(["One", "Two"]);
+/// let buf = cmpstrs.to_bytes();
+///
+/// let view = CompactStrsRef::parse(&buf).unwrap();
+/// assert_eq!(view.get(0), Some("One"));
+/// assert_eq!(view.get(1), Some("Two"));
+/// assert_eq!(view.get(2), None);
+/// ```
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq)]
+pub struct CompactStrsRef<'a>(CompactBytesRef<'a>);
+
+impl<'a> CompactStrsRef<'a> {
+    /// Parses a buffer in the binary layout written by
+    /// [`CompactStrings::to_bytes`](crate::CompactStrings::to_bytes), borrowing the starting
+    /// indices and strings from it.
+    ///
+    /// # Errors
+    /// Returns an error if the length of the buffer does not match its header, if the starting
+    /// indices are out of order or out of bounds, or if any string is not valid UTF-8.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactBytestrings, CompactStrsRef, LayoutError};
+    /// let buf = CompactBytestrings::from([b"One", b"\xff\xff\xff"]).to_bytes();
+    ///
+    /// assert_eq!(CompactStrsRef::parse(&buf), Err(LayoutError::InvalidUtf8));
+    /// ```
+    pub fn parse(buf: &'a [u8]) -> Result<Self, LayoutError> {
+        let bytes = CompactBytesRef::parse(buf)?;
+        let (starts, data) = bytes.parts();
+        layout::check_utf8(
+            (0..bytes.len()).map(|i| layout::read_start(starts, i)),
+            data,
+        )?;
+        Ok(Self(bytes))
+    }
+
+    /// Creates a view over parts that have already been validated by [`layout::parse`] and
+    /// [`layout::check_utf8`].
+    #[cfg(feature = "mmap")]
+    pub(crate) fn from_parts(starts: &'a [u8], data: &'a [u8]) -> Self {
+        Self(CompactBytesRef::from_parts(starts, data))
+    }
+
+    /// Returns a reference to the string stored in the [`CompactStrsRef`] at that position.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactStrings, CompactStrsRef};
+    /// # let buf = CompactStrings::from(["One", "Two"]).to_bytes();
+    /// let view = CompactStrsRef::parse(&buf).unwrap();
+    ///
+    /// assert_eq!(view.get(0), Some("One"));
+    /// assert_eq!(view.get(1), Some("Two"));
+    /// assert_eq!(view.get(2), None);
+    /// ```
+    #[must_use]
+    pub fn get(self, index: usize) -> Option<&'a str> {
+        self.0.get(index).and_then(StrIter::from_utf8_maybe_checked)
+    }
+
+    /// Returns a reference to the string stored in the [`CompactStrsRef`] at that position, without
+    /// doing bounds checking.
+    ///
+    /// # Safety
+    /// Calling this method with an out-of-bounds index is undefined behavior even if the resulting reference is not used.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactStrings, CompactStrsRef};
+    /// # let buf = CompactStrings::from(["One", "Two"]).to_bytes();
+    /// let view = CompactStrsRef::parse(&buf).unwrap();
+    ///
+    /// unsafe {
+    ///     assert_eq!(view.get_unchecked(0), "One");
+    ///     assert_eq!(view.get_unchecked(1), "Two");
+    /// }
+    /// ```
+    #[must_use]
+    #[cfg(not(feature = "no_unsafe"))]
+    pub unsafe fn get_unchecked(self, index: usize) -> &'a str {
+        core::str::from_utf8_unchecked(self.0.get_unchecked(index))
+    }
+
+    /// Returns the number of strings in the [`CompactStrsRef`], also referred to as its 'length'.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactStrings, CompactStrsRef};
+    /// # let buf = CompactStrings::from(["One", "Two"]).to_bytes();
+    /// let view = CompactStrsRef::parse(&buf).unwrap();
+    ///
+    /// assert_eq!(view.len(), 2);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn len(self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if the [`CompactStrsRef`] contains no strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactStrings, CompactStrsRef};
+    /// # let buf = CompactStrings::new().to_bytes();
+    /// let view = CompactStrsRef::parse(&buf).unwrap();
+    ///
+    /// assert!(view.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_empty(self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Copies each string into an owned [`String`], returning them in a vector.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactStrings, CompactStrsRef};
+    /// # let buf = CompactStrings::from(["One", "Two"]).to_bytes();
+    /// let view = CompactStrsRef::parse(&buf).unwrap();
+    ///
+    /// assert_eq!(view.to_vec(), ["One", "Two"]);
+    /// ```
+    #[must_use]
+    pub fn to_vec(self) -> Vec<String> {
+        self.iter().map(String::from).collect()
+    }
+
+    /// Returns an iterator over the strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactStrings, CompactStrsRef};
+    /// # let buf = CompactStrings::from(["One", "Two"]).to_bytes();
+    /// let view = CompactStrsRef::parse(&buf).unwrap();
+    /// let mut iterator = view.iter();
+    ///
+    /// assert_eq!(iterator.next(), Some("One"));
+    /// assert_eq!(iterator.next(), Some("Two"));
+    /// assert_eq!(iterator.next(), None);
+    /// ```
+    #[inline]
+    pub fn iter(self) -> Iter<'a> {
+        Iter(self.0.iter())
+    }
+}
+
+impl Debug for CompactStrsRef<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl Index<usize> for CompactStrsRef<'_> {
+    type Output = str;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for CompactStrsRef<'a> {
+    type Error = LayoutError;
+
+    #[inline]
+    fn try_from(value: &'a [u8]) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl<'a> From<CompactStrsRef<'a>> for CompactBytesRef<'a> {
+    #[inline]
+    fn from(value: CompactStrsRef<'a>) -> Self {
+        value.0
+    }
+}
+
+/// Iterator over strings in a [`CompactStrsRef`]
+///
+/// # Examples
+/// ```
+/// # use compact_strings::{CompactStrings, CompactStrsRef};
+/// # let buf = CompactStrings::from(["One", "Two", "Three"]).to_bytes();
+/// let view = CompactStrsRef::parse(&buf).unwrap();
+/// let mut iter = view.into_iter();
+///
+/// assert_eq!(iter.next(), Some("One"));
+/// assert_eq!(iter.next_back(), Some("Three"));
+/// assert_eq!(iter.next(), Some("Two"));
+/// assert_eq!(iter.next(), None);
+/// ```
+#[must_use = "Iterators are lazy and do nothing unless consumed"]
+pub struct Iter<'a>(compact_bytes_ref::Iter<'a>);
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a str;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().and_then(StrIter::from_utf8_maybe_checked)
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.0.nth(n).and_then(StrIter::from_utf8_maybe_checked)
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.0.count()
+    }
+
+    #[inline]
+    fn last(self) -> Option<Self::Item> {
+        self.0.last().and_then(StrIter::from_utf8_maybe_checked)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Iter<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0
+            .next_back()
+            .and_then(StrIter::from_utf8_maybe_checked)
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl<'a> IntoIterator for CompactStrsRef<'a> {
+    type Item = &'a str;
+
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &CompactStrsRef<'a> {
+    type Item = &'a str;
+
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CompactBytestrings, CompactStrings, CompactStrsRef, LayoutError};
+
+    #[test]
+    fn rejects_split_char() {
+        let buf = CompactBytestrings::from([&b"Th\xc3"[..], b"\xa9"]).to_bytes();
+        assert_eq!(CompactStrsRef::parse(&buf), Err(LayoutError::InvalidUtf8));
+    }
+
+    #[test]
+    fn into_owned() {
+        let mut cmpstrs = CompactStrings::from(["One", "Two", "Thrée"]);
+        cmpstrs.remove(1);
+
+        let buf = cmpstrs.to_bytes();
+        let view = CompactStrsRef::parse(&buf).unwrap();
+        assert_eq!(CompactStrings::from(view), cmpstrs);
+    }
+}
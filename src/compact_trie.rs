@@ -0,0 +1,297 @@
+use core::{fmt::Debug, ops::Range};
+
+use alloc::{vec, vec::Vec};
+
+use crate::{fixed_compact_strings::Iter, search, FixedCompactStrings};
+
+/// A node of the trie, standing for the prefix of length `depth` shared by the strings in
+/// `start..end`.
+///
+/// Only prefixes where the strings branch off or end get a node, so there are at most about twice
+/// as many nodes as strings.
+#[derive(Clone, Copy)]
+struct Node {
+    depth: usize,
+    start: usize,
+    end: usize,
+    children_start: usize,
+    children_end: usize,
+}
+
+/// A prefix trie over a sorted [`FixedCompactStrings`], answering exact lookups, prefix counts
+/// and prefix iteration in *O*(*k*) time for keys of length *k*.
+///
+/// The strings sharing a prefix are adjacent in a sorted [`FixedCompactStrings`], so each node of
+/// the trie only stores the range of strings below it. The bytes themselves are read from the
+/// [`FixedCompactStrings`], and chains of nodes with a single child are merged into one.
+///
+/// # Examples
+/// ```
+/// # use compact_strings::FixedCompactStrings;
+/// let mut routes = FixedCompactStrings::from(["/api/users", "/api", "/static/app.js", "/api/posts"]);
+/// routes.sort();
+///
+/// let trie = routes.build_trie();
+/// assert_eq!(trie.position("/api"), Some(0));
+/// assert_eq!(trie.count_prefix("/api/"), 2);
+/// assert!(trie.iter_prefix("/api/").eq(["/api/posts", "/api/users"]));
+/// ```
+pub struct CompactTrie<'a> {
+    strings: &'a FixedCompactStrings,
+    nodes: Vec<Node>,
+    labels: Vec<u8>,
+}
+
+impl FixedCompactStrings {
+    /// Builds a [`CompactTrie`] over the strings, which must be sorted.
+    ///
+    /// # Panics
+    /// Panics if the strings are not sorted.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["car", "cart", "dog"]);
+    /// let trie = cmpstrs.build_trie();
+    ///
+    /// assert_eq!(trie.count_prefix("car"), 2);
+    /// ```
+    #[track_caller]
+    #[must_use]
+    pub fn build_trie(&self) -> CompactTrie<'_> {
+        #[cold]
+        #[inline(never)]
+        #[track_caller]
+        fn assert_failed() -> ! {
+            panic!("the strings must be sorted to build a trie");
+        }
+
+        if !self.iter().zip(self.iter().skip(1)).all(|(a, b)| a <= b) {
+            assert_failed();
+        }
+
+        let bytes = &self.0;
+        let mut nodes = vec![Node {
+            depth: 0,
+            start: 0,
+            end: self.len(),
+            children_start: 0,
+            children_end: 0,
+        }];
+        let mut labels = vec![0];
+
+        // Nodes are expanded in breadth-first order so that the children of each node are
+        // adjacent and sorted by their first byte.
+        let mut next = 0;
+        while next < nodes.len() {
+            let Node {
+                depth, start, end, ..
+            } = nodes[next];
+
+            // The strings ending at this node sort before the ones continuing past it.
+            let mut i = start;
+            while i < end && bytes[i].len() == depth {
+                i += 1;
+            }
+
+            nodes[next].children_start = nodes.len();
+            while i < end {
+                let byte = bytes[i][depth];
+                let run_end = i + search::partition_point(end - i, |k| bytes[i + k][depth] == byte);
+                nodes.push(Node {
+                    depth: common_prefix_len(&bytes[i], &bytes[run_end - 1]),
+                    start: i,
+                    end: run_end,
+                    children_start: 0,
+                    children_end: 0,
+                });
+                labels.push(byte);
+                i = run_end;
+            }
+            nodes[next].children_end = nodes.len();
+
+            next += 1;
+        }
+
+        CompactTrie {
+            strings: self,
+            nodes,
+            labels,
+        }
+    }
+}
+
+impl<'a> CompactTrie<'a> {
+    /// Returns the index of the first string equal to `key`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["car", "cart", "dog"]);
+    /// let trie = cmpstrs.build_trie();
+    ///
+    /// assert_eq!(trie.position("cart"), Some(1));
+    /// assert_eq!(trie.position("ca"), None);
+    /// ```
+    #[must_use]
+    pub fn position(&self, key: &str) -> Option<usize> {
+        let node = self.find(key.as_bytes())?;
+        let first = node.start;
+        (self.strings.0[first].len() == key.len()).then(|| first)
+    }
+
+    /// Returns true if any string is equal to `key`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["car", "cart", "dog"]);
+    /// let trie = cmpstrs.build_trie();
+    ///
+    /// assert!(trie.contains("dog"));
+    /// assert!(!trie.contains("do"));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, key: &str) -> bool {
+        self.position(key).is_some()
+    }
+
+    /// Returns the range of indices of the strings starting with `prefix`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["ant", "car", "cart", "dog"]);
+    /// let trie = cmpstrs.build_trie();
+    ///
+    /// assert_eq!(trie.prefix_range("car"), 1..3);
+    /// assert!(trie.prefix_range("cat").is_empty());
+    /// ```
+    #[must_use]
+    pub fn prefix_range(&self, prefix: &str) -> Range<usize> {
+        match self.find(prefix.as_bytes()) {
+            Some(node) => node.start..node.end,
+            None => 0..0,
+        }
+    }
+
+    /// Returns the number of strings starting with `prefix`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["ant", "car", "cart", "dog"]);
+    /// let trie = cmpstrs.build_trie();
+    ///
+    /// assert_eq!(trie.count_prefix("c"), 2);
+    /// assert_eq!(trie.count_prefix(""), 4);
+    /// assert_eq!(trie.count_prefix("e"), 0);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn count_prefix(&self, prefix: &str) -> usize {
+        self.prefix_range(prefix).len()
+    }
+
+    /// Returns an iterator over the strings starting with `prefix`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["ant", "car", "cart", "dog"]);
+    /// let trie = cmpstrs.build_trie();
+    ///
+    /// assert!(trie.iter_prefix("car").eq(["car", "cart"]));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn iter_prefix(&self, prefix: &str) -> Iter<'a> {
+        self.strings.iter_slice(self.prefix_range(prefix))
+    }
+
+    /// Returns the node for the shortest prefix of at least the length of `prefix` that the
+    /// strings starting with `prefix` share, if any do.
+    fn find(&self, prefix: &[u8]) -> Option<Node> {
+        let bytes = &self.strings.0;
+        let mut node = self.nodes[0];
+        let mut checked = 0;
+        loop {
+            if node.start == node.end {
+                return None;
+            }
+
+            // Merged nodes skip bytes, which are checked against any string below the node.
+            let upto = node.depth.min(prefix.len());
+            if bytes[node.start][checked..upto] != prefix[checked..upto] {
+                return None;
+            }
+            if prefix.len() <= node.depth {
+                return Some(node);
+            }
+            checked = upto;
+
+            let labels = &self.labels[node.children_start..node.children_end];
+            let child = labels.binary_search(&prefix[node.depth]).ok()?;
+            node = self.nodes[node.children_start + child];
+        }
+    }
+}
+
+impl Debug for CompactTrie<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CompactTrie")
+            .field("strings", &self.strings)
+            .field("nodes", &self.nodes.len())
+            .finish()
+    }
+}
+
+/// Returns the length of the longest common prefix of `a` and `b`.
+fn common_prefix_len(a: &[u8], b: &[u8]) -> usize {
+    a.iter().zip(b).take_while(|(a, b)| a == b).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, string::String, vec::Vec};
+
+    use crate::FixedCompactStrings;
+
+    #[test]
+    fn matches_linear_scan() {
+        let words = [
+            "", "a", "a", "ab", "abc", "abd", "abd", "b", "ba", "bab", "babc", "c", "héllo", "hélp",
+        ];
+        let cmpstrs = FixedCompactStrings::from(words);
+        let trie = cmpstrs.build_trie();
+
+        let mut queries: Vec<String> = words.iter().map(|&w| String::from(w)).collect();
+        queries.extend(words.iter().map(|w| format!("{w}x")));
+        queries.extend(["ba", "bb", "h", "hé", "abcd"].map(String::from));
+
+        for query in &queries {
+            assert_eq!(
+                trie.position(query),
+                words.iter().position(|w| w == query),
+                "{query}"
+            );
+            assert!(
+                trie.iter_prefix(query).eq(words
+                    .iter()
+                    .copied()
+                    .filter(|w| w.starts_with(query.as_str()))),
+                "{query}"
+            );
+        }
+    }
+
+    #[test]
+    fn empty() {
+        let cmpstrs = FixedCompactStrings::new();
+        let trie = cmpstrs.build_trie();
+
+        assert_eq!(trie.count_prefix(""), 0);
+        assert_eq!(trie.position(""), None);
+    }
+}
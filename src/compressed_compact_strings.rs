@@ -0,0 +1,443 @@
+use core::{cell::RefCell, fmt::Debug, iter::FusedIterator};
+
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+use crate::CompactStrings;
+
+/// The number of decompressed blocks kept around by a new [`CompressedCompactStrings`].
+const DEFAULT_CACHE_BLOCKS: usize = 4;
+
+/// A read-only list of strings whose bytes are split into fixed-size blocks, each compressed with
+/// LZ4, and decompressed on access.
+///
+/// The most recently accessed blocks are kept decompressed in a small cache, so reading nearby
+/// strings, or iterating over them, only decompresses each block once. Since the cache is behind
+/// a [`RefCell`], the [`CompressedCompactStrings`] is not [`Sync`].
+///
+/// Strings are returned as owned [`String`]s, as they may span several blocks and the blocks they
+/// came from may be evicted from the cache.
+///
+/// # Examples
+/// ```
+/// # use compact_strings::CompactStrings;
+/// let cmpstrs = CompactStrings::from(["One", "Two", "Three"]);
+/// let compressed = cmpstrs.compress_blocks(4);
+///
+/// assert_eq!(compressed.block_count(), 3);
+/// assert_eq!(compressed.get(2).as_deref(), Some("Three"));
+/// assert_eq!(compressed.to_compact_strings(), cmpstrs);
+/// ```
+#[derive(Clone)]
+pub struct CompressedCompactStrings {
+    blocks: Vec<Box<[u8]>>,
+    block_size: usize,
+    ends: Vec<usize>,
+    cache: RefCell<BlockCache>,
+}
+
+/// The most recently used decompressed blocks, least recently used first.
+#[derive(Clone)]
+struct BlockCache {
+    capacity: usize,
+    blocks: Vec<(usize, Vec<u8>)>,
+}
+
+impl BlockCache {
+    /// Returns the decompressed `block`, calling `decompress` and evicting the least recently
+    /// used block if it is not cached.
+    fn get(&mut self, block: usize, decompress: impl FnOnce() -> Vec<u8>) -> &[u8] {
+        if let Some(i) = self.blocks.iter().position(|&(cached, _)| cached == block) {
+            let entry = self.blocks.remove(i);
+            self.blocks.push(entry);
+        } else {
+            if self.blocks.len() >= self.capacity {
+                self.blocks.remove(0);
+            }
+            self.blocks.push((block, decompress()));
+        }
+
+        &self.blocks[self.blocks.len() - 1].1
+    }
+}
+
+impl CompactStrings {
+    /// Compresses the bytes of the strings in blocks of `block_size` bytes, returning a
+    /// [`CompressedCompactStrings`] with the same strings in the same order.
+    ///
+    /// Larger blocks compress better, while smaller blocks make accessing a single string cheaper.
+    ///
+    /// # Panics
+    /// Panics if `block_size` is zero.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let cmpstrs: CompactStrings = (0..1000).map(|i| format!("row {}", i % 10)).collect();
+    /// let compressed = cmpstrs.compress_blocks(4096);
+    ///
+    /// assert!(compressed.compressed_len() < cmpstrs.data_len());
+    /// assert_eq!(compressed.get(123).as_deref(), Some("row 3"));
+    /// ```
+    #[track_caller]
+    #[must_use]
+    pub fn compress_blocks(&self, block_size: usize) -> CompressedCompactStrings {
+        assert!(block_size > 0, "block size should be greater than zero");
+
+        let mut blocks = Vec::new();
+        let mut ends = Vec::with_capacity(self.len());
+        let mut buf = Vec::with_capacity(block_size);
+        let mut end = 0;
+        for string in self {
+            end += string.len();
+            ends.push(end);
+
+            let mut bytes = string.as_bytes();
+            while !bytes.is_empty() {
+                let (head, tail) = bytes.split_at(bytes.len().min(block_size - buf.len()));
+                buf.extend_from_slice(head);
+                bytes = tail;
+                if buf.len() == block_size {
+                    blocks.push(lz4_flex::block::compress(&buf).into_boxed_slice());
+                    buf.clear();
+                }
+            }
+        }
+
+        if !buf.is_empty() {
+            blocks.push(lz4_flex::block::compress(&buf).into_boxed_slice());
+        }
+
+        CompressedCompactStrings {
+            blocks,
+            block_size,
+            ends,
+            cache: RefCell::new(BlockCache {
+                capacity: DEFAULT_CACHE_BLOCKS,
+                blocks: Vec::new(),
+            }),
+        }
+    }
+}
+
+impl CompressedCompactStrings {
+    /// Returns the string stored in the [`CompressedCompactStrings`] at that position,
+    /// decompressing the blocks it is in unless they are cached.
+    ///
+    /// # Panics
+    /// Panics if a block fails to decompress, rather than returning [`None`] as if the index were
+    /// out of bounds. Blocks are only ever written by [`CompactStrings::compress_blocks`], so this
+    /// indicates a bug rather than bad input.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let compressed = CompactStrings::from(["One", "Two"]).compress_blocks(2);
+    ///
+    /// assert_eq!(compressed.get(1).as_deref(), Some("Two"));
+    /// assert_eq!(compressed.get(2), None);
+    /// ```
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<String> {
+        let end = *self.ends.get(index)?;
+        let start = index.checked_sub(1).map_or(0, |prev| self.ends[prev]);
+
+        let mut bytes = Vec::with_capacity(end - start);
+        let mut cache = self.cache.borrow_mut();
+        let mut pos = start;
+        while pos < end {
+            let block = pos / self.block_size;
+            let block_start = block * self.block_size;
+            let data = cache.get(block, || self.decompress(block));
+            let to = (end - block_start).min(data.len());
+            bytes.extend_from_slice(&data[pos - block_start..to]);
+            pos = block_start + to;
+        }
+
+        if cfg!(feature = "no_unsafe") {
+            Some(
+                String::from_utf8(bytes)
+                    .expect("the blocks should hold the bytes of valid strings"),
+            )
+        } else {
+            // SAFETY: The blocks hold the bytes of valid strings, split at the same boundaries.
+            Some(unsafe { String::from_utf8_unchecked(bytes) })
+        }
+    }
+
+    /// Returns the number of strings in the [`CompressedCompactStrings`], also referred to as its 'length'.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let compressed = CompactStrings::from(["One", "Two"]).compress_blocks(16);
+    ///
+    /// assert_eq!(compressed.len(), 2);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.ends.len()
+    }
+
+    /// Returns true if the [`CompressedCompactStrings`] contains no strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let compressed = CompactStrings::new().compress_blocks(16);
+    ///
+    /// assert!(compressed.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    /// Returns the number of bytes of the strings before compression.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let compressed = CompactStrings::from(["One", "Two"]).compress_blocks(16);
+    ///
+    /// assert_eq!(compressed.data_len(), 6);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn data_len(&self) -> usize {
+        self.ends.last().copied().unwrap_or(0)
+    }
+
+    /// Returns the number of bytes taken up by the compressed blocks.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let cmpstrs: CompactStrings = core::iter::repeat("abc").take(1000).collect();
+    /// let compressed = cmpstrs.compress_blocks(1024);
+    ///
+    /// assert!(compressed.compressed_len() < compressed.data_len() / 10);
+    /// ```
+    #[must_use]
+    pub fn compressed_len(&self) -> usize {
+        self.blocks.iter().map(|block| block.len()).sum()
+    }
+
+    /// Returns the number of bytes in each block before compression.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let compressed = CompactStrings::from(["One", "Two"]).compress_blocks(16);
+    ///
+    /// assert_eq!(compressed.block_size(), 16);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// Returns the number of compressed blocks.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let compressed = CompactStrings::from(["One", "Two"]).compress_blocks(4);
+    ///
+    /// assert_eq!(compressed.block_count(), 2);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn block_count(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Returns the maximum number of decompressed blocks kept in the cache.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let compressed = CompactStrings::from(["One", "Two"]).compress_blocks(4);
+    ///
+    /// assert_eq!(compressed.cache_capacity(), 4);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn cache_capacity(&self) -> usize {
+        self.cache.borrow().capacity
+    }
+
+    /// Sets the maximum number of decompressed blocks kept in the cache, evicting the least
+    /// recently used blocks if there are more.
+    ///
+    /// At least one block is always kept, so a `capacity` of zero is treated as one.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let mut compressed = CompactStrings::from(["One", "Two"]).compress_blocks(4);
+    /// compressed.set_cache_capacity(16);
+    ///
+    /// assert_eq!(compressed.cache_capacity(), 16);
+    /// ```
+    pub fn set_cache_capacity(&mut self, capacity: usize) {
+        let cache = self.cache.get_mut();
+        cache.capacity = capacity.max(1);
+        if cache.blocks.len() > cache.capacity {
+            let excess = cache.blocks.len() - cache.capacity;
+            cache.blocks.drain(..excess);
+        }
+    }
+
+    /// Returns an iterator over the strings, decompressing each block once while iterating.
+    ///
+    /// Like [`get`](CompressedCompactStrings::get), the iterator panics rather than ending early if
+    /// a block fails to decompress.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let compressed = CompactStrings::from(["One", "Two"]).compress_blocks(4);
+    ///
+    /// assert!(compressed.iter().eq(["One", "Two"]));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            inner: self,
+            front: 0,
+            back: self.len(),
+        }
+    }
+
+    /// Decompresses every block into a [`CompactStrings`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let compressed = CompactStrings::from(["One", "Two"]).compress_blocks(4);
+    ///
+    /// assert_eq!(compressed.to_compact_strings(), CompactStrings::from(["One", "Two"]));
+    /// ```
+    #[must_use]
+    pub fn to_compact_strings(&self) -> CompactStrings {
+        let mut cmpstrs = CompactStrings::with_capacity(self.data_len(), self.len());
+        for string in self {
+            cmpstrs.push(string);
+        }
+        cmpstrs
+    }
+
+    /// Decompresses `block` without going through the cache.
+    ///
+    /// The blocks are only ever written by [`CompactStrings::compress_blocks`], so failing to
+    /// decompress one is a bug, and panics rather than cutting a string or an iteration short.
+    fn decompress(&self, block: usize) -> Vec<u8> {
+        let len = (self.data_len() - block * self.block_size).min(self.block_size);
+        lz4_flex::block::decompress(&self.blocks[block], len)
+            .expect("blocks written by `compress_blocks` should decompress")
+    }
+}
+
+/// An iterator over the strings of a [`CompressedCompactStrings`], created by
+/// [`CompressedCompactStrings::iter`].
+pub struct Iter<'a> {
+    inner: &'a CompressedCompactStrings,
+    front: usize,
+    back: usize,
+}
+
+impl Iterator for Iter<'_> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.front += 1;
+        self.inner.get(self.front - 1)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.front = self.front.saturating_add(n).min(self.back);
+        self.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Iter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.back -= 1;
+        self.inner.get(self.back)
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+impl FusedIterator for Iter<'_> {}
+
+impl Debug for CompressedCompactStrings {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a> IntoIterator for &'a CompressedCompactStrings {
+    type Item = String;
+
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl From<&CompactStrings> for CompressedCompactStrings {
+    /// Compresses the strings in blocks of 64 KiB.
+    #[inline]
+    fn from(value: &CompactStrings) -> Self {
+        value.compress_blocks(1 << 16)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+
+    use crate::CompactStrings;
+
+    #[test]
+    fn strings_spanning_blocks() {
+        let cmpstrs: CompactStrings = (0..300)
+            .map(|i| format!("{}é{}", "x".repeat(i % 13), i))
+            .collect();
+
+        for block_size in [1, 7, 64, 1 << 16] {
+            let mut compressed = cmpstrs.compress_blocks(block_size);
+            compressed.set_cache_capacity(2);
+            assert_eq!(compressed.len(), cmpstrs.len());
+            assert_eq!(compressed.data_len(), cmpstrs.data_len());
+
+            for i in (0..cmpstrs.len()).rev().step_by(7) {
+                assert_eq!(compressed.get(i).as_deref(), cmpstrs.get(i));
+            }
+            assert!(compressed.iter().eq(&cmpstrs));
+            assert!(compressed.iter().rev().eq(cmpstrs.iter().rev()));
+            assert_eq!(compressed.get(cmpstrs.len()), None);
+        }
+    }
+}
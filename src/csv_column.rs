@@ -0,0 +1,125 @@
+use std::io::{self, Read, Write};
+
+use alloc::format;
+
+use csv::{ByteRecord, Reader, Writer};
+
+use crate::{utf8, CompactStrings};
+
+impl CompactStrings {
+    /// Reads the field at index `column` of every remaining record of a CSV reader into a new
+    /// [`CompactStrings`].
+    ///
+    /// Records are read into a single reused buffer, so no `String` is allocated per cell, and
+    /// only the fields of `column` are validated as UTF-8. Whether the first row is a header is
+    /// decided by the reader's configuration.
+    ///
+    /// # Errors
+    /// Returns any error encountered while reading, and an error of kind
+    /// [`io::ErrorKind::InvalidData`] if a record has no field at `column` or the field is not
+    /// valid UTF-8.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let data = "id,name\n1,One\n2,Two\n";
+    /// let mut reader = csv::Reader::from_reader(data.as_bytes());
+    ///
+    /// let column = reader.headers().unwrap().iter().position(|h| h == "name").unwrap();
+    /// let names = CompactStrings::from_csv_column(&mut reader, column).unwrap();
+    ///
+    /// assert!(names.iter().eq(["One", "Two"]));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "csv")))]
+    pub fn from_csv_column<R: Read>(reader: &mut Reader<R>, column: usize) -> csv::Result<Self> {
+        let mut cmpstrs = Self::new();
+        let mut record = ByteRecord::new();
+        while reader.read_byte_record(&mut record)? {
+            let field = record.get(column).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("record has no field at column {column}"),
+                )
+            })?;
+            let field = utf8::from_utf8(field)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+            cmpstrs.push(field);
+        }
+
+        Ok(cmpstrs)
+    }
+
+    /// Writes each string as a record with a single field to a CSV writer.
+    ///
+    /// A header, if any, must be written to the writer beforehand. The writer is flushed
+    /// afterwards.
+    ///
+    /// # Errors
+    /// Returns any error encountered while writing.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let cmpstrs = CompactStrings::from(["One", "Two, Three"]);
+    ///
+    /// let mut writer = csv::Writer::from_writer(Vec::new());
+    /// writer.write_record(["name"]).unwrap();
+    /// cmpstrs.write_csv_column(&mut writer).unwrap();
+    ///
+    /// let data = writer.into_inner().unwrap();
+    /// assert_eq!(data, b"name\nOne\n\"Two, Three\"\n");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "csv")))]
+    pub fn write_csv_column<W: Write>(&self, writer: &mut Writer<W>) -> csv::Result<()> {
+        for string in self {
+            writer.write_record([string])?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::CompactStrings;
+
+    #[test]
+    fn round_trips_awkward_fields() {
+        let cmpstrs = CompactStrings::from(["", "\"quoted\"", "multi\nline", "naïve"]);
+
+        let mut writer = csv::WriterBuilder::new()
+            .has_headers(false)
+            .from_writer(Vec::new());
+        cmpstrs.write_csv_column(&mut writer).unwrap();
+        let data = writer.into_inner().unwrap();
+
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .from_reader(data.as_slice());
+        let read = CompactStrings::from_csv_column(&mut reader, 0).unwrap();
+        assert_eq!(read, cmpstrs);
+    }
+
+    #[test]
+    fn missing_fields_and_invalid_utf8_are_errors() {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(&b"a,b\nc\n"[..]);
+        assert!(CompactStrings::from_csv_column(&mut reader, 1).is_err());
+
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .from_reader(&b"a,\xff\n"[..]);
+        assert!(CompactStrings::from_csv_column(&mut reader, 0).is_ok());
+
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .from_reader(&b"a,\xff\n"[..]);
+        assert!(CompactStrings::from_csv_column(&mut reader, 1).is_err());
+    }
+}
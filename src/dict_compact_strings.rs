@@ -0,0 +1,438 @@
+use core::{
+    fmt::Debug,
+    hash::BuildHasher,
+    iter::FusedIterator,
+    ops::{Deref, Index},
+    slice,
+};
+use std::collections::hash_map::RandomState;
+
+use alloc::vec::Vec;
+
+use crate::{CompactStrings, IndexedCompactStrings};
+
+/// A list of strings that stores each distinct string once, in a dictionary, and every element as
+/// a `u32` code into that dictionary.
+///
+/// Columns with few distinct values, such as country codes or enum-like values, take up one code
+/// per element plus the distinct strings, instead of the bytes of every element.
+///
+/// # Examples
+/// ```
+/// # use compact_strings::DictCompactStrings;
+/// let mut dict = DictCompactStrings::new();
+/// dict.push("NL");
+/// dict.push("SG");
+/// dict.push("NL");
+///
+/// assert_eq!(dict.get(2), Some("NL"));
+/// assert_eq!(dict.code(2), Some(0));
+/// assert_eq!(dict.dictionary().len(), 2);
+/// assert!(dict.iter().eq(["NL", "SG", "NL"]));
+/// ```
+pub struct DictCompactStrings<S = RandomState> {
+    dict: IndexedCompactStrings<S>,
+    codes: Vec<u32>,
+}
+
+impl CompactStrings {
+    /// Copies each distinct string once into a new dictionary, returning a [`DictCompactStrings`]
+    /// with the same strings in the same order and dropping the [`CompactStrings`].
+    ///
+    /// # Panics
+    /// Panics if there are more than [`u32::MAX`] distinct strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let cmpstrs = CompactStrings::from(["One", "Two", "One", "One"]);
+    /// let dict = cmpstrs.dictionary_encode();
+    ///
+    /// assert_eq!(dict.len(), 4);
+    /// assert_eq!(dict.dictionary().data_len(), 6);
+    /// ```
+    #[must_use]
+    pub fn dictionary_encode(self) -> DictCompactStrings {
+        let mut dict = DictCompactStrings::new();
+        dict.extend(&self);
+        dict
+    }
+}
+
+impl DictCompactStrings {
+    /// Constructs a new, empty [`DictCompactStrings`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::DictCompactStrings;
+    /// let dict = DictCompactStrings::new();
+    ///
+    /// assert!(dict.is_empty());
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<S> DictCompactStrings<S>
+where
+    S: BuildHasher,
+{
+    /// Constructs a new, empty [`DictCompactStrings`] which will use `hash_builder` to look up
+    /// strings in the dictionary.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::DictCompactStrings;
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let mut dict = DictCompactStrings::with_hasher(RandomState::new());
+    /// dict.push("One");
+    ///
+    /// assert_eq!(dict.get(0), Some("One"));
+    /// ```
+    #[must_use]
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self {
+            dict: IndexedCompactStrings::with_hasher(hash_builder),
+            codes: Vec::new(),
+        }
+    }
+
+    /// Appends a string to the back of the [`DictCompactStrings`], adding it to the dictionary if
+    /// it is not already there.
+    ///
+    /// # Panics
+    /// Panics if the dictionary would hold more than [`u32::MAX`] strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::DictCompactStrings;
+    /// let mut dict = DictCompactStrings::new();
+    /// dict.push("One");
+    /// dict.push("One");
+    ///
+    /// assert_eq!(dict.len(), 2);
+    /// assert_eq!(dict.dictionary().len(), 1);
+    /// ```
+    #[track_caller]
+    pub fn push<T>(&mut self, string: T)
+    where
+        T: Deref<Target = str>,
+    {
+        let code = if let Some(code) = self.code_of(&string) {
+            code
+        } else {
+            // Checked before pushing, so that a full dictionary is left unchanged.
+            let code = to_code(self.dict.len());
+            self.dict.push(string);
+            code
+        };
+
+        self.codes.push(code);
+    }
+
+    /// Returns the dictionary code of `string`, if it is in the dictionary.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let dict = CompactStrings::from(["One", "Two", "One"]).dictionary_encode();
+    ///
+    /// assert_eq!(dict.code_of("Two"), Some(1));
+    /// assert_eq!(dict.code_of("Three"), None);
+    /// ```
+    #[must_use]
+    pub fn code_of(&self, string: &str) -> Option<u32> {
+        self.dict.position(string).map(to_code)
+    }
+}
+
+/// Converts the index of a string in the dictionary into its code.
+#[track_caller]
+fn to_code(index: usize) -> u32 {
+    u32::try_from(index).expect("dictionary should hold at most u32::MAX strings")
+}
+
+impl<S> DictCompactStrings<S> {
+    /// Returns a reference to the string stored in the [`DictCompactStrings`] at that position.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let dict = CompactStrings::from(["One", "Two"]).dictionary_encode();
+    ///
+    /// assert_eq!(dict.get(1), Some("Two"));
+    /// assert_eq!(dict.get(2), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&str> {
+        let code = *self.codes.get(index)?;
+        self.dict.get(code as usize)
+    }
+
+    /// Returns the dictionary code of the string at that position.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let dict = CompactStrings::from(["One", "Two", "One"]).dictionary_encode();
+    ///
+    /// assert_eq!(dict.code(2), Some(0));
+    /// assert_eq!(dict.code(3), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn code(&self, index: usize) -> Option<u32> {
+        self.codes.get(index).copied()
+    }
+
+    /// Returns the dictionary codes of every string, in order.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let dict = CompactStrings::from(["One", "Two", "One"]).dictionary_encode();
+    ///
+    /// assert_eq!(dict.codes(), [0, 1, 0]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn codes(&self) -> &[u32] {
+        &self.codes
+    }
+
+    /// Returns the distinct strings, in the order of their first occurrences, so that a code is
+    /// an index into it.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let dict = CompactStrings::from(["One", "Two", "One"]).dictionary_encode();
+    ///
+    /// assert_eq!(dict.dictionary(), &CompactStrings::from(["One", "Two"]));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn dictionary(&self) -> &CompactStrings {
+        self.dict.as_compact_strings()
+    }
+
+    /// Returns the number of strings in the [`DictCompactStrings`], also referred to as its 'length'.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let dict = CompactStrings::from(["One", "One"]).dictionary_encode();
+    ///
+    /// assert_eq!(dict.len(), 2);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.codes.len()
+    }
+
+    /// Returns true if the [`DictCompactStrings`] contains no strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::DictCompactStrings;
+    /// let dict = DictCompactStrings::new();
+    ///
+    /// assert!(dict.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.codes.is_empty()
+    }
+
+    /// Returns an iterator over the strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let dict = CompactStrings::from(["One", "Two", "One"]).dictionary_encode();
+    ///
+    /// assert!(dict.iter().eq(["One", "Two", "One"]));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            dict: self.dictionary(),
+            codes: self.codes.iter(),
+        }
+    }
+
+    /// Decodes the strings back into a [`CompactStrings`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let dict = CompactStrings::from(["One", "Two", "One"]).dictionary_encode();
+    ///
+    /// assert_eq!(dict.to_compact_strings(), CompactStrings::from(["One", "Two", "One"]));
+    /// ```
+    #[must_use]
+    pub fn to_compact_strings(&self) -> CompactStrings {
+        let data_len = self.iter().map(str::len).sum();
+        let mut cmpstrs = CompactStrings::with_capacity(data_len, self.len());
+        cmpstrs.extend(self);
+        cmpstrs
+    }
+}
+
+impl<S> DictCompactStrings<S>
+where
+    S: BuildHasher,
+{
+    /// Clears the [`DictCompactStrings`], removing all strings and emptying the dictionary.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let mut dict = CompactStrings::from(["One", "Two"]).dictionary_encode();
+    /// dict.clear();
+    ///
+    /// assert!(dict.is_empty());
+    /// assert!(dict.dictionary().is_empty());
+    /// ```
+    pub fn clear(&mut self) {
+        self.dict.clear();
+        self.codes.clear();
+    }
+}
+
+/// An iterator over the strings of a [`DictCompactStrings`], created by
+/// [`DictCompactStrings::iter`].
+pub struct Iter<'a> {
+    dict: &'a CompactStrings,
+    codes: slice::Iter<'a, u32>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a str;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let code = *self.codes.next()?;
+        self.dict.get(code as usize)
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let code = *self.codes.nth(n)?;
+        self.dict.get(code as usize)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.codes.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Iter<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let code = *self.codes.next_back()?;
+        self.dict.get(code as usize)
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+impl FusedIterator for Iter<'_> {}
+
+impl Default for DictCompactStrings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Debug for DictCompactStrings<S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<S> Index<usize> for DictCompactStrings<S> {
+    type Output = str;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+
+impl<S, T> Extend<T> for DictCompactStrings<S>
+where
+    S: BuildHasher,
+    T: Deref<Target = str>,
+{
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for s in iter {
+            self.push(s);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for DictCompactStrings
+where
+    T: Deref<Target = str>,
+{
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut dict = Self::new();
+        dict.extend(iter);
+        dict
+    }
+}
+
+impl<'a, S> IntoIterator for &'a DictCompactStrings<S> {
+    type Item = &'a str;
+
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl From<CompactStrings> for DictCompactStrings {
+    #[inline]
+    fn from(value: CompactStrings) -> Self {
+        value.dictionary_encode()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+
+    use crate::{CompactStrings, DictCompactStrings};
+
+    #[test]
+    fn round_trips_low_cardinality_column() {
+        let cmpstrs: CompactStrings = (0..1000).map(|i| format!("code{}", i % 7)).collect();
+
+        let dict = cmpstrs.clone().dictionary_encode();
+        assert_eq!(dict.len(), 1000);
+        assert_eq!(dict.dictionary().len(), 7);
+        assert!(dict.iter().eq(&cmpstrs));
+        assert!(dict.iter().rev().eq(cmpstrs.iter().rev()));
+        assert_eq!(dict.to_compact_strings(), cmpstrs);
+
+        let mut dict: DictCompactStrings = ["", "a", ""].into_iter().collect();
+        assert_eq!(dict.codes(), [0, 1, 0]);
+        dict.clear();
+        dict.push("b");
+        assert_eq!(dict.code_of("b"), Some(0));
+        assert_eq!(&dict[0], "b");
+    }
+}
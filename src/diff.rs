@@ -0,0 +1,98 @@
+use core::cmp::Ordering;
+
+use alloc::vec::Vec;
+
+/// The elements that differ between two collections, as returned by `diff` methods such as
+/// [`CompactStrings::diff`](crate::CompactStrings::diff).
+///
+/// Elements are compared as a multiset, so an element held twice by one collection and once by
+/// the other is reported once. Both lists of indices are in ascending order.
+///
+/// # Examples
+/// ```
+/// # use compact_strings::CompactStrings;
+/// let old = CompactStrings::from(["One", "Two", "Two", "Three"]);
+/// let new = CompactStrings::from(["Two", "Four", "One"]);
+///
+/// let diff = old.diff(&new);
+/// assert_eq!(diff.removed, [2, 3]);
+/// assert_eq!(diff.added, [1]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Diff {
+    /// Indices of the elements of the first collection that the second does not hold.
+    pub removed: Vec<usize>,
+    /// Indices of the elements of the second collection that the first does not hold.
+    pub added: Vec<usize>,
+}
+
+impl Diff {
+    /// Walks the elements of two collections in sorted order, given as the indices `a_order` and
+    /// `b_order` that would sort them, and collects the indices of the elements without a match.
+    pub(crate) fn new<'a, T, A, B>(a_order: &[usize], a: A, b_order: &[usize], b: B) -> Self
+    where
+        T: Ord + ?Sized + 'a,
+        A: Fn(usize) -> &'a T,
+        B: Fn(usize) -> &'a T,
+    {
+        let mut diff = Self::default();
+        let (mut i, mut j) = (0, 0);
+        while let (Some(&x), Some(&y)) = (a_order.get(i), b_order.get(j)) {
+            match a(x).cmp(b(y)) {
+                Ordering::Less => {
+                    diff.removed.push(x);
+                    i += 1;
+                }
+                Ordering::Greater => {
+                    diff.added.push(y);
+                    j += 1;
+                }
+                Ordering::Equal => {
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+        diff.removed.extend_from_slice(&a_order[i..]);
+        diff.added.extend_from_slice(&b_order[j..]);
+
+        diff.removed.sort_unstable();
+        diff.added.sort_unstable();
+        diff
+    }
+
+    /// Returns true if neither collection holds an element the other does not.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let a = CompactStrings::from(["One", "Two"]);
+    /// let b = CompactStrings::from(["Two", "One"]);
+    ///
+    /// assert!(a.diff(&b).is_empty());
+    /// ```
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.removed.is_empty() && self.added.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::CompactBytestrings;
+
+    #[test]
+    fn diff_against_empty() {
+        let cmpbytes = CompactBytestrings::from([b"Two", b"One", b"Two"]);
+        let empty = CompactBytestrings::new();
+
+        let diff = cmpbytes.diff(&empty);
+        assert_eq!(diff.removed, [0, 1, 2]);
+        assert!(diff.added.is_empty());
+
+        let diff = empty.diff(&cmpbytes);
+        assert!(diff.removed.is_empty());
+        assert_eq!(diff.added, [0, 1, 2]);
+        assert!(empty.diff(&empty).is_empty());
+    }
+}
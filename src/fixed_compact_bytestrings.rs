@@ -1,8 +1,21 @@
-use core::{fmt::Debug, ops::Index};
-
-use alloc::vec::Vec;
-
-use crate::FixedCompactStrings;
+use core::{
+    cmp::Ordering,
+    fmt::Debug,
+    hash::Hasher,
+    ops::{Index, Range, RangeBounds},
+    str::Utf8Error,
+};
+
+use alloc::{borrow::Cow, boxed::Box, collections::BTreeMap, vec::Vec};
+
+use crate::{
+    layout,
+    memory_usage::with_slack,
+    permutation::check_permutation,
+    radix::radix_sort,
+    range::{check_sorted_indices, to_range},
+    search, utf8, Diff, FixedCompactStrings, LengthStats, MemoryUsage, SetError,
+};
 
 /// An even more compact but limited representation of a list of bytestrings.
 ///
@@ -54,7 +67,7 @@ impl FixedCompactBytestrings {
     ///
     /// - `data_capacity`: The capacity of the data vector where the bytes of the bytestrings are stored.
     /// - `capacity_meta`: The capacity of the meta vector where the starting indices
-    /// of the bytestrings are stored.
+    ///   of the bytestrings are stored.
     ///
     /// The [`FixedCompactBytestrings`] will be able to hold at least *`data_capacity`* bytes worth of bytestrings
     /// without reallocating the data vector, and at least *`capacity_meta`* of starting indices
@@ -86,6 +99,37 @@ impl FixedCompactBytestrings {
         }
     }
 
+    /// Constructs a new [`FixedCompactBytestrings`] holding copies of the bytestrings in `bytestrings`.
+    ///
+    /// Unlike collecting from an iterator, this sums up the lengths of the bytestrings first so that
+    /// both vectors are allocated exactly once, which makes it the fastest way to convert
+    /// an existing `Vec<Vec<u8>>`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let vec = vec![b"One".to_vec(), b"Two".to_vec(), b"Three".to_vec()];
+    /// let cmpbytes = FixedCompactBytestrings::from_slice(&vec);
+    ///
+    /// assert_eq!(cmpbytes.len(), 3);
+    /// assert!(cmpbytes.capacity() >= 11);
+    /// assert_eq!(cmpbytes.get(0), Some(b"One".as_slice()));
+    /// assert_eq!(cmpbytes.get(2), Some(b"Three".as_slice()));
+    /// ```
+    #[must_use]
+    pub fn from_slice<S>(bytestrings: &[S]) -> Self
+    where
+        S: AsRef<[u8]>,
+    {
+        let data_capacity = bytestrings.iter().map(|b| b.as_ref().len()).sum();
+        let mut out = Self::with_capacity(data_capacity, bytestrings.len());
+        for bytestring in bytestrings {
+            out.push(bytestring);
+        }
+
+        out
+    }
+
     /// Appends a bytestring to the back of the [`FixedCompactBytestrings`].
     ///
     /// # Examples
@@ -110,8 +154,73 @@ impl FixedCompactBytestrings {
         self.data.extend_from_slice(bytestr);
     }
 
+    /// Appends every bytestring in `bytestrings` to the back of the [`FixedCompactBytestrings`].
+    ///
+    /// The lengths of the bytestrings are summed up first so that each vector is reserved at most
+    /// once, rather than potentially reallocating on every [`push`](FixedCompactBytestrings::push).
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let mut cmpbytes = FixedCompactBytestrings::from([b"One"]);
+    /// cmpbytes.push_many(&[&b"Two"[..], b"Three"]);
+    ///
+    /// assert!(cmpbytes.capacity() >= 11);
+    /// assert!(cmpbytes.iter().eq([&b"One"[..], b"Two", b"Three"]));
+    /// ```
+    pub fn push_many<S>(&mut self, bytestrings: &[S])
+    where
+        S: AsRef<[u8]>,
+    {
+        let data_len = bytestrings.iter().map(|b| b.as_ref().len()).sum();
+        self.data.reserve(data_len);
+        self.starts.reserve(bytestrings.len());
+        for bytestring in bytestrings {
+            self.push(bytestring);
+        }
+    }
+
+    /// Appends bytes to the end of the last bytestring in the [`FixedCompactBytestrings`].
+    ///
+    /// # Panics
+    /// Panics if the [`FixedCompactBytestrings`] is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let mut cmpbytes = FixedCompactBytestrings::new();
+    /// cmpbytes.push(b"One");
+    /// cmpbytes.push(b"Tw");
+    /// cmpbytes.push_bytes_to_last(b"o");
+    ///
+    /// assert_eq!(cmpbytes.get(0), Some(b"One".as_slice()));
+    /// assert_eq!(cmpbytes.get(1), Some(b"Two".as_slice()));
+    /// ```
+    #[track_caller]
+    pub fn push_bytes_to_last<S>(&mut self, bytestring: S)
+    where
+        S: AsRef<[u8]>,
+    {
+        #[cold]
+        #[inline(never)]
+        #[track_caller]
+        fn assert_failed() -> ! {
+            panic!("cannot push to the last bytestring of an empty collection");
+        }
+
+        if self.starts.is_empty() {
+            assert_failed();
+        }
+
+        // The last bytestring always ends at the end of the data vector.
+        self.data.extend_from_slice(bytestring.as_ref());
+    }
+
     /// Returns a reference to the bytestring stored in the [`FixedCompactBytestrings`] at that position.
     ///
+    /// The end of the bytestring is the start of the next one (or the end of the data vector for the
+    /// last bytestring), so this only reads two starting indices and takes constant time.
+    ///
     /// # Examples
     /// ```
     /// # use compact_strings::FixedCompactBytestrings;
@@ -140,6 +249,73 @@ impl FixedCompactBytestrings {
         }
     }
 
+    /// Returns a mutable reference to the bytestring stored in the [`FixedCompactBytestrings`] at that position.
+    ///
+    /// The bytes can be changed in place, but the length of the bytestring cannot.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let mut cmpbytes = FixedCompactBytestrings::new();
+    /// cmpbytes.push(b"One");
+    /// cmpbytes.push(b"Two");
+    ///
+    /// if let Some(bytes) = cmpbytes.get_mut(1) {
+    ///     bytes.make_ascii_uppercase();
+    /// }
+    ///
+    /// assert_eq!(cmpbytes.get(1), Some(b"TWO".as_slice()));
+    /// assert_eq!(cmpbytes.get_mut(2), None);
+    /// ```
+    #[must_use]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut [u8]> {
+        let &start = self.starts.get(index)?;
+        let &next = self
+            .starts
+            .get(index.checked_add(1)?)
+            .unwrap_or(&self.data.len());
+
+        if cfg!(feature = "no_unsafe") {
+            self.data.get_mut(start..next)
+        } else {
+            unsafe { Some(self.data.get_unchecked_mut(start..next)) }
+        }
+    }
+
+    /// Overwrites the bytestring at the specified index with `bytestring`, which must have the same
+    /// length in bytes.
+    ///
+    /// As the length does not change, no bytes have to be shifted over.
+    ///
+    /// # Errors
+    /// Returns [`SetError::OutOfBounds`] if `index` is out of bounds, or
+    /// [`SetError::LengthMismatch`] if the lengths differ, leaving the [`FixedCompactBytestrings`] unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{FixedCompactBytestrings, SetError};
+    /// let mut cmpbytes = FixedCompactBytestrings::from([&b"One"[..], b"Two"]);
+    ///
+    /// assert_eq!(cmpbytes.try_set(1, b"Six"), Ok(()));
+    /// assert_eq!(cmpbytes.try_set(1, b"Three"), Err(SetError::LengthMismatch));
+    /// assert_eq!(cmpbytes.try_set(2, b"Ten"), Err(SetError::OutOfBounds));
+    ///
+    /// assert!(cmpbytes.iter().eq([&b"One"[..], b"Six"]));
+    /// ```
+    pub fn try_set<S>(&mut self, index: usize, bytestring: S) -> Result<(), SetError>
+    where
+        S: AsRef<[u8]>,
+    {
+        let bytestr = bytestring.as_ref();
+        let bytes = self.get_mut(index).ok_or(SetError::OutOfBounds)?;
+        if bytes.len() != bytestr.len() {
+            return Err(SetError::LengthMismatch);
+        }
+
+        bytes.copy_from_slice(bytestr);
+        Ok(())
+    }
+
     /// Returns a reference to the bytestring stored in the [`FixedCompactBytestrings`] at that position, without
     /// doing bounds checking.
     ///
@@ -168,6 +344,66 @@ impl FixedCompactBytestrings {
         self.data.get_unchecked(start..next)
     }
 
+    /// Returns references to the bytestrings at each of `indices`, or [`None`] if any index is out of
+    /// bounds.
+    ///
+    /// Every index is bounds checked up front, after which the bytestrings are read without any
+    /// further checks. Indices may repeat.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let cmpbytes = FixedCompactBytestrings::from([&b"One"[..], b"Two", b"Three"]);
+    ///
+    /// assert_eq!(cmpbytes.get_many([2, 0]), Some([&b"Three"[..], b"One"]));
+    /// assert_eq!(cmpbytes.get_many([0, 3]), None);
+    /// ```
+    #[must_use]
+    pub fn get_many<const N: usize>(&self, indices: [usize; N]) -> Option<[&[u8]; N]> {
+        if indices.iter().any(|&index| index >= self.len()) {
+            return None;
+        }
+
+        #[cfg(feature = "no_unsafe")]
+        let bytestrings = indices.map(|index| &self[index]);
+        #[cfg(not(feature = "no_unsafe"))]
+        let bytestrings = indices.map(|index| unsafe { self.get_unchecked(index) });
+
+        Some(bytestrings)
+    }
+
+    /// Returns references to the bytestrings at each index yielded by `indices`, or [`None`] if any
+    /// index is out of bounds, as described in [`get_many`](FixedCompactBytestrings::get_many).
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let cmpbytes = FixedCompactBytestrings::from([&b"One"[..], b"Two", b"Three"]);
+    ///
+    /// assert_eq!(cmpbytes.get_many_vec((0..3).rev().step_by(2)), Some(vec![&b"Three"[..], b"One"]));
+    /// assert_eq!(cmpbytes.get_many_vec([3]), None);
+    /// ```
+    #[must_use]
+    pub fn get_many_vec<I>(&self, indices: I) -> Option<Vec<&[u8]>>
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        let indices: Vec<usize> = indices.into_iter().collect();
+        if indices.iter().any(|&index| index >= self.len()) {
+            return None;
+        }
+
+        #[cfg(feature = "no_unsafe")]
+        let bytestrings = indices.into_iter().map(|index| &self[index]).collect();
+        #[cfg(not(feature = "no_unsafe"))]
+        let bytestrings = indices
+            .into_iter()
+            .map(|index| unsafe { self.get_unchecked(index) })
+            .collect();
+
+        Some(bytestrings)
+    }
+
     /// Returns the number of bytestrings in the [`FixedCompactBytestrings`], also referred to as its 'length'.
     ///
     /// # Examples
@@ -205,6 +441,69 @@ impl FixedCompactBytestrings {
         self.len() == 0
     }
 
+    /// Returns the number of bytes in the data vector, as opposed to its [`capacity`].
+    ///
+    /// [`capacity`]: FixedCompactBytestrings::capacity
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let mut cmpbytes = FixedCompactBytestrings::with_capacity(20, 3);
+    ///
+    /// cmpbytes.push(b"One");
+    /// cmpbytes.push(b"Three");
+    ///
+    /// assert_eq!(cmpbytes.data_len(), 8);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn data_len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns the number of bytes used and allocated by the data and meta vectors.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let mut cmpbytes = FixedCompactBytestrings::with_capacity(20, 3);
+    /// cmpbytes.push(b"One");
+    ///
+    /// let usage = cmpbytes.memory_usage();
+    /// assert_eq!(usage.data_used, 3);
+    /// assert!(usage.data_reserved >= 20);
+    /// assert!(usage.meta_reserved >= usage.meta_used);
+    /// ```
+    #[must_use]
+    pub fn memory_usage(&self) -> MemoryUsage {
+        MemoryUsage::new::<usize>(
+            self.data.len(),
+            self.data.capacity(),
+            self.starts.len(),
+            self.starts.capacity(),
+        )
+    }
+
+    /// Returns statistics about the lengths of the bytestrings, computed in one pass over the
+    /// starting indices.
+    ///
+    /// A [`FixedCompactBytestrings`] never holds garbage, so its garbage length is always zero.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let cmpbytes = FixedCompactBytestrings::from([&b"One"[..], b"Three"]);
+    ///
+    /// let stats = cmpbytes.stats();
+    /// assert_eq!(stats.count, 2);
+    /// assert_eq!((stats.min_len, stats.max_len), (3, 5));
+    /// assert_eq!(stats.total_len, 8);
+    /// ```
+    #[must_use]
+    pub fn stats(&self) -> LengthStats {
+        LengthStats::new(self.iter().map(<[u8]>::len), self.data.len())
+    }
+
     /// Returns the number of bytes the data vector can store without reallocating.
     ///
     /// # Examples
@@ -360,6 +659,77 @@ impl FixedCompactBytestrings {
         self.starts.shrink_to(min_capacity);
     }
 
+    /// Shrinks the capacities of both the data vector and the meta vector as much as possible.
+    ///
+    /// This is equivalent to calling both [`shrink_to_fit`] and [`shrink_meta_to_fit`].
+    ///
+    /// [`shrink_to_fit`]: FixedCompactBytestrings::shrink_to_fit
+    /// [`shrink_meta_to_fit`]: FixedCompactBytestrings::shrink_meta_to_fit
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let mut cmpbytes = FixedCompactBytestrings::with_capacity(20, 10);
+    ///
+    /// cmpbytes.push(b"One");
+    /// cmpbytes.push(b"Two");
+    ///
+    /// cmpbytes.shrink_all_to_fit();
+    /// assert!(cmpbytes.capacity() >= 6);
+    /// assert!(cmpbytes.capacity_meta() >= 2);
+    /// ```
+    #[inline]
+    pub fn shrink_all_to_fit(&mut self) {
+        self.shrink_to_fit();
+        self.shrink_meta_to_fit();
+    }
+
+    /// Shrinks the capacities of both the data vector and the meta vector, leaving room for a
+    /// fraction `slack` of their lengths to be added without reallocating.
+    ///
+    /// For example, a `slack` of `0.1` keeps 10% of spare capacity in each vector. A negative or
+    /// NaN `slack` is treated as zero. If a capacity is already below its target, it is left
+    /// unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let mut cmpbytes = FixedCompactBytestrings::with_capacity(100, 100);
+    ///
+    /// for _ in 0..10 {
+    ///     cmpbytes.push(b"Ten");
+    /// }
+    ///
+    /// cmpbytes.shrink_all_to_fit_with_slack(0.5);
+    /// assert!(cmpbytes.capacity() >= 45 && cmpbytes.capacity() < 100);
+    /// assert!(cmpbytes.capacity_meta() >= 15 && cmpbytes.capacity_meta() < 100);
+    /// ```
+    pub fn shrink_all_to_fit_with_slack(&mut self, slack: f64) {
+        self.data.shrink_to(with_slack(self.data.len(), slack));
+        self.starts.shrink_to(with_slack(self.starts.len(), slack));
+    }
+
+    /// Consumes and leaks the [`FixedCompactBytestrings`], returning a reference that lives for the rest of the
+    /// program, like [`Vec::leak`].
+    ///
+    /// The buffers are shrunk to fit before being leaked. Since the reference can be `'static`,
+    /// the bytestrings it hands out can be `'static` as well, which suits tables that are built once
+    /// and kept for the lifetime of the process.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let table: &'static FixedCompactBytestrings = FixedCompactBytestrings::from([b"One", b"Two"]).leak();
+    /// let first: &'static [u8] = table.get(0).unwrap();
+    ///
+    /// assert_eq!(first, b"One");
+    /// ```
+    #[must_use]
+    pub fn leak<'a>(mut self) -> &'a Self {
+        self.shrink_all_to_fit();
+        Box::leak(Box::new(self))
+    }
+
     /// Removes the bytes of the bytestring and data pointing to the bytestring is stored.
     ///
     /// Note: This does not shrink the vectors where the bytes of the bytestring and data to the bytestring
@@ -414,6 +784,7 @@ impl FixedCompactBytestrings {
 
         if cfg!(feature = "no_unsafe") {
             self.data.copy_within(start + len..inner_len, start);
+            self.data.truncate(inner_len - len);
         } else {
             unsafe {
                 let ptr = self.data.as_mut_ptr().add(start);
@@ -425,63 +796,1351 @@ impl FixedCompactBytestrings {
         }
     }
 
-    /// Returns an iterator over the slice.
+    /// Removes the bytestring at the specified index and returns it, shifting the bytestrings after it
+    /// to the left.
     ///
-    /// The iterator yields all items from start to end.
+    /// The bytes of the bytestring are copied out before the remaining bytes are shifted over, so
+    /// this has the same worst-case performance of *O*(*n*) as [`remove`].
     ///
-    /// # Examples
+    /// [`remove`]: FixedCompactBytestrings::remove
     ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    ///
+    /// # Examples
     /// ```
     /// # use compact_strings::FixedCompactBytestrings;
-    /// let mut cmpbytes = FixedCompactBytestrings::with_capacity(20, 3);
-    /// cmpbytes.push(b"One");
-    /// cmpbytes.push(b"Two");
-    /// cmpbytes.push(b"Three");
-    /// let mut iterator = cmpbytes.iter();
+    /// let mut cmpbytes = FixedCompactBytestrings::from([&b"One"[..], b"Two", b"Three"]);
     ///
-    /// assert_eq!(iterator.next(), Some(b"One".as_slice()));
-    /// assert_eq!(iterator.next(), Some(b"Two".as_slice()));
-    /// assert_eq!(iterator.next(), Some(b"Three".as_slice()));
-    /// assert_eq!(iterator.next(), None);
+    /// assert_eq!(cmpbytes.take(1), b"Two");
+    /// assert!(cmpbytes.iter().eq([&b"One"[..], b"Three"]));
     /// ```
-    #[inline]
-    pub fn iter(&self) -> Iter<'_> {
-        Iter::new(self)
-    }
-}
-
-impl Clone for FixedCompactBytestrings {
-    fn clone(&self) -> Self {
-        let mut data = Vec::with_capacity(self.starts.windows(2).map(|s| s[1] - s[0]).sum());
-        let mut meta = Vec::with_capacity(self.starts.len());
+    #[track_caller]
+    pub fn take(&mut self, index: usize) -> Vec<u8> {
+        #[cold]
+        #[inline(never)]
+        #[track_caller]
+        fn assert_failed(index: usize, len: usize) -> ! {
+            panic!("removal index (is {index}) should be < len (is {len})");
+        }
 
-        for bytes in self {
-            meta.push(bytes.len());
-            data.extend_from_slice(bytes);
+        let len = self.len();
+        if index >= len {
+            assert_failed(index, len);
         }
 
-        Self { data, starts: meta }
+        let taken = self[index].to_vec();
+        self.remove(index);
+        taken
     }
-}
 
-impl PartialEq for FixedCompactBytestrings {
-    fn eq(&self, other: &Self) -> bool {
-        let len = self.len();
-        if len != other.len() {
-            return false;
+    /// Removes the bytestrings at the specified indices, which must be sorted and unique.
+    ///
+    /// Every remaining bytestring is shifted over at most once, so this takes *O*(*n*) time in total
+    /// rather than *O*(*k* \* *n*) for *k* separate calls to [`remove`].
+    ///
+    /// [`remove`]: FixedCompactBytestrings::remove
+    ///
+    /// # Panics
+    /// Panics if `indices` is not strictly increasing or if an index is out of bounds.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let mut cmpbytes = FixedCompactBytestrings::from([&b"One"[..], b"Two", b"Three", b"Four"]);
+    /// cmpbytes.remove_many(&[0, 2]);
+    ///
+    /// assert!(cmpbytes.iter().eq([&b"Two"[..], b"Four"]));
+    /// ```
+    #[track_caller]
+    pub fn remove_many(&mut self, indices: &[usize]) {
+        check_sorted_indices(indices, self.len());
+        if indices.is_empty() {
+            return;
         }
 
-        for idx in 0..len {
-            if self[idx] != other[idx] {
-                return false;
+        let data_len = self.data.len();
+        let mut removed = indices.iter().peekable();
+        let mut kept = 0;
+        let mut write = 0;
+        for read in 0..self.starts.len() {
+            if removed.next_if_eq(&&read).is_some() {
+                continue;
             }
+
+            let start = self.starts[read];
+            let next = *self.starts.get(read + 1).unwrap_or(&data_len);
+            self.data.copy_within(start..next, write);
+            self.starts[kept] = write;
+            kept += 1;
+            write += next - start;
         }
 
-        true
+        self.starts.truncate(kept);
+        self.data.truncate(write);
     }
-}
 
-impl Debug for FixedCompactBytestrings {
+    /// Removes the bytes of the bytestrings in the specified range and the data pointing to them.
+    ///
+    /// This shifts the remaining bytes in the data vector and the remaining elements in the meta
+    /// vector only once, rather than once per bytestring as repeated calls to [`remove`] would.
+    ///
+    /// [`remove`]: FixedCompactBytestrings::remove
+    ///
+    /// # Panics
+    /// Panics if the starting point is greater than the end point or if the end point is greater
+    /// than the length of the [`FixedCompactBytestrings`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let mut cmpbytes = FixedCompactBytestrings::with_capacity(20, 4);
+    ///
+    /// cmpbytes.push(b"One");
+    /// cmpbytes.push(b"Two");
+    /// cmpbytes.push(b"Three");
+    /// cmpbytes.push(b"Four");
+    ///
+    /// cmpbytes.remove_range(1..3);
+    ///
+    /// assert_eq!(cmpbytes.get(0), Some(b"One".as_slice()));
+    /// assert_eq!(cmpbytes.get(1), Some(b"Four".as_slice()));
+    /// assert_eq!(cmpbytes.get(2), None);
+    /// ```
+    #[track_caller]
+    pub fn remove_range<R>(&mut self, range: R)
+    where
+        R: RangeBounds<usize>,
+    {
+        let Range { start, end } = to_range(range, self.len());
+        if start == end {
+            return;
+        }
+
+        let lo = self.starts[start];
+        let hi = *self.starts.get(end).unwrap_or(&self.data.len());
+
+        self.starts.drain(start..end);
+        for s in self.starts.iter_mut().skip(start) {
+            *s -= hi - lo;
+        }
+
+        self.data.drain(lo..hi);
+    }
+
+    /// Replaces the bytestrings in the specified range with the bytestrings of `replace_with`, returning
+    /// the removed bytestrings in a new [`FixedCompactBytestrings`].
+    ///
+    /// Like [`Vec::splice`], the tail of the data vector and of the starting indices is only
+    /// shifted once, regardless of how many bytestrings are removed or inserted.
+    ///
+    /// # Panics
+    /// Panics if the starting point is greater than the end point or if the end point is greater
+    /// than the length of the [`FixedCompactBytestrings`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let mut cmpbytes = FixedCompactBytestrings::from([&b"One"[..], b"Two", b"Three", b"Four"]);
+    /// let removed = cmpbytes.splice(1..3, [&b"2"[..], b"3", b"3.5"]);
+    ///
+    /// assert!(cmpbytes.iter().eq([&b"One"[..], b"2", b"3", b"3.5", b"Four"]));
+    /// assert!(removed.iter().eq([b"Two".as_slice(), b"Three"]));
+    /// ```
+    #[track_caller]
+    #[must_use = "the removed bytestrings are returned in a new collection"]
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Self
+    where
+        R: RangeBounds<usize>,
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        let range = to_range(range, self.len());
+        let replacement = replace_with.into_iter().collect();
+        self.splice_from(range, replacement)
+    }
+
+    /// Replaces the bytestrings in `range`, which must be in bounds, with those of `replacement`.
+    pub(crate) fn splice_from(&mut self, range: Range<usize>, replacement: Self) -> Self {
+        let removed = self.gather(range.clone());
+
+        let data_len = self.data.len();
+        let lo = self.starts.get(range.start).copied().unwrap_or(data_len);
+        let hi = self.starts.get(range.end).copied().unwrap_or(data_len);
+
+        let inserted = replacement.data.len();
+        let inserted_count = replacement.starts.len();
+        self.data.splice(lo..hi, replacement.data);
+        self.starts.splice(
+            range.clone(),
+            replacement.starts.into_iter().map(|start| lo + start),
+        );
+        for start in self.starts.iter_mut().skip(range.start + inserted_count) {
+            *start = *start - (hi - lo) + inserted;
+        }
+
+        removed
+    }
+
+    /// Removes all but the first of consecutive bytestrings that `same_bucket` considers equal.
+    ///
+    /// `same_bucket` is passed each bytestring followed by the last bytestring that was kept before
+    /// it, like [`Vec::dedup_by`]. The bytes of the remaining bytestrings are moved together in a
+    /// single pass over the data vector.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let mut cmpbytes = FixedCompactBytestrings::from([b"One", b"ONE", b"Two", b"one"]);
+    /// cmpbytes.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    ///
+    /// assert!(cmpbytes.iter().eq([b"One", b"Two", b"one"]));
+    /// ```
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+    where
+        F: FnMut(&[u8], &[u8]) -> bool,
+    {
+        self.retain_with(|bytes, prev| prev.map_or(true, |prev| !same_bucket(bytes, prev)));
+    }
+
+    /// Removes every bytestring for which `pred` returns true, returning how many were removed.
+    ///
+    /// The bytes of the remaining bytestrings are moved together in a single pass over the data
+    /// vector, rather than once per bytestring as repeated calls to [`remove`] would.
+    ///
+    /// [`remove`]: FixedCompactBytestrings::remove
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let mut cmpbytes = FixedCompactBytestrings::from([&b"One"[..], b"Two", b"Three", b"Four"]);
+    ///
+    /// assert_eq!(cmpbytes.remove_matching(|bytes| bytes.starts_with(b"T")), 2);
+    /// assert!(cmpbytes.iter().eq([&b"One"[..], b"Four"]));
+    /// ```
+    pub fn remove_matching<F>(&mut self, mut pred: F) -> usize
+    where
+        F: FnMut(&[u8]) -> bool,
+    {
+        let len = self.starts.len();
+        self.retain_with(|bytes, _| !pred(bytes));
+        len - self.starts.len()
+    }
+
+    /// Keeps only the bytestrings for which `keep` returns true, moving their bytes together in a
+    /// single pass over the data vector.
+    ///
+    /// `keep` is passed each bytestring followed by the last bytestring that was kept before it,
+    /// if any.
+    fn retain_with<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&[u8], Option<&[u8]>) -> bool,
+    {
+        /// Moves the bytestrings that have not been visited yet down to the ones that were kept,
+        /// which also keeps the [`FixedCompactBytestrings`] consistent if `keep` panics.
+        struct Guard<'a> {
+            inner: &'a mut FixedCompactBytestrings,
+            read: usize,
+            kept: usize,
+            write: usize,
+        }
+
+        impl Drop for Guard<'_> {
+            fn drop(&mut self) {
+                let inner = &mut *self.inner;
+                let data_len = inner.data.len();
+                let tail = inner.starts.get(self.read).copied().unwrap_or(data_len);
+                let shift = tail - self.write;
+
+                inner.data.copy_within(tail.., self.write);
+                inner.data.truncate(data_len - shift);
+                for start in &mut inner.starts[self.read..] {
+                    *start -= shift;
+                }
+                inner.starts.drain(self.kept..self.read);
+            }
+        }
+
+        let mut guard = Guard {
+            inner: self,
+            read: 0,
+            kept: 0,
+            write: 0,
+        };
+
+        while guard.read < guard.inner.starts.len() {
+            let data = &guard.inner.data;
+            let start = guard.inner.starts[guard.read];
+            let end = guard
+                .inner
+                .starts
+                .get(guard.read + 1)
+                .copied()
+                .unwrap_or(data.len());
+
+            let prev =
+                (guard.kept > 0).then(|| &data[guard.inner.starts[guard.kept - 1]..guard.write]);
+            if !keep(&data[start..end], prev) {
+                guard.read += 1;
+                continue;
+            }
+
+            guard.inner.data.copy_within(start..end, guard.write);
+            guard.inner.starts[guard.kept] = guard.write;
+            guard.write += end - start;
+            guard.kept += 1;
+            guard.read += 1;
+        }
+    }
+
+    /// Removes all but the first of consecutive bytestrings that map to the same key.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let mut cmpbytes = FixedCompactBytestrings::from([&b"One"[..], b"Two", b"Three", b"Four"]);
+    /// cmpbytes.dedup_by_key(|bytes| bytes.len());
+    ///
+    /// assert!(cmpbytes.iter().eq([b"One".as_slice(), b"Three", b"Four"]));
+    /// ```
+    #[inline]
+    pub fn dedup_by_key<K, F>(&mut self, mut key: F)
+    where
+        K: PartialEq,
+        F: FnMut(&[u8]) -> K,
+    {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    /// Returns a new [`FixedCompactBytestrings`] holding copies of the bytestrings at `indices`, in that order.
+    ///
+    /// Indices may repeat. The lengths of the selected bytestrings are summed first, so the data
+    /// vector of the new [`FixedCompactBytestrings`] is allocated exactly once and with no spare capacity.
+    ///
+    /// # Panics
+    /// Panics if any index is out of bounds.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let cmpbytes = FixedCompactBytestrings::from([&b"One"[..], b"Two", b"Three"]);
+    /// let gathered = cmpbytes.gather([2, 0, 2]);
+    ///
+    /// assert!(gathered.iter().eq([&b"Three"[..], b"One", b"Three"]));
+    /// ```
+    #[track_caller]
+    #[must_use]
+    pub fn gather<I>(&self, indices: I) -> Self
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        #[cold]
+        #[inline(never)]
+        #[track_caller]
+        fn assert_failed(index: usize, len: usize) -> ! {
+            panic!("gather index (is {index}) should be < len (is {len})");
+        }
+
+        let indices: Vec<usize> = indices.into_iter().collect();
+        let mut data_len = 0;
+        for &index in &indices {
+            match self.get(index) {
+                Some(bytestring) => data_len += bytestring.len(),
+                None => assert_failed(index, self.len()),
+            }
+        }
+
+        let mut gathered = Self::with_capacity(data_len, indices.len());
+        for index in indices {
+            gathered.push(&self[index]);
+        }
+
+        gathered
+    }
+
+    /// Returns a new [`FixedCompactBytestrings`] holding copies of the bytestrings for which `pred` returns true.
+    ///
+    /// Unlike collecting a filtered iterator, this finds the matching bytestrings and the total
+    /// length of their bytes first, so the data vector of the new [`FixedCompactBytestrings`] is allocated only once.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let cmpbytes = FixedCompactBytestrings::from([&b"One"[..], b"Two", b"Three"]);
+    /// let filtered = cmpbytes.filtered(|bytes| bytes.len() == 3);
+    ///
+    /// assert!(filtered.iter().eq([b"One", b"Two"]));
+    /// ```
+    #[must_use]
+    pub fn filtered<F>(&self, mut pred: F) -> Self
+    where
+        F: FnMut(&[u8]) -> bool,
+    {
+        let indices: Vec<usize> = (0..self.len()).filter(|&i| pred(&self[i])).collect();
+        self.gather(indices)
+    }
+
+    /// Returns a new [`FixedCompactBytestrings`] holding the result of `f` for each bytestring.
+    ///
+    /// Borrowed results are copied straight into the data vector of the new [`FixedCompactBytestrings`], so bytestrings
+    /// that `f` leaves unchanged or only slices never need an allocation of their own.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// use std::borrow::Cow;
+    ///
+    /// let cmpbytes = FixedCompactBytestrings::from([&b"One"[..], b"Two!", b"Three"]);
+    /// let mapped = cmpbytes.map_bytestrings(|bytes| match bytes.strip_suffix(b"!") {
+    ///     Some(bytes) => Cow::Owned(bytes.repeat(2)),
+    ///     None => Cow::Borrowed(bytes),
+    /// });
+    ///
+    /// assert!(mapped.iter().eq([&b"One"[..], b"TwoTwo", b"Three"]));
+    /// ```
+    #[must_use]
+    pub fn map_bytestrings<'a, F>(&'a self, mut f: F) -> Self
+    where
+        F: FnMut(&'a [u8]) -> Cow<'a, [u8]>,
+    {
+        let mut mapped = Self::with_capacity(self.data_len(), self.len());
+        for item in self {
+            mapped.push(f(item));
+        }
+
+        mapped
+    }
+
+    /// Groups the bytestrings by the key that `key` returns for each of them, returning a map from
+    /// each key to a new [`FixedCompactBytestrings`] holding copies of its bytestrings in their original
+    /// order.
+    ///
+    /// The keys are computed once per bytestring, and the number and total length of the
+    /// bytestrings in each group are counted before copying them, so the buffers of every group
+    /// are allocated exactly once.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let cmpbytes = FixedCompactBytestrings::from([&b"One"[..], b"Three", b"Two", b"Four"]);
+    /// let groups = cmpbytes.group_by_key(<[u8]>::len);
+    ///
+    /// assert_eq!(groups.len(), 3);
+    /// assert!(groups[&3].iter().eq([b"One", b"Two"]));
+    /// assert!(groups[&5].iter().eq([b"Three"]));
+    /// ```
+    #[must_use]
+    pub fn group_by_key<K, F>(&self, mut key: F) -> BTreeMap<K, Self>
+    where
+        K: Ord,
+        F: FnMut(&[u8]) -> K,
+    {
+        let mut ids = BTreeMap::new();
+        let mut sizes: Vec<(usize, usize)> = Vec::new();
+        let mut group_of = Vec::with_capacity(self.len());
+        for bytestring in self {
+            let next_id = ids.len();
+            let id = *ids.entry(key(bytestring)).or_insert(next_id);
+            if id == sizes.len() {
+                sizes.push((0, 0));
+            }
+            sizes[id].0 += bytestring.len();
+            sizes[id].1 += 1;
+            group_of.push(id);
+        }
+
+        let mut groups: Vec<Self> = sizes
+            .into_iter()
+            .map(|(data_len, len)| Self::with_capacity(data_len, len))
+            .collect();
+        for (bytestring, id) in self.iter().zip(group_of) {
+            groups[id].push(bytestring);
+        }
+
+        ids.into_iter()
+            .map(|(key, id)| (key, core::mem::take(&mut groups[id])))
+            .collect()
+    }
+
+    /// Sorts the bytestrings.
+    ///
+    /// This sort is stable. The bytestrings are copied into a new data vector in their sorted
+    /// order.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let mut cmpbytes = FixedCompactBytestrings::from([b"Two", b"One", b"Six"]);
+    /// cmpbytes.sort();
+    ///
+    /// assert!(cmpbytes.iter().eq([b"One", b"Six", b"Two"]));
+    /// ```
+    #[inline]
+    pub fn sort(&mut self) {
+        self.sort_by(Ord::cmp);
+    }
+
+    /// Sorts the bytestrings with a comparator function.
+    ///
+    /// This sort is stable, and rewrites the data vector once like [`sort`].
+    ///
+    /// [`sort`]: FixedCompactBytestrings::sort
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let mut cmpbytes = FixedCompactBytestrings::from([&b"Three"[..], b"One", b"Four"]);
+    /// cmpbytes.sort_by(|a, b| b.cmp(a));
+    ///
+    /// assert!(cmpbytes.iter().eq([&b"Three"[..], b"One", b"Four"]));
+    /// ```
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&[u8], &[u8]) -> Ordering,
+    {
+        let mut ranges = self.ranges();
+        let data = &self.data;
+        ranges.sort_by(|a, b| compare(&data[a.clone()], &data[b.clone()]));
+        self.rewrite_from_ranges(&ranges);
+    }
+
+    /// Sorts the bytestrings with a key extraction function.
+    ///
+    /// This sort is stable, and rewrites the data vector once like [`sort`].
+    ///
+    /// [`sort`]: FixedCompactBytestrings::sort
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let mut cmpbytes = FixedCompactBytestrings::from([&b"Three"[..], b"One", b"Four"]);
+    /// cmpbytes.sort_by_key(<[u8]>::len);
+    ///
+    /// assert!(cmpbytes.iter().eq([&b"One"[..], b"Four", b"Three"]));
+    /// ```
+    #[inline]
+    pub fn sort_by_key<K, F>(&mut self, mut key: F)
+    where
+        K: Ord,
+        F: FnMut(&[u8]) -> K,
+    {
+        self.sort_by(|a, b| key(a).cmp(&key(b)));
+    }
+
+    /// Returns true if the bytestrings are sorted in ascending order.
+    ///
+    /// Adjacent bytestrings are compared straight out of the data vector, without allocating. This
+    /// checks the precondition of binary-search-based methods such as [`iter_sorted_with_prefix`].
+    ///
+    /// [`iter_sorted_with_prefix`]: FixedCompactBytestrings::iter_sorted_with_prefix
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let mut cmpbytes = FixedCompactBytestrings::from([b"Two", b"One", b"Six"]);
+    /// assert!(!cmpbytes.is_sorted());
+    ///
+    /// cmpbytes.sort();
+    /// assert!(cmpbytes.is_sorted());
+    /// ```
+    #[must_use]
+    pub fn is_sorted(&self) -> bool {
+        self.is_sorted_by(Ord::cmp)
+    }
+
+    /// Returns true if the bytestrings are sorted by a comparator function, meaning that it never
+    /// returns [`Ordering::Greater`] for a bytestring and the one after it.
+    ///
+    /// This takes the same comparator as [`sort_by`], after which it always returns true.
+    ///
+    /// [`sort_by`]: FixedCompactBytestrings::sort_by
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let cmpbytes = FixedCompactBytestrings::from([&b"Two"[..], b"Three", b"One"]);
+    ///
+    /// assert!(cmpbytes.is_sorted_by(|a, b| b.cmp(a)));
+    /// assert!(!cmpbytes.is_sorted_by(|a, b| a.len().cmp(&b.len())));
+    /// ```
+    pub fn is_sorted_by<F>(&self, mut compare: F) -> bool
+    where
+        F: FnMut(&[u8], &[u8]) -> Ordering,
+    {
+        self.iter()
+            .zip(self.iter().skip(1))
+            .all(|(a, b)| compare(a, b) != Ordering::Greater)
+    }
+
+    /// Returns the index of the first bytestring for which `pred` returns false, assuming that it
+    /// returns true for every bytestring before that one and false for every bytestring after it, like
+    /// [`slice::partition_point`].
+    ///
+    /// This is a binary search, so it runs in *O*(log *n*) time. If the bytestrings are not
+    /// partitioned by `pred`, the index returned is unspecified.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let cmpbytes = FixedCompactBytestrings::from([&b"ant"[..], b"bee", b"cat", b"dog"]);
+    ///
+    /// assert_eq!(cmpbytes.partition_point(|s| s < &b"c"[..]), 2);
+    /// assert_eq!(cmpbytes.partition_point(|_| true), 4);
+    /// ```
+    pub fn partition_point<P>(&self, mut pred: P) -> usize
+    where
+        P: FnMut(&[u8]) -> bool,
+    {
+        search::partition_point(self.len(), |i| pred(&self[i]))
+    }
+
+    /// Inserts a bytestring at the position that keeps the [`FixedCompactBytestrings`] sorted,
+    /// returning its index.
+    ///
+    /// The position is found with a binary search, after any bytestrings equal to the new one, and
+    /// the bytes after it are shifted over once. If the [`FixedCompactBytestrings`] is not sorted,
+    /// the position is unspecified.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let mut cmpbytes = FixedCompactBytestrings::from([b"One", b"Two"]);
+    ///
+    /// assert_eq!(cmpbytes.insert_sorted(b"Six"), 1);
+    /// assert_eq!(cmpbytes.insert_sorted(b"Ten"), 2);
+    /// assert!(cmpbytes.iter().eq([b"One", b"Six", b"Ten", b"Two"]));
+    /// ```
+    pub fn insert_sorted<S>(&mut self, bytestring: S) -> usize
+    where
+        S: AsRef<[u8]>,
+    {
+        let bytestr = bytestring.as_ref();
+        let index = self.partition_point(|s| s <= bytestr);
+        let start = self.starts.get(index).copied().unwrap_or(self.data.len());
+
+        self.data.splice(start..start, bytestr.iter().copied());
+        for start in &mut self.starts[index..] {
+            *start += bytestr.len();
+        }
+        self.starts.insert(index, start);
+
+        index
+    }
+
+    /// Merges two sorted [`FixedCompactBytestrings`] into a new sorted [`FixedCompactBytestrings`].
+    ///
+    /// Both the data vector and the meta vector of the result are allocated exactly once. Equal
+    /// bytestrings from `a` are placed before those from `b`. If either input is not sorted, the
+    /// order of the result is unspecified.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let a = FixedCompactBytestrings::from([b"One", b"Ten"]);
+    /// let b = FixedCompactBytestrings::from([b"Six", b"Two"]);
+    /// let merged = FixedCompactBytestrings::merge_sorted(&a, &b);
+    ///
+    /// assert!(merged.iter().eq([b"One", b"Six", b"Ten", b"Two"]));
+    /// ```
+    #[must_use]
+    pub fn merge_sorted(a: &Self, b: &Self) -> Self {
+        let data_len = a.lengths().sum::<usize>() + b.lengths().sum::<usize>();
+        let mut merged = Self::with_capacity(data_len, a.len() + b.len());
+        let mut a_iter = a.iter().peekable();
+        let mut b_iter = b.iter().peekable();
+
+        while let (Some(&x), Some(&y)) = (a_iter.peek(), b_iter.peek()) {
+            if y < x {
+                merged.push(y);
+                b_iter.next();
+            } else {
+                merged.push(x);
+                a_iter.next();
+            }
+        }
+        merged.extend(a_iter);
+        merged.extend(b_iter);
+
+        merged
+    }
+
+    /// Returns the indices of the bytestrings that are only in `self` and of those that are only
+    /// in `other`, comparing them as multisets.
+    ///
+    /// Sorted collections are compared in one linear pass. Otherwise, the unsorted collection is
+    /// put in order with [`sorted_indices`](FixedCompactBytestrings::sorted_indices) first.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let old = FixedCompactBytestrings::from([b"One", b"Six", b"Two"]);
+    /// let new = FixedCompactBytestrings::from([b"One", b"Ten", b"Two"]);
+    ///
+    /// let diff = old.diff(&new);
+    /// assert_eq!(diff.removed, [1]);
+    /// assert_eq!(diff.added, [1]);
+    /// ```
+    #[must_use]
+    pub fn diff(&self, other: &Self) -> Diff {
+        fn order(cmpbytes: &FixedCompactBytestrings) -> Vec<usize> {
+            if cmpbytes.is_sorted() {
+                (0..cmpbytes.len()).collect()
+            } else {
+                cmpbytes.sorted_indices()
+            }
+        }
+
+        Diff::new(&order(self), |i| &self[i], &order(other), |i| &other[i])
+    }
+
+    /// Sorts the bytestrings with a radix sort, which distributes them by one byte at a time
+    /// instead of comparing them.
+    ///
+    /// This sort is stable and gives the same order as [`sort`], but is usually faster for large
+    /// collections of short bytestrings.
+    ///
+    /// [`sort`]: FixedCompactBytestrings::sort
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let mut cmpbytes = FixedCompactBytestrings::from([&b"Two"[..], b"One", b"On", b"Six"]);
+    /// cmpbytes.sort_radix();
+    ///
+    /// assert!(cmpbytes.iter().eq([&b"On"[..], b"One", b"Six", b"Two"]));
+    /// ```
+    pub fn sort_radix(&mut self) {
+        let mut ranges = self.ranges();
+        let data = &self.data;
+        radix_sort(&mut ranges, |range| &data[range.clone()]);
+        self.rewrite_from_ranges(&ranges);
+    }
+
+    /// Returns the indices of the bytestrings in the order that would sort them, without reordering
+    /// the [`FixedCompactBytestrings`] itself.
+    ///
+    /// Equal bytestrings keep their relative order.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let cmpbytes = FixedCompactBytestrings::from([b"Two", b"One", b"Six"]);
+    ///
+    /// assert_eq!(cmpbytes.sorted_indices(), [1, 2, 0]);
+    /// ```
+    #[must_use]
+    pub fn sorted_indices(&self) -> Vec<usize> {
+        self.sorted_indices_by(Ord::cmp)
+    }
+
+    /// Returns the indices of the bytestrings in the order that would sort them with a comparator
+    /// function, without reordering the [`FixedCompactBytestrings`] itself.
+    ///
+    /// Equal bytestrings keep their relative order.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let cmpbytes = FixedCompactBytestrings::from([&b"Three"[..], b"One", b"Four"]);
+    ///
+    /// assert_eq!(cmpbytes.sorted_indices_by(|a, b| a.len().cmp(&b.len())), [1, 2, 0]);
+    /// ```
+    pub fn sorted_indices_by<F>(&self, mut compare: F) -> Vec<usize>
+    where
+        F: FnMut(&[u8], &[u8]) -> Ordering,
+    {
+        let mut indices: Vec<usize> = (0..self.len()).collect();
+        indices.sort_by(|&a, &b| compare(&self[a], &self[b]));
+        indices
+    }
+
+    /// Reorders the bytestrings so that the bytestring at each position `i` is the one that was at
+    /// `perm[i]`, rewriting the data vector once.
+    ///
+    /// Passing the result of [`sorted_indices`] sorts the [`FixedCompactBytestrings`].
+    ///
+    /// [`sorted_indices`]: FixedCompactBytestrings::sorted_indices
+    ///
+    /// # Panics
+    /// Panics if `perm` is not a permutation of `0..len`, where `len` is the number of bytestrings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let mut cmpbytes = FixedCompactBytestrings::from([b"One", b"Two", b"Six"]);
+    /// cmpbytes.apply_permutation(&[2, 0, 1]);
+    ///
+    /// assert!(cmpbytes.iter().eq([b"Six", b"One", b"Two"]));
+    /// ```
+    #[track_caller]
+    pub fn apply_permutation(&mut self, perm: &[usize]) {
+        check_permutation(perm, self.starts.len());
+        let ranges = self.ranges();
+        let ranges: Vec<Range<usize>> = perm.iter().map(|&i| ranges[i].clone()).collect();
+        self.rewrite_from_ranges(&ranges);
+    }
+
+    /// Returns true if any bytestring contains `needle`.
+    ///
+    /// The data vector is searched in one pass rather than bytestring by bytestring, using a
+    /// SIMD-accelerated search when the `memchr` feature is enabled. Matches spanning two
+    /// bytestrings are not counted.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let cmpbytes = FixedCompactBytestrings::from([b"One", b"Two", b"Six"]);
+    ///
+    /// assert!(cmpbytes.contains_bytes(b"wo"));
+    /// assert!(!cmpbytes.contains_bytes(b"eT"));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn contains_bytes(&self, needle: &[u8]) -> bool {
+        self.find_bytes(needle).is_some()
+    }
+
+    /// Returns the index of the first bytestring that contains `needle`.
+    ///
+    /// Like [`contains_bytes`], this searches the data vector in one pass.
+    ///
+    /// [`contains_bytes`]: FixedCompactBytestrings::contains_bytes
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let cmpbytes = FixedCompactBytestrings::from([b"One", b"Two", b"Six"]);
+    ///
+    /// assert_eq!(cmpbytes.find_bytes(b"i"), Some(2));
+    /// assert_eq!(cmpbytes.find_bytes(b"eT"), None);
+    /// ```
+    #[must_use]
+    pub fn find_bytes(&self, needle: &[u8]) -> Option<usize> {
+        self.indices_containing(needle).next()
+    }
+
+    /// Returns an iterator over the indices of the bytestrings that contain `pattern`, in order.
+    ///
+    /// The data vector is searched in one pass like [`contains_bytes`], and each match is resolved
+    /// to the bytestring it falls into. Matches spanning two bytestrings are not counted, and each index
+    /// is returned at most once.
+    ///
+    /// [`contains_bytes`]: FixedCompactBytestrings::contains_bytes
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let cmpbytes = FixedCompactBytestrings::from([&b"error: disk"[..], b"ok", b"error: net"]);
+    ///
+    /// assert!(cmpbytes.indices_containing(b"error").eq([0, 2]));
+    /// ```
+    pub fn indices_containing<'a>(&'a self, pattern: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+        search::matches(self.len(), &self.data, pattern, move |pos| {
+            let index = self.starts.partition_point(|&s| s <= pos).checked_sub(1)?;
+            let end = self
+                .starts
+                .get(index + 1)
+                .copied()
+                .unwrap_or(self.data.len());
+            Some((index, self.starts[index]..end))
+        })
+    }
+
+    /// Returns the index of the first bytestring that is equal to `bytestring` when ignoring ASCII case.
+    ///
+    /// Bytes are compared in place with ASCII case folding, without allocating lowercased copies.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let cmpbytes = FixedCompactBytestrings::from([&b"Host"[..], b"Content-Type"]);
+    ///
+    /// assert_eq!(cmpbytes.position_ignore_ascii_case(b"content-type"), Some(1));
+    /// assert_eq!(cmpbytes.position_ignore_ascii_case(b"Accept"), None);
+    /// ```
+    #[must_use]
+    pub fn position_ignore_ascii_case(&self, bytestring: &[u8]) -> Option<usize> {
+        self.iter().position(|s| s.eq_ignore_ascii_case(bytestring))
+    }
+
+    /// Returns true if any bytestring is equal to `bytestring` when ignoring ASCII case.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let cmpbytes = FixedCompactBytestrings::from([&b"Host"[..], b"Content-Type"]);
+    ///
+    /// assert!(cmpbytes.contains_ignore_ascii_case(b"content-type"));
+    /// assert!(!cmpbytes.contains_ignore_ascii_case(b"Accept"));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn contains_ignore_ascii_case(&self, bytestring: &[u8]) -> bool {
+        self.position_ignore_ascii_case(bytestring).is_some()
+    }
+
+    /// Returns a copy of the [`FixedCompactBytestrings`] with every bytestring converted to ASCII lowercase.
+    ///
+    /// As lengths do not change, the whole data vector is converted in one pass instead of bytestring
+    /// by bytestring.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let cmpbytes = FixedCompactBytestrings::from([&b"Host"[..], b"Content-Type"]);
+    ///
+    /// assert!(cmpbytes.to_ascii_lowercase().iter().eq([&b"host"[..], b"content-type"]));
+    /// ```
+    #[must_use]
+    pub fn to_ascii_lowercase(&self) -> Self {
+        let mut converted = self.clone();
+        converted.data.make_ascii_lowercase();
+        converted
+    }
+
+    /// Returns a copy of the [`FixedCompactBytestrings`] with every bytestring converted to ASCII uppercase.
+    ///
+    /// As lengths do not change, the whole data vector is converted in one pass instead of bytestring
+    /// by bytestring.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let cmpbytes = FixedCompactBytestrings::from([&b"Host"[..], b"Content-Type"]);
+    ///
+    /// assert!(cmpbytes.to_ascii_uppercase().iter().eq([&b"HOST"[..], b"CONTENT-TYPE"]));
+    /// ```
+    #[must_use]
+    pub fn to_ascii_uppercase(&self) -> Self {
+        let mut converted = self.clone();
+        converted.data.make_ascii_uppercase();
+        converted
+    }
+
+    /// Returns a new [`FixedCompactBytestrings`] holding the bytestrings repeated `n` times.
+    ///
+    /// Both vectors of the new [`FixedCompactBytestrings`] are allocated once with exactly the capacity they need.
+    ///
+    /// # Panics
+    /// Panics if the capacity would overflow.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let cmpbytes = FixedCompactBytestrings::from([b"One", b"Two"]);
+    ///
+    /// assert!(cmpbytes.repeat(3).iter().eq([b"One", b"Two", b"One", b"Two", b"One", b"Two"]));
+    /// assert!(cmpbytes.repeat(0).is_empty());
+    /// ```
+    #[track_caller]
+    #[must_use]
+    pub fn repeat(&self, n: usize) -> Self {
+        #[cold]
+        #[inline(never)]
+        #[track_caller]
+        fn capacity_overflow() -> ! {
+            panic!("capacity overflow");
+        }
+
+        let once = self.data.len();
+        let count = self.starts.len();
+        let (data_len, starts_len) = match (once.checked_mul(n), count.checked_mul(n)) {
+            (Some(data_len), Some(starts_len)) => (data_len, starts_len),
+            _ => capacity_overflow(),
+        };
+
+        let mut repeated = Self::with_capacity(data_len, starts_len);
+        for copy in 0..n {
+            repeated.data.extend_from_slice(&self.data);
+            repeated
+                .starts
+                .extend(self.starts.iter().map(|start| start + copy * once));
+        }
+
+        repeated
+    }
+
+    /// Returns an iterator over the bytestrings that start with `prefix`.
+    ///
+    /// Every bytestring is checked. If the [`FixedCompactBytestrings`] is sorted, [`iter_sorted_with_prefix`] finds
+    /// the matching bytestrings with a binary search instead.
+    ///
+    /// [`iter_sorted_with_prefix`]: FixedCompactBytestrings::iter_sorted_with_prefix
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let cmpbytes = FixedCompactBytestrings::from([&b"car"[..], b"dog", b"cart"]);
+    ///
+    /// assert!(cmpbytes.iter_with_prefix(b"car").eq([&b"car"[..], b"cart"]));
+    /// ```
+    #[must_use]
+    pub fn iter_with_prefix<'a>(
+        &'a self,
+        prefix: &'a [u8],
+    ) -> impl DoubleEndedIterator<Item = &'a [u8]> + 'a {
+        self.iter().filter(move |s| s.starts_with(prefix))
+    }
+
+    /// Returns an iterator over the bytestrings that start with `prefix`, assuming the [`FixedCompactBytestrings`] is
+    /// sorted.
+    ///
+    /// The bytestrings sharing a prefix are adjacent in a sorted [`FixedCompactBytestrings`], so their range is found
+    /// with two binary searches. If the [`FixedCompactBytestrings`] is not sorted, the bytestrings returned are
+    /// unspecified.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let mut cmpbytes = FixedCompactBytestrings::from([&b"cart"[..], b"dog", b"cab", b"car"]);
+    /// cmpbytes.sort();
+    ///
+    /// let mut iter = cmpbytes.iter_sorted_with_prefix(b"car");
+    /// assert_eq!(iter.len(), 2);
+    /// assert!(iter.eq([&b"car"[..], b"cart"]));
+    /// ```
+    pub fn iter_sorted_with_prefix(&self, prefix: &[u8]) -> Iter<'_> {
+        let range = search::sorted_prefix_range(self.starts.len(), prefix, |i| &self[i]);
+        self.iter_slice(range)
+    }
+
+    /// Returns an iterator over the bytestrings in `range`, which must be in bounds.
+    pub(crate) fn iter_slice(&self, range: Range<usize>) -> Iter<'_> {
+        let end = self
+            .starts
+            .get(range.end)
+            .copied()
+            .unwrap_or(self.data.len());
+        Iter::from_parts(&self.data[..end], &self.starts[range])
+    }
+
+    /// Returns the range of the data vector occupied by each bytestring.
+    pub(crate) fn ranges(&self) -> Vec<Range<usize>> {
+        let ends = self
+            .starts
+            .iter()
+            .skip(1)
+            .copied()
+            .chain(Some(self.data.len()));
+        self.starts
+            .iter()
+            .zip(ends)
+            .map(|(&start, end)| start..end)
+            .collect()
+    }
+
+    /// Replaces the bytestrings with the bytes at `ranges`, copying them into a new data vector.
+    pub(crate) fn rewrite_from_ranges(&mut self, ranges: &[Range<usize>]) {
+        let mut data = Vec::with_capacity(ranges.iter().map(ExactSizeIterator::len).sum());
+        self.starts.clear();
+        for range in ranges {
+            self.starts.push(data.len());
+            data.extend_from_slice(&self.data[range.clone()]);
+        }
+
+        self.data = data;
+    }
+
+    /// Checks that every bytestring is valid UTF-8, validating the data vector in one pass where
+    /// possible.
+    pub(crate) fn validate_utf8(&self) -> Result<(), Utf8Error> {
+        utf8::validate(&self.data, self.starts.iter().copied(), self)
+    }
+
+    /// Returns an iterator over the starting index in the data vector and the length of each bytestring.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let mut cmpbytes = FixedCompactBytestrings::new();
+    /// cmpbytes.push(b"One");
+    /// cmpbytes.push(b"Two");
+    /// cmpbytes.push(b"Three");
+    ///
+    /// let mut offsets = cmpbytes.offsets();
+    /// assert_eq!(offsets.next(), Some((0, 3)));
+    /// assert_eq!(offsets.next(), Some((3, 3)));
+    /// assert_eq!(offsets.next(), Some((6, 5)));
+    /// assert_eq!(offsets.next(), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn offsets(
+        &self,
+    ) -> impl ExactSizeIterator<Item = (usize, usize)> + DoubleEndedIterator + '_ {
+        (0..self.starts.len()).map(move |idx| {
+            let start = self.starts[idx];
+            let end = *self.starts.get(idx + 1).unwrap_or(&self.data.len());
+            (start, end - start)
+        })
+    }
+
+    /// Returns the range of bytes in the data vector holding the bytestring at that position, or
+    /// [`None`] if `index` is out of bounds.
+    ///
+    /// This translates between element indices and byte positions in the data vector, such as
+    /// those found by searching it directly.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let cmpbytes = FixedCompactBytestrings::from([b"One", b"Two"]);
+    ///
+    /// assert_eq!(cmpbytes.byte_range(1), Some(3..6));
+    /// assert_eq!(cmpbytes.byte_range(2), None);
+    /// ```
+    #[must_use]
+    pub fn byte_range(&self, index: usize) -> Option<Range<usize>> {
+        let &start = self.starts.get(index)?;
+        let &end = self.starts.get(index + 1).unwrap_or(&self.data.len());
+        Some(start..end)
+    }
+
+    /// Returns the index of the bytestring that `bytes` was borrowed from, or [`None`] if it does
+    /// not refer to a whole bytestring in the [`FixedCompactBytestrings`].
+    ///
+    /// Unlike searching for an equal bytestring, this tells apart bytestrings with the same
+    /// contents, so a reference handed out earlier can be mapped back to its index. If several
+    /// empty bytestrings share a position in the data vector, the first of them is returned.
+    ///
+    /// The reference is located with pointer arithmetic against the data vector and a binary
+    /// search of the starting indices, so no bytes are ever compared.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let cmpbytes = FixedCompactBytestrings::from([&b"One"[..], b"Two", b"Two"]);
+    /// let last = &cmpbytes[2];
+    ///
+    /// assert_eq!(cmpbytes.index_of_ref(last), Some(2));
+    /// assert_eq!(cmpbytes.index_of_ref(&b"Two"[..]), None);
+    /// ```
+    #[must_use]
+    pub fn index_of_ref(&self, bytes: &[u8]) -> Option<usize> {
+        let offset = (bytes.as_ptr() as usize).checked_sub(self.data.as_ptr() as usize)?;
+        if offset + bytes.len() > self.data.len() {
+            return None;
+        }
+
+        let mut index = self.starts.partition_point(|&start| start < offset);
+        // Empty bytestrings share their starting index with the bytestring after them.
+        while self.starts.get(index) == Some(&offset) {
+            if self.byte_range(index)?.len() == bytes.len() {
+                return Some(index);
+            }
+            index += 1;
+        }
+
+        None
+    }
+
+    /// Returns an iterator over the length in bytes of each bytestring.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let mut cmpbytes = FixedCompactBytestrings::new();
+    /// cmpbytes.push(b"One");
+    /// cmpbytes.push(b"Two");
+    /// cmpbytes.push(b"Three");
+    ///
+    /// assert!(cmpbytes.lengths().eq([3, 3, 5]));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn lengths(&self) -> impl ExactSizeIterator<Item = usize> + DoubleEndedIterator + '_ {
+        self.offsets().map(|(_, len)| len)
+    }
+
+    /// Returns the length in bytes of the bytestring at that position, or [`None`] if `index` is out
+    /// of bounds, reading only its starting index and the next one.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let cmpbytes = FixedCompactBytestrings::from([&b"One"[..], b"Three"]);
+    ///
+    /// assert_eq!(cmpbytes.len_at(1), Some(5));
+    /// assert_eq!(cmpbytes.len_at(2), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn len_at(&self, index: usize) -> Option<usize> {
+        self.byte_range(index).map(|range| range.len())
+    }
+
+    /// Feeds the number of bytestrings, the length of each of them and then their bytes into
+    /// `state`, in order, as described in [`CompactBytestrings::digest`](crate::CompactBytestrings::digest).
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// use std::collections::hash_map::DefaultHasher;
+    /// use std::hash::Hasher;
+    ///
+    /// let mut a = DefaultHasher::new();
+    /// FixedCompactBytestrings::from([&b"One"[..], b"Two"]).digest(&mut a);
+    ///
+    /// let mut b = DefaultHasher::new();
+    /// FixedCompactBytestrings::from([&b"On"[..], b"eTwo"]).digest(&mut b);
+    /// assert_ne!(a.finish(), b.finish());
+    /// ```
+    pub fn digest<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.len() as u64);
+        for len in self.lengths() {
+            state.write_u64(len as u64);
+        }
+        for bytestring in self {
+            state.write(bytestring);
+        }
+    }
+
+    /// Copies each bytestring into an owned `Vec<u8>`, returning them in a vector.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let mut cmpbytes = FixedCompactBytestrings::new();
+    /// cmpbytes.push(b"One");
+    /// cmpbytes.push(b"Two");
+    ///
+    /// let vec: Vec<Vec<u8>> = cmpbytes.to_vec();
+    /// assert_eq!(vec, [b"One".to_vec(), b"Two".to_vec()]);
+    /// ```
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<Vec<u8>> {
+        self.iter().map(<[u8]>::to_vec).collect()
+    }
+
+    /// Encodes the [`FixedCompactBytestrings`] in the binary layout documented on
+    /// [`CompactBytestrings::to_bytes`](crate::CompactBytestrings::to_bytes).
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactBytesRef, FixedCompactBytestrings};
+    /// let mut cmpbytes = FixedCompactBytestrings::new();
+    /// cmpbytes.push(b"One");
+    /// cmpbytes.push(b"Two");
+    ///
+    /// let buf = cmpbytes.to_bytes();
+    /// let view = CompactBytesRef::parse(&buf).unwrap();
+    /// assert!(view.iter().eq(cmpbytes.iter()));
+    /// ```
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        layout::encode(self.len(), || self.iter())
+    }
+
+    /// Returns an iterator over the slice.
+    ///
+    /// The iterator yields all items from start to end.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let mut cmpbytes = FixedCompactBytestrings::with_capacity(20, 3);
+    /// cmpbytes.push(b"One");
+    /// cmpbytes.push(b"Two");
+    /// cmpbytes.push(b"Three");
+    /// let mut iterator = cmpbytes.iter();
+    ///
+    /// assert_eq!(iterator.next(), Some(b"One".as_slice()));
+    /// assert_eq!(iterator.next(), Some(b"Two".as_slice()));
+    /// assert_eq!(iterator.next(), Some(b"Three".as_slice()));
+    /// assert_eq!(iterator.next(), None);
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter::new(self)
+    }
+
+    /// Returns an iterator over the bytestrings in `range`.
+    ///
+    /// The iterator starts at the first bytestrings in `range` without stepping over the ones before
+    /// it, unlike calling [`skip`](Iterator::skip) on [`iter`](FixedCompactBytestrings::iter).
+    ///
+    /// # Panics
+    /// Panics if the starting point is greater than the end point or if the end point is greater
+    /// than the length of the [`FixedCompactBytestrings`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let cmpbytes = FixedCompactBytestrings::from([&b"One"[..], b"Two", b"Three", b"Four"]);
+    ///
+    /// assert!(cmpbytes.iter_range(1..3).eq([&b"Two"[..], b"Three"]));
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn iter_range<R>(&self, range: R) -> Iter<'_>
+    where
+        R: RangeBounds<usize>,
+    {
+        self.iter_slice(to_range(range, self.starts.len()))
+    }
+
+    /// Returns an iterator over the bytestrings from `start` onwards, as described in
+    /// [`iter_range`](FixedCompactBytestrings::iter_range).
+    ///
+    /// # Panics
+    /// Panics if `start` is greater than the length of the [`FixedCompactBytestrings`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let cmpbytes = FixedCompactBytestrings::from([&b"One"[..], b"Two", b"Three", b"Four"]);
+    ///
+    /// assert!(cmpbytes.iter_from(2).eq([&b"Three"[..], b"Four"]));
+    /// assert_eq!(cmpbytes.iter_from(4).next(), None);
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn iter_from(&self, start: usize) -> Iter<'_> {
+        self.iter_range(start..)
+    }
+}
+
+impl Clone for FixedCompactBytestrings {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            starts: self.starts.clone(),
+        }
+    }
+}
+
+impl Default for FixedCompactBytestrings {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialEq for FixedCompactBytestrings {
+    fn eq(&self, other: &Self) -> bool {
+        let len = self.len();
+        if len != other.len() {
+            return false;
+        }
+
+        // With the same starting indices, the bytestrings are equal exactly when the data vectors
+        // are.
+        if self.starts == other.starts {
+            return self.data == other.data;
+        }
+
+        for idx in 0..len {
+            if self[idx] != other[idx] {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+impl Debug for FixedCompactBytestrings {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_list().entries(self.iter()).finish()
     }
@@ -538,6 +2197,16 @@ impl<'a> Iter<'a> {
             starts: inner.starts.iter(),
         }
     }
+
+    /// Creates an iterator over the bytestrings starting at `starts`, the last of which ends at
+    /// the end of `data`.
+    #[inline]
+    pub(crate) fn from_parts(data: &'a [u8], starts: &'a [usize]) -> Self {
+        Self {
+            data,
+            starts: starts.iter(),
+        }
+    }
 }
 
 impl<'a> Iterator for Iter<'a> {
@@ -587,7 +2256,7 @@ impl<'a> Iterator for Iter<'a> {
     }
 }
 
-impl<'a> DoubleEndedIterator for Iter<'a> {
+impl DoubleEndedIterator for Iter<'_> {
     fn next_back(&mut self) -> Option<Self::Item> {
         let &start = self.starts.next_back()?;
         let end = self.data.len();
@@ -679,8 +2348,127 @@ impl From<FixedCompactStrings> for FixedCompactBytestrings {
 
 #[cfg(test)]
 mod tests {
+    use alloc::{vec, vec::Vec};
+
     use crate::FixedCompactBytestrings;
 
+    #[test]
+    fn index_of_ref_with_empty_bytestrings() {
+        let cmpbytes = FixedCompactBytestrings::from([&b"One"[..], b"", b"", b"Two", b""]);
+
+        for index in 0..cmpbytes.len() {
+            let expected = if index == 2 { 1 } else { index };
+            assert_eq!(cmpbytes.index_of_ref(&cmpbytes[index]), Some(expected));
+        }
+        assert_eq!(cmpbytes.index_of_ref(&cmpbytes[0][1..]), None);
+        assert_eq!(cmpbytes.index_of_ref(&cmpbytes[3][..2]), None);
+    }
+
+    #[test]
+    fn splice_matches_vec() {
+        let strings: [&[u8]; 5] = [b"One", b"Two", b"", b"Three", b"Four"];
+        let mut cmpbytes = FixedCompactBytestrings::from(strings);
+        let mut model = strings.to_vec();
+
+        for (range, replacement) in [
+            (0..0, &[&b"A"[..], b"Bb"][..]),
+            (1..3, &[b"Long replacement"]),
+            (2..2, &[]),
+            (0..1, &[b"", b""]),
+            (3..5, &[b"C"]),
+        ] {
+            let removed = cmpbytes.splice(range.clone(), replacement);
+            let expected: alloc::vec::Vec<_> =
+                model.splice(range, replacement.iter().copied()).collect();
+
+            assert!(removed.iter().eq(expected));
+            assert!(cmpbytes.iter().eq(model.iter().copied()));
+        }
+        let end = cmpbytes.len();
+        let _ = cmpbytes.splice(end.., [b"End"]);
+        assert_eq!(cmpbytes.get(end), Some(&b"End"[..]));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_uses_byte_arrays() {
+        use serde_test::{assert_de_tokens, assert_tokens, Configure, Token};
+
+        let cmpbytes = FixedCompactBytestrings::from([&b"One"[..], b""]);
+        assert_tokens(
+            &cmpbytes.clone().readable(),
+            &[
+                Token::Seq { len: Some(2) },
+                Token::Bytes(b"One"),
+                Token::Bytes(b""),
+                Token::SeqEnd,
+            ],
+        );
+        assert_de_tokens(
+            &cmpbytes.clone().readable(),
+            &[
+                Token::Seq { len: Some(2) },
+                Token::Seq { len: Some(3) },
+                Token::U8(b'O'),
+                Token::U8(b'n'),
+                Token::U8(b'e'),
+                Token::SeqEnd,
+                Token::Bytes(b""),
+                Token::SeqEnd,
+            ],
+        );
+
+        let encoded = bincode::serialize(&cmpbytes).unwrap();
+        let decoded: FixedCompactBytestrings = bincode::deserialize(&encoded).unwrap();
+        assert_eq!(decoded, cmpbytes);
+    }
+
+    #[test]
+    fn gather_is_exactly_sized() {
+        let cmpbytes = FixedCompactBytestrings::from([&b"One"[..], b"", b"Three"]);
+        let gathered = cmpbytes.gather([2, 1, 2]);
+
+        assert!(gathered.iter().eq([&b"Three"[..], b"", b"Three"]));
+        assert_eq!(gathered.data.capacity(), 10);
+        assert_eq!(gathered.starts.capacity(), 3);
+    }
+
+    #[test]
+    #[should_panic = "gather index (is 3) should be < len (is 3)"]
+    fn gather_out_of_bounds() {
+        let cmpbytes = FixedCompactBytestrings::from([b"One", b"Two", b"Six"]);
+        let _ = cmpbytes.gather([0, 3]);
+    }
+
+    #[test]
+    fn remove_many_matches_vec() {
+        let items = (0..20u8)
+            .map(|i| vec![i; usize::from(i % 4)])
+            .collect::<Vec<_>>();
+        let indices = [0, 3, 4, 5, 11, 19];
+
+        let mut cmpbytes = FixedCompactBytestrings::from(&items);
+        cmpbytes.remove_many(&indices);
+
+        let expected = items
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !indices.contains(i))
+            .map(|(_, item)| item.as_slice());
+        assert!(cmpbytes.iter().eq(expected));
+        assert_eq!(
+            cmpbytes.data_len(),
+            cmpbytes.iter().map(<[u8]>::len).sum::<usize>()
+        );
+    }
+
+    #[test]
+    #[should_panic = "indices should be sorted and unique (found 1 after 2)"]
+    fn remove_many_unsorted() {
+        let mut cmpbytes = FixedCompactBytestrings::from([b"One", b"Two", b"Six"]);
+        cmpbytes.remove_many(&[2, 1]);
+    }
+
     #[test]
     fn exact_size_iterator() {
         let mut cmpbytes = FixedCompactBytestrings::new();
@@ -718,4 +2506,153 @@ mod tests {
         assert_eq!(iter.next(), None);
         assert_eq!(iter.next_back(), None);
     }
+
+    #[test]
+    fn dedup_by_panic_keeps_remaining() {
+        extern crate std;
+
+        let mut cmpbytes = FixedCompactBytestrings::from([b"a", b"a", b"b", b"c"]);
+        let result = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| {
+            cmpbytes.dedup_by(|a, _| {
+                assert_ne!(a, b"b");
+                true
+            });
+        }));
+
+        assert!(result.is_err());
+        assert!(cmpbytes.iter().eq([b"a", b"b", b"c"]));
+    }
+
+    #[test]
+    fn find_bytes_across_empty_bytestrings() {
+        let cmpbytes = FixedCompactBytestrings::from([&b"ab"[..], b"", b"", b"cd", b"", b"bc"]);
+
+        assert_eq!(cmpbytes.find_bytes(b"bc"), Some(5));
+        assert_eq!(cmpbytes.find_bytes(b"c"), Some(3));
+        assert_eq!(cmpbytes.find_bytes(b"dbc"), None);
+    }
+
+    #[test]
+    fn iter_sorted_with_prefix_ends_at_range() {
+        let cmpbytes = FixedCompactBytestrings::from([&b"a"[..], b"ba", b"bb", b"c"]);
+
+        let mut iter = cmpbytes.iter_sorted_with_prefix(b"b");
+        assert_eq!(iter.next_back(), Some(&b"bb"[..]));
+        assert_eq!(iter.next_back(), Some(&b"ba"[..]));
+        assert_eq!(iter.next_back(), None);
+
+        assert_eq!(cmpbytes.iter_sorted_with_prefix(b"").len(), 4);
+        assert_eq!(cmpbytes.iter_sorted_with_prefix(b"bc").len(), 0);
+        assert_eq!(cmpbytes.iter_sorted_with_prefix(b"d").len(), 0);
+    }
+
+    #[test]
+    fn remove_range_bounds() {
+        let mut cmpbytes = FixedCompactBytestrings::new();
+        cmpbytes.extend([b"A".as_slice(), b"Bb", b"Ccc", b"Dddd", b"Eeeee"]);
+
+        cmpbytes.remove_range(2..2);
+        assert_eq!(cmpbytes.len(), 5);
+
+        cmpbytes.remove_range(..=1);
+        assert!(cmpbytes.iter().eq([b"Ccc".as_slice(), b"Dddd", b"Eeeee"]));
+
+        cmpbytes.remove_range(1..);
+        assert!(cmpbytes.iter().eq([b"Ccc".as_slice()]));
+        assert_eq!(cmpbytes.data, b"Ccc");
+
+        cmpbytes.remove_range(..);
+        assert!(cmpbytes.is_empty());
+        assert!(cmpbytes.data.is_empty());
+    }
+
+    #[test]
+    fn clone() {
+        let mut cmpbytes = FixedCompactBytestrings::new();
+        cmpbytes.extend([b"One".as_slice(), b"Two", b"Three"]);
+
+        let cloned = cmpbytes.clone();
+        assert!(cloned.iter().eq(cmpbytes.iter()));
+        assert_eq!(cloned, cmpbytes);
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde {
+    use serde::{
+        de::{SeqAccess, Visitor},
+        Deserialize, Deserializer, Serialize,
+    };
+
+    use crate::{flat, FixedCompactBytestrings};
+
+    impl Serialize for FixedCompactBytestrings {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(self.iter().map(flat::Bytes))
+        }
+    }
+
+    impl<'de> Deserialize<'de> for FixedCompactBytestrings {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_seq(FixedCompactBytestringsVisitor)
+        }
+    }
+
+    impl flat::Encode for FixedCompactBytestrings {
+        fn serialize_flat<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            flat::serialize(
+                serializer,
+                self.starts.iter().copied(),
+                self.starts.len(),
+                core::iter::once(&self.data[..]),
+                self.data.len(),
+            )
+        }
+    }
+
+    impl flat::Decode for FixedCompactBytestrings {
+        fn deserialize_flat<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let (starts, data) = flat::deserialize(deserializer)?;
+            Ok(FixedCompactBytestrings { data, starts })
+        }
+    }
+
+    struct FixedCompactBytestringsVisitor;
+
+    impl<'de> Visitor<'de> for FixedCompactBytestringsVisitor {
+        type Value = FixedCompactBytestrings;
+
+        fn expecting(&self, formatter: &mut alloc::fmt::Formatter) -> alloc::fmt::Result {
+            formatter.write_str("an array of byte arrays")
+        }
+
+        #[inline]
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut out =
+                FixedCompactBytestrings::with_capacity(0, seq.size_hint().unwrap_or_default());
+            loop {
+                let start = out.data.len();
+                if seq
+                    .next_element_seed(flat::AppendBytes(&mut out.data))?
+                    .is_none()
+                {
+                    break;
+                }
+                out.starts.push(start);
+            }
+
+            Ok(out)
+        }
+    }
 }
+
+#[cfg(feature = "serde")]
+#[cfg_attr(feature = "serde", allow(unused_imports))]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub use self::serde::*;
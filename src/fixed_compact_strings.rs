@@ -1,9 +1,16 @@
 use core::{
-    fmt::Debug,
-    ops::{Deref, Index},
+    cmp::Ordering,
+    fmt::{self, Debug},
+    hash::Hasher,
+    ops::{Deref, Index, Range, RangeBounds},
 };
 
-use crate::FixedCompactBytestrings;
+use alloc::{borrow::Cow, boxed::Box, collections::BTreeMap, string::String, vec::Vec};
+
+use crate::{
+    compact_strings::DataWriter, range::to_range, search, utf8, Diff, FixedCompactBytestrings,
+    LengthStats, MemoryUsage, SetError,
+};
 
 /// An even more compact but limited representation of a list of strings.
 ///
@@ -51,7 +58,7 @@ impl FixedCompactStrings {
     ///
     /// - `data_capacity`: The capacity of the data vector where the bytes of the strings are stored.
     /// - `capacity_meta`: The capacity of the meta vector where the starting indices
-    /// of the strings are stored.
+    ///   of the strings are stored.
     ///
     /// The [`FixedCompactStrings`] will be able to hold at least *`data_capacity`* bytes worth of strings
     /// without reallocating the data vector, and at least *`capacity_meta`* of starting indices
@@ -83,6 +90,66 @@ impl FixedCompactStrings {
         ))
     }
 
+    /// Constructs a new [`FixedCompactStrings`] holding copies of the strings in `strings`.
+    ///
+    /// Unlike collecting from an iterator, this sums up the lengths of the strings first so that
+    /// both vectors are allocated exactly once, which makes it the fastest way to convert
+    /// an existing `Vec<String>`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let vec = vec![String::from("One"), String::from("Two"), String::from("Three")];
+    /// let cmpstrs = FixedCompactStrings::from_slice(&vec);
+    ///
+    /// assert_eq!(cmpstrs.len(), 3);
+    /// assert!(cmpstrs.capacity() >= 11);
+    /// assert_eq!(cmpstrs.get(0), Some("One"));
+    /// assert_eq!(cmpstrs.get(2), Some("Three"));
+    /// ```
+    #[must_use]
+    pub fn from_slice<S>(strings: &[S]) -> Self
+    where
+        S: Deref<Target = str>,
+    {
+        let data_capacity = strings.iter().map(|s| s.len()).sum();
+        let mut out = Self::with_capacity(data_capacity, strings.len());
+        for string in strings {
+            out.0.push(string.as_bytes());
+        }
+
+        out
+    }
+
+    /// Converts a [`FixedCompactBytestrings`] into a [`FixedCompactStrings`], replacing invalid UTF-8 sequences in each
+    /// bytestring with [`U+FFFD REPLACEMENT CHARACTER`][U+FFFD] like [`String::from_utf8_lossy`].
+    ///
+    /// If every bytestring is valid UTF-8, the [`FixedCompactBytestrings`] is reused as it is without copying.
+    ///
+    /// [U+FFFD]: core::char::REPLACEMENT_CHARACTER
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{FixedCompactBytestrings, FixedCompactStrings};
+    /// let cmpbytes = FixedCompactBytestrings::from([&b"One"[..], b"Tw\xffo"]);
+    /// let cmpstrs = FixedCompactStrings::from_utf8_lossy(cmpbytes);
+    ///
+    /// assert!(cmpstrs.iter().eq(["One", "Tw\u{fffd}o"]));
+    /// ```
+    #[must_use]
+    pub fn from_utf8_lossy(bytestrings: FixedCompactBytestrings) -> Self {
+        if bytestrings.validate_utf8().is_ok() {
+            return Self(bytestrings);
+        }
+
+        let mut strings = Self::with_capacity(bytestrings.data_len(), bytestrings.len());
+        for bytes in &bytestrings {
+            strings.push(String::from_utf8_lossy(bytes));
+        }
+
+        strings
+    }
+
     /// Appends a string to the back of the [`FixedCompactStrings`].
     ///
     /// # Examples
@@ -105,8 +172,117 @@ impl FixedCompactStrings {
         self.0.push(string.as_bytes());
     }
 
+    /// Appends every string in `strings` to the back of the [`FixedCompactStrings`].
+    ///
+    /// The lengths of the strings are summed up first so that each vector is reserved at most
+    /// once, rather than potentially reallocating on every [`push`](FixedCompactStrings::push).
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let mut cmpstrs = FixedCompactStrings::from(["One"]);
+    /// cmpstrs.push_many(&["Two", "Three"]);
+    ///
+    /// assert!(cmpstrs.capacity() >= 11);
+    /// assert!(cmpstrs.iter().eq(["One", "Two", "Three"]));
+    /// ```
+    pub fn push_many<S>(&mut self, strings: &[S])
+    where
+        S: Deref<Target = str>,
+    {
+        let data_len = strings.iter().map(|s| s.len()).sum();
+        self.0.data.reserve(data_len);
+        self.0.starts.reserve(strings.len());
+        for string in strings {
+            self.0.push(string.as_bytes());
+        }
+    }
+
+    /// Appends a formatted string to the back of the [`FixedCompactStrings`], writing it directly
+    /// into the data vector instead of allocating a temporary [`String`].
+    ///
+    /// # Errors
+    /// Returns an error if a formatting trait implementation returns an error, in which case
+    /// nothing is pushed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let mut cmpstrs = FixedCompactStrings::new();
+    /// cmpstrs.push_fmt(format_args!("{}-{}", "One", 2)).unwrap();
+    /// cmpstrs.push_fmt(format_args!("{:>5}", 3)).unwrap();
+    ///
+    /// assert_eq!(cmpstrs.get(0), Some("One-2"));
+    /// assert_eq!(cmpstrs.get(1), Some("    3"));
+    /// ```
+    pub fn push_fmt(&mut self, args: fmt::Arguments<'_>) -> fmt::Result {
+        let start = self.0.data.len();
+        if let Err(err) = fmt::Write::write_fmt(&mut DataWriter(&mut self.0.data), args) {
+            self.0.data.truncate(start);
+            return Err(err);
+        }
+
+        self.0.starts.push(start);
+        Ok(())
+    }
+
+    /// Appends the [`Display`](fmt::Display) representation of `value` to the back of the
+    /// [`FixedCompactStrings`], writing it directly into the data vector instead of allocating a
+    /// temporary [`String`] as [`ToString::to_string`] would.
+    ///
+    /// # Panics
+    /// Panics if the [`Display`](fmt::Display) implementation returns an error, like
+    /// [`ToString::to_string`] does.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// # use std::net::Ipv4Addr;
+    /// let mut cmpstrs = FixedCompactStrings::new();
+    /// cmpstrs.push_display(42);
+    /// cmpstrs.push_display(Ipv4Addr::LOCALHOST);
+    ///
+    /// assert_eq!(cmpstrs.get(0), Some("42"));
+    /// assert_eq!(cmpstrs.get(1), Some("127.0.0.1"));
+    /// ```
+    pub fn push_display<T>(&mut self, value: T)
+    where
+        T: fmt::Display,
+    {
+        self.push_fmt(format_args!("{value}"))
+            .expect("a Display implementation returned an error unexpectedly");
+    }
+
+    /// Appends a string to the end of the last string in the [`FixedCompactStrings`].
+    ///
+    /// # Panics
+    /// Panics if the [`FixedCompactStrings`] is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let mut cmpstrs = FixedCompactStrings::new();
+    /// cmpstrs.push("One");
+    /// cmpstrs.push("Tw");
+    /// cmpstrs.push_str_to_last("o");
+    ///
+    /// assert_eq!(cmpstrs.get(0), Some("One"));
+    /// assert_eq!(cmpstrs.get(1), Some("Two"));
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn push_str_to_last<S>(&mut self, string: S)
+    where
+        S: Deref<Target = str>,
+    {
+        self.0.push_bytes_to_last(string.as_bytes());
+    }
+
     /// Returns a reference to the string stored in the [`FixedCompactStrings`] at that position.
     ///
+    /// The end of the string is the start of the next one (or the end of the data vector for the
+    /// last string), so this only reads two starting indices and takes constant time.
+    ///
     /// # Examples
     /// ```
     /// # use compact_strings::FixedCompactStrings;
@@ -124,7 +300,7 @@ impl FixedCompactStrings {
     pub fn get(&self, index: usize) -> Option<&str> {
         let bytes = self.0.get(index)?;
         if cfg!(feature = "no_unsafe") {
-            core::str::from_utf8(bytes).ok()
+            utf8::from_utf8(bytes).ok()
         } else {
             unsafe { Some(core::str::from_utf8_unchecked(bytes)) }
         }
@@ -157,6 +333,79 @@ impl FixedCompactStrings {
         core::str::from_utf8_unchecked(bytes)
     }
 
+    /// Returns references to the strings at each of `indices`, or [`None`] if any index is out of
+    /// bounds.
+    ///
+    /// Every index is bounds checked up front, after which the strings are read without any
+    /// further checks. Indices may repeat.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["One", "Two", "Three"]);
+    ///
+    /// assert_eq!(cmpstrs.get_many([2, 0]), Some(["Three", "One"]));
+    /// assert_eq!(cmpstrs.get_many([0, 3]), None);
+    /// ```
+    #[must_use]
+    pub fn get_many<const N: usize>(&self, indices: [usize; N]) -> Option<[&str; N]> {
+        let bytestrings = self.0.get_many(indices)?;
+        Some(bytestrings.map(|bytes| Iter::from_utf8_maybe_checked(bytes).unwrap_or_default()))
+    }
+
+    /// Returns references to the strings at each index yielded by `indices`, or [`None`] if any
+    /// index is out of bounds, as described in [`get_many`](FixedCompactStrings::get_many).
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["One", "Two", "Three"]);
+    ///
+    /// assert_eq!(cmpstrs.get_many_vec((0..3).rev().step_by(2)), Some(vec!["Three", "One"]));
+    /// assert_eq!(cmpstrs.get_many_vec([3]), None);
+    /// ```
+    #[must_use]
+    pub fn get_many_vec<I>(&self, indices: I) -> Option<Vec<&str>>
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        let bytestrings = self.0.get_many_vec(indices)?;
+        Some(
+            bytestrings
+                .into_iter()
+                .map(|bytes| Iter::from_utf8_maybe_checked(bytes).unwrap_or_default())
+                .collect(),
+        )
+    }
+
+    /// Overwrites the string at the specified index with `string`, which must have the same
+    /// length in bytes.
+    ///
+    /// As the length does not change, no bytes have to be shifted over.
+    ///
+    /// # Errors
+    /// Returns [`SetError::OutOfBounds`] if `index` is out of bounds, or
+    /// [`SetError::LengthMismatch`] if the lengths differ, leaving the [`FixedCompactStrings`] unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{FixedCompactStrings, SetError};
+    /// let mut cmpstrs = FixedCompactStrings::from(["One", "Two"]);
+    ///
+    /// assert_eq!(cmpstrs.try_set(1, "Six"), Ok(()));
+    /// assert_eq!(cmpstrs.try_set(1, "Three"), Err(SetError::LengthMismatch));
+    /// assert_eq!(cmpstrs.try_set(2, "Ten"), Err(SetError::OutOfBounds));
+    ///
+    /// assert!(cmpstrs.iter().eq(["One", "Six"]));
+    /// ```
+    #[inline]
+    pub fn try_set<S>(&mut self, index: usize, string: S) -> Result<(), SetError>
+    where
+        S: Deref<Target = str>,
+    {
+        self.0.try_set(index, string.as_bytes())
+    }
+
     /// Returns the number of strings in the [`FixedCompactStrings`], also referred to as its 'length'.
     ///
     /// # Examples
@@ -194,6 +443,63 @@ impl FixedCompactStrings {
         self.len() == 0
     }
 
+    /// Returns the number of bytes in the data vector, as opposed to its [`capacity`].
+    ///
+    /// [`capacity`]: FixedCompactStrings::capacity
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let mut cmpstrs = FixedCompactStrings::with_capacity(20, 3);
+    ///
+    /// cmpstrs.push("One");
+    /// cmpstrs.push("Three");
+    ///
+    /// assert_eq!(cmpstrs.data_len(), 8);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn data_len(&self) -> usize {
+        self.0.data_len()
+    }
+
+    /// Returns the number of bytes used and allocated by the data and meta vectors.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let mut cmpstrs = FixedCompactStrings::with_capacity(20, 3);
+    /// cmpstrs.push("One");
+    ///
+    /// let usage = cmpstrs.memory_usage();
+    /// assert_eq!(usage.data_used, 3);
+    /// assert!(usage.data_reserved >= 20);
+    /// assert!(usage.meta_reserved >= usage.meta_used);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn memory_usage(&self) -> MemoryUsage {
+        self.0.memory_usage()
+    }
+
+    /// Returns statistics about the lengths of the strings in bytes, as described in
+    /// [`FixedCompactBytestrings::stats`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["One", "Three"]);
+    ///
+    /// let stats = cmpstrs.stats();
+    /// assert_eq!(stats.count, 2);
+    /// assert_eq!(stats.mean_len(), 4.0);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn stats(&self) -> LengthStats {
+        self.0.stats()
+    }
+
     /// Returns the number of bytes the data vector can store without reallocating.
     ///
     /// # Examples
@@ -348,6 +654,76 @@ impl FixedCompactStrings {
         self.0.shrink_meta_to(min_capacity);
     }
 
+    /// Shrinks the capacities of both the data vector and the meta vector as much as possible.
+    ///
+    /// This is equivalent to calling both [`shrink_to_fit`] and [`shrink_meta_to_fit`].
+    ///
+    /// [`shrink_to_fit`]: FixedCompactStrings::shrink_to_fit
+    /// [`shrink_meta_to_fit`]: FixedCompactStrings::shrink_meta_to_fit
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let mut cmpstrs = FixedCompactStrings::with_capacity(20, 10);
+    ///
+    /// cmpstrs.push("One");
+    /// cmpstrs.push("Two");
+    ///
+    /// cmpstrs.shrink_all_to_fit();
+    /// assert!(cmpstrs.capacity() >= 6);
+    /// assert!(cmpstrs.capacity_meta() >= 2);
+    /// ```
+    #[inline]
+    pub fn shrink_all_to_fit(&mut self) {
+        self.0.shrink_all_to_fit();
+    }
+
+    /// Shrinks the capacities of both the data vector and the meta vector, leaving room for a
+    /// fraction `slack` of their lengths to be added without reallocating.
+    ///
+    /// For example, a `slack` of `0.1` keeps 10% of spare capacity in each vector. A negative or
+    /// NaN `slack` is treated as zero. If a capacity is already below its target, it is left
+    /// unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let mut cmpstrs = FixedCompactStrings::with_capacity(100, 100);
+    ///
+    /// for _ in 0..10 {
+    ///     cmpstrs.push("Ten");
+    /// }
+    ///
+    /// cmpstrs.shrink_all_to_fit_with_slack(0.5);
+    /// assert!(cmpstrs.capacity() >= 45 && cmpstrs.capacity() < 100);
+    /// assert!(cmpstrs.capacity_meta() >= 15 && cmpstrs.capacity_meta() < 100);
+    /// ```
+    #[inline]
+    pub fn shrink_all_to_fit_with_slack(&mut self, slack: f64) {
+        self.0.shrink_all_to_fit_with_slack(slack);
+    }
+
+    /// Consumes and leaks the [`FixedCompactStrings`], returning a reference that lives for the rest of the
+    /// program, like [`Vec::leak`].
+    ///
+    /// The buffers are shrunk to fit before being leaked. Since the reference can be `'static`,
+    /// the strings it hands out can be `'static` as well, which suits tables that are built once
+    /// and kept for the lifetime of the process.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let table: &'static FixedCompactStrings = FixedCompactStrings::from(["One", "Two"]).leak();
+    /// let first: &'static str = table.get(0).unwrap();
+    ///
+    /// assert_eq!(first, "One");
+    /// ```
+    #[must_use]
+    pub fn leak<'a>(mut self) -> &'a Self {
+        self.shrink_all_to_fit();
+        Box::leak(Box::new(self))
+    }
+
     /// Removes the bytes of the string and data pointing to the string is stored.
     ///
     /// Note: This does not shrink the vectors where the bytes of the string and data to the string
@@ -381,66 +757,1077 @@ impl FixedCompactStrings {
         self.0.remove(index);
     }
 
-    /// Returns an iterator over the slice.
+    /// Removes the string at the specified index and returns it, shifting the strings after it
+    /// to the left.
     ///
-    /// The iterator yields all items from start to end.
+    /// The bytes of the string are copied out before the remaining bytes are shifted over, so
+    /// this has the same worst-case performance of *O*(*n*) as [`remove`].
     ///
-    /// # Examples
+    /// [`remove`]: FixedCompactStrings::remove
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
     ///
+    /// # Examples
     /// ```
     /// # use compact_strings::FixedCompactStrings;
-    /// let mut cmpstrs = FixedCompactStrings::with_capacity(20, 3);
-    /// cmpstrs.push("One");
-    /// cmpstrs.push("Two");
-    /// cmpstrs.push("Three");
-    /// let mut iterator = cmpstrs.iter();
+    /// let mut cmpstrs = FixedCompactStrings::from(["One", "Two", "Three"]);
     ///
-    /// assert_eq!(iterator.next(), Some("One"));
-    /// assert_eq!(iterator.next(), Some("Two"));
-    /// assert_eq!(iterator.next(), Some("Three"));
-    /// assert_eq!(iterator.next(), None);
+    /// assert_eq!(cmpstrs.take(1), "Two");
+    /// assert!(cmpstrs.iter().eq(["One", "Three"]));
     /// ```
-    #[inline]
-    #[must_use]
-    pub fn iter(&self) -> Iter<'_> {
-        Iter(self.0.iter())
-    }
-}
-
-impl PartialEq for FixedCompactStrings {
-    fn eq(&self, other: &Self) -> bool {
-        let len = self.len();
-        if len != other.len() {
-            return false;
-        }
-
-        for idx in 0..len {
-            if self[idx] != other[idx] {
-                return false;
-            }
+    #[track_caller]
+    pub fn take(&mut self, index: usize) -> String {
+        let taken = self.0.take(index);
+        if cfg!(feature = "no_unsafe") {
+            String::from_utf8(taken).unwrap_or_default()
+        } else {
+            unsafe { String::from_utf8_unchecked(taken) }
         }
-
-        true
-    }
-}
-
-impl Debug for FixedCompactStrings {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.debug_list().entries(self.iter()).finish()
     }
-}
 
-impl<S> Extend<S> for FixedCompactStrings
-where
-    S: Deref<Target = str>,
-{
+    /// Removes the strings at the specified indices, which must be sorted and unique.
+    ///
+    /// Every remaining string is shifted over at most once, so this takes *O*(*n*) time in total
+    /// rather than *O*(*k* \* *n*) for *k* separate calls to [`remove`].
+    ///
+    /// [`remove`]: FixedCompactStrings::remove
+    ///
+    /// # Panics
+    /// Panics if `indices` is not strictly increasing or if an index is out of bounds.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let mut cmpstrs = FixedCompactStrings::from(["One", "Two", "Three", "Four"]);
+    /// cmpstrs.remove_many(&[0, 2]);
+    ///
+    /// assert!(cmpstrs.iter().eq(["Two", "Four"]));
+    /// ```
     #[inline]
-    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
-        for s in iter {
-            self.push(s);
-        }
+    #[track_caller]
+    pub fn remove_many(&mut self, indices: &[usize]) {
+        self.0.remove_many(indices);
     }
-}
+
+    /// Removes the bytes of the strings in the specified range and the data pointing to them.
+    ///
+    /// This shifts the remaining bytes in the data vector and the remaining elements in the meta
+    /// vector only once, rather than once per string as repeated calls to [`remove`] would.
+    ///
+    /// [`remove`]: FixedCompactStrings::remove
+    ///
+    /// # Panics
+    /// Panics if the starting point is greater than the end point or if the end point is greater
+    /// than the length of the [`FixedCompactStrings`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let mut cmpstrs = FixedCompactStrings::with_capacity(20, 4);
+    ///
+    /// cmpstrs.push("One");
+    /// cmpstrs.push("Two");
+    /// cmpstrs.push("Three");
+    /// cmpstrs.push("Four");
+    ///
+    /// cmpstrs.remove_range(1..3);
+    ///
+    /// assert_eq!(cmpstrs.get(0), Some("One"));
+    /// assert_eq!(cmpstrs.get(1), Some("Four"));
+    /// assert_eq!(cmpstrs.get(2), None);
+    /// ```
+    #[track_caller]
+    pub fn remove_range<R>(&mut self, range: R)
+    where
+        R: RangeBounds<usize>,
+    {
+        self.0.remove_range(range);
+    }
+
+    /// Replaces the strings in the specified range with the strings of `replace_with`, returning
+    /// the removed strings in a new [`FixedCompactStrings`].
+    ///
+    /// Like [`Vec::splice`], the tail of the data vector and of the starting indices is only
+    /// shifted once, regardless of how many strings are removed or inserted.
+    ///
+    /// # Panics
+    /// Panics if the starting point is greater than the end point or if the end point is greater
+    /// than the length of the [`FixedCompactStrings`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let mut cmpstrs = FixedCompactStrings::from(["One", "Two", "Three", "Four"]);
+    /// let removed = cmpstrs.splice(1..3, ["2", "3", "3.5"]);
+    ///
+    /// assert!(cmpstrs.iter().eq(["One", "2", "3", "3.5", "Four"]));
+    /// assert!(removed.iter().eq(["Two", "Three"]));
+    /// ```
+    #[track_caller]
+    #[must_use = "the removed strings are returned in a new collection"]
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Self
+    where
+        R: RangeBounds<usize>,
+        I: IntoIterator,
+        I::Item: Deref<Target = str>,
+    {
+        let range = to_range(range, self.len());
+        let replacement = FixedCompactStrings::from_iter(replace_with).0;
+        FixedCompactStrings(self.0.splice_from(range, replacement))
+    }
+
+    /// Removes all but the first of consecutive strings that `same_bucket` considers equal.
+    ///
+    /// `same_bucket` is passed each string followed by the last string that was kept before it,
+    /// like [`Vec::dedup_by`]. The bytes of the remaining strings are moved together in a single
+    /// pass over the data vector.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let mut cmpstrs = FixedCompactStrings::from(["One", "ONE", "Two", "one"]);
+    /// cmpstrs.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    ///
+    /// assert!(cmpstrs.iter().eq(["One", "Two", "one"]));
+    /// ```
+    #[inline]
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+    where
+        F: FnMut(&str, &str) -> bool,
+    {
+        self.0.dedup_by(|a, b| {
+            same_bucket(
+                Iter::from_utf8_maybe_checked(a).unwrap_or_default(),
+                Iter::from_utf8_maybe_checked(b).unwrap_or_default(),
+            )
+        });
+    }
+
+    /// Removes every string for which `pred` returns true, returning how many were removed.
+    ///
+    /// The bytes of the remaining strings are moved together in a single pass over the data
+    /// vector, rather than once per string as repeated calls to [`remove`] would.
+    ///
+    /// [`remove`]: FixedCompactStrings::remove
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let mut cmpstrs = FixedCompactStrings::from(["One", "Two", "Three", "Four"]);
+    ///
+    /// assert_eq!(cmpstrs.remove_matching(|s| s.starts_with('T')), 2);
+    /// assert!(cmpstrs.iter().eq(["One", "Four"]));
+    /// ```
+    #[inline]
+    pub fn remove_matching<F>(&mut self, mut pred: F) -> usize
+    where
+        F: FnMut(&str) -> bool,
+    {
+        self.0
+            .remove_matching(|bytes| pred(Iter::from_utf8_maybe_checked(bytes).unwrap_or_default()))
+    }
+
+    /// Removes all but the first of consecutive strings that map to the same key.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let mut cmpstrs = FixedCompactStrings::from([" One", "One ", "Two", "one"]);
+    /// cmpstrs.dedup_by_key(|s| s.trim().to_lowercase());
+    ///
+    /// assert!(cmpstrs.iter().eq([" One", "Two", "one"]));
+    /// ```
+    #[inline]
+    pub fn dedup_by_key<K, F>(&mut self, mut key: F)
+    where
+        K: PartialEq,
+        F: FnMut(&str) -> K,
+    {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    /// Returns a new [`FixedCompactStrings`] holding copies of the strings at `indices`, in that order.
+    ///
+    /// Indices may repeat. The lengths of the selected strings are summed first, so the data
+    /// vector of the new [`FixedCompactStrings`] is allocated exactly once and with no spare capacity.
+    ///
+    /// # Panics
+    /// Panics if any index is out of bounds.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["One", "Two", "Three"]);
+    /// let gathered = cmpstrs.gather([2, 0, 2]);
+    ///
+    /// assert!(gathered.iter().eq(["Three", "One", "Three"]));
+    /// ```
+    #[inline]
+    #[track_caller]
+    #[must_use]
+    pub fn gather<I>(&self, indices: I) -> Self
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        FixedCompactStrings(self.0.gather(indices))
+    }
+
+    /// Returns a new [`FixedCompactStrings`] holding copies of the strings for which `pred` returns true.
+    ///
+    /// Unlike collecting a filtered iterator, this finds the matching strings and the total
+    /// length of their bytes first, so the data vector of the new [`FixedCompactStrings`] is allocated only once.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["One", "Two", "Three"]);
+    /// let filtered = cmpstrs.filtered(|s| s.len() == 3);
+    ///
+    /// assert!(filtered.iter().eq(["One", "Two"]));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn filtered<F>(&self, mut pred: F) -> Self
+    where
+        F: FnMut(&str) -> bool,
+    {
+        FixedCompactStrings(
+            self.0
+                .filtered(|bytes| pred(Iter::from_utf8_maybe_checked(bytes).unwrap_or_default())),
+        )
+    }
+
+    /// Returns a new [`FixedCompactStrings`] holding the result of `f` for each string.
+    ///
+    /// Borrowed results are copied straight into the data vector of the new [`FixedCompactStrings`], so strings
+    /// that `f` leaves unchanged or only slices never need an allocation of their own.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// use std::borrow::Cow;
+    ///
+    /// let cmpstrs = FixedCompactStrings::from([" One ", "two", "Three"]);
+    /// let mapped = cmpstrs.map_strings(|s| {
+    ///     let s = s.trim();
+    ///     if s.starts_with(char::is_lowercase) {
+    ///         Cow::Owned(s.to_uppercase())
+    ///     } else {
+    ///         Cow::Borrowed(s)
+    ///     }
+    /// });
+    ///
+    /// assert!(mapped.iter().eq(["One", "TWO", "Three"]));
+    /// ```
+    #[must_use]
+    pub fn map_strings<'a, F>(&'a self, mut f: F) -> Self
+    where
+        F: FnMut(&'a str) -> Cow<'a, str>,
+    {
+        let mut mapped = Self::with_capacity(self.data_len(), self.len());
+        for item in self {
+            mapped.push(f(item));
+        }
+
+        mapped
+    }
+
+    /// Groups the strings by the key that `key` returns for each of them, returning a map from each
+    /// key to a new [`FixedCompactStrings`], as described in [`FixedCompactBytestrings::group_by_key`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["One", "Three", "Two", "Four"]);
+    /// let groups = cmpstrs.group_by_key(str::len);
+    ///
+    /// assert_eq!(groups.len(), 3);
+    /// assert!(groups[&3].iter().eq(["One", "Two"]));
+    /// assert!(groups[&5].iter().eq(["Three"]));
+    /// ```
+    #[must_use]
+    pub fn group_by_key<K, F>(&self, mut key: F) -> BTreeMap<K, Self>
+    where
+        K: Ord,
+        F: FnMut(&str) -> K,
+    {
+        self.0
+            .group_by_key(|bytes| key(Iter::from_utf8_maybe_checked(bytes).unwrap_or_default()))
+            .into_iter()
+            .map(|(key, group)| (key, FixedCompactStrings(group)))
+            .collect()
+    }
+
+    /// Sorts the strings.
+    ///
+    /// This sort is stable, and rewrites the data vector once like
+    /// [`FixedCompactBytestrings::sort`](crate::FixedCompactBytestrings::sort).
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let mut cmpstrs = FixedCompactStrings::from(["Two", "One", "Six"]);
+    /// cmpstrs.sort();
+    ///
+    /// assert!(cmpstrs.iter().eq(["One", "Six", "Two"]));
+    /// ```
+    #[inline]
+    pub fn sort(&mut self) {
+        // Comparing UTF-8 bytes gives the same order as comparing the strings.
+        self.0.sort();
+    }
+
+    /// Sorts the strings with a comparator function.
+    ///
+    /// This sort is stable, and rewrites the data vector once like [`sort`].
+    ///
+    /// [`sort`]: FixedCompactStrings::sort
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let mut cmpstrs = FixedCompactStrings::from(["b", "A", "c"]);
+    /// cmpstrs.sort_by(|a, b| a.to_lowercase().cmp(&b.to_lowercase()));
+    ///
+    /// assert!(cmpstrs.iter().eq(["A", "b", "c"]));
+    /// ```
+    #[inline]
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&str, &str) -> Ordering,
+    {
+        self.0.sort_by(|a, b| {
+            compare(
+                Iter::from_utf8_maybe_checked(a).unwrap_or_default(),
+                Iter::from_utf8_maybe_checked(b).unwrap_or_default(),
+            )
+        });
+    }
+
+    /// Sorts the strings with a key extraction function.
+    ///
+    /// This sort is stable, and rewrites the data vector once like [`sort`].
+    ///
+    /// [`sort`]: FixedCompactStrings::sort
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let mut cmpstrs = FixedCompactStrings::from(["Three", "One", "Four"]);
+    /// cmpstrs.sort_by_key(str::len);
+    ///
+    /// assert!(cmpstrs.iter().eq(["One", "Four", "Three"]));
+    /// ```
+    #[inline]
+    pub fn sort_by_key<K, F>(&mut self, mut key: F)
+    where
+        K: Ord,
+        F: FnMut(&str) -> K,
+    {
+        self.sort_by(|a, b| key(a).cmp(&key(b)));
+    }
+
+    /// Returns true if the strings are sorted in ascending order.
+    ///
+    /// Adjacent strings are compared straight out of the data vector, without allocating. This
+    /// checks the precondition of binary-search-based methods such as [`iter_sorted_with_prefix`].
+    ///
+    /// [`iter_sorted_with_prefix`]: FixedCompactStrings::iter_sorted_with_prefix
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let mut cmpstrs = FixedCompactStrings::from(["Two", "One", "Six"]);
+    /// assert!(!cmpstrs.is_sorted());
+    ///
+    /// cmpstrs.sort();
+    /// assert!(cmpstrs.is_sorted());
+    /// ```
+    #[must_use]
+    pub fn is_sorted(&self) -> bool {
+        self.0.is_sorted()
+    }
+
+    /// Returns true if the strings are sorted by a comparator function, meaning that it never
+    /// returns [`Ordering::Greater`] for a string and the one after it.
+    ///
+    /// This takes the same comparator as [`sort_by`], after which it always returns true.
+    ///
+    /// [`sort_by`]: FixedCompactStrings::sort_by
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["Two", "Three", "One"]);
+    ///
+    /// assert!(cmpstrs.is_sorted_by(|a, b| b.cmp(a)));
+    /// assert!(!cmpstrs.is_sorted_by(|a, b| a.len().cmp(&b.len())));
+    /// ```
+    pub fn is_sorted_by<F>(&self, mut compare: F) -> bool
+    where
+        F: FnMut(&str, &str) -> Ordering,
+    {
+        self.iter()
+            .zip(self.iter().skip(1))
+            .all(|(a, b)| compare(a, b) != Ordering::Greater)
+    }
+
+    /// Returns the index of the first string for which `pred` returns false, assuming that it
+    /// returns true for every string before that one and false for every string after it, like
+    /// [`slice::partition_point`].
+    ///
+    /// This is a binary search, so it runs in *O*(log *n*) time. If the strings are not
+    /// partitioned by `pred`, the index returned is unspecified.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["ant", "bee", "cat", "dog"]);
+    ///
+    /// assert_eq!(cmpstrs.partition_point(|s| s < &"c"[..]), 2);
+    /// assert_eq!(cmpstrs.partition_point(|_| true), 4);
+    /// ```
+    pub fn partition_point<P>(&self, mut pred: P) -> usize
+    where
+        P: FnMut(&str) -> bool,
+    {
+        search::partition_point(self.len(), |i| pred(&self[i]))
+    }
+
+    /// Inserts a string at the position that keeps the [`FixedCompactStrings`] sorted, returning
+    /// its index.
+    ///
+    /// The position is found with a binary search, after any strings equal to the new one, and the
+    /// bytes after it are shifted over once. If the [`FixedCompactStrings`] is not sorted, the
+    /// position is unspecified.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let mut cmpstrs = FixedCompactStrings::from(["One", "Two"]);
+    ///
+    /// assert_eq!(cmpstrs.insert_sorted("Six"), 1);
+    /// assert_eq!(cmpstrs.insert_sorted("Ten"), 2);
+    /// assert!(cmpstrs.iter().eq(["One", "Six", "Ten", "Two"]));
+    /// ```
+    #[inline]
+    pub fn insert_sorted<S>(&mut self, string: S) -> usize
+    where
+        S: Deref<Target = str>,
+    {
+        self.0.insert_sorted(string.as_bytes())
+    }
+
+    /// Merges two sorted [`FixedCompactStrings`] into a new sorted [`FixedCompactStrings`].
+    ///
+    /// Both the data vector and the meta vector of the result are allocated exactly once. Equal
+    /// strings from `a` are placed before those from `b`. If either input is not sorted, the
+    /// order of the result is unspecified.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let a = FixedCompactStrings::from(["One", "Ten"]);
+    /// let b = FixedCompactStrings::from(["Six", "Two"]);
+    /// let merged = FixedCompactStrings::merge_sorted(&a, &b);
+    ///
+    /// assert!(merged.iter().eq(["One", "Six", "Ten", "Two"]));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn merge_sorted(a: &Self, b: &Self) -> Self {
+        FixedCompactStrings(FixedCompactBytestrings::merge_sorted(&a.0, &b.0))
+    }
+
+    /// Returns the indices of the strings that are only in `self` and of those that are only in
+    /// `other`, as described in [`FixedCompactBytestrings::diff`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let old = FixedCompactStrings::from(["One", "Six", "Two"]);
+    /// let new = FixedCompactStrings::from(["One", "Ten", "Two"]);
+    ///
+    /// let diff = old.diff(&new);
+    /// assert_eq!(diff.removed, [1]);
+    /// assert_eq!(diff.added, [1]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn diff(&self, other: &Self) -> Diff {
+        self.0.diff(&other.0)
+    }
+
+    /// Sorts the strings with a radix sort, which distributes them by one byte at a time instead of
+    /// comparing them.
+    ///
+    /// This sort is stable and gives the same order as [`sort`], but is usually faster for large
+    /// collections of short strings.
+    ///
+    /// [`sort`]: FixedCompactStrings::sort
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let mut cmpstrs = FixedCompactStrings::from(["Two", "One", "On", "Six"]);
+    /// cmpstrs.sort_radix();
+    ///
+    /// assert!(cmpstrs.iter().eq(["On", "One", "Six", "Two"]));
+    /// ```
+    #[inline]
+    pub fn sort_radix(&mut self) {
+        // Comparing UTF-8 bytes gives the same order as comparing the strings.
+        self.0.sort_radix();
+    }
+
+    /// Returns the indices of the strings in the order that would sort them, without reordering
+    /// the [`FixedCompactStrings`] itself.
+    ///
+    /// Equal strings keep their relative order.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["Two", "One", "Six"]);
+    ///
+    /// assert_eq!(cmpstrs.sorted_indices(), [1, 2, 0]);
+    /// ```
+    #[must_use]
+    pub fn sorted_indices(&self) -> Vec<usize> {
+        self.sorted_indices_by(Ord::cmp)
+    }
+
+    /// Returns the indices of the strings in the order that would sort them with a comparator
+    /// function, without reordering the [`FixedCompactStrings`] itself.
+    ///
+    /// Equal strings keep their relative order.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["Three", "One", "Four"]);
+    ///
+    /// assert_eq!(cmpstrs.sorted_indices_by(|a, b| a.len().cmp(&b.len())), [1, 2, 0]);
+    /// ```
+    pub fn sorted_indices_by<F>(&self, mut compare: F) -> Vec<usize>
+    where
+        F: FnMut(&str, &str) -> Ordering,
+    {
+        let mut indices: Vec<usize> = (0..self.len()).collect();
+        indices.sort_by(|&a, &b| compare(&self[a], &self[b]));
+        indices
+    }
+
+    /// Reorders the strings so that the string at each position `i` is the one that was at
+    /// `perm[i]`, rewriting the data vector once.
+    ///
+    /// Passing the result of [`sorted_indices`] sorts the [`FixedCompactStrings`].
+    ///
+    /// [`sorted_indices`]: FixedCompactStrings::sorted_indices
+    ///
+    /// # Panics
+    /// Panics if `perm` is not a permutation of `0..len`, where `len` is the number of strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let mut cmpstrs = FixedCompactStrings::from(["One", "Two", "Six"]);
+    /// cmpstrs.apply_permutation(&[2, 0, 1]);
+    ///
+    /// assert!(cmpstrs.iter().eq(["Six", "One", "Two"]));
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn apply_permutation(&mut self, perm: &[usize]) {
+        self.0.apply_permutation(perm);
+    }
+
+    /// Returns true if any string contains `needle`.
+    ///
+    /// The data vector is searched in one pass like
+    /// [`FixedCompactBytestrings::contains_bytes`](crate::FixedCompactBytestrings::contains_bytes).
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["One", "Two", "Six"]);
+    ///
+    /// assert!(cmpstrs.contains_str("wo"));
+    /// assert!(!cmpstrs.contains_str("eT"));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn contains_str(&self, needle: &str) -> bool {
+        self.0.contains_bytes(needle.as_bytes())
+    }
+
+    /// Returns the index of the first string that contains `needle`.
+    ///
+    /// The data vector is searched in one pass like
+    /// [`FixedCompactBytestrings::find_bytes`](crate::FixedCompactBytestrings::find_bytes).
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["One", "Two", "Six"]);
+    ///
+    /// assert_eq!(cmpstrs.find_str("i"), Some(2));
+    /// assert_eq!(cmpstrs.find_str("eT"), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn find_str(&self, needle: &str) -> Option<usize> {
+        self.0.find_bytes(needle.as_bytes())
+    }
+
+    /// Returns an iterator over the indices of the strings that contain `pattern`, in order.
+    ///
+    /// The data vector is searched in one pass like
+    /// [`FixedCompactBytestrings::indices_containing`](crate::FixedCompactBytestrings::indices_containing).
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["error: disk", "ok", "error: net"]);
+    ///
+    /// assert!(cmpstrs.indices_containing("error").eq([0, 2]));
+    /// ```
+    #[inline]
+    pub fn indices_containing<'a>(&'a self, pattern: &'a str) -> impl Iterator<Item = usize> + 'a {
+        self.0.indices_containing(pattern.as_bytes())
+    }
+
+    /// Returns the index of the first string that is equal to `string` when ignoring ASCII case.
+    ///
+    /// Bytes are compared in place with ASCII case folding, without allocating lowercased copies.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["Host", "Content-Type"]);
+    ///
+    /// assert_eq!(cmpstrs.position_ignore_ascii_case("content-type"), Some(1));
+    /// assert_eq!(cmpstrs.position_ignore_ascii_case("Accept"), None);
+    /// ```
+    #[must_use]
+    pub fn position_ignore_ascii_case(&self, string: &str) -> Option<usize> {
+        self.iter().position(|s| s.eq_ignore_ascii_case(string))
+    }
+
+    /// Returns true if any string is equal to `string` when ignoring ASCII case.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["Host", "Content-Type"]);
+    ///
+    /// assert!(cmpstrs.contains_ignore_ascii_case("content-type"));
+    /// assert!(!cmpstrs.contains_ignore_ascii_case("Accept"));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn contains_ignore_ascii_case(&self, string: &str) -> bool {
+        self.position_ignore_ascii_case(string).is_some()
+    }
+
+    /// Returns a copy of the [`FixedCompactStrings`] with every string converted to ASCII lowercase.
+    ///
+    /// As lengths do not change, the whole data vector is converted in one pass instead of string
+    /// by string.
+    ///
+    /// Non-ASCII characters are left unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["Host", "Content-Type", "Grüße"]);
+    ///
+    /// assert!(cmpstrs.to_ascii_lowercase().iter().eq(["host", "content-type", "grüße"]));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn to_ascii_lowercase(&self) -> Self {
+        // Changing the case of ASCII bytes keeps the data vector valid UTF-8.
+        FixedCompactStrings(self.0.to_ascii_lowercase())
+    }
+
+    /// Returns a copy of the [`FixedCompactStrings`] with every string converted to ASCII uppercase.
+    ///
+    /// As lengths do not change, the whole data vector is converted in one pass instead of string
+    /// by string.
+    ///
+    /// Non-ASCII characters are left unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["Host", "Content-Type", "Grüße"]);
+    ///
+    /// assert!(cmpstrs.to_ascii_uppercase().iter().eq(["HOST", "CONTENT-TYPE", "GRüßE"]));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn to_ascii_uppercase(&self) -> Self {
+        // Changing the case of ASCII bytes keeps the data vector valid UTF-8.
+        FixedCompactStrings(self.0.to_ascii_uppercase())
+    }
+
+    /// Returns a new [`FixedCompactStrings`] holding the strings repeated `n` times.
+    ///
+    /// Both vectors of the new [`FixedCompactStrings`] are allocated once with exactly the capacity they need.
+    ///
+    /// # Panics
+    /// Panics if the capacity would overflow.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["One", "Two"]);
+    ///
+    /// assert!(cmpstrs.repeat(3).iter().eq(["One", "Two", "One", "Two", "One", "Two"]));
+    /// assert!(cmpstrs.repeat(0).is_empty());
+    /// ```
+    #[inline]
+    #[track_caller]
+    #[must_use]
+    pub fn repeat(&self, n: usize) -> Self {
+        FixedCompactStrings(self.0.repeat(n))
+    }
+
+    /// Returns an iterator over the strings that start with `prefix`.
+    ///
+    /// Every string is checked. If the [`FixedCompactStrings`] is sorted, [`iter_sorted_with_prefix`] finds
+    /// the matching strings with a binary search instead.
+    ///
+    /// [`iter_sorted_with_prefix`]: FixedCompactStrings::iter_sorted_with_prefix
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["car", "dog", "cart"]);
+    ///
+    /// assert!(cmpstrs.iter_with_prefix("car").eq(["car", "cart"]));
+    /// ```
+    #[must_use]
+    pub fn iter_with_prefix<'a>(
+        &'a self,
+        prefix: &'a str,
+    ) -> impl DoubleEndedIterator<Item = &'a str> + 'a {
+        self.iter().filter(move |s| s.starts_with(prefix))
+    }
+
+    /// Returns an iterator over the strings that start with `prefix`, assuming the [`FixedCompactStrings`] is
+    /// sorted.
+    ///
+    /// The strings sharing a prefix are adjacent in a sorted [`FixedCompactStrings`], so their range is found
+    /// with two binary searches. If the [`FixedCompactStrings`] is not sorted, the strings returned are
+    /// unspecified.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let mut cmpstrs = FixedCompactStrings::from(["cart", "dog", "cab", "car"]);
+    /// cmpstrs.sort();
+    ///
+    /// let mut iter = cmpstrs.iter_sorted_with_prefix("car");
+    /// assert_eq!(iter.len(), 2);
+    /// assert!(iter.eq(["car", "cart"]));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn iter_sorted_with_prefix(&self, prefix: &str) -> Iter<'_> {
+        // Sorting by UTF-8 bytes is the same as sorting by string.
+        Iter(self.0.iter_sorted_with_prefix(prefix.as_bytes()))
+    }
+
+    /// Returns an iterator over the strings in `range`, which must be in bounds.
+    pub(crate) fn iter_slice(&self, range: core::ops::Range<usize>) -> Iter<'_> {
+        Iter(self.0.iter_slice(range))
+    }
+
+    /// Returns an iterator over the starting index in the data vector and the length of each string.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let mut cmpstrs = FixedCompactStrings::new();
+    /// cmpstrs.push("One");
+    /// cmpstrs.push("Two");
+    /// cmpstrs.push("Three");
+    ///
+    /// let mut offsets = cmpstrs.offsets();
+    /// assert_eq!(offsets.next(), Some((0, 3)));
+    /// assert_eq!(offsets.next(), Some((3, 3)));
+    /// assert_eq!(offsets.next(), Some((6, 5)));
+    /// assert_eq!(offsets.next(), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn offsets(
+        &self,
+    ) -> impl ExactSizeIterator<Item = (usize, usize)> + DoubleEndedIterator + '_ {
+        self.0.offsets()
+    }
+
+    /// Returns the range of bytes in the data vector holding the string at that position, or
+    /// [`None`] if `index` is out of bounds.
+    ///
+    /// This translates between element indices and byte positions in the data vector, such as
+    /// those found by searching it directly.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["One", "Two"]);
+    ///
+    /// assert_eq!(cmpstrs.byte_range(1), Some(3..6));
+    /// assert_eq!(cmpstrs.byte_range(2), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn byte_range(&self, index: usize) -> Option<Range<usize>> {
+        self.0.byte_range(index)
+    }
+
+    /// Returns the index of the string that `string` was borrowed from, or [`None`] if it does
+    /// not refer to a whole string in the [`FixedCompactStrings`].
+    ///
+    /// Unlike searching for an equal string, this tells apart strings with the same contents,
+    /// so a reference handed out earlier can be mapped back to its index. If several empty
+    /// strings share a position in the data vector, the first of them is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["One", "Two", "Two"]);
+    /// let last = &cmpstrs[2];
+    ///
+    /// assert_eq!(cmpstrs.index_of_ref(last), Some(2));
+    /// assert_eq!(cmpstrs.index_of_ref("Two"), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn index_of_ref(&self, string: &str) -> Option<usize> {
+        self.0.index_of_ref(string.as_bytes())
+    }
+
+    /// Returns an iterator over the length in bytes of each string.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let mut cmpstrs = FixedCompactStrings::new();
+    /// cmpstrs.push("One");
+    /// cmpstrs.push("Two");
+    /// cmpstrs.push("Three");
+    ///
+    /// assert!(cmpstrs.lengths().eq([3, 3, 5]));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn lengths(&self) -> impl ExactSizeIterator<Item = usize> + DoubleEndedIterator + '_ {
+        self.0.lengths()
+    }
+
+    /// Returns the length in bytes of the string at that position, or [`None`] if `index` is out
+    /// of bounds, reading only its starting index and the next one.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["One", "Three"]);
+    ///
+    /// assert_eq!(cmpstrs.len_at(1), Some(5));
+    /// assert_eq!(cmpstrs.len_at(2), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn len_at(&self, index: usize) -> Option<usize> {
+        self.0.len_at(index)
+    }
+
+    /// Feeds the number of strings, the length of each of them and then their bytes into `state`,
+    /// in order, as described in [`CompactBytestrings::digest`](crate::CompactBytestrings::digest).
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// use std::collections::hash_map::DefaultHasher;
+    /// use std::hash::Hasher;
+    ///
+    /// let mut a = DefaultHasher::new();
+    /// FixedCompactStrings::from(["One", "Two"]).digest(&mut a);
+    ///
+    /// let mut b = DefaultHasher::new();
+    /// FixedCompactStrings::from(["On", "eTwo"]).digest(&mut b);
+    /// assert_ne!(a.finish(), b.finish());
+    /// ```
+    #[inline]
+    pub fn digest<H: Hasher>(&self, state: &mut H) {
+        self.0.digest(state);
+    }
+
+    /// Copies each string into an owned [`String`], returning them in a vector.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let mut cmpstrs = FixedCompactStrings::new();
+    /// cmpstrs.push("One");
+    /// cmpstrs.push("Two");
+    ///
+    /// let vec: Vec<String> = cmpstrs.to_vec();
+    /// assert_eq!(vec, ["One", "Two"]);
+    /// ```
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<String> {
+        self.iter().map(String::from).collect()
+    }
+
+    /// Copies each string into an owned [`Box<str>`], returning them in a boxed slice.
+    ///
+    /// Every string is allocated exactly once from its known length, so unlike going through
+    /// [`to_vec`](FixedCompactStrings::to_vec), no allocation holds spare capacity.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["One", "Two"]);
+    ///
+    /// let boxed: Box<[Box<str>]> = cmpstrs.into_boxed_strs();
+    /// assert_eq!(&*boxed, [Box::from("One"), Box::from("Two")]);
+    /// ```
+    #[must_use]
+    pub fn into_boxed_strs(self) -> Box<[Box<str>]> {
+        self.iter().map(Box::from).collect()
+    }
+
+    /// Encodes the [`FixedCompactStrings`] in the binary layout documented on
+    /// [`CompactBytestrings::to_bytes`](crate::CompactBytestrings::to_bytes).
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactStrsRef, FixedCompactStrings};
+    /// let mut cmpstrs = FixedCompactStrings::new();
+    /// cmpstrs.push("One");
+    /// cmpstrs.push("Two");
+    ///
+    /// let buf = cmpstrs.to_bytes();
+    /// let view = CompactStrsRef::parse(&buf).unwrap();
+    /// assert!(view.iter().eq(cmpstrs.iter()));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.to_bytes()
+    }
+
+    /// Returns an iterator over the slice.
+    ///
+    /// The iterator yields all items from start to end.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let mut cmpstrs = FixedCompactStrings::with_capacity(20, 3);
+    /// cmpstrs.push("One");
+    /// cmpstrs.push("Two");
+    /// cmpstrs.push("Three");
+    /// let mut iterator = cmpstrs.iter();
+    ///
+    /// assert_eq!(iterator.next(), Some("One"));
+    /// assert_eq!(iterator.next(), Some("Two"));
+    /// assert_eq!(iterator.next(), Some("Three"));
+    /// assert_eq!(iterator.next(), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter(self.0.iter())
+    }
+
+    /// Returns an iterator over the strings in `range`.
+    ///
+    /// The iterator starts at the first strings in `range` without stepping over the ones before
+    /// it, unlike calling [`skip`](Iterator::skip) on [`iter`](FixedCompactStrings::iter).
+    ///
+    /// # Panics
+    /// Panics if the starting point is greater than the end point or if the end point is greater
+    /// than the length of the [`FixedCompactStrings`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["One", "Two", "Three", "Four"]);
+    ///
+    /// assert!(cmpstrs.iter_range(1..3).eq(["Two", "Three"]));
+    /// ```
+    #[inline]
+    #[track_caller]
+    #[must_use]
+    pub fn iter_range<R>(&self, range: R) -> Iter<'_>
+    where
+        R: RangeBounds<usize>,
+    {
+        Iter(self.0.iter_range(range))
+    }
+
+    /// Returns an iterator over the strings from `start` onwards, as described in
+    /// [`iter_range`](FixedCompactStrings::iter_range).
+    ///
+    /// # Panics
+    /// Panics if `start` is greater than the length of the [`FixedCompactStrings`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["One", "Two", "Three", "Four"]);
+    ///
+    /// assert!(cmpstrs.iter_from(2).eq(["Three", "Four"]));
+    /// assert_eq!(cmpstrs.iter_from(4).next(), None);
+    /// ```
+    #[inline]
+    #[track_caller]
+    #[must_use]
+    pub fn iter_from(&self, start: usize) -> Iter<'_> {
+        self.iter_range(start..)
+    }
+}
+
+impl Default for FixedCompactStrings {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialEq for FixedCompactStrings {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        // Strings are equal exactly when their UTF-8 bytes are.
+        self.0 == other.0
+    }
+}
+
+impl Debug for FixedCompactStrings {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<S> Extend<S> for FixedCompactStrings
+where
+    S: Deref<Target = str>,
+{
+    #[inline]
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        for s in iter {
+            self.push(s);
+        }
+    }
+}
 
 impl Index<usize> for FixedCompactStrings {
     type Output = str;
@@ -476,7 +1863,7 @@ impl<'a> Iter<'a> {
 
     fn from_utf8_maybe_checked(bytes: &[u8]) -> Option<&str> {
         if cfg!(feature = "no_unsafe") {
-            core::str::from_utf8(bytes).ok()
+            utf8::from_utf8(bytes).ok()
         } else {
             Some(unsafe { core::str::from_utf8_unchecked(bytes) })
         }
@@ -516,7 +1903,7 @@ impl<'a> Iterator for Iter<'a> {
     }
 }
 
-impl<'a> DoubleEndedIterator for Iter<'a> {
+impl DoubleEndedIterator for Iter<'_> {
     fn next_back(&mut self) -> Option<Self::Item> {
         self.0.next_back().and_then(Self::from_utf8_maybe_checked)
     }
@@ -575,9 +1962,7 @@ impl TryFrom<FixedCompactBytestrings> for FixedCompactStrings {
     type Error = core::str::Utf8Error;
 
     fn try_from(value: FixedCompactBytestrings) -> Result<Self, Self::Error> {
-        for bstr in &value {
-            let _ = core::str::from_utf8(bstr)?;
-        }
+        value.validate_utf8()?;
 
         Ok(Self(value))
     }
@@ -624,6 +2009,44 @@ mod tests {
         assert_eq!(iter.next(), None);
         assert_eq!(iter.next_back(), None);
     }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_flat_roundtrip() {
+        use crate::Flat;
+
+        let mut cmpstrs = FixedCompactStrings::new();
+        cmpstrs.push("One");
+        cmpstrs.push("Two");
+        cmpstrs.push("Thrée");
+        cmpstrs.remove(1);
+
+        let encoded = bincode::serialize(&Flat(&cmpstrs)).unwrap();
+        let Flat(decoded): Flat<FixedCompactStrings> = bincode::deserialize(&encoded).unwrap();
+        assert_eq!(decoded, cmpstrs);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_defaults_to_a_sequence_of_strings() {
+        let mut cmpstrs = FixedCompactStrings::new();
+        cmpstrs.push("One");
+        cmpstrs.push("Thrée");
+
+        let encoded = bincode::serialize(&cmpstrs).unwrap();
+        assert_eq!(encoded, bincode::serialize(&["One", "Thrée"][..]).unwrap());
+        let decoded: FixedCompactStrings = bincode::deserialize(&encoded).unwrap();
+        assert_eq!(decoded, cmpstrs);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_flat_rejects_split_char() {
+        use crate::Flat;
+
+        let encoded = bincode::serialize(&(&[0_u64, 3][..], "Thé".as_bytes())).unwrap();
+        assert!(bincode::deserialize::<Flat<FixedCompactStrings>>(&encoded).is_err());
+    }
 }
 
 #[cfg(feature = "serde")]
@@ -633,7 +2056,7 @@ mod serde {
         Deserialize, Deserializer, Serialize,
     };
 
-    use crate::FixedCompactStrings;
+    use crate::{flat, FixedCompactBytestrings, FixedCompactStrings};
 
     impl Serialize for FixedCompactStrings {
         fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
@@ -650,6 +2073,24 @@ mod serde {
         }
     }
 
+    impl flat::Encode for FixedCompactStrings {
+        fn serialize_flat<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.0.serialize_flat(serializer)
+        }
+    }
+
+    impl flat::Decode for FixedCompactStrings {
+        fn deserialize_flat<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let (starts, data) = flat::deserialize(deserializer)?;
+            flat::check_utf8(&starts, &data)?;
+
+            Ok(FixedCompactStrings(FixedCompactBytestrings {
+                data,
+                starts,
+            }))
+        }
+    }
+
     struct FixedCompactStringsVisitor;
 
     impl<'de> Visitor<'de> for FixedCompactStringsVisitor {
@@ -0,0 +1,311 @@
+//! The flat encoding written and read through the [`Flat`] wrapper.
+//!
+//! A list is encoded as a `(starts, data)` tuple, where `starts` is a sequence of `u64` offsets
+//! into `data` and `data` is a single byte buffer. The element at `i` spans from `starts[i]` to
+//! `starts[i + 1]`, or to the end of `data` for the last element.
+//!
+//! Also holds the helpers used to serialize single bytestrings as byte arrays.
+
+use core::fmt;
+
+use alloc::vec::Vec;
+
+use serde::{
+    de::{self, DeserializeSeed, SeqAccess, Visitor},
+    ser::{SerializeSeq, SerializeTuple},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::utf8;
+
+/// Serializes and deserializes the wrapped collection as a `(starts, data)` tuple instead of a
+/// sequence of strings.
+///
+/// The default serde implementations write one element per string, as earlier releases did, which
+/// every format can read. For binary formats such as bincode or postcard, wrapping the
+/// collection in [`Flat`] instead writes the starts and then all of the bytes in one go, which is
+/// smaller and much faster to read back. The two encodings are not interchangeable, so data
+/// written through [`Flat`] must also be read through [`Flat`].
+///
+/// Implemented for [`CompactStrings`](crate::CompactStrings),
+/// [`CompactBytestrings`](crate::CompactBytestrings),
+/// [`FixedCompactStrings`](crate::FixedCompactStrings) and
+/// [`FixedCompactBytestrings`](crate::FixedCompactBytestrings), and for references to them when
+/// serializing.
+///
+/// # Examples
+/// ```
+/// # use compact_strings::{CompactStrings, Flat};
+/// let mut cmpstrs = CompactStrings::new();
+/// cmpstrs.push("One");
+/// cmpstrs.push("Two");
+///
+/// let encoded = bincode::serialize(&Flat(&cmpstrs)).unwrap();
+/// let Flat(decoded): Flat<CompactStrings> = bincode::deserialize(&encoded).unwrap();
+/// assert_eq!(decoded, cmpstrs);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Flat<T>(pub T);
+
+/// Collections that can be written in the flat encoding.
+pub trait Encode {
+    #[doc(hidden)]
+    fn serialize_flat<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+}
+
+/// Collections that can be read from the flat encoding.
+pub trait Decode: Sized {
+    #[doc(hidden)]
+    fn deserialize_flat<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>;
+}
+
+impl<T: Encode + ?Sized> Encode for &T {
+    fn serialize_flat<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (**self).serialize_flat(serializer)
+    }
+}
+
+impl<T: Encode> Serialize for Flat<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize_flat(serializer)
+    }
+}
+
+impl<'de, T: Decode> Deserialize<'de> for Flat<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize_flat(deserializer).map(Flat)
+    }
+}
+
+/// Serializes the `count` starts yielded by `starts` and the `len` bytes yielded by `chunks` in
+/// the flat encoding.
+pub(crate) fn serialize<'a, S, I, C>(
+    serializer: S,
+    starts: I,
+    count: usize,
+    chunks: C,
+    len: usize,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    I: Iterator<Item = usize> + Clone,
+    C: Iterator<Item = &'a [u8]> + Clone,
+{
+    let mut tuple = serializer.serialize_tuple(2)?;
+    tuple.serialize_element(&Starts { starts, count })?;
+    tuple.serialize_element(&Chunks { chunks, len })?;
+    tuple.end()
+}
+
+/// Deserializes the flat encoding, checking that the starts begin at zero and are in order and
+/// in bounds.
+pub(crate) fn deserialize<'de, D>(deserializer: D) -> Result<(Vec<usize>, Vec<u8>), D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_tuple(2, FlatVisitor)
+}
+
+/// Checks that `data` is valid UTF-8 and that every start lies on a character boundary.
+pub(crate) fn check_utf8<E: de::Error>(starts: &[usize], data: &[u8]) -> Result<(), E> {
+    let str = utf8::from_utf8(data).map_err(E::custom)?;
+    if starts.iter().all(|&start| str.is_char_boundary(start)) {
+        Ok(())
+    } else {
+        Err(E::custom("start is not on a character boundary"))
+    }
+}
+
+struct Starts<I> {
+    starts: I,
+    count: usize,
+}
+
+impl<I> Serialize for Starts<I>
+where
+    I: Iterator<Item = usize> + Clone,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.count))?;
+        for start in self.starts.clone() {
+            seq.serialize_element(&(start as u64))?;
+        }
+        seq.end()
+    }
+}
+
+/// Serializes the bytes of `chunks` as one byte array.
+///
+/// A single chunk is written with `serialize_bytes`. Several chunks cannot be, so they are written
+/// as a sequence of `u8` instead, which bincode and postcard encode the same way as a byte array
+/// and which [`ByteBuf`] accepts as well.
+struct Chunks<C> {
+    chunks: C,
+    len: usize,
+}
+
+impl<'a, C> Serialize for Chunks<C>
+where
+    C: Iterator<Item = &'a [u8]> + Clone,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut chunks = self.chunks.clone();
+        match (chunks.next(), chunks.next()) {
+            (None, _) => serializer.serialize_bytes(&[]),
+            (Some(chunk), None) => serializer.serialize_bytes(chunk),
+            _ => {
+                let mut seq = serializer.serialize_seq(Some(self.len))?;
+                for byte in self.chunks.clone().flatten() {
+                    seq.serialize_element(byte)?;
+                }
+                seq.end()
+            }
+        }
+    }
+}
+
+/// Serializes a bytestring with `serialize_bytes` rather than as a sequence of integers.
+pub(crate) struct Bytes<'a>(pub(crate) &'a [u8]);
+
+impl Serialize for Bytes<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+struct FlatVisitor;
+
+impl<'de> Visitor<'de> for FlatVisitor {
+    type Value = (Vec<usize>, Vec<u8>);
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a tuple of starts and bytes")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let StartsBuf(starts) = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let ByteBuf(data) = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+
+        if starts.first().map_or(!data.is_empty(), |&first| first != 0) {
+            return Err(de::Error::custom("bytes are not covered by any start"));
+        }
+
+        let mut prev = 0;
+        for &start in &starts {
+            if start < prev || start > data.len() {
+                return Err(de::Error::custom("start is out of order or out of bounds"));
+            }
+            prev = start;
+        }
+
+        Ok((starts, data))
+    }
+}
+
+struct StartsBuf(Vec<usize>);
+
+impl<'de> Deserialize<'de> for StartsBuf {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(StartsVisitor)
+    }
+}
+
+struct StartsVisitor;
+
+impl<'de> Visitor<'de> for StartsVisitor {
+    type Value = StartsBuf;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence of starts")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut starts = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(start) = seq.next_element::<u64>()? {
+            starts.push(usize::try_from(start).map_err(de::Error::custom)?);
+        }
+
+        Ok(StartsBuf(starts))
+    }
+}
+
+struct ByteBuf(Vec<u8>);
+
+impl<'de> Deserialize<'de> for ByteBuf {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_bytes(ByteBufVisitor)
+    }
+}
+
+struct ByteBufVisitor;
+
+impl<'de> Visitor<'de> for ByteBufVisitor {
+    type Value = ByteBuf;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a byte array")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(ByteBuf(v.to_vec()))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut data = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(byte) = seq.next_element::<u8>()? {
+            data.push(byte);
+        }
+
+        Ok(ByteBuf(data))
+    }
+}
+
+/// Deserializes a bytestring with `deserialize_bytes`, appending its bytes to a data vector.
+///
+/// Sequences of integers are accepted as well, for formats that cannot store byte arrays.
+pub(crate) struct AppendBytes<'a>(pub(crate) &'a mut Vec<u8>);
+
+impl<'de> DeserializeSeed<'de> for AppendBytes<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_bytes(self)
+    }
+}
+
+impl<'de> Visitor<'de> for AppendBytes<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a byte array")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        self.0.extend_from_slice(v);
+        Ok(())
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        while let Some(byte) = seq.next_element::<u8>()? {
+            self.0.push(byte);
+        }
+
+        Ok(())
+    }
+}
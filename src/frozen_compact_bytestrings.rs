@@ -0,0 +1,262 @@
+use core::{fmt::Debug, ops::Index};
+
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+
+use crate::{compact_bytestrings::Iter, metadata::Metadata, CompactBytestrings};
+
+/// An immutable list of bytestrings.
+///
+/// The data and meta buffers are boxed slices rather than vectors, so a [`FrozenCompactBytestrings`]
+/// does not store their capacities and never reallocates, making it well suited for long-lived
+/// lists that are built once and only read afterwards.
+///
+/// # Examples
+/// ```
+/// # use compact_strings::CompactBytestrings;
+/// let mut cmpbytes = CompactBytestrings::new();
+/// cmpbytes.push(b"One");
+/// cmpbytes.push(b"Two");
+///
+/// let frozen = cmpbytes.freeze();
+///
+/// assert_eq!(frozen.get(0), Some(b"One".as_slice()));
+/// assert_eq!(frozen.get(1), Some(b"Two".as_slice()));
+/// assert_eq!(frozen.get(2), None);
+/// ```
+#[derive(Clone)]
+pub struct FrozenCompactBytestrings {
+    pub(crate) data: Box<[u8]>,
+    pub(crate) meta: Box<[Metadata]>,
+}
+
+impl FrozenCompactBytestrings {
+    /// Returns a reference to the bytestring stored in the [`FrozenCompactBytestrings`] at that position.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FrozenCompactBytestrings;
+    /// let frozen = FrozenCompactBytestrings::from([b"One", b"Two"]);
+    ///
+    /// assert_eq!(frozen.get(0), Some(b"One".as_slice()));
+    /// assert_eq!(frozen.get(1), Some(b"Two".as_slice()));
+    /// assert_eq!(frozen.get(2), None);
+    /// ```
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&[u8]> {
+        let (start, len) = self.meta.get(index)?.as_tuple();
+        if cfg!(feature = "no_unsafe") {
+            self.data.get(start..start + len)
+        } else {
+            unsafe { Some(self.data.get_unchecked(start..start + len)) }
+        }
+    }
+
+    /// Returns a reference to the bytestring stored in the [`FrozenCompactBytestrings`] at that position, without
+    /// doing bounds checking.
+    ///
+    /// # Safety
+    /// Calling this method with an out-of-bounds index is undefined behavior even if the resulting reference is not used.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FrozenCompactBytestrings;
+    /// let frozen = FrozenCompactBytestrings::from([b"One", b"Two"]);
+    ///
+    /// unsafe {
+    ///     assert_eq!(frozen.get_unchecked(0), b"One".as_slice());
+    ///     assert_eq!(frozen.get_unchecked(1), b"Two".as_slice());
+    /// }
+    /// ```
+    #[must_use]
+    #[cfg(not(feature = "no_unsafe"))]
+    pub unsafe fn get_unchecked(&self, index: usize) -> &[u8] {
+        let (start, len) = self.meta.get_unchecked(index).as_tuple();
+        self.data.get_unchecked(start..start + len)
+    }
+
+    /// Returns the number of bytestrings in the [`FrozenCompactBytestrings`], also referred to as its 'length'.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FrozenCompactBytestrings;
+    /// let frozen = FrozenCompactBytestrings::from([b"One", b"Two"]);
+    ///
+    /// assert_eq!(frozen.len(), 2);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.meta.len()
+    }
+
+    /// Returns true if the [`FrozenCompactBytestrings`] contains no bytestrings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactBytestrings, FrozenCompactBytestrings};
+    /// let frozen = FrozenCompactBytestrings::from(CompactBytestrings::new());
+    ///
+    /// assert!(frozen.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Copies each bytestring into an owned `Vec<u8>`, returning them in a vector.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FrozenCompactBytestrings;
+    /// let frozen = FrozenCompactBytestrings::from([b"One", b"Two"]);
+    ///
+    /// assert_eq!(frozen.to_vec(), [b"One".to_vec(), b"Two".to_vec()]);
+    /// ```
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<Vec<u8>> {
+        self.iter().map(<[u8]>::to_vec).collect()
+    }
+
+    /// Returns an iterator over the bytestrings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FrozenCompactBytestrings;
+    /// let frozen = FrozenCompactBytestrings::from([b"One", b"Two"]);
+    /// let mut iterator = frozen.iter();
+    ///
+    /// assert_eq!(iterator.next(), Some(b"One".as_slice()));
+    /// assert_eq!(iterator.next(), Some(b"Two".as_slice()));
+    /// assert_eq!(iterator.next(), None);
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter::from_parts(&self.data, &self.meta)
+    }
+}
+
+impl CompactBytestrings {
+    /// Converts the [`CompactBytestrings`] into a [`FrozenCompactBytestrings`], shrinking both
+    /// vectors to fit.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::with_capacity(20, 3);
+    /// cmpbytes.push(b"One");
+    /// cmpbytes.push(b"Two");
+    ///
+    /// let frozen = cmpbytes.freeze();
+    ///
+    /// assert_eq!(frozen.len(), 2);
+    /// assert_eq!(frozen.get(1), Some(b"Two".as_slice()));
+    /// ```
+    #[must_use]
+    pub fn freeze(self) -> FrozenCompactBytestrings {
+        self.into()
+    }
+
+    /// Copies the current contents into a [`FrozenCompactBytestrings`] behind an [`Arc`], which can be cloned
+    /// cheaply and sent to other threads while the [`CompactBytestrings`] keeps being modified.
+    ///
+    /// Bytes left behind by removed elements are not copied.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// use std::{sync::Arc, thread};
+    ///
+    /// let mut cmpbytes = CompactBytestrings::from([b"One", b"Two"]);
+    /// let snapshot = cmpbytes.snapshot();
+    ///
+    /// let reader = {
+    ///     let snapshot = Arc::clone(&snapshot);
+    ///     thread::spawn(move || snapshot.iter().map(<[u8]>::len).sum::<usize>())
+    /// };
+    /// cmpbytes.push(b"Three");
+    ///
+    /// assert_eq!(reader.join().unwrap(), 6);
+    /// assert_eq!(snapshot.len(), 2);
+    /// assert_eq!(cmpbytes.len(), 3);
+    /// ```
+    #[must_use]
+    pub fn snapshot(&self) -> Arc<FrozenCompactBytestrings> {
+        Arc::new(self.clone().freeze())
+    }
+}
+
+impl PartialEq for FrozenCompactBytestrings {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl Debug for FrozenCompactBytestrings {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl Index<usize> for FrozenCompactBytestrings {
+    type Output = [u8];
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+
+impl<'a> IntoIterator for &'a FrozenCompactBytestrings {
+    type Item = &'a [u8];
+
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<S> FromIterator<S> for FrozenCompactBytestrings
+where
+    S: AsRef<[u8]>,
+{
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        CompactBytestrings::from_iter(iter).into()
+    }
+}
+
+impl<S, I> From<I> for FrozenCompactBytestrings
+where
+    S: AsRef<[u8]>,
+    I: IntoIterator<Item = S>,
+{
+    #[inline]
+    fn from(value: I) -> Self {
+        FromIterator::from_iter(value)
+    }
+}
+
+impl From<CompactBytestrings> for FrozenCompactBytestrings {
+    fn from(value: CompactBytestrings) -> Self {
+        Self {
+            data: value.data.into_boxed_slice(),
+            meta: value.meta.into_boxed_slice(),
+        }
+    }
+}
+
+/// Bytes shared between bytestrings are copied apart, so that each bytestring can be modified in
+/// place on its own.
+impl From<FrozenCompactBytestrings> for CompactBytestrings {
+    fn from(value: FrozenCompactBytestrings) -> Self {
+        let mut cmpbytes = Self {
+            data: value.data.into_vec(),
+            meta: value.meta.into_vec(),
+        };
+        cmpbytes.restore_order();
+        cmpbytes
+    }
+}
@@ -0,0 +1,268 @@
+use core::{
+    fmt::Debug,
+    ops::{Deref, Index},
+};
+
+use alloc::{string::String, sync::Arc, vec::Vec};
+
+use crate::{compact_strings::Iter, CompactStrings, FrozenCompactBytestrings};
+
+/// An immutable list of strings.
+///
+/// The data and meta buffers are boxed slices rather than vectors, so a [`FrozenCompactStrings`]
+/// does not store their capacities and never reallocates, making it well suited for long-lived
+/// lists that are built once and only read afterwards.
+///
+/// # Examples
+/// ```
+/// # use compact_strings::CompactStrings;
+/// let mut cmpstrs = CompactStrings::new();
+/// cmpstrs.push("One");
+/// cmpstrs.push("Two");
+///
+/// let frozen = cmpstrs.freeze();
+///
+/// assert_eq!(frozen.get(0), Some("One"));
+/// assert_eq!(frozen.get(1), Some("Two"));
+/// assert_eq!(frozen.get(2), None);
+/// ```
+#[repr(transparent)]
+#[derive(Clone, PartialEq)]
+pub struct FrozenCompactStrings(pub(crate) FrozenCompactBytestrings);
+
+impl FrozenCompactStrings {
+    /// Returns a reference to the string stored in the [`FrozenCompactStrings`] at that position.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FrozenCompactStrings;
+    /// let frozen = FrozenCompactStrings::from(["One", "Two"]);
+    ///
+    /// assert_eq!(frozen.get(0), Some("One"));
+    /// assert_eq!(frozen.get(1), Some("Two"));
+    /// assert_eq!(frozen.get(2), None);
+    /// ```
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.0.get(index).and_then(Iter::from_utf8_maybe_checked)
+    }
+
+    /// Returns a reference to the string stored in the [`FrozenCompactStrings`] at that position, without
+    /// doing bounds checking.
+    ///
+    /// # Safety
+    /// Calling this method with an out-of-bounds index is undefined behavior even if the resulting reference is not used.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FrozenCompactStrings;
+    /// let frozen = FrozenCompactStrings::from(["One", "Two"]);
+    ///
+    /// unsafe {
+    ///     assert_eq!(frozen.get_unchecked(0), "One");
+    ///     assert_eq!(frozen.get_unchecked(1), "Two");
+    /// }
+    /// ```
+    #[must_use]
+    #[cfg(not(feature = "no_unsafe"))]
+    pub unsafe fn get_unchecked(&self, index: usize) -> &str {
+        core::str::from_utf8_unchecked(self.0.get_unchecked(index))
+    }
+
+    /// Returns the number of strings in the [`FrozenCompactStrings`], also referred to as its 'length'.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FrozenCompactStrings;
+    /// let frozen = FrozenCompactStrings::from(["One", "Two"]);
+    ///
+    /// assert_eq!(frozen.len(), 2);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if the [`FrozenCompactStrings`] contains no strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactStrings, FrozenCompactStrings};
+    /// let frozen = FrozenCompactStrings::from(CompactStrings::new());
+    ///
+    /// assert!(frozen.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Copies each string into an owned [`String`], returning them in a vector.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FrozenCompactStrings;
+    /// let frozen = FrozenCompactStrings::from(["One", "Two"]);
+    ///
+    /// assert_eq!(frozen.to_vec(), ["One", "Two"]);
+    /// ```
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<String> {
+        self.iter().map(String::from).collect()
+    }
+
+    /// Returns an iterator over the strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FrozenCompactStrings;
+    /// let frozen = FrozenCompactStrings::from(["One", "Two"]);
+    /// let mut iterator = frozen.iter();
+    ///
+    /// assert_eq!(iterator.next(), Some("One"));
+    /// assert_eq!(iterator.next(), Some("Two"));
+    /// assert_eq!(iterator.next(), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter::from_bytes(self.0.iter())
+    }
+}
+
+impl CompactStrings {
+    /// Converts the [`CompactStrings`] into a [`FrozenCompactStrings`], shrinking both
+    /// vectors to fit.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let mut cmpstrs = CompactStrings::with_capacity(20, 3);
+    /// cmpstrs.push("One");
+    /// cmpstrs.push("Two");
+    ///
+    /// let frozen = cmpstrs.freeze();
+    ///
+    /// assert_eq!(frozen.len(), 2);
+    /// assert_eq!(frozen.get(1), Some("Two"));
+    /// ```
+    #[must_use]
+    pub fn freeze(self) -> FrozenCompactStrings {
+        self.into()
+    }
+
+    /// Copies the current contents into a [`FrozenCompactStrings`] behind an [`Arc`], which can be cloned
+    /// cheaply and sent to other threads while the [`CompactStrings`] keeps being modified.
+    ///
+    /// Bytes left behind by removed elements are not copied.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// use std::{sync::Arc, thread};
+    ///
+    /// let mut cmpstrs = CompactStrings::from(["One", "Two"]);
+    /// let snapshot = cmpstrs.snapshot();
+    ///
+    /// let reader = {
+    ///     let snapshot = Arc::clone(&snapshot);
+    ///     thread::spawn(move || snapshot.iter().map(str::len).sum::<usize>())
+    /// };
+    /// cmpstrs.push("Three");
+    ///
+    /// assert_eq!(reader.join().unwrap(), 6);
+    /// assert_eq!(snapshot.len(), 2);
+    /// assert_eq!(cmpstrs.len(), 3);
+    /// ```
+    #[must_use]
+    pub fn snapshot(&self) -> Arc<FrozenCompactStrings> {
+        Arc::new(self.clone().freeze())
+    }
+}
+
+impl Debug for FrozenCompactStrings {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl Index<usize> for FrozenCompactStrings {
+    type Output = str;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+
+impl<'a> IntoIterator for &'a FrozenCompactStrings {
+    type Item = &'a str;
+
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<S> FromIterator<S> for FrozenCompactStrings
+where
+    S: Deref<Target = str>,
+{
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        CompactStrings::from_iter(iter).into()
+    }
+}
+
+impl<S, I> From<I> for FrozenCompactStrings
+where
+    S: Deref<Target = str>,
+    I: IntoIterator<Item = S>,
+{
+    #[inline]
+    fn from(value: I) -> Self {
+        FromIterator::from_iter(value)
+    }
+}
+
+impl From<CompactStrings> for FrozenCompactStrings {
+    fn from(value: CompactStrings) -> Self {
+        Self(value.0.into())
+    }
+}
+
+impl From<FrozenCompactStrings> for FrozenCompactBytestrings {
+    fn from(value: FrozenCompactStrings) -> Self {
+        value.0
+    }
+}
+
+impl From<FrozenCompactStrings> for CompactStrings {
+    fn from(value: FrozenCompactStrings) -> Self {
+        Self(value.0.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::CompactStrings;
+
+    #[test]
+    fn freeze_and_thaw() {
+        let mut cmpstrs = CompactStrings::with_capacity(64, 8);
+        cmpstrs.push("One");
+        cmpstrs.push("Two");
+        cmpstrs.push("Three");
+        cmpstrs.ignore(1);
+
+        let frozen = cmpstrs.clone().freeze();
+        assert!(frozen.iter().eq(["One", "Three"]));
+
+        let thawed = CompactStrings::from(frozen);
+        assert_eq!(thawed, cmpstrs);
+    }
+}
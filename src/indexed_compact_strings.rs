@@ -0,0 +1,660 @@
+use core::{
+    fmt::Debug,
+    hash::{BuildHasher, Hash, Hasher},
+    ops::{Deref, Index},
+};
+use std::collections::hash_map::RandomState;
+
+use alloc::{vec, vec::Vec};
+
+use crate::{compact_strings::Iter, CompactStrings};
+
+/// Marks a slot of the hash table that holds no index.
+const EMPTY: usize = usize::MAX;
+
+/// A slot of the hash table, holding the index of the first occurrence of a distinct string and
+/// the number of times that string occurs.
+#[derive(Clone, Copy)]
+struct Slot {
+    first: usize,
+    count: usize,
+}
+
+impl Slot {
+    const EMPTY: Self = Self {
+        first: EMPTY,
+        count: 0,
+    };
+
+    #[inline]
+    const fn is_empty(self) -> bool {
+        self.first == EMPTY
+    }
+}
+
+/// A [`CompactStrings`] paired with a hash table of its indices, making [`contains`] and
+/// [`position`] take *O*(1) expected time.
+///
+/// The strings stay packed in the [`CompactStrings`], and the hash table only stores one slot per
+/// distinct string, holding the index of its first occurrence and its number of occurrences, so
+/// duplicates never lengthen a lookup. Both are kept in sync by [`push`], [`remove`] and
+/// [`clear`].
+///
+/// [`contains`]: IndexedCompactStrings::contains
+/// [`position`]: IndexedCompactStrings::position
+/// [`push`]: IndexedCompactStrings::push
+/// [`remove`]: IndexedCompactStrings::remove
+/// [`clear`]: IndexedCompactStrings::clear
+///
+/// # Examples
+/// ```
+/// # use compact_strings::CompactStrings;
+/// let cmpstrs = CompactStrings::from(["One", "Two"]);
+///
+/// let mut indexed = cmpstrs.build_index();
+/// indexed.push("Three");
+///
+/// assert_eq!(indexed.position("Three"), Some(2));
+/// assert!(indexed.contains("One"));
+/// assert!(!indexed.contains("Four"));
+/// ```
+pub struct IndexedCompactStrings<S = RandomState> {
+    strings: CompactStrings,
+    slots: Vec<Slot>,
+    distinct: usize,
+    hash_builder: S,
+}
+
+impl CompactStrings {
+    /// Builds a hash table over the strings, returning an [`IndexedCompactStrings`] that can look
+    /// strings up in *O*(1) expected time.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let indexed = CompactStrings::from(["One", "Two"]).build_index();
+    ///
+    /// assert_eq!(indexed.position("Two"), Some(1));
+    /// ```
+    #[must_use]
+    pub fn build_index(self) -> IndexedCompactStrings {
+        self.build_index_with_hasher(RandomState::new())
+    }
+
+    /// Builds a hash table over the strings using `hash_builder` to hash them, returning an
+    /// [`IndexedCompactStrings`] that can look strings up in *O*(1) expected time.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let indexed = CompactStrings::from(["One", "Two"]).build_index_with_hasher(RandomState::new());
+    ///
+    /// assert_eq!(indexed.position("Two"), Some(1));
+    /// ```
+    #[must_use]
+    pub fn build_index_with_hasher<S>(self, hash_builder: S) -> IndexedCompactStrings<S>
+    where
+        S: BuildHasher,
+    {
+        let mut indexed = IndexedCompactStrings {
+            strings: self,
+            slots: Vec::new(),
+            distinct: 0,
+            hash_builder,
+        };
+        for index in 0..indexed.strings.len() {
+            indexed.reserve_slot();
+            indexed.insert_index(index);
+        }
+        indexed
+    }
+
+    /// Returns each distinct string together with the number of times it occurs, in the order of
+    /// their first occurrences.
+    ///
+    /// The strings are counted with a hash table of indices, so the returned strings borrow from
+    /// the [`CompactStrings`] instead of being cloned.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let cmpstrs = CompactStrings::from(["One", "Two", "One", "Three", "One"]);
+    ///
+    /// assert_eq!(cmpstrs.counts(), [("One", 3), ("Two", 1), ("Three", 1)]);
+    /// ```
+    #[must_use]
+    pub fn counts(&self) -> Vec<(&str, usize)> {
+        count_occurrences(self, &RandomState::new())
+    }
+}
+
+impl IndexedCompactStrings {
+    /// Constructs a new, empty [`IndexedCompactStrings`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::IndexedCompactStrings;
+    /// let indexed = IndexedCompactStrings::new();
+    ///
+    /// assert!(indexed.is_empty());
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<S> IndexedCompactStrings<S>
+where
+    S: BuildHasher,
+{
+    /// Constructs a new, empty [`IndexedCompactStrings`] which will use `hash_builder` to hash the
+    /// strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::IndexedCompactStrings;
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let mut indexed = IndexedCompactStrings::with_hasher(RandomState::new());
+    /// indexed.push("One");
+    ///
+    /// assert_eq!(indexed.position("One"), Some(0));
+    /// ```
+    #[must_use]
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self {
+            strings: CompactStrings::new(),
+            slots: Vec::new(),
+            distinct: 0,
+            hash_builder,
+        }
+    }
+
+    /// Appends a string to the back of the [`IndexedCompactStrings`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::IndexedCompactStrings;
+    /// let mut indexed = IndexedCompactStrings::new();
+    /// indexed.push("One");
+    /// indexed.push("Two");
+    ///
+    /// assert_eq!(indexed.get(1), Some("Two"));
+    /// assert_eq!(indexed.position("Two"), Some(1));
+    /// ```
+    pub fn push<T>(&mut self, string: T)
+    where
+        T: Deref<Target = str>,
+    {
+        self.reserve_slot();
+        self.strings.push(string);
+        self.insert_index(self.strings.len() - 1);
+    }
+
+    /// Removes the string at the specified index, shifting the strings after it to the left.
+    ///
+    /// Note: Like [`CompactStrings::remove`], this has a worst-case performance of *O*(*n*), as
+    /// the indices after the removed string have to be updated, which means visiting every slot of
+    /// the hash table. Removing the first occurrence of a string that occurs again also searches
+    /// the strings after it for the next occurrence.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let mut indexed = CompactStrings::from(["One", "Two", "Three"]).build_index();
+    /// indexed.remove(0);
+    ///
+    /// assert_eq!(indexed.position("One"), None);
+    /// assert_eq!(indexed.position("Three"), Some(1));
+    /// ```
+    #[track_caller]
+    pub fn remove(&mut self, index: usize) {
+        #[cold]
+        #[inline(never)]
+        #[track_caller]
+        fn assert_failed(index: usize, len: usize) -> ! {
+            panic!("removal index (is {index}) should be < len (is {len})");
+        }
+
+        let len = self.strings.len();
+        if index >= len {
+            assert_failed(index, len);
+        }
+
+        let found = self
+            .find(&self.strings[index])
+            .expect("every string should have a slot");
+        if self.slots[found].count == 1 {
+            self.remove_slot(found);
+        } else {
+            self.slots[found].count -= 1;
+            if self.slots[found].first == index {
+                let string = &self.strings[index];
+                self.slots[found].first = (index + 1..len)
+                    .find(|&next| &self.strings[next] == string)
+                    .expect("a string counted twice should occur again");
+            }
+        }
+
+        self.strings.remove(index);
+        for slot in &mut self.slots {
+            if !slot.is_empty() && slot.first > index {
+                slot.first -= 1;
+            }
+        }
+    }
+
+    /// Clears the [`IndexedCompactStrings`], removing all strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let mut indexed = CompactStrings::from(["One", "Two"]).build_index();
+    /// indexed.clear();
+    ///
+    /// assert!(indexed.is_empty());
+    /// assert!(!indexed.contains("One"));
+    /// ```
+    pub fn clear(&mut self) {
+        self.strings.clear();
+        self.slots.fill(Slot::EMPTY);
+        self.distinct = 0;
+    }
+
+    /// Returns the index of the first string equal to `string`, in *O*(1) expected time.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let indexed = CompactStrings::from(["One", "Two", "One"]).build_index();
+    ///
+    /// assert_eq!(indexed.position("One"), Some(0));
+    /// assert_eq!(indexed.position("Three"), None);
+    /// ```
+    #[must_use]
+    pub fn position(&self, string: &str) -> Option<usize> {
+        if self.slots.is_empty() {
+            return None;
+        }
+
+        self.find(string).ok().map(|slot| self.slots[slot].first)
+    }
+
+    /// Returns true if any string is equal to `string`, in *O*(1) expected time.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let indexed = CompactStrings::from(["One", "Two"]).build_index();
+    ///
+    /// assert!(indexed.contains("One"));
+    /// assert!(!indexed.contains("Three"));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, string: &str) -> bool {
+        self.position(string).is_some()
+    }
+
+    /// Returns each distinct string together with the number of times it occurs, in the order of
+    /// their first occurrences.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let mut indexed = CompactStrings::from(["One", "Two"]).build_index();
+    /// indexed.push("One");
+    ///
+    /// assert_eq!(indexed.counts(), [("One", 2), ("Two", 1)]);
+    /// ```
+    #[must_use]
+    pub fn counts(&self) -> Vec<(&str, usize)> {
+        let mut slots: Vec<Slot> = self
+            .slots
+            .iter()
+            .copied()
+            .filter(|slot| !slot.is_empty())
+            .collect();
+        slots.sort_unstable_by_key(|slot| slot.first);
+        slots
+            .into_iter()
+            .map(|slot| (&self.strings[slot.first], slot.count))
+            .collect()
+    }
+
+    /// Hashes `string` with the hasher of the [`IndexedCompactStrings`].
+    fn hash(&self, string: &str) -> usize {
+        hash_str(&self.hash_builder, string)
+    }
+
+    /// Probes the table for `string`, returning the slot holding it, or the empty slot it would
+    /// be inserted into. The table must not be empty.
+    fn find(&self, string: &str) -> Result<usize, usize> {
+        let mask = self.slots.len() - 1;
+        let mut slot = self.hash(string) & mask;
+        while !self.slots[slot].is_empty() {
+            if &self.strings[self.slots[slot].first] == string {
+                return Ok(slot);
+            }
+            slot = (slot + 1) & mask;
+        }
+
+        Err(slot)
+    }
+
+    /// Counts the string at `index`, which must come after every other occurrence of it, adding a
+    /// slot for it if it is the first.
+    fn insert_index(&mut self, index: usize) {
+        match self.find(&self.strings[index]) {
+            Ok(slot) => self.slots[slot].count += 1,
+            Err(slot) => {
+                self.slots[slot] = Slot {
+                    first: index,
+                    count: 1,
+                };
+                self.distinct += 1;
+            }
+        }
+    }
+
+    /// Empties the slot at `hole`, shifting back the slots after it so that every string stays
+    /// reachable from the slot it hashes to.
+    fn remove_slot(&mut self, mut hole: usize) {
+        let mask = self.slots.len() - 1;
+        let mut slot = (hole + 1) & mask;
+        while !self.slots[slot].is_empty() {
+            let moved = self.slots[slot];
+            let ideal = self.hash(&self.strings[moved.first]) & mask;
+            // The index can fill the hole unless the slot it hashes to lies after the hole.
+            if slot.wrapping_sub(ideal) & mask >= slot.wrapping_sub(hole) & mask {
+                self.slots[hole] = moved;
+                hole = slot;
+            }
+            slot = (slot + 1) & mask;
+        }
+
+        self.slots[hole] = Slot::EMPTY;
+        self.distinct -= 1;
+    }
+
+    /// Grows the table if it could not hold one more distinct string while staying at most half
+    /// full, moving every slot into a larger table.
+    fn reserve_slot(&mut self) {
+        let len = table_len(self.distinct + 1);
+        if len <= self.slots.len() {
+            return;
+        }
+
+        let old = core::mem::replace(&mut self.slots, vec![Slot::EMPTY; len]);
+        for slot in old.into_iter().filter(|slot| !slot.is_empty()) {
+            let empty = self
+                .find(&self.strings[slot.first])
+                .expect_err("distinct strings should not share a slot");
+            self.slots[empty] = slot;
+        }
+    }
+}
+
+impl<S> IndexedCompactStrings<S> {
+    /// Returns a reference to the string stored in the [`IndexedCompactStrings`] at that position.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let indexed = CompactStrings::from(["One", "Two"]).build_index();
+    ///
+    /// assert_eq!(indexed.get(0), Some("One"));
+    /// assert_eq!(indexed.get(2), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.strings.get(index)
+    }
+
+    /// Returns the number of strings in the [`IndexedCompactStrings`], also referred to as its 'length'.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let indexed = CompactStrings::from(["One", "Two"]).build_index();
+    ///
+    /// assert_eq!(indexed.len(), 2);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns true if the [`IndexedCompactStrings`] contains no strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::IndexedCompactStrings;
+    /// let indexed = IndexedCompactStrings::new();
+    ///
+    /// assert!(indexed.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Returns an iterator over the strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let indexed = CompactStrings::from(["One", "Two"]).build_index();
+    ///
+    /// assert!(indexed.iter().eq(["One", "Two"]));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn iter(&self) -> Iter<'_> {
+        self.strings.iter()
+    }
+
+    /// Returns a reference to the underlying [`CompactStrings`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let indexed = CompactStrings::from(["One", "Two"]).build_index();
+    ///
+    /// assert_eq!(indexed.as_compact_strings().data_len(), 6);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn as_compact_strings(&self) -> &CompactStrings {
+        &self.strings
+    }
+
+    /// Drops the hash table, returning the underlying [`CompactStrings`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let indexed = CompactStrings::from(["One", "Two"]).build_index();
+    ///
+    /// assert_eq!(indexed.into_inner(), CompactStrings::from(["One", "Two"]));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> CompactStrings {
+        self.strings
+    }
+}
+
+/// Returns the number of slots needed to hold `len` distinct strings, keeping the table at most
+/// half full.
+fn table_len(len: usize) -> usize {
+    (len * 2).next_power_of_two().max(8)
+}
+
+/// Hashes `string` with `hash_builder`, truncating the hash to fit in a [`usize`].
+#[allow(clippy::cast_possible_truncation)]
+fn hash_str<S: BuildHasher>(hash_builder: &S, string: &str) -> usize {
+    let mut hasher = hash_builder.build_hasher();
+    string.hash(&mut hasher);
+    hasher.finish() as usize
+}
+
+/// Counts the occurrences of each distinct string in `strings` with a hash table whose slots
+/// hold indices into the returned vector.
+fn count_occurrences<'a, S: BuildHasher>(
+    strings: &'a CompactStrings,
+    hash_builder: &S,
+) -> Vec<(&'a str, usize)> {
+    let mut slots = vec![EMPTY; table_len(strings.len())];
+    let mask = slots.len() - 1;
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for string in strings {
+        let mut slot = hash_str(hash_builder, string) & mask;
+        loop {
+            match slots[slot] {
+                EMPTY => {
+                    slots[slot] = counts.len();
+                    counts.push((string, 1));
+                    break;
+                }
+                index if counts[index].0 == string => {
+                    counts[index].1 += 1;
+                    break;
+                }
+                _ => slot = (slot + 1) & mask,
+            }
+        }
+    }
+
+    counts
+}
+
+impl Default for IndexedCompactStrings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Debug for IndexedCompactStrings<S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<S> Index<usize> for IndexedCompactStrings<S> {
+    type Output = str;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+
+impl<S, T> Extend<T> for IndexedCompactStrings<S>
+where
+    S: BuildHasher,
+    T: Deref<Target = str>,
+{
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for s in iter {
+            self.push(s);
+        }
+    }
+}
+
+impl<'a, S> IntoIterator for &'a IndexedCompactStrings<S> {
+    type Item = &'a str;
+
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl From<CompactStrings> for IndexedCompactStrings {
+    #[inline]
+    fn from(value: CompactStrings) -> Self {
+        value.build_index()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+
+    use crate::{CompactStrings, IndexedCompactStrings};
+
+    #[test]
+    fn stays_in_sync() {
+        let mut indexed = IndexedCompactStrings::new();
+        for i in 0..100 {
+            indexed.push(format!("{}", i % 40).as_str());
+        }
+
+        for i in (0..100).step_by(3).rev() {
+            indexed.remove(i);
+        }
+
+        let cmpstrs = indexed.as_compact_strings().clone();
+        assert_eq!(indexed.counts(), cmpstrs.counts());
+        for i in 0..50 {
+            let string = format!("{i}");
+            assert_eq!(
+                indexed.position(&string),
+                cmpstrs.iter().position(|s| s == string),
+            );
+        }
+    }
+
+    #[test]
+    fn duplicates_share_a_slot() {
+        let mut indexed: IndexedCompactStrings = core::iter::repeat("a")
+            .take(10_000)
+            .collect::<CompactStrings>()
+            .build_index();
+        indexed.push("b");
+        indexed.push("a");
+        assert_eq!(indexed.distinct, 2);
+        assert_eq!(indexed.slots.len(), 8);
+        assert_eq!(indexed.position("b"), Some(10_000));
+
+        indexed.remove(0);
+        assert_eq!(indexed.position("a"), Some(0));
+        assert_eq!(indexed.position("b"), Some(9_999));
+        indexed.remove(9_999);
+        assert_eq!(indexed.position("b"), None);
+        assert_eq!(indexed.counts(), [("a", 10_000)]);
+    }
+
+    #[test]
+    fn built_from_compact_strings() {
+        let indexed = CompactStrings::from(["a", "b", "a", ""]).build_index();
+
+        assert_eq!(indexed.position("a"), Some(0));
+        assert_eq!(indexed.position(""), Some(3));
+        assert_eq!(indexed.position("c"), None);
+    }
+
+    #[test]
+    fn counts_match_naive_count() {
+        let cmpstrs: CompactStrings = (0..200).map(|i| format!("{}", i % 37)).collect();
+
+        let counts = cmpstrs.counts();
+        assert_eq!(counts.len(), 37);
+        for (string, count) in counts {
+            assert_eq!(count, cmpstrs.iter().filter(|&s| s == string).count());
+        }
+    }
+}
@@ -0,0 +1,888 @@
+use std::io::{self, Read, Write};
+
+use alloc::vec::Vec;
+
+use crate::{
+    layout::{self, LayoutError, START_LEN},
+    metadata::Metadata,
+    search, CompactBytestrings, CompactStrings, FixedCompactBytestrings, FixedCompactStrings,
+};
+
+/// Upper bound on how many starts are preallocated from an untrusted header.
+const MAX_PREALLOCATED_STARTS: usize = 4096;
+
+/// Writes `count` elements in the binary layout documented on [`CompactBytestrings::to_bytes`],
+/// without first encoding them into a buffer.
+pub(crate) fn write_layout<'a, W, F, I>(mut writer: W, count: usize, iter: F) -> io::Result<()>
+where
+    W: Write,
+    F: Fn() -> I,
+    I: Iterator<Item = &'a [u8]>,
+{
+    let data_len: usize = iter().map(<[u8]>::len).sum();
+    writer.write_all(&(count as u64).to_le_bytes())?;
+    writer.write_all(&(data_len as u64).to_le_bytes())?;
+
+    let mut start = 0;
+    for bytes in iter() {
+        writer.write_all(&(start as u64).to_le_bytes())?;
+        start += bytes.len();
+    }
+
+    for bytes in iter() {
+        writer.write_all(bytes)?;
+    }
+
+    Ok(())
+}
+
+/// Reads the binary layout documented on [`CompactBytestrings::to_bytes`], checking that the
+/// starts begin at zero and are in order and in bounds.
+fn read_layout<R: Read>(mut reader: R) -> io::Result<(Vec<usize>, Vec<u8>)> {
+    let count = read_u64(&mut reader)?.ok_or_else(|| invalid_data(LayoutError::LengthMismatch))?;
+    let data_len =
+        read_u64(&mut reader)?.ok_or_else(|| invalid_data(LayoutError::LengthMismatch))?;
+
+    let mut starts = Vec::with_capacity(count.min(MAX_PREALLOCATED_STARTS));
+    for _ in 0..count {
+        starts.push(read_u64(&mut reader)?.ok_or_else(|| invalid_data(LayoutError::InvalidStart))?);
+    }
+    layout::check_starts(starts.iter().copied().map(Some), data_len).map_err(invalid_data)?;
+
+    let mut data = Vec::new();
+    reader.take(data_len as u64).read_to_end(&mut data)?;
+    if data.len() != data_len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+
+    Ok((starts, data))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> io::Result<Option<usize>> {
+    let mut buf = [0; START_LEN];
+    reader.read_exact(&mut buf)?;
+    Ok(layout::read_u64(&buf))
+}
+
+pub(crate) fn invalid_data(err: LayoutError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+/// Writes each element as its length in an unsigned LEB128 varint followed by its bytes.
+fn write_length_prefixed<'a, W, I>(mut writer: W, iter: I) -> io::Result<()>
+where
+    W: Write,
+    I: Iterator<Item = &'a [u8]>,
+{
+    for bytes in iter {
+        write_varint(&mut writer, bytes.len() as u64)?;
+        writer.write_all(bytes)?;
+    }
+
+    Ok(())
+}
+
+/// Reads length-prefixed elements until the end of `reader`, returning their starts and data.
+fn read_length_prefixed<R: Read>(mut reader: R) -> io::Result<(Vec<usize>, Vec<u8>)> {
+    let mut starts = Vec::new();
+    let mut data = Vec::new();
+    while let Some(len) = read_varint(&mut reader)? {
+        starts.push(data.len());
+        // Reading through `take` only grows the data vector as bytes arrive, so a corrupt length
+        // cannot make it preallocate an arbitrary amount of memory.
+        let read = (&mut reader).take(len).read_to_end(&mut data)?;
+        if read as u64 != len {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+    }
+
+    Ok((starts, data))
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn write_varint<W: Write>(writer: &mut W, mut value: u64) -> io::Result<()> {
+    let mut buf = [0; 10];
+    let mut len = 0;
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            buf[len] = byte;
+            len += 1;
+            break;
+        }
+
+        buf[len] = byte | 0x80;
+        len += 1;
+    }
+
+    writer.write_all(&buf[..len])
+}
+
+/// Reads an unsigned LEB128 varint, returning [`None`] if `reader` ends before its first byte.
+fn read_varint<R: Read>(reader: &mut R) -> io::Result<Option<u64>> {
+    let mut value = 0;
+    let mut shift = 0;
+    loop {
+        let mut byte = [0];
+        if let Err(err) = reader.read_exact(&mut byte) {
+            return if shift == 0 && err.kind() == io::ErrorKind::UnexpectedEof {
+                Ok(None)
+            } else {
+                Err(err)
+            };
+        }
+
+        let bits = u64::from(byte[0] & 0x7f);
+        if shift > 63 || (shift == 63 && bits > 1) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "length does not fit in 64 bits",
+            ));
+        }
+
+        value |= bits << shift;
+        if byte[0] & 0x80 == 0 {
+            return Ok(Some(value));
+        }
+        shift += 7;
+    }
+}
+
+/// Writes each element followed by `delimiter`, failing if an element contains `delimiter`.
+fn write_delimited<'a, W, I>(mut writer: W, iter: I, delimiter: u8) -> io::Result<()>
+where
+    W: Write,
+    I: Iterator<Item = &'a [u8]>,
+{
+    for bytes in iter {
+        if search::find_byte(delimiter, bytes).is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "element contains the delimiter",
+            ));
+        }
+
+        writer.write_all(bytes)?;
+        writer.write_all(&[delimiter])?;
+    }
+
+    Ok(())
+}
+
+/// Reads all of `reader` and splits it with [`CompactBytestrings::from_delimited`], then compacts
+/// the data vector in place to drop the delimiters.
+fn read_delimited<R: Read>(mut reader: R, delimiter: u8) -> io::Result<CompactBytestrings> {
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf)?;
+
+    let mut cmpbytes = CompactBytestrings::from_delimited(buf, delimiter);
+    cmpbytes.compact();
+    Ok(cmpbytes)
+}
+
+pub(crate) fn to_meta(starts: &[usize], data_len: usize) -> Vec<Metadata> {
+    let ends = starts.iter().skip(1).copied().chain(Some(data_len));
+    starts
+        .iter()
+        .zip(ends)
+        .map(|(&start, end)| Metadata::new(start, end - start))
+        .collect()
+}
+
+impl CompactBytestrings {
+    /// Writes the [`CompactBytestrings`] to `writer` in the binary layout documented on
+    /// [`CompactBytestrings::to_bytes`], which can be read back with
+    /// [`CompactBytestrings::read_from`].
+    ///
+    /// This issues many small writes, so `writer` should usually be buffered.
+    ///
+    /// # Errors
+    /// Returns any error encountered while writing.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::new();
+    /// cmpbytes.push(b"One");
+    /// cmpbytes.push(b"Two");
+    ///
+    /// let mut buf = Vec::new();
+    /// cmpbytes.write_to(&mut buf).unwrap();
+    ///
+    /// assert_eq!(CompactBytestrings::read_from(buf.as_slice()).unwrap(), cmpbytes);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn write_to<W: Write>(&self, writer: W) -> io::Result<()> {
+        write_layout(writer, self.len(), || self.iter())
+    }
+
+    /// Reads a [`CompactBytestrings`] from `reader` in the layout written by
+    /// [`CompactBytestrings::write_to`].
+    ///
+    /// # Errors
+    /// Returns any error encountered while reading, and an error of kind
+    /// [`io::ErrorKind::InvalidData`] if the starting indices are out of order or out of bounds.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([b"One", b"Two"]);
+    ///
+    /// let mut buf = Vec::new();
+    /// cmpbytes.write_to(&mut buf).unwrap();
+    ///
+    /// let read = CompactBytestrings::read_from(buf.as_slice()).unwrap();
+    /// assert_eq!(read.get(0), Some(b"One".as_slice()));
+    /// assert_eq!(read.get(1), Some(b"Two".as_slice()));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn read_from<R: Read>(reader: R) -> io::Result<Self> {
+        let (starts, data) = read_layout(reader)?;
+        let meta = to_meta(&starts, data.len());
+        Ok(Self { data, meta })
+    }
+
+    /// Writes the bytestrings to `writer` one after another, each as its length in bytes followed
+    /// by its bytes, which can be read back with [`CompactBytestrings::read_length_prefixed`].
+    ///
+    /// The length is an unsigned LEB128 varint, as used by protobuf for length-delimited fields.
+    /// Unlike [`write_to`](CompactBytestrings::write_to), no header is written up front, so the
+    /// output can be produced and consumed as a stream.
+    ///
+    /// This issues many small writes, so `writer` should usually be buffered.
+    ///
+    /// # Errors
+    /// Returns any error encountered while writing.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([b"One", b"Two"]);
+    ///
+    /// let mut buf = Vec::new();
+    /// cmpbytes.write_length_prefixed(&mut buf).unwrap();
+    /// assert_eq!(buf, b"\x03One\x03Two");
+    ///
+    /// assert_eq!(CompactBytestrings::read_length_prefixed(buf.as_slice()).unwrap(), cmpbytes);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn write_length_prefixed<W: Write>(&self, writer: W) -> io::Result<()> {
+        write_length_prefixed(writer, self.iter())
+    }
+
+    /// Reads bytestrings from `reader` in the format written by
+    /// [`CompactBytestrings::write_length_prefixed`], until `reader` ends.
+    ///
+    /// # Errors
+    /// Returns any error encountered while reading, an error of kind
+    /// [`io::ErrorKind::UnexpectedEof`] if `reader` ends in the middle of a bytestring, and an error
+    /// of kind [`io::ErrorKind::InvalidData`] if a length does not fit in 64 bits.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::read_length_prefixed(&b"\x03One\x00\x03Two"[..]).unwrap();
+    ///
+    /// assert_eq!(cmpbytes.len(), 3);
+    /// assert_eq!(cmpbytes.get(1).map(|x| x.len()), Some(0));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn read_length_prefixed<R: Read>(reader: R) -> io::Result<Self> {
+        let (starts, data) = read_length_prefixed(reader)?;
+        let meta = to_meta(&starts, data.len());
+        Ok(Self { data, meta })
+    }
+
+    /// Writes the bytestrings to `writer` one after another, each followed by `delimiter`, which
+    /// can be read back with [`CompactBytestrings::read_delimited`].
+    ///
+    /// With `b'\n'` or `b'\0'` as the delimiter, this is the format read and written by Unix
+    /// tools such as `sort`, `uniq` and `xargs -0`.
+    ///
+    /// This issues many small writes, so `writer` should usually be buffered.
+    ///
+    /// # Errors
+    /// Returns any error encountered while writing, and an error of kind
+    /// [`io::ErrorKind::InvalidInput`] if a bytestring contains `delimiter`, in which case the
+    /// bytestrings before it have already been written.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([b"One", b"Two"]);
+    ///
+    /// let mut buf = Vec::new();
+    /// cmpbytes.write_delimited(&mut buf, b'\n').unwrap();
+    /// assert_eq!(buf, b"One\nTwo\n");
+    ///
+    /// assert_eq!(CompactBytestrings::read_delimited(buf.as_slice(), b'\n').unwrap(), cmpbytes);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn write_delimited<W: Write>(&self, writer: W, delimiter: u8) -> io::Result<()> {
+        write_delimited(writer, self.iter(), delimiter)
+    }
+
+    /// Reads bytestrings from `reader` that are each followed by `delimiter`, as written by
+    /// [`CompactBytestrings::write_delimited`].
+    ///
+    /// The last bytestring does not need to be followed by `delimiter`, and reading nothing
+    /// gives an empty [`CompactBytestrings`]. The whole of `reader` is read into the data vector
+    /// and split like [`CompactBytestrings::from_delimited`], after which the delimiters are
+    /// removed from it in a single pass.
+    ///
+    /// # Errors
+    /// Returns any error encountered while reading.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::read_delimited(&b"One\0\0Two"[..], 0).unwrap();
+    ///
+    /// assert_eq!(cmpbytes.len(), 3);
+    /// assert_eq!(cmpbytes.data_len(), 6);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn read_delimited<R: Read>(reader: R, delimiter: u8) -> io::Result<Self> {
+        read_delimited(reader, delimiter)
+    }
+}
+
+impl CompactStrings {
+    /// Writes the [`CompactStrings`] to `writer` in the binary layout documented on
+    /// [`CompactBytestrings::to_bytes`].
+    ///
+    /// This issues many small writes, so `writer` should usually be buffered.
+    ///
+    /// # Errors
+    /// Returns any error encountered while writing.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let mut cmpstrs = CompactStrings::new();
+    /// cmpstrs.push("One");
+    /// cmpstrs.push("Two");
+    ///
+    /// let mut buf = Vec::new();
+    /// cmpstrs.write_to(&mut buf).unwrap();
+    ///
+    /// assert_eq!(CompactStrings::read_from(buf.as_slice()).unwrap(), cmpstrs);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn write_to<W: Write>(&self, writer: W) -> io::Result<()> {
+        self.0.write_to(writer)
+    }
+
+    /// Reads a [`CompactStrings`] from `reader` in the layout written by
+    /// [`CompactStrings::write_to`].
+    ///
+    /// # Errors
+    /// Returns any error encountered while reading, and an error of kind
+    /// [`io::ErrorKind::InvalidData`] if the starting indices are out of order or out of bounds,
+    /// or if any string is not valid UTF-8.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let cmpstrs = CompactStrings::from(["One", "Two"]);
+    ///
+    /// let mut buf = Vec::new();
+    /// cmpstrs.write_to(&mut buf).unwrap();
+    ///
+    /// let read = CompactStrings::read_from(buf.as_slice()).unwrap();
+    /// assert_eq!(read.get(0), Some("One"));
+    /// assert_eq!(read.get(1), Some("Two"));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn read_from<R: Read>(reader: R) -> io::Result<Self> {
+        let (starts, data) = read_layout(reader)?;
+        layout::check_utf8(starts.iter().copied(), &data).map_err(invalid_data)?;
+        let meta = to_meta(&starts, data.len());
+        Ok(Self(CompactBytestrings { data, meta }))
+    }
+
+    /// Writes the strings to `writer` one after another, each as its length in bytes followed
+    /// by its bytes, in the format documented on
+    /// [`CompactBytestrings::write_length_prefixed`].
+    ///
+    /// This issues many small writes, so `writer` should usually be buffered.
+    ///
+    /// # Errors
+    /// Returns any error encountered while writing.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let cmpstrs = CompactStrings::from(["One", "Two"]);
+    ///
+    /// let mut buf = Vec::new();
+    /// cmpstrs.write_length_prefixed(&mut buf).unwrap();
+    /// assert_eq!(buf, b"\x03One\x03Two");
+    ///
+    /// assert_eq!(CompactStrings::read_length_prefixed(buf.as_slice()).unwrap(), cmpstrs);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn write_length_prefixed<W: Write>(&self, writer: W) -> io::Result<()> {
+        self.0.write_length_prefixed(writer)
+    }
+
+    /// Reads strings from `reader` in the format written by
+    /// [`CompactStrings::write_length_prefixed`], until `reader` ends.
+    ///
+    /// # Errors
+    /// Returns any error encountered while reading, an error of kind
+    /// [`io::ErrorKind::UnexpectedEof`] if `reader` ends in the middle of a string, and an error
+    /// of kind [`io::ErrorKind::InvalidData`] if a length does not fit in 64 bits, or if any string is not valid UTF-8.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let cmpstrs = CompactStrings::read_length_prefixed(&b"\x03One\x00\x03Two"[..]).unwrap();
+    ///
+    /// assert_eq!(cmpstrs.len(), 3);
+    /// assert_eq!(cmpstrs.get(1).map(|x| x.len()), Some(0));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn read_length_prefixed<R: Read>(reader: R) -> io::Result<Self> {
+        let (starts, data) = read_length_prefixed(reader)?;
+        layout::check_utf8(starts.iter().copied(), &data).map_err(invalid_data)?;
+        let meta = to_meta(&starts, data.len());
+        Ok(Self(CompactBytestrings { data, meta }))
+    }
+
+    /// Writes the strings to `writer` one after another, each followed by `delimiter`, in the
+    /// format documented on [`CompactBytestrings::write_delimited`].
+    ///
+    /// This issues many small writes, so `writer` should usually be buffered.
+    ///
+    /// # Errors
+    /// Returns any error encountered while writing, and an error of kind
+    /// [`io::ErrorKind::InvalidInput`] if a string contains `delimiter`, in which case the
+    /// strings before it have already been written.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let cmpstrs = CompactStrings::from(["One", "Two"]);
+    ///
+    /// let mut buf = Vec::new();
+    /// cmpstrs.write_delimited(&mut buf, b'\n').unwrap();
+    /// assert_eq!(buf, b"One\nTwo\n");
+    ///
+    /// assert_eq!(CompactStrings::read_delimited(buf.as_slice(), b'\n').unwrap(), cmpstrs);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn write_delimited<W: Write>(&self, writer: W, delimiter: u8) -> io::Result<()> {
+        self.0.write_delimited(writer, delimiter)
+    }
+
+    /// Reads strings from `reader` that are each followed by `delimiter`, in the format documented
+    /// on [`CompactBytestrings::read_delimited`].
+    ///
+    /// # Errors
+    /// Returns any error encountered while reading, and an error of kind
+    /// [`io::ErrorKind::InvalidData`] if any string is not valid UTF-8.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let cmpstrs = CompactStrings::read_delimited(&b"One\0\0Two"[..], 0).unwrap();
+    ///
+    /// assert_eq!(cmpstrs.len(), 3);
+    /// assert_eq!(cmpstrs.data_len(), 6);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn read_delimited<R: Read>(reader: R, delimiter: u8) -> io::Result<Self> {
+        let cmpbytes = read_delimited(reader, delimiter)?;
+        layout::check_utf8(cmpbytes.meta.iter().map(|meta| meta.start), &cmpbytes.data)
+            .map_err(invalid_data)?;
+        Ok(Self(cmpbytes))
+    }
+}
+
+impl FixedCompactBytestrings {
+    /// Writes the [`FixedCompactBytestrings`] to `writer` in the binary layout documented on
+    /// [`CompactBytestrings::to_bytes`].
+    ///
+    /// This issues many small writes, so `writer` should usually be buffered.
+    ///
+    /// # Errors
+    /// Returns any error encountered while writing.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let mut cmpbytes = FixedCompactBytestrings::new();
+    /// cmpbytes.push(b"One");
+    /// cmpbytes.push(b"Two");
+    ///
+    /// let mut buf = Vec::new();
+    /// cmpbytes.write_to(&mut buf).unwrap();
+    ///
+    /// assert_eq!(FixedCompactBytestrings::read_from(buf.as_slice()).unwrap(), cmpbytes);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn write_to<W: Write>(&self, writer: W) -> io::Result<()> {
+        write_layout(writer, self.len(), || self.iter())
+    }
+
+    /// Reads a [`FixedCompactBytestrings`] from `reader` in the layout written by
+    /// [`FixedCompactBytestrings::write_to`].
+    ///
+    /// # Errors
+    /// Returns any error encountered while reading, and an error of kind
+    /// [`io::ErrorKind::InvalidData`] if the starting indices are out of order or out of bounds.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let cmpbytes = FixedCompactBytestrings::from([b"One", b"Two"]);
+    ///
+    /// let mut buf = Vec::new();
+    /// cmpbytes.write_to(&mut buf).unwrap();
+    ///
+    /// let read = FixedCompactBytestrings::read_from(buf.as_slice()).unwrap();
+    /// assert_eq!(read.get(0), Some(b"One".as_slice()));
+    /// assert_eq!(read.get(1), Some(b"Two".as_slice()));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn read_from<R: Read>(reader: R) -> io::Result<Self> {
+        let (starts, data) = read_layout(reader)?;
+        Ok(Self { data, starts })
+    }
+
+    /// Writes the bytestrings to `writer` one after another, each as its length in bytes followed
+    /// by its bytes, in the format documented on
+    /// [`CompactBytestrings::write_length_prefixed`].
+    ///
+    /// This issues many small writes, so `writer` should usually be buffered.
+    ///
+    /// # Errors
+    /// Returns any error encountered while writing.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let cmpbytes = FixedCompactBytestrings::from([b"One", b"Two"]);
+    ///
+    /// let mut buf = Vec::new();
+    /// cmpbytes.write_length_prefixed(&mut buf).unwrap();
+    /// assert_eq!(buf, b"\x03One\x03Two");
+    ///
+    /// assert_eq!(FixedCompactBytestrings::read_length_prefixed(buf.as_slice()).unwrap(), cmpbytes);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn write_length_prefixed<W: Write>(&self, writer: W) -> io::Result<()> {
+        write_length_prefixed(writer, self.iter())
+    }
+
+    /// Reads bytestrings from `reader` in the format written by
+    /// [`FixedCompactBytestrings::write_length_prefixed`], until `reader` ends.
+    ///
+    /// # Errors
+    /// Returns any error encountered while reading, an error of kind
+    /// [`io::ErrorKind::UnexpectedEof`] if `reader` ends in the middle of a bytestring, and an error
+    /// of kind [`io::ErrorKind::InvalidData`] if a length does not fit in 64 bits.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let cmpbytes = FixedCompactBytestrings::read_length_prefixed(&b"\x03One\x00\x03Two"[..]).unwrap();
+    ///
+    /// assert_eq!(cmpbytes.len(), 3);
+    /// assert_eq!(cmpbytes.get(1).map(|x| x.len()), Some(0));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn read_length_prefixed<R: Read>(reader: R) -> io::Result<Self> {
+        let (starts, data) = read_length_prefixed(reader)?;
+        Ok(Self { data, starts })
+    }
+
+    /// Writes the bytestrings to `writer` one after another, each followed by `delimiter`, in the
+    /// format documented on [`CompactBytestrings::write_delimited`].
+    ///
+    /// This issues many small writes, so `writer` should usually be buffered.
+    ///
+    /// # Errors
+    /// Returns any error encountered while writing, and an error of kind
+    /// [`io::ErrorKind::InvalidInput`] if a bytestring contains `delimiter`, in which case the
+    /// bytestrings before it have already been written.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let cmpbytes = FixedCompactBytestrings::from([b"One", b"Two"]);
+    ///
+    /// let mut buf = Vec::new();
+    /// cmpbytes.write_delimited(&mut buf, b'\n').unwrap();
+    /// assert_eq!(buf, b"One\nTwo\n");
+    ///
+    /// assert_eq!(FixedCompactBytestrings::read_delimited(buf.as_slice(), b'\n').unwrap(), cmpbytes);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn write_delimited<W: Write>(&self, writer: W, delimiter: u8) -> io::Result<()> {
+        write_delimited(writer, self.iter(), delimiter)
+    }
+
+    /// Reads bytestrings from `reader` that are each followed by `delimiter`, in the format documented
+    /// on [`CompactBytestrings::read_delimited`].
+    ///
+    /// # Errors
+    /// Returns any error encountered while reading.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let cmpbytes = FixedCompactBytestrings::read_delimited(&b"One\0\0Two"[..], 0).unwrap();
+    ///
+    /// assert_eq!(cmpbytes.len(), 3);
+    /// assert_eq!(cmpbytes.data_len(), 6);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn read_delimited<R: Read>(reader: R, delimiter: u8) -> io::Result<Self> {
+        let cmpbytes = read_delimited(reader, delimiter)?;
+        let starts = cmpbytes.meta.iter().map(|meta| meta.start).collect();
+        Ok(Self {
+            data: cmpbytes.data,
+            starts,
+        })
+    }
+}
+
+impl FixedCompactStrings {
+    /// Writes the [`FixedCompactStrings`] to `writer` in the binary layout documented on
+    /// [`CompactBytestrings::to_bytes`].
+    ///
+    /// This issues many small writes, so `writer` should usually be buffered.
+    ///
+    /// # Errors
+    /// Returns any error encountered while writing.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let mut cmpstrs = FixedCompactStrings::new();
+    /// cmpstrs.push("One");
+    /// cmpstrs.push("Two");
+    ///
+    /// let mut buf = Vec::new();
+    /// cmpstrs.write_to(&mut buf).unwrap();
+    ///
+    /// assert_eq!(FixedCompactStrings::read_from(buf.as_slice()).unwrap(), cmpstrs);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn write_to<W: Write>(&self, writer: W) -> io::Result<()> {
+        self.0.write_to(writer)
+    }
+
+    /// Reads a [`FixedCompactStrings`] from `reader` in the layout written by
+    /// [`FixedCompactStrings::write_to`].
+    ///
+    /// # Errors
+    /// Returns any error encountered while reading, and an error of kind
+    /// [`io::ErrorKind::InvalidData`] if the starting indices are out of order or out of bounds,
+    /// or if any string is not valid UTF-8.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["One", "Two"]);
+    ///
+    /// let mut buf = Vec::new();
+    /// cmpstrs.write_to(&mut buf).unwrap();
+    ///
+    /// let read = FixedCompactStrings::read_from(buf.as_slice()).unwrap();
+    /// assert_eq!(read.get(0), Some("One"));
+    /// assert_eq!(read.get(1), Some("Two"));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn read_from<R: Read>(reader: R) -> io::Result<Self> {
+        let (starts, data) = read_layout(reader)?;
+        layout::check_utf8(starts.iter().copied(), &data).map_err(invalid_data)?;
+        Ok(Self(FixedCompactBytestrings { data, starts }))
+    }
+
+    /// Writes the strings to `writer` one after another, each as its length in bytes followed
+    /// by its bytes, in the format documented on
+    /// [`CompactBytestrings::write_length_prefixed`].
+    ///
+    /// This issues many small writes, so `writer` should usually be buffered.
+    ///
+    /// # Errors
+    /// Returns any error encountered while writing.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["One", "Two"]);
+    ///
+    /// let mut buf = Vec::new();
+    /// cmpstrs.write_length_prefixed(&mut buf).unwrap();
+    /// assert_eq!(buf, b"\x03One\x03Two");
+    ///
+    /// assert_eq!(FixedCompactStrings::read_length_prefixed(buf.as_slice()).unwrap(), cmpstrs);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn write_length_prefixed<W: Write>(&self, writer: W) -> io::Result<()> {
+        self.0.write_length_prefixed(writer)
+    }
+
+    /// Reads strings from `reader` in the format written by
+    /// [`FixedCompactStrings::write_length_prefixed`], until `reader` ends.
+    ///
+    /// # Errors
+    /// Returns any error encountered while reading, an error of kind
+    /// [`io::ErrorKind::UnexpectedEof`] if `reader` ends in the middle of a string, and an error
+    /// of kind [`io::ErrorKind::InvalidData`] if a length does not fit in 64 bits, or if any string is not valid UTF-8.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::read_length_prefixed(&b"\x03One\x00\x03Two"[..]).unwrap();
+    ///
+    /// assert_eq!(cmpstrs.len(), 3);
+    /// assert_eq!(cmpstrs.get(1).map(|x| x.len()), Some(0));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn read_length_prefixed<R: Read>(reader: R) -> io::Result<Self> {
+        let (starts, data) = read_length_prefixed(reader)?;
+        layout::check_utf8(starts.iter().copied(), &data).map_err(invalid_data)?;
+        Ok(Self(FixedCompactBytestrings { data, starts }))
+    }
+
+    /// Writes the strings to `writer` one after another, each followed by `delimiter`, in the
+    /// format documented on [`CompactBytestrings::write_delimited`].
+    ///
+    /// This issues many small writes, so `writer` should usually be buffered.
+    ///
+    /// # Errors
+    /// Returns any error encountered while writing, and an error of kind
+    /// [`io::ErrorKind::InvalidInput`] if a string contains `delimiter`, in which case the
+    /// strings before it have already been written.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["One", "Two"]);
+    ///
+    /// let mut buf = Vec::new();
+    /// cmpstrs.write_delimited(&mut buf, b'\n').unwrap();
+    /// assert_eq!(buf, b"One\nTwo\n");
+    ///
+    /// assert_eq!(FixedCompactStrings::read_delimited(buf.as_slice(), b'\n').unwrap(), cmpstrs);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn write_delimited<W: Write>(&self, writer: W, delimiter: u8) -> io::Result<()> {
+        self.0.write_delimited(writer, delimiter)
+    }
+
+    /// Reads strings from `reader` that are each followed by `delimiter`, in the format documented
+    /// on [`CompactBytestrings::read_delimited`].
+    ///
+    /// # Errors
+    /// Returns any error encountered while reading, and an error of kind
+    /// [`io::ErrorKind::InvalidData`] if any string is not valid UTF-8.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::read_delimited(&b"One\0\0Two"[..], 0).unwrap();
+    ///
+    /// assert_eq!(cmpstrs.len(), 3);
+    /// assert_eq!(cmpstrs.data_len(), 6);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn read_delimited<R: Read>(reader: R, delimiter: u8) -> io::Result<Self> {
+        let cmpbytes = FixedCompactBytestrings::read_delimited(reader, delimiter)?;
+        layout::check_utf8(cmpbytes.starts.iter().copied(), &cmpbytes.data)
+            .map_err(invalid_data)?;
+        Ok(Self(cmpbytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::{CompactBytestrings, CompactStrings};
+
+    #[test]
+    fn skips_removed_bytes() {
+        let mut cmpbytes = CompactBytestrings::from([b"One", b"Two", b"Six"]);
+        cmpbytes.remove(1);
+
+        let mut buf = Vec::new();
+        cmpbytes.write_to(&mut buf).unwrap();
+
+        assert_eq!(buf.len(), 8 * 4 + 6);
+        assert_eq!(
+            CompactBytestrings::read_from(buf.as_slice()).unwrap(),
+            cmpbytes
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_data() {
+        let mut buf = Vec::new();
+        CompactBytestrings::from([b"Th\xc3\xa9"])
+            .write_to(&mut buf)
+            .unwrap();
+        buf.truncate(buf.len() - 1);
+        assert!(CompactBytestrings::read_from(buf.as_slice()).is_err());
+
+        let mut buf = Vec::new();
+        CompactBytestrings::from([&b"Th\xc3"[..], b"\xa9"])
+            .write_to(&mut buf)
+            .unwrap();
+        assert!(CompactBytestrings::read_from(buf.as_slice()).is_ok());
+        assert!(CompactStrings::read_from(buf.as_slice()).is_err());
+    }
+
+    #[test]
+    fn length_prefixed_round_trip() {
+        let long = [b'x'; 300];
+        let cmpbytes = CompactBytestrings::from([&b""[..], &long, b"One"]);
+
+        let mut buf = Vec::new();
+        cmpbytes.write_length_prefixed(&mut buf).unwrap();
+        assert_eq!(&buf[..3], b"\x00\xac\x02");
+
+        let read = CompactBytestrings::read_length_prefixed(buf.as_slice()).unwrap();
+        assert_eq!(read, cmpbytes);
+
+        let err = CompactBytestrings::read_length_prefixed(&buf[..buf.len() - 1]).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn rejects_overlong_varint() {
+        let buf = [0xff; 10];
+        let err = CompactBytestrings::read_length_prefixed(&buf[..]).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn delimited_round_trip() {
+        for items in [&[][..], &[""], &["", ""], &["One", "", "Three"]] {
+            let cmpstrs = CompactStrings::from(items.iter().copied());
+
+            let mut buf = Vec::new();
+            cmpstrs.write_delimited(&mut buf, b'\n').unwrap();
+
+            let read = CompactStrings::read_delimited(buf.as_slice(), b'\n').unwrap();
+            assert_eq!(read, cmpstrs);
+            assert_eq!(read.garbage_len(), 0);
+        }
+    }
+
+    #[test]
+    fn delimited_rejects_delimiter_in_element() {
+        let cmpbytes = CompactBytestrings::from([b"One", b"T\0o"]);
+
+        let mut buf = Vec::new();
+        let err = cmpbytes.write_delimited(&mut buf, 0).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        assert_eq!(buf, b"One\0");
+    }
+}
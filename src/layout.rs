@@ -0,0 +1,154 @@
+use core::fmt::{self, Display};
+
+use alloc::vec::Vec;
+
+use crate::utf8;
+
+/// Size of the count and data length that precede the starting indices.
+pub(crate) const HEADER_LEN: usize = 16;
+
+/// Size of each starting index.
+pub(crate) const START_LEN: usize = 8;
+
+/// The reason a buffer does not hold the binary layout written by
+/// [`CompactBytestrings::to_bytes`](crate::CompactBytestrings::to_bytes), or a valid file written
+/// by `save`.
+///
+/// # Examples
+/// ```
+/// # use compact_strings::{CompactBytesRef, LayoutError};
+/// assert_eq!(CompactBytesRef::parse(&[0; 4]), Err(LayoutError::LengthMismatch));
+/// ```
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LayoutError {
+    /// The buffer is shorter or longer than its header says.
+    LengthMismatch,
+    /// A starting index is out of order or out of bounds.
+    InvalidStart,
+    /// The data is not valid UTF-8, or a string does not start on a character boundary.
+    InvalidUtf8,
+    /// The file does not start with the expected magic bytes, or its header is malformed.
+    InvalidHeader,
+    /// The file was written in a format version that this version of the crate cannot read.
+    UnsupportedVersion,
+    /// The checksum stored in the file does not match its contents.
+    ChecksumMismatch,
+}
+
+impl Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            LayoutError::LengthMismatch => "buffer length does not match its header",
+            LayoutError::InvalidStart => "start is out of order or out of bounds",
+            LayoutError::InvalidUtf8 => "data is not valid UTF-8",
+            LayoutError::InvalidHeader => "file header is malformed",
+            LayoutError::UnsupportedVersion => "file format version is not supported",
+            LayoutError::ChecksumMismatch => "file checksum does not match its contents",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LayoutError {}
+
+/// Encodes `count` elements in the binary layout documented on
+/// [`CompactBytestrings::to_bytes`](crate::CompactBytestrings::to_bytes).
+pub(crate) fn encode<'a, F, I>(count: usize, iter: F) -> Vec<u8>
+where
+    F: Fn() -> I,
+    I: Iterator<Item = &'a [u8]>,
+{
+    let data_len: usize = iter().map(<[u8]>::len).sum();
+    let mut buf = Vec::with_capacity(HEADER_LEN + count * START_LEN + data_len);
+    buf.extend_from_slice(&(count as u64).to_le_bytes());
+    buf.extend_from_slice(&(data_len as u64).to_le_bytes());
+
+    let mut start = 0;
+    for bytes in iter() {
+        buf.extend_from_slice(&(start as u64).to_le_bytes());
+        start += bytes.len();
+    }
+
+    for bytes in iter() {
+        buf.extend_from_slice(bytes);
+    }
+
+    buf
+}
+
+/// Splits `buf` into its starting indices and data, checking that the starts begin at zero and
+/// are in order and in bounds.
+pub(crate) fn parse(buf: &[u8]) -> Result<(&[u8], &[u8]), LayoutError> {
+    let header = buf.get(..HEADER_LEN).ok_or(LayoutError::LengthMismatch)?;
+    let len = read_u64(&header[..START_LEN]).ok_or(LayoutError::LengthMismatch)?;
+    let data_len = read_u64(&header[START_LEN..]).ok_or(LayoutError::LengthMismatch)?;
+
+    let data_start = len
+        .checked_mul(START_LEN)
+        .and_then(|table_len| table_len.checked_add(HEADER_LEN))
+        .filter(|&data_start| data_start.checked_add(data_len) == Some(buf.len()))
+        .ok_or(LayoutError::LengthMismatch)?;
+
+    let (starts, data) = buf[HEADER_LEN..].split_at(data_start - HEADER_LEN);
+    check_starts(starts.chunks_exact(START_LEN).map(read_u64), data_len)?;
+
+    Ok((starts, data))
+}
+
+/// Checks that the starts begin at zero and are in order and in bounds, and that any data is
+/// covered by at least one start.
+pub(crate) fn check_starts<I>(starts: I, data_len: usize) -> Result<(), LayoutError>
+where
+    I: Iterator<Item = Option<usize>>,
+{
+    let mut prev = None;
+    for start in starts {
+        let start = start.ok_or(LayoutError::InvalidStart)?;
+        let in_order = match prev {
+            Some(prev) => start >= prev,
+            None => start == 0,
+        };
+        if !in_order || start > data_len {
+            return Err(LayoutError::InvalidStart);
+        }
+        prev = Some(start);
+    }
+
+    if prev.is_none() && data_len != 0 {
+        return Err(LayoutError::InvalidStart);
+    }
+
+    Ok(())
+}
+
+/// Checks that `data` is valid UTF-8 and that every start lies on a character boundary.
+pub(crate) fn check_utf8<I>(starts: I, data: &[u8]) -> Result<(), LayoutError>
+where
+    I: IntoIterator<Item = usize>,
+{
+    let str = utf8::from_utf8(data).map_err(|_| LayoutError::InvalidUtf8)?;
+    if starts.into_iter().all(|start| str.is_char_boundary(start)) {
+        Ok(())
+    } else {
+        Err(LayoutError::InvalidUtf8)
+    }
+}
+
+/// Reads a little-endian `u64` from the first 8 bytes of `bytes`, returning [`None`] if it does
+/// not fit in a [`usize`].
+pub(crate) fn read_u64(bytes: &[u8]) -> Option<usize> {
+    let mut buf = [0; START_LEN];
+    buf.copy_from_slice(&bytes[..START_LEN]);
+    usize::try_from(u64::from_le_bytes(buf)).ok()
+}
+
+/// Reads the start at `index` from a table that has already been validated by [`parse`].
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) fn read_start(starts: &[u8], index: usize) -> usize {
+    let at = index * START_LEN;
+    let mut buf = [0; START_LEN];
+    buf.copy_from_slice(&starts[at..at + START_LEN]);
+    // Every start was checked to fit in a usize when the table was parsed.
+    u64::from_le_bytes(buf) as usize
+}
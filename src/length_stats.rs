@@ -0,0 +1,88 @@
+/// Statistics about the lengths of the elements of a collection, as returned by `stats` methods
+/// such as [`CompactStrings::stats`](crate::CompactStrings::stats).
+///
+/// Every length is a number of bytes. The minimum and maximum lengths are zero for an empty
+/// collection.
+///
+/// # Examples
+/// ```
+/// # use compact_strings::CompactStrings;
+/// let cmpstrs = CompactStrings::from(["One", "Two", "Three"]);
+///
+/// let stats = cmpstrs.stats();
+/// assert_eq!(stats.count, 3);
+/// assert_eq!(stats.total_len, 11);
+/// assert_eq!(stats.min_len, 3);
+/// assert_eq!(stats.max_len, 5);
+/// assert_eq!(stats.garbage_len, 0);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LengthStats {
+    /// Number of elements.
+    pub count: usize,
+    /// Sum of the lengths of all elements.
+    pub total_len: usize,
+    /// Length of the shortest element.
+    pub min_len: usize,
+    /// Length of the longest element.
+    pub max_len: usize,
+    /// Bytes in the data vector that are not referenced by any element.
+    pub garbage_len: usize,
+}
+
+impl LengthStats {
+    /// Computes the statistics of elements with the lengths `lens`, stored in a data vector of
+    /// length `data_len`.
+    pub(crate) fn new<I>(lens: I, data_len: usize) -> Self
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        let mut stats = Self {
+            min_len: usize::MAX,
+            ..Self::default()
+        };
+        for len in lens {
+            stats.count += 1;
+            stats.total_len += len;
+            stats.min_len = stats.min_len.min(len);
+            stats.max_len = stats.max_len.max(len);
+        }
+
+        if stats.count == 0 {
+            stats.min_len = 0;
+        }
+        stats.garbage_len = data_len - stats.total_len;
+        stats
+    }
+
+    /// Returns the mean length of the elements, or `0.0` if there are none.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let cmpstrs = CompactStrings::from(["One", "Three"]);
+    ///
+    /// assert_eq!(cmpstrs.stats().mean_len(), 4.0);
+    /// ```
+    #[allow(clippy::cast_precision_loss)]
+    #[must_use]
+    pub fn mean_len(&self) -> f64 {
+        if self.count == 0 {
+            return 0.0;
+        }
+
+        self.total_len as f64 / self.count as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LengthStats;
+
+    #[test]
+    fn empty_stats_are_zero() {
+        let stats = LengthStats::new([], 0);
+        assert_eq!(stats, LengthStats::default());
+        assert!(stats.mean_len().abs() < f64::EPSILON);
+    }
+}
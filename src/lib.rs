@@ -20,18 +20,256 @@
 //! assert_eq!(cmpstrs.get(1), Some("Three"));
 //! assert_eq!(cmpstrs.get(2), None);
 //! ```
+//!
+//! # Choosing a type
+//! Each list comes in a string and a bytestring flavour, and in a few storage variants:
+//! - [`CompactStrings`] can ignore strings without moving any bytes, at the cost of a length per
+//!   string, while [`FixedCompactStrings`] only stores a starting index per string.
+//! - [`FrozenCompactStrings`] and [`SharedCompactStrings`] keep their buffers in boxed slices and
+//!   [`Arc`](alloc::sync::Arc)s, and cannot be modified.
+//! - [`CompactStrsRef`] and `MmapCompactStrings` read strings straight out of a borrowed or
+//!   memory-mapped buffer in the layout written by [`CompactStrings::to_bytes`].
+//!
+//! These are separate types rather than one type generic over its buffer, so that each only
+//! exposes the operations its buffer can support and none of them carries a type parameter.
 #![no_std]
 #![warn(clippy::pedantic)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
+#[cfg(feature = "alloc")]
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
+#[cfg(feature = "alloc")]
 mod compact_strings;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub use compact_strings::CompactStrings;
+#[cfg(feature = "alloc")]
 mod compact_bytestrings;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub use compact_bytestrings::CompactBytestrings;
+mod array_compact_strings;
+pub use array_compact_strings::ArrayCompactStrings;
+#[cfg(feature = "alloc")]
+mod auto_compact_strings;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use auto_compact_strings::AutoCompactStrings;
+#[cfg(feature = "alloc")]
+mod string_builder;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use string_builder::StringBuilder;
+#[cfg(feature = "alloc")]
+mod overlapping_builder;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use overlapping_builder::{OverlappingBuilder, OverlappingBytesBuilder};
+mod capacity_error;
+#[cfg(feature = "alloc")]
+mod layout;
 mod metadata;
+#[cfg(feature = "alloc")]
+mod parts_error;
+#[cfg(feature = "alloc")]
+mod permutation;
+#[cfg(feature = "alloc")]
+mod radix;
+#[cfg(feature = "alloc")]
+mod range;
+#[cfg(feature = "alloc")]
+mod search;
+#[cfg(feature = "alloc")]
+mod set_error;
+mod utf8;
+pub use capacity_error::CapacityError;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use layout::LayoutError;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use parts_error::PartsError;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use set_error::SetError;
+#[cfg(feature = "alloc")]
+mod memory_usage;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use memory_usage::MemoryUsage;
+#[cfg(feature = "alloc")]
+mod length_stats;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use length_stats::LengthStats;
+#[cfg(feature = "alloc")]
+mod diff;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use diff::Diff;
+#[cfg(feature = "alloc")]
+mod mphf;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use mphf::Mphf;
+#[cfg(feature = "alloc")]
+mod rle_compact_strings;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use rle_compact_strings::RleCompactStrings;
 
+#[cfg(feature = "serde")]
+mod flat;
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub use self::flat::Flat;
+
+#[cfg(feature = "alloc")]
 mod fixed_compact_strings;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub use fixed_compact_strings::FixedCompactStrings;
+#[cfg(feature = "alloc")]
 mod fixed_compact_bytestrings;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub use fixed_compact_bytestrings::FixedCompactBytestrings;
+#[cfg(feature = "alloc")]
+mod compact_trie;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use compact_trie::CompactTrie;
+#[cfg(feature = "alloc")]
+mod compact_string_arena;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use compact_string_arena::{ArenaKey, CompactStringArena};
+
+#[cfg(feature = "alloc")]
+mod compact_string_deque;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use compact_string_deque::CompactStringDeque;
+#[cfg(feature = "alloc")]
+mod compact_bytestring_deque;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use compact_bytestring_deque::CompactBytestringDeque;
+
+#[cfg(feature = "alloc")]
+mod shared_compact_strings;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use shared_compact_strings::SharedCompactStrings;
+#[cfg(feature = "alloc")]
+mod shared_compact_bytestrings;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use shared_compact_bytestrings::SharedCompactBytestrings;
+
+#[cfg(feature = "alloc")]
+mod frozen_compact_strings;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use frozen_compact_strings::FrozenCompactStrings;
+#[cfg(feature = "alloc")]
+mod frozen_compact_bytestrings;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use frozen_compact_bytestrings::FrozenCompactBytestrings;
+
+#[cfg(feature = "alloc")]
+mod compact_strs_ref;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use compact_strs_ref::CompactStrsRef;
+#[cfg(feature = "alloc")]
+mod compact_bytes_ref;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub use compact_bytes_ref::CompactBytesRef;
+
+#[cfg(feature = "chunked")]
+mod chunked_compact_strings;
+#[cfg(feature = "chunked")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chunked")))]
+pub use self::chunked_compact_strings::ChunkedCompactStrings;
+
+#[cfg(feature = "small_fixed_array")]
+mod small_fixed_array;
+#[cfg(feature = "small_fixed_array")]
+#[cfg_attr(docsrs, doc(cfg(feature = "small_fixed_array")))]
+pub use self::small_fixed_array::{FixedArrayCompactBytestrings, FixedArrayCompactStrings};
+
+#[cfg(feature = "std")]
+mod io;
+#[cfg(feature = "std")]
+mod save;
+
+#[cfg(feature = "csv")]
+mod csv_column;
+
+#[cfg(feature = "base64")]
+mod base64_encoding;
+
+#[cfg(feature = "rand")]
+mod random;
+
+#[cfg(feature = "wasm")]
+mod wasm;
+
+#[cfg(feature = "pyo3")]
+mod python;
+#[cfg(feature = "pyo3")]
+#[cfg_attr(docsrs, doc(cfg(feature = "pyo3")))]
+pub use self::python::PyCompactStrings;
+
+#[cfg(feature = "sqlx")]
+mod postgres;
+
+#[cfg(feature = "redis")]
+mod resp;
+
+#[cfg(feature = "arrow")]
+mod arrow;
+
+#[cfg(feature = "speedy")]
+mod speedy_encoding;
+
+#[cfg(feature = "lz4")]
+mod compressed_compact_strings;
+#[cfg(feature = "lz4")]
+#[cfg_attr(docsrs, doc(cfg(feature = "lz4")))]
+pub use self::compressed_compact_strings::CompressedCompactStrings;
+
+#[cfg(feature = "std")]
+mod indexed_compact_strings;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use self::indexed_compact_strings::IndexedCompactStrings;
+
+#[cfg(feature = "std")]
+mod dict_compact_strings;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use self::dict_compact_strings::DictCompactStrings;
+
+#[cfg(feature = "bytes")]
+mod bytes_compact_bytestrings;
+#[cfg(feature = "bytes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+pub use self::bytes_compact_bytestrings::BytesCompactBytestrings;
+
+#[cfg(feature = "unicode")]
+mod normalization;
+#[cfg(feature = "unicode")]
+#[cfg_attr(docsrs, doc(cfg(feature = "unicode")))]
+pub use self::normalization::NormalizationForm;
+
+#[cfg(feature = "mmap")]
+mod mmap;
+#[cfg(feature = "mmap")]
+#[cfg_attr(docsrs, doc(cfg(feature = "mmap")))]
+pub use self::mmap::MmapCompactStrings;
@@ -0,0 +1,103 @@
+/// The heap memory held by a collection, as returned by `memory_usage` methods such as
+/// [`CompactStrings::memory_usage`](crate::CompactStrings::memory_usage).
+///
+/// Every field is a number of bytes. The `used` fields count the bytes holding elements, while
+/// the `reserved` fields count the bytes allocated, including spare capacity.
+///
+/// # Examples
+/// ```
+/// # use compact_strings::CompactStrings;
+/// let mut cmpstrs = CompactStrings::with_capacity(20, 3);
+/// cmpstrs.push("One");
+///
+/// let usage = cmpstrs.memory_usage();
+/// assert_eq!(usage.data_used, 3);
+/// assert!(usage.data_reserved >= 20);
+/// assert!(usage.reserved() >= usage.used());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// Bytes used by the data vector.
+    pub data_used: usize,
+    /// Bytes allocated for the data vector.
+    pub data_reserved: usize,
+    /// Bytes used by the meta vector.
+    pub meta_used: usize,
+    /// Bytes allocated for the meta vector.
+    pub meta_reserved: usize,
+}
+
+impl MemoryUsage {
+    /// Creates a [`MemoryUsage`] from the lengths and capacities of the data vector and a meta
+    /// vector with elements of type `T`.
+    pub(crate) fn new<T>(
+        data_len: usize,
+        data_capacity: usize,
+        meta_len: usize,
+        meta_capacity: usize,
+    ) -> Self {
+        let size = core::mem::size_of::<T>();
+        Self {
+            data_used: data_len,
+            data_reserved: data_capacity,
+            meta_used: meta_len * size,
+            meta_reserved: meta_capacity * size,
+        }
+    }
+
+    /// Returns the total number of bytes used by both vectors.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["One", "Two"]);
+    ///
+    /// let usage = cmpstrs.memory_usage();
+    /// assert_eq!(usage.used(), 6 + 2 * core::mem::size_of::<usize>());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn used(&self) -> usize {
+        self.data_used + self.meta_used
+    }
+
+    /// Returns the total number of bytes allocated for both vectors.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::with_capacity(20, 3);
+    ///
+    /// let usage = cmpstrs.memory_usage();
+    /// assert!(usage.reserved() >= 20 + 3 * core::mem::size_of::<usize>());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn reserved(&self) -> usize {
+        self.data_reserved + self.meta_reserved
+    }
+}
+
+/// Returns `len` plus `slack` times `len`, treating a negative or NaN `slack` as zero.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+pub(crate) fn with_slack(len: usize, slack: f64) -> usize {
+    // Float to integer casts saturate, and NaN becomes zero.
+    len.saturating_add((len as f64 * slack) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::with_slack;
+
+    #[test]
+    fn slack_is_clamped() {
+        assert_eq!(with_slack(100, 0.1), 110);
+        assert_eq!(with_slack(100, -1.0), 100);
+        assert_eq!(with_slack(100, f64::NAN), 100);
+        assert_eq!(with_slack(100, f64::INFINITY), usize::MAX);
+    }
+}
@@ -1,3 +1,12 @@
+/// Location of a single element in the data vector.
+///
+/// Every element, however short, is stored in the data vector and described by a `start..start + len`
+/// range into it. Short elements are not stored inline in the metadata: both fields use every bit
+/// of their `usize`, so telling an inline element apart would need a tag that either shrinks the
+/// maximum length or grows every entry, and every access would have to branch on it. The data
+/// vector would also stop holding every byte, which `with_capacity`, `capacity` and `data_len`
+/// describe it as doing, and which `offsets`, `byte_range`, `find_bytes` and `to_bytes` rely on.
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub(crate) struct Metadata {
     pub(crate) start: usize,
     pub(crate) len: usize,
@@ -0,0 +1,227 @@
+use core::{fmt::Debug, ops::Index};
+use std::{fs::File, io, path::Path};
+
+use memmap2::Mmap;
+
+use crate::{
+    compact_strs_ref::Iter,
+    io::invalid_data,
+    layout::HEADER_LEN,
+    save::{self, CHECKSUM_LEN, FILE_HEADER_LEN},
+    CompactBytesRef, CompactStrings, CompactStrsRef,
+};
+
+/// An immutable list of strings backed by a memory-mapped file written by
+/// [`CompactStrings::save`].
+///
+/// The starting indices and strings are read directly from the mapped file, so opening a
+/// file does not copy it and its pages can be shared with other processes mapping the same
+/// file.
+///
+/// # Examples
+/// ```
+/// # use compact_strings::CompactStrings;
+/// let cmpstrs = CompactStrings::from(["One", "Two"]);
+///
+/// let path = std::env::temp_dir().join("compact_strings_mmap_doc.bin");
+/// cmpstrs.save(&path).unwrap();
+///
+/// let mapped = unsafe { CompactStrings::open_mmap(&path).unwrap() };
+/// assert_eq!(mapped.get(0), Some("One"));
+/// assert_eq!(mapped.get(1), Some("Two"));
+/// assert_eq!(mapped.get(2), None);
+/// ```
+pub struct MmapCompactStrings {
+    map: Mmap,
+    data_start: usize,
+}
+
+impl CompactStrings {
+    /// Memory-maps a file written by [`CompactStrings::save`].
+    ///
+    /// The checksum, starting indices and UTF-8 of the strings are validated once when opening, after which
+    /// lookups read straight from the mapped pages.
+    ///
+    /// # Safety
+    /// The file must not be modified or truncated while it is mapped, whether by this process or
+    /// any other, as the strings would change or become invalid behind the returned references.
+    ///
+    /// # Errors
+    /// Returns any error encountered while opening or mapping the file, and an error of kind
+    /// [`io::ErrorKind::InvalidData`] if the file is not valid, as described in
+    /// [`CompactStrings::load`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let cmpstrs = CompactStrings::from(["One", "Two"]);
+    ///
+    /// let path = std::env::temp_dir().join("compact_strings_open_mmap_doc.bin");
+    /// cmpstrs.save(&path).unwrap();
+    ///
+    /// let mapped = unsafe { CompactStrings::open_mmap(&path).unwrap() };
+    /// assert_eq!(mapped.len(), 2);
+    /// assert!(mapped.iter().eq(cmpstrs.iter()));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "mmap")))]
+    pub unsafe fn open_mmap<P: AsRef<Path>>(path: P) -> io::Result<MmapCompactStrings> {
+        let file = File::open(path)?;
+        MmapCompactStrings::new(Mmap::map(&file)?)
+    }
+}
+
+impl MmapCompactStrings {
+    fn new(map: Mmap) -> io::Result<Self> {
+        let view = save::parse_file(&map)
+            .and_then(CompactStrsRef::parse)
+            .map_err(invalid_data)?;
+        let (_, data) = CompactBytesRef::from(view).parts();
+        let data_start = map.len() - CHECKSUM_LEN - data.len();
+
+        Ok(Self { map, data_start })
+    }
+
+    /// Returns a [`CompactStrsRef`] borrowing the strings from the mapped file.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// # let path = std::env::temp_dir().join("compact_strings_mmap_view_doc.bin");
+    /// # CompactStrings::from(["One", "Two"]).save(&path).unwrap();
+    /// let mapped = unsafe { CompactStrings::open_mmap(&path).unwrap() };
+    /// let view = mapped.view();
+    ///
+    /// assert_eq!(view.get(1), Some("Two"));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn view(&self) -> CompactStrsRef<'_> {
+        let starts = &self.map[FILE_HEADER_LEN + HEADER_LEN..self.data_start];
+        let data = &self.map[self.data_start..self.map.len() - CHECKSUM_LEN];
+        CompactStrsRef::from_parts(starts, data)
+    }
+
+    /// Returns a reference to the string stored in the [`MmapCompactStrings`] at that position.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// # let path = std::env::temp_dir().join("compact_strings_mmap_get_doc.bin");
+    /// # CompactStrings::from(["One", "Two"]).save(&path).unwrap();
+    /// let mapped = unsafe { CompactStrings::open_mmap(&path).unwrap() };
+    ///
+    /// assert_eq!(mapped.get(0), Some("One"));
+    /// assert_eq!(mapped.get(1), Some("Two"));
+    /// assert_eq!(mapped.get(2), None);
+    /// ```
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.view().get(index)
+    }
+
+    /// Returns the number of strings in the [`MmapCompactStrings`], also referred to as its 'length'.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// # let path = std::env::temp_dir().join("compact_strings_mmap_len_doc.bin");
+    /// # CompactStrings::from(["One", "Two"]).save(&path).unwrap();
+    /// let mapped = unsafe { CompactStrings::open_mmap(&path).unwrap() };
+    ///
+    /// assert_eq!(mapped.len(), 2);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.view().len()
+    }
+
+    /// Returns true if the [`MmapCompactStrings`] contains no strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// # let path = std::env::temp_dir().join("compact_strings_mmap_is_empty_doc.bin");
+    /// # CompactStrings::new().save(&path).unwrap();
+    /// let mapped = unsafe { CompactStrings::open_mmap(&path).unwrap() };
+    ///
+    /// assert!(mapped.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// # let path = std::env::temp_dir().join("compact_strings_mmap_iter_doc.bin");
+    /// # CompactStrings::from(["One", "Two"]).save(&path).unwrap();
+    /// let mapped = unsafe { CompactStrings::open_mmap(&path).unwrap() };
+    /// let mut iterator = mapped.iter();
+    ///
+    /// assert_eq!(iterator.next(), Some("One"));
+    /// assert_eq!(iterator.next(), Some("Two"));
+    /// assert_eq!(iterator.next(), None);
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        self.view().iter()
+    }
+}
+
+impl Debug for MmapCompactStrings {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl Index<usize> for MmapCompactStrings {
+    type Output = str;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+
+impl<'a> IntoIterator for &'a MmapCompactStrings {
+    type Item = &'a str;
+
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, io::ErrorKind};
+
+    use crate::CompactStrings;
+
+    #[test]
+    fn rejects_invalid_files() {
+        let path = std::env::temp_dir().join("compact_strings_mmap_rejects.bin");
+        CompactStrings::from(["One", "Thé"]).save(&path).unwrap();
+        let buf = fs::read(&path).unwrap();
+
+        fs::write(&path, &buf[..buf.len() - 1]).unwrap();
+        let err = unsafe { CompactStrings::open_mmap(&path) }.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        // Layouts without the header and checksum written by `save` are not accepted.
+        fs::write(&path, CompactStrings::from(["One", "Thé"]).to_bytes()).unwrap();
+        let err = unsafe { CompactStrings::open_mmap(&path) }.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        fs::write(&path, &buf).unwrap();
+        let mapped = unsafe { CompactStrings::open_mmap(&path) }.unwrap();
+        assert!(mapped.iter().rev().eq(["Thé", "One"]));
+    }
+}
@@ -0,0 +1,479 @@
+use alloc::{boxed::Box, vec, vec::Vec};
+
+use crate::{
+    FixedCompactBytestrings, FixedCompactStrings, FrozenCompactBytestrings, FrozenCompactStrings,
+};
+
+/// Bits reserved per remaining key at each level. Larger values use more memory but leave fewer
+/// keys for the following levels.
+const GAMMA: usize = 2;
+
+/// Number of levels after which the remaining keys are assumed to be duplicates.
+const MAX_LEVELS: usize = 64;
+
+/// Number of 64-bit words covered by each precomputed rank.
+const RANK_BLOCK: usize = 8;
+
+/// Hashes `key` with `seed`, mixing each 8-byte chunk in turn and finishing with the `MurmurHash3`
+/// finalizer.
+fn hash(key: &[u8], seed: u64) -> u64 {
+    const K: u64 = 0x9E37_79B9_7F4A_7C15;
+
+    let mut h = seed ^ (key.len() as u64).wrapping_mul(K);
+    let mut chunks = key.chunks_exact(8);
+    for chunk in &mut chunks {
+        let mut word = [0; 8];
+        word.copy_from_slice(chunk);
+        h = (h ^ u64::from_le_bytes(word))
+            .wrapping_mul(K)
+            .rotate_left(31);
+    }
+
+    let remainder = chunks.remainder();
+    let mut word = [0; 8];
+    word[..remainder.len()].copy_from_slice(remainder);
+    h = (h ^ u64::from_le_bytes(word)).wrapping_mul(K);
+
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xFF51_AFD7_ED55_8CCD);
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xC4CE_B9FE_1A85_EC53);
+    h ^ (h >> 33)
+}
+
+/// Returns the bit that `key` maps to in a level of `len` bits.
+#[allow(clippy::cast_possible_truncation)]
+fn position(key: &[u8], level: usize, len: usize) -> usize {
+    // Multiplying instead of taking the remainder maps the hash onto `0..len` without a division.
+    ((u128::from(hash(key, level as u64)) * len as u128) >> 64) as usize
+}
+
+#[inline]
+fn test_bit(words: &[u64], bit: usize) -> bool {
+    words[bit / 64] & (1 << (bit % 64)) != 0
+}
+
+#[inline]
+fn set_bit(words: &mut [u64], bit: usize) {
+    words[bit / 64] |= 1 << (bit % 64);
+}
+
+/// A minimal perfect hash function over the elements of a frozen or fixed collection, as built
+/// by [`FrozenCompactBytestrings::build_mphf`] or [`FixedCompactBytestrings::build_mphf`].
+///
+/// It maps each of the `n` elements it was built from to a distinct index in `0..n`, taking a
+/// little over 3 bits per element. Other keys are mapped to an arbitrary index or to none, so a
+/// lookup has to compare the key with the element at that index, as
+/// [`FrozenCompactBytestrings::lookup`] does.
+///
+/// The function is built level by level: every remaining key is hashed into a bit array twice as
+/// long as the number of remaining keys, the keys that land on a bit of their own are placed, and
+/// the rest move on to the next level.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mphf {
+    /// The bits of every level, back to back.
+    bits: Box<[u64]>,
+    /// The first word and the length in bits of each level.
+    levels: Box<[(usize, usize)]>,
+    /// The number of set bits before each block of [`RANK_BLOCK`] words.
+    ranks: Box<[usize]>,
+    len: usize,
+}
+
+impl Mphf {
+    /// Builds the function over `len` distinct keys, returning [`None`] if some of them are equal.
+    fn build<'a, F>(len: usize, key: F) -> Option<Self>
+    where
+        F: Fn(usize) -> &'a [u8],
+    {
+        let mut bits = Vec::new();
+        let mut levels = Vec::new();
+        let mut remaining: Vec<usize> = (0..len).collect();
+
+        while !remaining.is_empty() {
+            if levels.len() == MAX_LEVELS {
+                return None;
+            }
+
+            let level = levels.len();
+            let words = (remaining.len() * GAMMA + 63) / 64;
+            let level_len = words * 64;
+            let mut hit = vec![0; words];
+            let mut collided = vec![0; words];
+            for &index in &remaining {
+                let bit = position(key(index), level, level_len);
+                if test_bit(&hit, bit) {
+                    set_bit(&mut collided, bit);
+                } else {
+                    set_bit(&mut hit, bit);
+                }
+            }
+
+            remaining.retain(|&index| test_bit(&collided, position(key(index), level, level_len)));
+            for (hit, collided) in hit.iter_mut().zip(&collided) {
+                *hit &= !collided;
+            }
+
+            levels.push((bits.len(), level_len));
+            bits.extend_from_slice(&hit);
+        }
+
+        let mut ranks = Vec::with_capacity(bits.len() / RANK_BLOCK + 1);
+        let mut rank = 0;
+        for block in bits.chunks(RANK_BLOCK) {
+            ranks.push(rank);
+            rank += block
+                .iter()
+                .map(|word| word.count_ones() as usize)
+                .sum::<usize>();
+        }
+
+        Some(Self {
+            bits: bits.into_boxed_slice(),
+            levels: levels.into_boxed_slice(),
+            ranks: ranks.into_boxed_slice(),
+            len,
+        })
+    }
+
+    /// Returns the number of set bits before `bit`.
+    fn rank(&self, bit: usize) -> usize {
+        let word = bit / 64;
+        let block_start = word - word % RANK_BLOCK;
+        let before: usize = self.bits[block_start..word]
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum();
+        let mask = (1 << (bit % 64)) - 1;
+
+        self.ranks[word / RANK_BLOCK] + before + (self.bits[word] & mask).count_ones() as usize
+    }
+
+    /// Returns the index that `key` maps to, which is only meaningful if `key` is one of the
+    /// elements the function was built from.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FrozenCompactBytestrings;
+    /// let (frozen, mphf) = FrozenCompactBytestrings::from([b"One", b"Two"])
+    ///     .build_mphf()
+    ///     .unwrap();
+    ///
+    /// let index = mphf.index(b"Two").unwrap();
+    /// assert_eq!(frozen.get(index), Some(b"Two".as_slice()));
+    /// ```
+    #[must_use]
+    pub fn index(&self, key: &[u8]) -> Option<usize> {
+        self.levels
+            .iter()
+            .enumerate()
+            .find_map(|(level, &(first_word, level_len))| {
+                let bit = first_word * 64 + position(key, level, level_len);
+                test_bit(&self.bits, bit).then(|| self.rank(bit))
+            })
+    }
+
+    /// Returns the number of elements the function was built from.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FrozenCompactBytestrings;
+    /// let (_, mphf) = FrozenCompactBytestrings::from([b"One", b"Two"])
+    ///     .build_mphf()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(mphf.len(), 2);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the function was built from no elements.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let (_, mphf) = CompactBytestrings::new().freeze().build_mphf().unwrap();
+    ///
+    /// assert!(mphf.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of bytes used by the function on the heap.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FrozenCompactStrings;
+    /// let words = (0..1000).map(|i| format!("word{i}"));
+    /// let (_, mphf) = FrozenCompactStrings::from_iter(words).build_mphf().unwrap();
+    ///
+    /// assert!(mphf.heap_size() * 8 < mphf.len() * 5);
+    /// ```
+    #[must_use]
+    pub fn heap_size(&self) -> usize {
+        core::mem::size_of_val(&*self.bits)
+            + core::mem::size_of_val(&*self.levels)
+            + core::mem::size_of_val(&*self.ranks)
+    }
+}
+
+impl FrozenCompactBytestrings {
+    /// Builds a minimal perfect hash function over the bytestrings, and reorders them so that each
+    /// one sits at the index the function maps it to.
+    ///
+    /// Together they give exact *O*(1) lookups through [`lookup`](FrozenCompactBytestrings::lookup)
+    /// while adding only a little over 3 bits per bytestring, which suits static dictionaries such
+    /// as keyword sets and stopword lists. Only the metadata is reordered; the bytes are not moved.
+    ///
+    /// # Errors
+    /// Returns the bytestrings unchanged if some of them are equal, as no such function exists.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FrozenCompactBytestrings;
+    /// let frozen = FrozenCompactBytestrings::from([b"One", b"Two", b"Six"]);
+    ///
+    /// let (frozen, mphf) = frozen.build_mphf().unwrap();
+    /// assert_eq!(frozen.len(), 3);
+    /// assert!(frozen.lookup(&mphf, b"Two").is_some());
+    ///
+    /// let duplicates = FrozenCompactBytestrings::from([b"One", b"One"]);
+    /// assert!(duplicates.build_mphf().is_err());
+    /// ```
+    pub fn build_mphf(self) -> Result<(Self, Mphf), Self> {
+        let mphf = match Mphf::build(self.len(), |index| &self[index]) {
+            Some(mphf) => mphf,
+            None => return Err(self),
+        };
+
+        let mut meta = self.meta.clone();
+        for (bytestring, &old) in self.iter().zip(self.meta.iter()) {
+            // Every bytestring was placed when building, so it always has an index.
+            if let Some(index) = mphf.index(bytestring) {
+                meta[index] = old;
+            }
+        }
+
+        Ok((
+            Self {
+                data: self.data,
+                meta,
+            },
+            mphf,
+        ))
+    }
+
+    /// Returns the index of `key` in bytestrings reordered by
+    /// [`build_mphf`](FrozenCompactBytestrings::build_mphf), or [`None`] if they do not hold it,
+    /// comparing `key` with a single bytestring.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FrozenCompactBytestrings;
+    /// let (frozen, mphf) = FrozenCompactBytestrings::from([b"One", b"Two"])
+    ///     .build_mphf()
+    ///     .unwrap();
+    ///
+    /// let index = frozen.lookup(&mphf, b"One").unwrap();
+    /// assert_eq!(frozen.get(index), Some(b"One".as_slice()));
+    /// assert_eq!(frozen.lookup(&mphf, b"Six"), None);
+    /// ```
+    #[must_use]
+    pub fn lookup(&self, mphf: &Mphf, key: &[u8]) -> Option<usize> {
+        let index = mphf.index(key)?;
+        (self.get(index)? == key).then(|| index)
+    }
+}
+
+impl FrozenCompactStrings {
+    /// Builds a minimal perfect hash function over the strings, and reorders them so that each one
+    /// sits at the index the function maps it to, as described in
+    /// [`FrozenCompactBytestrings::build_mphf`].
+    ///
+    /// # Errors
+    /// Returns the strings unchanged if some of them are equal.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FrozenCompactStrings;
+    /// let stopwords = FrozenCompactStrings::from(["a", "an", "the"]);
+    ///
+    /// let (stopwords, mphf) = stopwords.build_mphf().unwrap();
+    /// assert!(stopwords.lookup(&mphf, "the").is_some());
+    /// assert!(stopwords.lookup(&mphf, "then").is_none());
+    /// ```
+    pub fn build_mphf(self) -> Result<(Self, Mphf), Self> {
+        match self.0.build_mphf() {
+            Ok((frozen, mphf)) => Ok((Self(frozen), mphf)),
+            Err(frozen) => Err(Self(frozen)),
+        }
+    }
+
+    /// Returns the index of `key` in strings reordered by
+    /// [`build_mphf`](FrozenCompactStrings::build_mphf), or [`None`] if they do not hold it.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FrozenCompactStrings;
+    /// let (frozen, mphf) = FrozenCompactStrings::from(["One", "Two"]).build_mphf().unwrap();
+    ///
+    /// let index = frozen.lookup(&mphf, "Two").unwrap();
+    /// assert_eq!(frozen.get(index), Some("Two"));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn lookup(&self, mphf: &Mphf, key: &str) -> Option<usize> {
+        self.0.lookup(mphf, key.as_bytes())
+    }
+}
+
+impl FixedCompactBytestrings {
+    /// Builds a minimal perfect hash function over the bytestrings, and reorders them so that each
+    /// one sits at the index the function maps it to, as described in
+    /// [`FrozenCompactBytestrings::build_mphf`].
+    ///
+    /// Each bytestring ends where the next one starts, so unlike for the frozen types the bytes
+    /// are copied into a new data vector in their new order. Pushing or removing bytestrings
+    /// afterwards invalidates the function.
+    ///
+    /// # Errors
+    /// Returns the bytestrings unchanged if some of them are equal, as no such function exists.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let cmpbytes = FixedCompactBytestrings::from([b"One", b"Two", b"Six"]);
+    ///
+    /// let (cmpbytes, mphf) = cmpbytes.build_mphf().unwrap();
+    /// assert_eq!(cmpbytes.len(), 3);
+    /// assert!(cmpbytes.lookup(&mphf, b"Two").is_some());
+    ///
+    /// let duplicates = FixedCompactBytestrings::from([b"One", b"One"]);
+    /// assert!(duplicates.build_mphf().is_err());
+    /// ```
+    pub fn build_mphf(self) -> Result<(Self, Mphf), Self> {
+        let mphf = match Mphf::build(self.len(), |index| &self[index]) {
+            Some(mphf) => mphf,
+            None => return Err(self),
+        };
+
+        let mut order = vec![0; self.len()];
+        for (old, bytestring) in self.iter().enumerate() {
+            // Every bytestring was placed when building, so it always has an index.
+            if let Some(index) = mphf.index(bytestring) {
+                order[index] = old;
+            }
+        }
+
+        let mut cmpbytes = Self::with_capacity(self.data.len(), self.len());
+        for old in order {
+            cmpbytes.push(&self[old]);
+        }
+
+        Ok((cmpbytes, mphf))
+    }
+
+    /// Returns the index of `key` in bytestrings reordered by
+    /// [`build_mphf`](FixedCompactBytestrings::build_mphf), or [`None`] if they do not hold it,
+    /// comparing `key` with a single bytestring.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let (cmpbytes, mphf) = FixedCompactBytestrings::from([b"One", b"Two"])
+    ///     .build_mphf()
+    ///     .unwrap();
+    ///
+    /// let index = cmpbytes.lookup(&mphf, b"One").unwrap();
+    /// assert_eq!(cmpbytes.get(index), Some(b"One".as_slice()));
+    /// assert_eq!(cmpbytes.lookup(&mphf, b"Six"), None);
+    /// ```
+    #[must_use]
+    pub fn lookup(&self, mphf: &Mphf, key: &[u8]) -> Option<usize> {
+        let index = mphf.index(key)?;
+        (self.get(index)? == key).then(|| index)
+    }
+}
+
+impl FixedCompactStrings {
+    /// Builds a minimal perfect hash function over the strings, and reorders them so that each one
+    /// sits at the index the function maps it to, as described in
+    /// [`FixedCompactBytestrings::build_mphf`].
+    ///
+    /// # Errors
+    /// Returns the strings unchanged if some of them are equal.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let stopwords = FixedCompactStrings::from(["a", "an", "the"]);
+    ///
+    /// let (stopwords, mphf) = stopwords.build_mphf().unwrap();
+    /// assert!(stopwords.lookup(&mphf, "the").is_some());
+    /// assert!(stopwords.lookup(&mphf, "then").is_none());
+    /// ```
+    pub fn build_mphf(self) -> Result<(Self, Mphf), Self> {
+        match self.0.build_mphf() {
+            Ok((cmpbytes, mphf)) => Ok((Self(cmpbytes), mphf)),
+            Err(cmpbytes) => Err(Self(cmpbytes)),
+        }
+    }
+
+    /// Returns the index of `key` in strings reordered by
+    /// [`build_mphf`](FixedCompactStrings::build_mphf), or [`None`] if they do not hold it.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let (cmpstrs, mphf) = FixedCompactStrings::from(["One", "Two"]).build_mphf().unwrap();
+    ///
+    /// let index = cmpstrs.lookup(&mphf, "Two").unwrap();
+    /// assert_eq!(cmpstrs.get(index), Some("Two"));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn lookup(&self, mphf: &Mphf, key: &str) -> Option<usize> {
+        self.0.lookup(mphf, key.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, vec};
+
+    use crate::{FixedCompactStrings, FrozenCompactBytestrings};
+
+    #[test]
+    fn mphf_is_minimal_and_perfect() {
+        let keys: vec::Vec<_> = (0..10_000).map(|i| format!("key{i}")).collect();
+        let (frozen, mphf) = FrozenCompactBytestrings::from(&keys).build_mphf().unwrap();
+
+        let mut seen = vec![false; keys.len()];
+        for key in &keys {
+            let index = frozen.lookup(&mphf, key.as_bytes()).unwrap();
+            assert!(!seen[index]);
+            seen[index] = true;
+        }
+        assert!(frozen.lookup(&mphf, b"key10000").is_none());
+        assert!(mphf.heap_size() * 8 < keys.len() * 4);
+    }
+
+    #[test]
+    fn fixed_strings_are_rewritten_in_mphf_order() {
+        let cmpstrs: FixedCompactStrings = (0..500).map(|i| format!("ü{i}")).collect();
+        let (reordered, mphf) = cmpstrs.clone().build_mphf().unwrap();
+
+        assert_eq!(reordered.data_len(), cmpstrs.data_len());
+        for string in &cmpstrs {
+            let index = reordered.lookup(&mphf, string).unwrap();
+            assert_eq!(reordered.get(index), Some(string));
+        }
+        assert!(reordered.lookup(&mphf, "ü500").is_none());
+    }
+}
@@ -0,0 +1,133 @@
+use alloc::vec::Vec;
+
+use unicode_normalization::{
+    is_nfc_quick, is_nfd_quick, is_nfkc_quick, is_nfkd_quick, IsNormalized, UnicodeNormalization,
+};
+
+use crate::{metadata::Metadata, CompactStrings, FixedCompactStrings};
+
+/// A Unicode normalization form, as defined in [Unicode Standard Annex #15].
+///
+/// [Unicode Standard Annex #15]: https://www.unicode.org/reports/tr15/
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NormalizationForm {
+    /// Canonical decomposition followed by canonical composition.
+    Nfc,
+    /// Canonical decomposition.
+    Nfd,
+    /// Compatibility decomposition followed by canonical composition.
+    Nfkc,
+    /// Compatibility decomposition.
+    Nfkd,
+}
+
+impl NormalizationForm {
+    /// Appends the bytes of `string` normalized to this form to `data`.
+    ///
+    /// Strings that are quickly found to already be normalized are copied as they are.
+    fn write(self, string: &str, data: &mut Vec<u8>) {
+        let is_normalized = match self {
+            Self::Nfc => is_nfc_quick(string.chars()),
+            Self::Nfd => is_nfd_quick(string.chars()),
+            Self::Nfkc => is_nfkc_quick(string.chars()),
+            Self::Nfkd => is_nfkd_quick(string.chars()),
+        };
+        if is_normalized == IsNormalized::Yes {
+            data.extend_from_slice(string.as_bytes());
+            return;
+        }
+
+        let mut buf = [0; 4];
+        let mut push = |c: char| data.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+        match self {
+            Self::Nfc => string.nfc().for_each(&mut push),
+            Self::Nfd => string.nfd().for_each(&mut push),
+            Self::Nfkc => string.nfkc().for_each(&mut push),
+            Self::Nfkd => string.nfkd().for_each(&mut push),
+        }
+    }
+}
+
+impl CompactStrings {
+    /// Returns a copy of the [`CompactStrings`] with every string normalized to `form`.
+    ///
+    /// Normalized characters are written straight into the data vector of the new
+    /// [`CompactStrings`], and strings that are already normalized are copied as they are.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactStrings, NormalizationForm};
+    /// let cmpstrs = CompactStrings::from(["cafe\u{301}", "ﬁ"]);
+    ///
+    /// let nfc = cmpstrs.normalized(NormalizationForm::Nfc);
+    /// assert!(nfc.iter().eq(["café", "ﬁ"]));
+    ///
+    /// let nfkc = cmpstrs.normalized(NormalizationForm::Nfkc);
+    /// assert!(nfkc.iter().eq(["café", "fi"]));
+    /// ```
+    #[must_use]
+    pub fn normalized(&self, form: NormalizationForm) -> Self {
+        let mut normalized = Self::with_capacity(self.data_len(), self.len());
+        let inner = &mut normalized.0;
+        for string in self {
+            let start = inner.data.len();
+            form.write(string, &mut inner.data);
+            inner
+                .meta
+                .push(Metadata::new(start, inner.data.len() - start));
+        }
+
+        normalized
+    }
+}
+
+impl FixedCompactStrings {
+    /// Returns a copy of the [`FixedCompactStrings`] with every string normalized to `form`.
+    ///
+    /// Normalized characters are written straight into the data vector of the new
+    /// [`FixedCompactStrings`], and strings that are already normalized are copied as they are.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{FixedCompactStrings, NormalizationForm};
+    /// let cmpstrs = FixedCompactStrings::from(["café", "ﬁ"]);
+    ///
+    /// let nfd = cmpstrs.normalized(NormalizationForm::Nfd);
+    /// assert!(nfd.iter().eq(["cafe\u{301}", "ﬁ"]));
+    ///
+    /// let nfkd = cmpstrs.normalized(NormalizationForm::Nfkd);
+    /// assert!(nfkd.iter().eq(["cafe\u{301}", "fi"]));
+    /// ```
+    #[must_use]
+    pub fn normalized(&self, form: NormalizationForm) -> Self {
+        let mut normalized = Self::with_capacity(self.data_len(), self.len());
+        let inner = &mut normalized.0;
+        for string in self {
+            inner.starts.push(inner.data.len());
+            form.write(string, &mut inner.data);
+        }
+
+        normalized
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use unicode_normalization::UnicodeNormalization;
+
+    use crate::{CompactStrings, NormalizationForm};
+
+    #[test]
+    fn round_trips_between_forms() {
+        let strings = ["", "Å", "A\u{30a}", "\u{212b}", "ascii", "한국어"];
+        let cmpstrs = CompactStrings::from(strings);
+
+        let nfd = cmpstrs.normalized(NormalizationForm::Nfd);
+        assert!(nfd.iter().eq(strings.map(|s| s.nfd().collect::<String>())));
+
+        let nfc = nfd.normalized(NormalizationForm::Nfc);
+        assert!(nfc.iter().eq(["", "Å", "Å", "Å", "ascii", "한국어"]));
+    }
+}
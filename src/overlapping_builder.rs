@@ -0,0 +1,378 @@
+use core::ops::Deref;
+
+use crate::{
+    metadata::Metadata, search, CompactBytestrings, FrozenCompactBytestrings, FrozenCompactStrings,
+};
+
+/// Builds a [`FrozenCompactBytestrings`] in which bytestrings share bytes with the ones pushed
+/// before them.
+///
+/// A pushed bytestring that already occurs in the data vector is stored as a reference to those
+/// bytes, and one whose start matches the end of the data vector only appends the bytes that do
+/// not match. Lists with many shared prefixes and suffixes, such as file paths, can end up with a
+/// much smaller data vector, while reading them works exactly as before.
+///
+/// Searching the data vector takes time linear in its length for every push, which can be bounded
+/// with [`with_window`](OverlappingBytesBuilder::with_window).
+///
+/// # Examples
+/// ```
+/// # use compact_strings::OverlappingBytesBuilder;
+/// let mut builder = OverlappingBytesBuilder::new();
+/// builder.push(b"src/lib.rs");
+/// builder.push(b"src");
+/// builder.push(b"lib.rs");
+///
+/// assert_eq!(builder.data_len(), 10);
+///
+/// let frozen = builder.finish();
+/// assert!(frozen.iter().eq([&b"src/lib.rs"[..], b"src", b"lib.rs"]));
+/// ```
+#[must_use = "the bytestrings are discarded unless `finish` is called"]
+pub struct OverlappingBytesBuilder {
+    inner: CompactBytestrings,
+    window: usize,
+}
+
+impl OverlappingBytesBuilder {
+    /// Constructs a new, empty [`OverlappingBytesBuilder`] which searches the whole data vector.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::OverlappingBytesBuilder;
+    /// let builder = OverlappingBytesBuilder::new();
+    ///
+    /// assert!(builder.is_empty());
+    /// ```
+    pub const fn new() -> Self {
+        Self::with_window(usize::MAX)
+    }
+
+    /// Constructs a new, empty [`OverlappingBytesBuilder`] which only searches the last `window`
+    /// bytes of the data vector, so that each push takes at most time linear in `window`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::OverlappingBytesBuilder;
+    /// let mut builder = OverlappingBytesBuilder::with_window(4);
+    /// builder.push(b"One");
+    /// builder.push(b"Two");
+    /// builder.push(b"One");
+    ///
+    /// assert_eq!(builder.data_len(), 9);
+    /// ```
+    pub const fn with_window(window: usize) -> Self {
+        Self {
+            inner: CompactBytestrings::new(),
+            window,
+        }
+    }
+
+    /// Appends a bytestring, reusing the bytes it shares with the data vector.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::OverlappingBytesBuilder;
+    /// let mut builder = OverlappingBytesBuilder::new();
+    /// builder.push(b"abc");
+    /// builder.push(b"cde");
+    ///
+    /// assert_eq!(builder.data_len(), 5);
+    /// ```
+    pub fn push<S>(&mut self, bytestring: S)
+    where
+        S: AsRef<[u8]>,
+    {
+        let bytestr = bytestring.as_ref();
+        let data = &mut self.inner.data;
+        let from = data.len().saturating_sub(self.window);
+
+        let start = if let Some(pos) = search::find(&data[from..], bytestr) {
+            from + pos
+        } else {
+            // Reuse the longest end of the data vector that the bytestring starts with.
+            let shared = (1..bytestr.len().min(data.len() - from + 1))
+                .rev()
+                .find(|&len| data.ends_with(&bytestr[..len]))
+                .unwrap_or(0);
+            let start = data.len() - shared;
+            data.extend_from_slice(&bytestr[shared..]);
+            start
+        };
+
+        self.inner.meta.push(Metadata::new(start, bytestr.len()));
+    }
+
+    /// Returns the number of bytestrings pushed so far.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::OverlappingBytesBuilder;
+    /// let mut builder = OverlappingBytesBuilder::new();
+    /// builder.push(b"One");
+    /// builder.push(b"One");
+    ///
+    /// assert_eq!(builder.len(), 2);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns true if no bytestrings have been pushed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::OverlappingBytesBuilder;
+    /// let builder = OverlappingBytesBuilder::new();
+    ///
+    /// assert!(builder.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Returns the length of the data vector, which is less than the total length of the
+    /// bytestrings when they share bytes.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::OverlappingBytesBuilder;
+    /// let mut builder = OverlappingBytesBuilder::new();
+    /// builder.push(b"One");
+    /// builder.push(b"One");
+    ///
+    /// assert_eq!(builder.data_len(), 3);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn data_len(&self) -> usize {
+        self.inner.data_len()
+    }
+
+    /// Converts the bytestrings pushed so far into a [`FrozenCompactBytestrings`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::OverlappingBytesBuilder;
+    /// let mut builder = OverlappingBytesBuilder::new();
+    /// builder.push(b"One");
+    ///
+    /// assert_eq!(builder.finish().get(0), Some(b"One".as_slice()));
+    /// ```
+    #[must_use]
+    pub fn finish(self) -> FrozenCompactBytestrings {
+        self.inner.freeze()
+    }
+}
+
+/// Builds a [`FrozenCompactStrings`] in which strings share bytes with the ones pushed before
+/// them, like [`OverlappingBytesBuilder`].
+///
+/// # Examples
+/// ```
+/// # use compact_strings::OverlappingBuilder;
+/// let mut builder = OverlappingBuilder::new();
+/// builder.extend(["/usr/bin/env", "/usr/bin", "bin/env", "env/"]);
+///
+/// assert_eq!(builder.data_len(), 13);
+///
+/// let frozen = builder.finish();
+/// assert!(frozen.iter().eq(["/usr/bin/env", "/usr/bin", "bin/env", "env/"]));
+/// ```
+#[must_use = "the strings are discarded unless `finish` is called"]
+pub struct OverlappingBuilder(OverlappingBytesBuilder);
+
+impl OverlappingBuilder {
+    /// Constructs a new, empty [`OverlappingBuilder`] which searches the whole data vector.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::OverlappingBuilder;
+    /// let builder = OverlappingBuilder::new();
+    ///
+    /// assert!(builder.is_empty());
+    /// ```
+    pub const fn new() -> Self {
+        Self(OverlappingBytesBuilder::new())
+    }
+
+    /// Constructs a new, empty [`OverlappingBuilder`] which only searches the last `window` bytes
+    /// of the data vector, so that each push takes at most time linear in `window`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::OverlappingBuilder;
+    /// let mut builder = OverlappingBuilder::with_window(4);
+    /// builder.push("One");
+    /// builder.push("Two");
+    /// builder.push("One");
+    ///
+    /// assert_eq!(builder.data_len(), 9);
+    /// ```
+    pub const fn with_window(window: usize) -> Self {
+        Self(OverlappingBytesBuilder::with_window(window))
+    }
+
+    /// Appends a string, reusing the bytes it shares with the data vector.
+    ///
+    /// A string occurring in valid UTF-8 data always starts and ends on character boundaries, so
+    /// every string stays valid UTF-8.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::OverlappingBuilder;
+    /// let mut builder = OverlappingBuilder::new();
+    /// builder.push("abc");
+    /// builder.push("cde");
+    ///
+    /// assert_eq!(builder.data_len(), 5);
+    /// ```
+    #[inline]
+    pub fn push<S>(&mut self, string: S)
+    where
+        S: Deref<Target = str>,
+    {
+        self.0.push(string.as_bytes());
+    }
+
+    /// Returns the number of strings pushed so far.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::OverlappingBuilder;
+    /// let mut builder = OverlappingBuilder::new();
+    /// builder.push("One");
+    /// builder.push("One");
+    ///
+    /// assert_eq!(builder.len(), 2);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if no strings have been pushed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::OverlappingBuilder;
+    /// let builder = OverlappingBuilder::new();
+    ///
+    /// assert!(builder.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the length of the data vector, which is less than the total length of the strings
+    /// when they share bytes.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::OverlappingBuilder;
+    /// let mut builder = OverlappingBuilder::new();
+    /// builder.push("One");
+    /// builder.push("ne");
+    ///
+    /// assert_eq!(builder.data_len(), 3);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn data_len(&self) -> usize {
+        self.0.data_len()
+    }
+
+    /// Converts the strings pushed so far into a [`FrozenCompactStrings`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::OverlappingBuilder;
+    /// let mut builder = OverlappingBuilder::new();
+    /// builder.push("One");
+    ///
+    /// assert_eq!(builder.finish().get(0), Some("One"));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn finish(self) -> FrozenCompactStrings {
+        FrozenCompactStrings(self.0.finish())
+    }
+}
+
+impl Default for OverlappingBytesBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Default for OverlappingBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Extend<S> for OverlappingBytesBuilder
+where
+    S: AsRef<[u8]>,
+{
+    #[inline]
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        for s in iter {
+            self.push(s);
+        }
+    }
+}
+
+impl<S> Extend<S> for OverlappingBuilder
+where
+    S: Deref<Target = str>,
+{
+    #[inline]
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        for s in iter {
+            self.push(s);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+
+    use crate::{CompactBytestrings, CompactStrings, OverlappingBuilder};
+
+    #[test]
+    fn shared_bytes_survive_thawing() {
+        let paths: CompactStrings = (0..50)
+            .map(|i| format!("/home/user/ü{}/file{}", i % 5, i % 3))
+            .chain((0..5).map(|i| format!("ü{i}")))
+            .collect();
+
+        let mut builder = OverlappingBuilder::new();
+        builder.extend(&paths);
+        assert!(builder.data_len() < paths.data_len() / 2);
+
+        let frozen = builder.finish();
+        assert!(frozen.iter().eq(&paths));
+
+        // Thawing copies the shared bytes apart, so modifying and removing strings in place
+        // leaves the others intact.
+        let mut cmpstrs = CompactStrings::from(frozen);
+        cmpstrs.remove(1);
+        cmpstrs.compact();
+        assert!(cmpstrs
+            .iter()
+            .eq(paths.iter().take(1).chain(paths.iter().skip(2))));
+
+        let mut cmpbytes = CompactBytestrings::from(cmpstrs);
+        cmpbytes.get_mut(0).unwrap().fill(b'-');
+        assert_eq!(&cmpbytes[14], paths[0].as_bytes());
+    }
+}
@@ -0,0 +1,36 @@
+use core::fmt::{self, Display};
+
+/// The reason prebuilt buffers could not be adopted by `try_from_parts`.
+///
+/// # Examples
+/// ```
+/// # use compact_strings::{CompactBytestrings, CompactStrings, PartsError};
+/// assert_eq!(
+///     CompactBytestrings::try_from_parts(b"One".to_vec(), vec![(1, 3)]),
+///     Err(PartsError::OutOfBounds)
+/// );
+/// assert_eq!(
+///     CompactStrings::try_from_parts("é".into(), vec![(0, 1)]),
+///     Err(PartsError::InvalidUtf8)
+/// );
+/// ```
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PartsError {
+    /// An element extends past the end of the data.
+    OutOfBounds,
+    /// An element is not valid UTF-8, or does not start or end on a character boundary.
+    InvalidUtf8,
+}
+
+impl Display for PartsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PartsError::OutOfBounds => "element extends past the end of the data",
+            PartsError::InvalidUtf8 => "element is not valid UTF-8",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PartsError {}
@@ -0,0 +1,54 @@
+use alloc::vec;
+
+/// Checks that `perm` contains every index in `0..len` exactly once.
+#[track_caller]
+pub(crate) fn check_permutation(perm: &[usize], len: usize) {
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    fn len_failed(perm_len: usize, len: usize) -> ! {
+        panic!("permutation has length {perm_len} but the collection has length {len}");
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    fn index_failed(index: usize) -> ! {
+        panic!("permutation index {index} is out of bounds or repeated");
+    }
+
+    if perm.len() != len {
+        len_failed(perm.len(), len);
+    }
+
+    let mut seen = vec![false; len];
+    for &index in perm {
+        match seen.get_mut(index) {
+            Some(seen @ false) => *seen = true,
+            _ => index_failed(index),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check_permutation;
+
+    #[test]
+    fn accepts_permutation() {
+        check_permutation(&[2, 0, 1], 3);
+        check_permutation(&[], 0);
+    }
+
+    #[test]
+    #[should_panic = "permutation index 1 is out of bounds or repeated"]
+    fn rejects_repeated_index() {
+        check_permutation(&[1, 1, 0], 3);
+    }
+
+    #[test]
+    #[should_panic = "permutation index 3 is out of bounds or repeated"]
+    fn rejects_out_of_bounds_index() {
+        check_permutation(&[0, 1, 3], 3);
+    }
+}
@@ -0,0 +1,296 @@
+use alloc::{string::String, vec::Vec};
+
+use sqlx_core::{
+    decode::Decode,
+    encode::{Encode, IsNull},
+    error::BoxDynError,
+    types::Type,
+};
+use sqlx_postgres::{PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+
+use crate::{CompactBytestrings, CompactStrings};
+
+/// Reads a big-endian `i32` from the front of `buf`, advancing past it.
+fn read_i32(buf: &mut &[u8]) -> Result<i32, BoxDynError> {
+    if buf.len() < 4 {
+        return Err("unexpected end of array data".into());
+    }
+
+    let (int, rest) = buf.split_at(4);
+    *buf = rest;
+    Ok(i32::from_be_bytes([int[0], int[1], int[2], int[3]]))
+}
+
+/// Splits a one-dimensional array in the binary format into the bytes of its elements, which
+/// still borrow from `buf`.
+fn binary_elements(mut buf: &[u8]) -> Result<Vec<&[u8]>, BoxDynError> {
+    let ndim = read_i32(&mut buf)?;
+    if ndim == 0 {
+        return Ok(Vec::new());
+    }
+    if ndim != 1 {
+        return Err("only one-dimensional arrays are supported".into());
+    }
+
+    // Skip the flags and the OID of the element type.
+    read_i32(&mut buf)?;
+    read_i32(&mut buf)?;
+    let len = usize::try_from(read_i32(&mut buf)?).map_err(|_| "negative array length")?;
+    if read_i32(&mut buf)? != 1 {
+        return Err("only arrays with a lower bound of one are supported".into());
+    }
+
+    // Every element takes at least the 4 bytes of its length, which bounds the preallocation
+    // when the header claims more elements than `buf` can hold.
+    let mut elements = Vec::with_capacity(len.min(buf.len() / 4));
+    for _ in 0..len {
+        let element_len =
+            usize::try_from(read_i32(&mut buf)?).map_err(|_| "array contains a NULL element")?;
+        if buf.len() < element_len {
+            return Err("unexpected end of array data".into());
+        }
+
+        let (element, rest) = buf.split_at(element_len);
+        elements.push(element);
+        buf = rest;
+    }
+
+    Ok(elements)
+}
+
+/// Decodes a one-dimensional array in the binary format into a [`CompactBytestrings`], sizing
+/// both of its vectors exactly.
+fn decode_binary(buf: &[u8]) -> Result<CompactBytestrings, BoxDynError> {
+    let elements = binary_elements(buf)?;
+    let data_len = elements.iter().map(|element| element.len()).sum();
+    let mut cmpbytes = CompactBytestrings::with_capacity(data_len, elements.len());
+    cmpbytes.extend(elements);
+
+    Ok(cmpbytes)
+}
+
+/// Parses a one-dimensional array literal in the text format, such as `{One,"Two, Three"}`,
+/// calling `push` with each element after removing its quotes and escapes.
+fn decode_text<F>(literal: &str, mut push: F) -> Result<(), BoxDynError>
+where
+    F: FnMut(&str) -> Result<(), BoxDynError>,
+{
+    let inner = literal
+        .strip_prefix('{')
+        .and_then(|literal| literal.strip_suffix('}'))
+        .ok_or("malformed array literal")?;
+    if inner.is_empty() {
+        return Ok(());
+    }
+    if inner.starts_with('{') {
+        return Err("only one-dimensional arrays are supported".into());
+    }
+
+    let mut element = String::new();
+    let mut quoted = false;
+    let mut in_quotes = false;
+    let mut chars = inner.chars();
+    loop {
+        let at_end = loop {
+            match chars.next() {
+                Some('"') => {
+                    in_quotes = !in_quotes;
+                    quoted = true;
+                }
+                Some('\\') => element.extend(chars.next()),
+                Some(',') if !in_quotes => break false,
+                Some(ch) => element.push(ch),
+                None => break true,
+            }
+        };
+
+        // Only an unquoted NULL stands for a missing element.
+        if !quoted && element == "NULL" {
+            return Err("array contains a NULL element".into());
+        }
+        push(&element)?;
+        if at_end {
+            return Ok(());
+        }
+
+        element.clear();
+        quoted = false;
+    }
+}
+
+/// Decodes the text format of a `bytea`, `\x` followed by two hexadecimal digits per byte, into
+/// `out`.
+fn decode_hex(text: &str, out: &mut Vec<u8>) -> Result<(), BoxDynError> {
+    fn digit(byte: u8) -> Result<u8, BoxDynError> {
+        match byte {
+            b'0'..=b'9' => Ok(byte - b'0'),
+            b'a'..=b'f' => Ok(byte - b'a' + 10),
+            b'A'..=b'F' => Ok(byte - b'A' + 10),
+            _ => Err("invalid hexadecimal digit in bytea".into()),
+        }
+    }
+
+    let hex = text
+        .strip_prefix("\\x")
+        .ok_or("bytea is not in the hex format")?;
+    if hex.len() % 2 != 0 {
+        return Err("odd number of hexadecimal digits in bytea".into());
+    }
+
+    out.clear();
+    for pair in hex.as_bytes().chunks_exact(2) {
+        out.push(digit(pair[0])? << 4 | digit(pair[1])?);
+    }
+
+    Ok(())
+}
+
+/// Maps to a Postgres `text[]`, and can also be decoded from other arrays of strings such as
+/// `varchar[]`.
+impl Type<Postgres> for CompactStrings {
+    fn type_info() -> PgTypeInfo {
+        <Vec<String> as Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <Vec<String> as Type<Postgres>>::compatible(ty)
+    }
+}
+
+/// Decodes a one-dimensional array of strings straight into a [`CompactStrings`], without
+/// creating a [`String`] for each element.
+///
+/// Arrays that contain `NULL` fail to decode.
+impl<'r> Decode<'r, Postgres> for CompactStrings {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Binary => Ok(Self::try_from(decode_binary(value.as_bytes()?)?)?),
+            PgValueFormat::Text => {
+                let mut cmpstrs = Self::new();
+                decode_text(value.as_str()?, |string| {
+                    cmpstrs.push(string);
+                    Ok(())
+                })?;
+
+                Ok(cmpstrs)
+            }
+        }
+    }
+}
+
+impl Encode<'_, Postgres> for CompactStrings {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        let strings: Vec<&str> = self.iter().collect();
+        <&[&str] as Encode<Postgres>>::encode_by_ref(&strings.as_slice(), buf)
+    }
+}
+
+/// Maps to a Postgres `bytea[]`.
+impl Type<Postgres> for CompactBytestrings {
+    fn type_info() -> PgTypeInfo {
+        <Vec<Vec<u8>> as Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <Vec<Vec<u8>> as Type<Postgres>>::compatible(ty)
+    }
+}
+
+/// Decodes a one-dimensional array of byte strings straight into a [`CompactBytestrings`],
+/// without creating a [`Vec`] for each element.
+///
+/// Arrays that contain `NULL` fail to decode.
+impl<'r> Decode<'r, Postgres> for CompactBytestrings {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Binary => decode_binary(value.as_bytes()?),
+            PgValueFormat::Text => {
+                let mut cmpbytes = Self::new();
+                let mut bytes = Vec::new();
+                decode_text(value.as_str()?, |text| {
+                    decode_hex(text, &mut bytes)?;
+                    cmpbytes.push(&bytes);
+                    Ok(())
+                })?;
+
+                Ok(cmpbytes)
+            }
+        }
+    }
+}
+
+impl Encode<'_, Postgres> for CompactBytestrings {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        let bytestrings: Vec<&[u8]> = self.iter().collect();
+        <&[&[u8]] as Encode<Postgres>>::encode_by_ref(&bytestrings.as_slice(), buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec::Vec};
+
+    use super::{binary_elements, decode_hex, decode_text};
+
+    #[test]
+    fn decodes_text_literals() {
+        let mut elements: Vec<String> = Vec::new();
+        decode_text(
+            r#"{One,"Two, Three","",NULLS,"NULL","a\"b\\c"}"#,
+            |element| {
+                elements.push(element.into());
+                Ok(())
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            elements,
+            ["One", "Two, Three", "", "NULLS", "NULL", r#"a"b\c"#]
+        );
+
+        assert!(decode_text("{}", |_| unreachable!()).is_ok());
+        assert!(decode_text("{One,NULL}", |_| Ok(())).is_err());
+        assert!(decode_text("{{One}}", |_| Ok(())).is_err());
+        assert!(decode_text("One", |_| Ok(())).is_err());
+    }
+
+    #[test]
+    fn decodes_binary_arrays() {
+        let mut buf = Vec::new();
+        for int in [1, 0, 25, 2, 1, 3] {
+            buf.extend_from_slice(&i32::to_be_bytes(int));
+        }
+        buf.extend_from_slice(b"One");
+        buf.extend_from_slice(&i32::to_be_bytes(0));
+
+        assert_eq!(binary_elements(&buf).unwrap(), [&b"One"[..], b""]);
+        assert!(binary_elements(&buf[..buf.len() - 1]).is_err());
+        assert!(binary_elements(&i32::to_be_bytes(0)).unwrap().is_empty());
+
+        buf.truncate(buf.len() - 4);
+        buf.extend_from_slice(&i32::to_be_bytes(-1));
+        assert!(binary_elements(&buf).is_err());
+    }
+
+    #[test]
+    fn rejects_huge_declared_length() {
+        let mut buf = Vec::new();
+        for int in [1, 0, 25, i32::MAX, 1, 3] {
+            buf.extend_from_slice(&i32::to_be_bytes(int));
+        }
+        buf.extend_from_slice(b"One");
+
+        assert!(binary_elements(&buf).is_err());
+    }
+
+    #[test]
+    fn decodes_hex_bytea() {
+        let mut bytes = Vec::new();
+        decode_hex(r"\x4f6E65", &mut bytes).unwrap();
+        assert_eq!(bytes, b"One");
+
+        assert!(decode_hex("4f6e65", &mut bytes).is_err());
+        assert!(decode_hex(r"\x4f6", &mut bytes).is_err());
+        assert!(decode_hex(r"\x4g", &mut bytes).is_err());
+    }
+}
@@ -0,0 +1,164 @@
+use alloc::{format, string::String};
+
+use pyo3::{
+    exceptions::{PyIndexError, PyTypeError},
+    prelude::*,
+    types::PyString,
+};
+
+use crate::CompactStrings;
+
+/// A Python sequence of strings backed by a [`CompactStrings`].
+///
+/// Converting a [`CompactStrings`] into a Python object wraps it in a [`PyCompactStrings`]
+/// instead of building a `list`, so each string is only turned into a Python `str` when it is
+/// indexed or iterated over. Extension modules need to register the class with
+/// [`PyModule::add_class`](pyo3::types::PyModuleMethods::add_class) to expose it under the name
+/// `CompactStrings`.
+///
+/// # Examples
+/// ```
+/// # use compact_strings::{CompactStrings, PyCompactStrings};
+/// use pyo3::prelude::*;
+///
+/// pyo3::prepare_freethreaded_python();
+/// Python::with_gil(|py| {
+///     let list = py.eval_bound("['One', 'Two']", None, None).unwrap();
+///     let cmpstrs: CompactStrings = list.extract().unwrap();
+///
+///     let seq = Bound::new(py, PyCompactStrings::from(cmpstrs)).unwrap();
+///     assert_eq!(seq.len().unwrap(), 2);
+///     assert_eq!(seq.get_item(-1).unwrap().extract::<&str>().unwrap(), "Two");
+/// });
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "pyo3")))]
+#[pyclass(name = "CompactStrings", module = "compact_strings", sequence, frozen)]
+pub struct PyCompactStrings(CompactStrings);
+
+#[pymethods]
+impl PyCompactStrings {
+    #[new]
+    fn py_new(strings: CompactStrings) -> Self {
+        Self(strings)
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.len()
+    }
+
+    fn __getitem__(&self, index: isize) -> PyResult<&str> {
+        // Negative indices count back from the end, as with a `list`.
+        let index = if index < 0 {
+            self.0.len().checked_sub(index.unsigned_abs())
+        } else {
+            usize::try_from(index).ok()
+        };
+
+        index
+            .and_then(|index| self.0.get(index))
+            .ok_or_else(|| PyIndexError::new_err("CompactStrings index out of range"))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("CompactStrings({:?})", self.0)
+    }
+}
+
+impl PyCompactStrings {
+    /// Returns a reference to the underlying [`CompactStrings`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactStrings, PyCompactStrings};
+    /// let seq = PyCompactStrings::from(CompactStrings::from(["One", "Two"]));
+    ///
+    /// assert_eq!(seq.as_compact_strings().len(), 2);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn as_compact_strings(&self) -> &CompactStrings {
+        &self.0
+    }
+
+    /// Returns the underlying [`CompactStrings`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactStrings, PyCompactStrings};
+    /// let seq = PyCompactStrings::from(CompactStrings::from(["One", "Two"]));
+    ///
+    /// assert_eq!(seq.into_inner(), CompactStrings::from(["One", "Two"]));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn into_inner(self) -> CompactStrings {
+        self.0
+    }
+}
+
+impl From<CompactStrings> for PyCompactStrings {
+    #[inline]
+    fn from(value: CompactStrings) -> Self {
+        Self(value)
+    }
+}
+
+/// Copies the strings of any Python iterable of `str`, such as a `list[str]`, straight into the
+/// data vector, without creating a [`String`](alloc::string::String) for each of them.
+///
+/// A [`PyCompactStrings`] is extracted by copying its [`CompactStrings`]. A lone `str` is
+/// rejected rather than split into its characters.
+impl<'py> FromPyObject<'py> for CompactStrings {
+    fn extract_bound(obj: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if let Ok(seq) = obj.downcast::<PyCompactStrings>() {
+            return Ok(seq.get().0.clone());
+        }
+        if obj.is_instance_of::<PyString>() {
+            return Err(PyTypeError::new_err(
+                "Can't extract `str` to `CompactStrings`",
+            ));
+        }
+
+        let mut cmpstrs = CompactStrings::with_capacity(0, obj.len().unwrap_or(0));
+        for item in obj.iter()? {
+            cmpstrs.push(item?.downcast::<PyString>()?.to_cow()?);
+        }
+
+        Ok(cmpstrs)
+    }
+}
+
+/// Wraps the [`CompactStrings`] in a [`PyCompactStrings`], which converts its strings into
+/// Python `str`s lazily.
+impl IntoPy<PyObject> for CompactStrings {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        PyCompactStrings(self).into_py(py)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pyo3::prelude::*;
+
+    use crate::CompactStrings;
+
+    #[test]
+    fn round_trips_through_python() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let string = py.eval_bound("'One'", None, None).unwrap();
+            assert!(string.extract::<CompactStrings>().is_err());
+
+            let cmpstrs = CompactStrings::from(["One", "Two", "Three"]);
+            let seq = cmpstrs.clone().into_py(py).into_bound(py);
+            assert_eq!(seq.get_item(-3).unwrap().extract::<&str>().unwrap(), "One");
+            assert!(seq.get_item(-4).is_err());
+            assert!(seq.get_item(3).is_err());
+
+            let list = py.eval_bound("list", None, None).unwrap();
+            let list = list.call1((&seq,)).unwrap();
+            assert_eq!(list.extract::<CompactStrings>().unwrap(), cmpstrs);
+            assert_eq!(seq.extract::<CompactStrings>().unwrap(), cmpstrs);
+        });
+    }
+}
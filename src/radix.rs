@@ -0,0 +1,95 @@
+use alloc::{vec, vec::Vec};
+
+/// Sorts `items` by the bytes of their keys with a stable MSD radix sort.
+///
+/// Each pass distributes a range of items into 257 buckets by the byte at the current depth,
+/// with keys that end at that depth going first, and then sorts every bucket one byte deeper.
+/// Ranges small enough that bucketing no longer pays off are finished with a comparison sort.
+pub(crate) fn radix_sort<'a, T, F>(items: &mut [T], key: F)
+where
+    T: Clone,
+    F: Fn(&T) -> &'a [u8],
+{
+    /// Ranges at most this long are sorted by comparison instead.
+    const SMALL: usize = 32;
+
+    let mut scratch = Vec::with_capacity(items.len());
+    // Ranges still to be sorted, whose keys all share their first `depth` bytes. Keeping them on
+    // a stack rather than recursing avoids overflowing the call stack on long common prefixes.
+    let mut pending = vec![(0, items.len(), 0)];
+    while let Some((start, end, depth)) = pending.pop() {
+        let items = &mut items[start..end];
+        if items.len() <= SMALL {
+            items.sort_by(|a, b| key(a)[depth..].cmp(&key(b)[depth..]));
+            continue;
+        }
+
+        let mut counts = [0; 257];
+        for item in &*items {
+            counts[bucket(key(item), depth)] += 1;
+        }
+
+        let mut offsets = [0; 257];
+        let mut offset = 0;
+        for (bucket_offset, count) in offsets.iter_mut().zip(counts) {
+            *bucket_offset = offset;
+            offset += count;
+        }
+
+        scratch.clear();
+        scratch.extend_from_slice(items);
+        for item in scratch.drain(..) {
+            let bucket = bucket(key(&item), depth);
+            items[offsets[bucket]] = item;
+            offsets[bucket] += 1;
+        }
+
+        // Keys in the first bucket end at `depth`, so they are all equal and already in place.
+        for (bucket_end, count) in offsets.into_iter().zip(counts).skip(1) {
+            if count > 1 {
+                pending.push((start + bucket_end - count, start + bucket_end, depth + 1));
+            }
+        }
+    }
+}
+
+/// Returns the bucket of `key` at `depth`, which is 0 if the key ends there.
+#[inline]
+fn bucket(key: &[u8], depth: usize) -> usize {
+    key.get(depth).map_or(0, |&byte| usize::from(byte) + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::radix_sort;
+
+    #[test]
+    fn matches_stable_sort() {
+        // A small linear congruential generator, so that keys share prefixes of varying lengths.
+        let mut state = 0x2545_f491_u32;
+        let mut next = move || {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            state >> 16
+        };
+
+        let mut keys: Vec<(Vec<u8>, usize)> = (0..2000)
+            .map(|i| {
+                let len = (next() % 6) as usize;
+                (
+                    (0..len).map(|_| b"aab\xff"[next() as usize % 4]).collect(),
+                    i,
+                )
+            })
+            .collect();
+        keys.extend((0..100).map(|i| (alloc::vec![b'a'; 40], 2000 + i)));
+
+        let mut expected = keys.clone();
+        expected.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut items: Vec<&(Vec<u8>, usize)> = keys.iter().collect();
+        radix_sort(&mut items, |item| &item.0);
+
+        assert!(items.into_iter().eq(&expected));
+    }
+}
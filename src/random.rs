@@ -0,0 +1,328 @@
+use alloc::vec::Vec;
+
+use rand::{
+    seq::{index, SliceRandom},
+    Rng,
+};
+
+use crate::{CompactBytestrings, CompactStrings, FixedCompactBytestrings, FixedCompactStrings};
+
+/// Returns the indices of `amount` distinct elements of a collection of length `len` in random
+/// order, or of every element if there are fewer than `amount`.
+fn sample_indices<R>(rng: &mut R, len: usize, amount: usize) -> Vec<usize>
+where
+    R: Rng + ?Sized,
+{
+    index::sample(rng, len, amount.min(len)).into_vec()
+}
+
+/// Returns a random permutation of `0..len`.
+fn shuffled_indices<R>(rng: &mut R, len: usize) -> Vec<usize>
+where
+    R: Rng + ?Sized,
+{
+    let mut perm: Vec<usize> = (0..len).collect();
+    perm.shuffle(rng);
+    perm
+}
+
+impl CompactBytestrings {
+    /// Returns a random bytestring, or [`None`] if the [`CompactBytestrings`] is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let cmpbytes = CompactBytestrings::from([b"One", b"Two", b"Six"]);
+    /// let mut rng = SmallRng::seed_from_u64(0);
+    ///
+    /// let choice = cmpbytes.choose(&mut rng).unwrap();
+    /// assert!(cmpbytes.iter().any(|bytes| bytes == choice));
+    /// assert_eq!(CompactBytestrings::new().choose(&mut rng), None);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+    #[must_use]
+    pub fn choose<R>(&self, rng: &mut R) -> Option<&[u8]>
+    where
+        R: Rng + ?Sized,
+    {
+        if self.is_empty() {
+            return None;
+        }
+
+        self.get(rng.gen_range(0..self.len()))
+    }
+
+    /// Returns a new [`CompactBytestrings`] holding copies of `amount` bytestrings at distinct
+    /// random positions, in random order, or of every bytestring if there are fewer than `amount`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let cmpbytes = CompactBytestrings::from([b"One", b"Two", b"Six"]);
+    /// let mut rng = SmallRng::seed_from_u64(0);
+    ///
+    /// assert_eq!(cmpbytes.choose_multiple(&mut rng, 2).len(), 2);
+    /// assert_eq!(cmpbytes.choose_multiple(&mut rng, 5).len(), 3);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+    #[must_use]
+    pub fn choose_multiple<R>(&self, rng: &mut R, amount: usize) -> Self
+    where
+        R: Rng + ?Sized,
+    {
+        self.gather(sample_indices(rng, self.len(), amount))
+    }
+
+    /// Shuffles the bytestrings in place, rewriting the data vector once with
+    /// [`apply_permutation`](CompactBytestrings::apply_permutation).
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let mut cmpbytes = CompactBytestrings::from([b"One", b"Two", b"Six"]);
+    /// cmpbytes.shuffle(&mut SmallRng::seed_from_u64(0));
+    ///
+    /// cmpbytes.sort();
+    /// assert!(cmpbytes.iter().eq([b"One", b"Six", b"Two"]));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+    pub fn shuffle<R>(&mut self, rng: &mut R)
+    where
+        R: Rng + ?Sized,
+    {
+        self.apply_permutation(&shuffled_indices(rng, self.len()));
+    }
+}
+
+impl FixedCompactBytestrings {
+    /// Returns a random bytestring, or [`None`] if the [`FixedCompactBytestrings`] is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let cmpbytes = FixedCompactBytestrings::from([b"One", b"Two", b"Six"]);
+    /// let mut rng = SmallRng::seed_from_u64(0);
+    ///
+    /// let choice = cmpbytes.choose(&mut rng).unwrap();
+    /// assert!(cmpbytes.iter().any(|bytes| bytes == choice));
+    /// assert_eq!(FixedCompactBytestrings::new().choose(&mut rng), None);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+    #[must_use]
+    pub fn choose<R>(&self, rng: &mut R) -> Option<&[u8]>
+    where
+        R: Rng + ?Sized,
+    {
+        if self.is_empty() {
+            return None;
+        }
+
+        self.get(rng.gen_range(0..self.len()))
+    }
+
+    /// Returns a new [`FixedCompactBytestrings`] holding copies of `amount` bytestrings at
+    /// distinct random positions, in random order, or of every bytestring if there are fewer than
+    /// `amount`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let cmpbytes = FixedCompactBytestrings::from([b"One", b"Two", b"Six"]);
+    /// let mut rng = SmallRng::seed_from_u64(0);
+    ///
+    /// assert_eq!(cmpbytes.choose_multiple(&mut rng, 2).len(), 2);
+    /// assert_eq!(cmpbytes.choose_multiple(&mut rng, 5).len(), 3);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+    #[must_use]
+    pub fn choose_multiple<R>(&self, rng: &mut R, amount: usize) -> Self
+    where
+        R: Rng + ?Sized,
+    {
+        self.gather(sample_indices(rng, self.len(), amount))
+    }
+
+    /// Shuffles the bytestrings in place, rewriting the data vector once with
+    /// [`apply_permutation`](FixedCompactBytestrings::apply_permutation).
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let mut cmpbytes = FixedCompactBytestrings::from([b"One", b"Two", b"Six"]);
+    /// cmpbytes.shuffle(&mut SmallRng::seed_from_u64(0));
+    ///
+    /// cmpbytes.sort();
+    /// assert!(cmpbytes.iter().eq([b"One", b"Six", b"Two"]));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+    pub fn shuffle<R>(&mut self, rng: &mut R)
+    where
+        R: Rng + ?Sized,
+    {
+        self.apply_permutation(&shuffled_indices(rng, self.len()));
+    }
+}
+
+impl CompactStrings {
+    /// Returns a random string, or [`None`] if the [`CompactStrings`] is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let cmpstrs = CompactStrings::from(["One", "Two", "Six"]);
+    /// let mut rng = SmallRng::seed_from_u64(0);
+    ///
+    /// let choice = cmpstrs.choose(&mut rng).unwrap();
+    /// assert!(cmpstrs.iter().any(|s| s == choice));
+    /// assert_eq!(CompactStrings::new().choose(&mut rng), None);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+    #[must_use]
+    pub fn choose<R>(&self, rng: &mut R) -> Option<&str>
+    where
+        R: Rng + ?Sized,
+    {
+        if self.is_empty() {
+            return None;
+        }
+
+        self.get(rng.gen_range(0..self.len()))
+    }
+
+    /// Returns a new [`CompactStrings`] holding copies of `amount` strings at distinct random
+    /// positions, in random order, or of every string if there are fewer than `amount`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let cmpstrs = CompactStrings::from(["One", "Two", "Six"]);
+    /// let mut rng = SmallRng::seed_from_u64(0);
+    ///
+    /// assert_eq!(cmpstrs.choose_multiple(&mut rng, 2).len(), 2);
+    /// assert_eq!(cmpstrs.choose_multiple(&mut rng, 5).len(), 3);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+    #[inline]
+    #[must_use]
+    pub fn choose_multiple<R>(&self, rng: &mut R, amount: usize) -> Self
+    where
+        R: Rng + ?Sized,
+    {
+        CompactStrings(self.0.choose_multiple(rng, amount))
+    }
+
+    /// Shuffles the strings in place, rewriting the data vector once with
+    /// [`apply_permutation`](CompactStrings::apply_permutation).
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let mut cmpstrs = CompactStrings::from(["One", "Two", "Six"]);
+    /// cmpstrs.shuffle(&mut SmallRng::seed_from_u64(0));
+    ///
+    /// cmpstrs.sort();
+    /// assert!(cmpstrs.iter().eq(["One", "Six", "Two"]));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+    #[inline]
+    pub fn shuffle<R>(&mut self, rng: &mut R)
+    where
+        R: Rng + ?Sized,
+    {
+        self.0.shuffle(rng);
+    }
+}
+
+impl FixedCompactStrings {
+    /// Returns a random string, or [`None`] if the [`FixedCompactStrings`] is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let cmpstrs = FixedCompactStrings::from(["One", "Two", "Six"]);
+    /// let mut rng = SmallRng::seed_from_u64(0);
+    ///
+    /// let choice = cmpstrs.choose(&mut rng).unwrap();
+    /// assert!(cmpstrs.iter().any(|s| s == choice));
+    /// assert_eq!(FixedCompactStrings::new().choose(&mut rng), None);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+    #[must_use]
+    pub fn choose<R>(&self, rng: &mut R) -> Option<&str>
+    where
+        R: Rng + ?Sized,
+    {
+        if self.is_empty() {
+            return None;
+        }
+
+        self.get(rng.gen_range(0..self.len()))
+    }
+
+    /// Returns a new [`FixedCompactStrings`] holding copies of `amount` strings at distinct random
+    /// positions, in random order, or of every string if there are fewer than `amount`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let cmpstrs = FixedCompactStrings::from(["One", "Two", "Six"]);
+    /// let mut rng = SmallRng::seed_from_u64(0);
+    ///
+    /// assert_eq!(cmpstrs.choose_multiple(&mut rng, 2).len(), 2);
+    /// assert_eq!(cmpstrs.choose_multiple(&mut rng, 5).len(), 3);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+    #[inline]
+    #[must_use]
+    pub fn choose_multiple<R>(&self, rng: &mut R, amount: usize) -> Self
+    where
+        R: Rng + ?Sized,
+    {
+        FixedCompactStrings(self.0.choose_multiple(rng, amount))
+    }
+
+    /// Shuffles the strings in place, rewriting the data vector once with
+    /// [`apply_permutation`](FixedCompactStrings::apply_permutation).
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// use rand::{rngs::SmallRng, SeedableRng};
+    ///
+    /// let mut cmpstrs = FixedCompactStrings::from(["One", "Two", "Six"]);
+    /// cmpstrs.shuffle(&mut SmallRng::seed_from_u64(0));
+    ///
+    /// cmpstrs.sort();
+    /// assert!(cmpstrs.iter().eq(["One", "Six", "Two"]));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+    #[inline]
+    pub fn shuffle<R>(&mut self, rng: &mut R)
+    where
+        R: Rng + ?Sized,
+    {
+        self.0.shuffle(rng);
+    }
+}
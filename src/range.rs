@@ -0,0 +1,94 @@
+use core::ops::{Bound, Range, RangeBounds};
+
+/// Converts `range` into a [`Range`] over a collection of length `len`, panicking with the same
+/// messages as slice indexing if it is out of bounds.
+#[track_caller]
+pub(crate) fn to_range<R>(range: R, len: usize) -> Range<usize>
+where
+    R: RangeBounds<usize>,
+{
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    fn start_overflow() -> ! {
+        panic!("attempted to index slice from after maximum usize");
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    fn end_overflow() -> ! {
+        panic!("attempted to index slice up to maximum usize");
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    fn order_failed(start: usize, end: usize) -> ! {
+        panic!("slice index starts at {start} but ends at {end}");
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    fn end_failed(end: usize, len: usize) -> ! {
+        panic!("range end index {end} out of range for slice of length {len}");
+    }
+
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => match start.checked_add(1) {
+            Some(start) => start,
+            None => start_overflow(),
+        },
+        Bound::Unbounded => 0,
+    };
+
+    let end = match range.end_bound() {
+        Bound::Included(&end) => match end.checked_add(1) {
+            Some(end) => end,
+            None => end_overflow(),
+        },
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+
+    if start > end {
+        order_failed(start, end);
+    }
+    if end > len {
+        end_failed(end, len);
+    }
+
+    start..end
+}
+
+/// Checks that `indices` are strictly increasing and less than `len`.
+#[track_caller]
+pub(crate) fn check_sorted_indices(indices: &[usize], len: usize) {
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    fn order_failed(prev: usize, index: usize) -> ! {
+        panic!("indices should be sorted and unique (found {index} after {prev})");
+    }
+
+    #[cold]
+    #[inline(never)]
+    #[track_caller]
+    fn index_failed(index: usize, len: usize) -> ! {
+        panic!("removal index (is {index}) should be < len (is {len})");
+    }
+
+    for pair in indices.windows(2) {
+        if pair[0] >= pair[1] {
+            order_failed(pair[0], pair[1]);
+        }
+    }
+
+    if let Some(&last) = indices.last() {
+        if last >= len {
+            index_failed(last, len);
+        }
+    }
+}
@@ -0,0 +1,348 @@
+use alloc::vec::Vec;
+
+use redis::{ErrorKind, FromRedisValue, RedisResult, RedisWrite, ToRedisArgs, Value};
+
+use crate::{CompactBytestrings, CompactStrings};
+
+/// Returns the number of bytes taken by a RESP header, such as `*3\r\n`, holding `n`.
+fn header_len(n: usize) -> usize {
+    let mut digits = 1;
+    let mut n = n / 10;
+    while n != 0 {
+        digits += 1;
+        n /= 10;
+    }
+
+    digits + 3
+}
+
+/// Appends a RESP header made of `marker` followed by `n` in decimal and a line break.
+#[allow(clippy::cast_possible_truncation)]
+fn push_header(out: &mut Vec<u8>, marker: u8, n: usize) {
+    let mut buf = [0; 20];
+    let mut i = buf.len();
+    let mut n = n;
+    loop {
+        i -= 1;
+        buf[i] = b'0' + (n % 10) as u8;
+        n /= 10;
+        if n == 0 {
+            break;
+        }
+    }
+
+    out.push(marker);
+    out.extend_from_slice(&buf[i..]);
+    out.extend_from_slice(b"\r\n");
+}
+
+/// Reads a RESP header starting with `marker` from the front of `buf`, advancing past it.
+///
+/// Returns [`None`] for a negative length, which RESP uses for a null array or bulk string.
+fn read_header(buf: &mut &[u8], marker: u8) -> RedisResult<Option<usize>> {
+    let end = buf
+        .windows(2)
+        .position(|window| window == b"\r\n")
+        .ok_or((ErrorKind::ParseError, "unexpected end of RESP data"))?;
+    let (line, rest) = buf.split_at(end);
+    *buf = &rest[2..];
+
+    match line.split_first() {
+        Some((&first, b"-1")) if first == marker => Ok(None),
+        Some((&first, digits)) if first == marker && !digits.is_empty() => digits
+            .iter()
+            .try_fold(0_usize, |n, &digit| {
+                if !digit.is_ascii_digit() {
+                    return None;
+                }
+                n.checked_mul(10)?.checked_add(usize::from(digit - b'0'))
+            })
+            .map(Some)
+            .ok_or_else(|| (ErrorKind::ParseError, "invalid length in RESP header").into()),
+        _ => Err((ErrorKind::ParseError, "unexpected RESP type").into()),
+    }
+}
+
+/// Splits a RESP array of bulk strings into the bytes of its elements, which still borrow from
+/// `buf`.
+fn bulk_elements(mut buf: &[u8]) -> RedisResult<Vec<&[u8]>> {
+    let len = read_header(&mut buf, b'*')?.ok_or((ErrorKind::TypeError, "RESP array is null"))?;
+
+    let mut elements = Vec::with_capacity(len.min(buf.len() / 6));
+    for _ in 0..len {
+        let element_len = read_header(&mut buf, b'$')?
+            .ok_or((ErrorKind::TypeError, "RESP array contains a null element"))?;
+        if buf.len() < element_len + 2 {
+            return Err((ErrorKind::ParseError, "unexpected end of RESP data").into());
+        }
+
+        let (element, rest) = buf.split_at(element_len);
+        if !rest.starts_with(b"\r\n") {
+            return Err((ErrorKind::ParseError, "bulk string is longer than its header").into());
+        }
+        elements.push(element);
+        buf = &rest[2..];
+    }
+
+    if !buf.is_empty() {
+        return Err((ErrorKind::ParseError, "trailing bytes after RESP array").into());
+    }
+
+    Ok(elements)
+}
+
+/// Collects the bytes of every element of an array reply, which must all be strings.
+fn reply_elements(value: &Value) -> RedisResult<Vec<&[u8]>> {
+    let items = match value {
+        Value::Nil => return Ok(Vec::new()),
+        Value::Array(items) | Value::Set(items) => items,
+        _ => {
+            return Err((ErrorKind::TypeError, "response was not an array of strings").into());
+        }
+    };
+
+    items
+        .iter()
+        .map(|item| match item {
+            Value::BulkString(bytes) => Ok(&bytes[..]),
+            Value::SimpleString(string) => Ok(string.as_bytes()),
+            _ => Err((ErrorKind::TypeError, "response was not an array of strings").into()),
+        })
+        .collect()
+}
+
+impl CompactBytestrings {
+    /// Appends the bytestrings to `out` as a RESP array of bulk strings, copying each of them
+    /// straight out of the data vector.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([&b"SET"[..], b"key", b"value"]);
+    ///
+    /// let mut out = Vec::new();
+    /// cmpbytes.write_resp(&mut out);
+    /// assert_eq!(out, b"*3\r\n$3\r\nSET\r\n$3\r\nkey\r\n$5\r\nvalue\r\n");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "redis")))]
+    pub fn write_resp(&self, out: &mut Vec<u8>) {
+        let resp_len = header_len(self.len())
+            + self
+                .lengths()
+                .map(|len| header_len(len) + len + 2)
+                .sum::<usize>();
+        out.reserve(resp_len);
+
+        push_header(out, b'*', self.len());
+        for bytestring in self {
+            push_header(out, b'$', bytestring.len());
+            out.extend_from_slice(bytestring);
+            out.extend_from_slice(b"\r\n");
+        }
+    }
+
+    /// Encodes the bytestrings as a RESP array of bulk strings, as described in
+    /// [`write_resp`](CompactBytestrings::write_resp).
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([&b"One"[..], b""]);
+    ///
+    /// assert_eq!(cmpbytes.to_resp(), b"*2\r\n$3\r\nOne\r\n$0\r\n\r\n");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "redis")))]
+    #[must_use]
+    pub fn to_resp(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.write_resp(&mut out);
+        out
+    }
+
+    /// Decodes a RESP array of bulk strings, such as one returned by
+    /// [`to_resp`](CompactBytestrings::to_resp), sizing both of the vectors exactly.
+    ///
+    /// # Errors
+    /// Returns an error if `buf` is not exactly one RESP array of bulk strings, or if the array or
+    /// any of its elements is null.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from_resp(b"*2\r\n$3\r\nOne\r\n$3\r\nTwo\r\n").unwrap();
+    ///
+    /// assert!(cmpbytes.iter().eq([b"One", b"Two"]));
+    /// assert!(CompactBytestrings::from_resp(b"*1\r\n$-1\r\n").is_err());
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "redis")))]
+    pub fn from_resp(buf: &[u8]) -> RedisResult<Self> {
+        let elements = bulk_elements(buf)?;
+        let data_len = elements.iter().map(|element| element.len()).sum();
+        let mut cmpbytes = Self::with_capacity(data_len, elements.len());
+        cmpbytes.extend(elements);
+
+        Ok(cmpbytes)
+    }
+}
+
+impl CompactStrings {
+    /// Appends the strings to `out` as a RESP array of bulk strings, as described in
+    /// [`CompactBytestrings::write_resp`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let cmpstrs = CompactStrings::from(["GET", "key"]);
+    ///
+    /// let mut out = Vec::new();
+    /// cmpstrs.write_resp(&mut out);
+    /// assert_eq!(out, b"*2\r\n$3\r\nGET\r\n$3\r\nkey\r\n");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "redis")))]
+    #[inline]
+    pub fn write_resp(&self, out: &mut Vec<u8>) {
+        self.0.write_resp(out);
+    }
+
+    /// Encodes the strings as a RESP array of bulk strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let cmpstrs = CompactStrings::from(["One"]);
+    ///
+    /// assert_eq!(cmpstrs.to_resp(), b"*1\r\n$3\r\nOne\r\n");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "redis")))]
+    #[inline]
+    #[must_use]
+    pub fn to_resp(&self) -> Vec<u8> {
+        self.0.to_resp()
+    }
+
+    /// Decodes a RESP array of bulk strings, such as one returned by
+    /// [`to_resp`](CompactStrings::to_resp).
+    ///
+    /// # Errors
+    /// Returns an error if `buf` is not a valid array, as described in
+    /// [`CompactBytestrings::from_resp`], or if any of its elements is not valid UTF-8.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let cmpstrs = CompactStrings::from_resp(b"*2\r\n$3\r\nOne\r\n$3\r\nTwo\r\n").unwrap();
+    ///
+    /// assert!(cmpstrs.iter().eq(["One", "Two"]));
+    /// assert!(CompactStrings::from_resp(b"*1\r\n$1\r\n\xff\r\n").is_err());
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "redis")))]
+    pub fn from_resp(buf: &[u8]) -> RedisResult<Self> {
+        Ok(Self::try_from(CompactBytestrings::from_resp(buf)?)?)
+    }
+}
+
+/// Passes each bytestring as its own argument, so a whole collection can be appended to a command
+/// such as `SADD` without building a `Vec<Vec<u8>>` first.
+impl ToRedisArgs for CompactBytestrings {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        for bytestring in self {
+            out.write_arg(bytestring);
+        }
+    }
+
+    fn num_of_args(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Passes each string as its own argument.
+impl ToRedisArgs for CompactStrings {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        self.0.write_redis_args(out);
+    }
+
+    fn num_of_args(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Copies an array or set reply of strings straight into a [`CompactBytestrings`]. A nil reply
+/// becomes an empty collection.
+impl FromRedisValue for CompactBytestrings {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        let elements = reply_elements(v)?;
+        let data_len = elements.iter().map(|element| element.len()).sum();
+        let mut cmpbytes = Self::with_capacity(data_len, elements.len());
+        cmpbytes.extend(elements);
+
+        Ok(cmpbytes)
+    }
+}
+
+/// Copies an array or set reply of strings straight into a [`CompactStrings`], failing if any of
+/// them is not valid UTF-8.
+impl FromRedisValue for CompactStrings {
+    fn from_redis_value(v: &Value) -> RedisResult<Self> {
+        Ok(Self::try_from(CompactBytestrings::from_redis_value(v)?)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use redis::{FromRedisValue, ToRedisArgs, Value};
+
+    use super::{bulk_elements, header_len, push_header};
+    use crate::{CompactBytestrings, CompactStrings};
+
+    #[test]
+    fn header_len_matches_written_header() {
+        for n in [0, 9, 10, 99, 100, 12345, usize::MAX] {
+            let mut out = vec![];
+            push_header(&mut out, b'$', n);
+            assert_eq!(out.len(), header_len(n));
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_arrays() {
+        assert!(bulk_elements(b"*0\r\n").unwrap().is_empty());
+        assert!(bulk_elements(b"*-1\r\n").is_err());
+        assert!(bulk_elements(b"*1\r\n$3\r\nOne").is_err());
+        assert!(bulk_elements(b"*1\r\n$2\r\nOne\r\n").is_err());
+        assert!(bulk_elements(b"*1\r\n$3\r\nOne\r\n+OK\r\n").is_err());
+        assert!(bulk_elements(b"*1\r\n+One\r\n").is_err());
+        assert!(bulk_elements(b"*\r\n").is_err());
+        assert!(bulk_elements(b"*99999999999999999999999\r\n").is_err());
+    }
+
+    #[test]
+    fn round_trips_through_redis_values() {
+        let cmpstrs = CompactStrings::from(["One", "", "Three"]);
+        assert_eq!(cmpstrs.num_of_args(), 3);
+        assert_eq!(cmpstrs.to_redis_args(), [&b"One"[..], b"", b"Three"]);
+
+        let reply = Value::Array(
+            cmpstrs
+                .iter()
+                .map(|string| Value::BulkString(string.into()))
+                .collect(),
+        );
+        assert_eq!(CompactStrings::from_redis_value(&reply).unwrap(), cmpstrs);
+        assert!(CompactBytestrings::from_redis_value(&Value::Nil)
+            .unwrap()
+            .is_empty());
+        assert!(CompactBytestrings::from_redis_value(&Value::Int(1)).is_err());
+        assert!(
+            CompactStrings::from_redis_value(&Value::Array(vec![Value::BulkString(vec![0xff])]))
+                .is_err()
+        );
+    }
+}
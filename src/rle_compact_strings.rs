@@ -0,0 +1,419 @@
+use core::{
+    fmt::Debug,
+    iter::FusedIterator,
+    ops::{Deref, Index},
+};
+
+use alloc::vec::Vec;
+
+use crate::CompactStrings;
+
+/// A list of strings that stores each run of equal adjacent strings once, together with the
+/// index its run ends at.
+///
+/// Sorted or grouped data, where the same string tends to repeat many times in a row, takes up
+/// one string and one index per run instead of the bytes of every element. Looking a string up
+/// by its index takes *O*(log *r*) time, where *r* is the number of runs.
+///
+/// # Examples
+/// ```
+/// # use compact_strings::RleCompactStrings;
+/// let mut rle = RleCompactStrings::new();
+/// rle.push("One");
+/// rle.push("One");
+/// rle.push("Two");
+///
+/// assert_eq!(rle.get(1), Some("One"));
+/// assert_eq!(rle.get(2), Some("Two"));
+/// assert_eq!(rle.run_count(), 2);
+/// assert!(rle.iter().eq(["One", "One", "Two"]));
+/// ```
+#[derive(Clone, Default, PartialEq)]
+pub struct RleCompactStrings {
+    runs: CompactStrings,
+    ends: Vec<usize>,
+}
+
+impl CompactStrings {
+    /// Merges each run of equal adjacent strings into one, returning a [`RleCompactStrings`]
+    /// with the same strings in the same order.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let cmpstrs = CompactStrings::from(["One", "One", "One", "Two", "One"]);
+    /// let rle = cmpstrs.rle_compress();
+    ///
+    /// assert_eq!(rle.len(), 5);
+    /// assert_eq!(rle.run_count(), 3);
+    /// assert!(rle.iter().eq(&cmpstrs));
+    /// ```
+    #[must_use]
+    pub fn rle_compress(&self) -> RleCompactStrings {
+        let mut rle = RleCompactStrings::new();
+        rle.extend(self);
+        rle
+    }
+}
+
+impl RleCompactStrings {
+    /// Constructs a new, empty [`RleCompactStrings`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::RleCompactStrings;
+    /// let rle = RleCompactStrings::new();
+    ///
+    /// assert!(rle.is_empty());
+    /// ```
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            runs: CompactStrings::new(),
+            ends: Vec::new(),
+        }
+    }
+
+    /// Appends a string to the back of the [`RleCompactStrings`], extending the last run if it
+    /// holds an equal string.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::RleCompactStrings;
+    /// let mut rle = RleCompactStrings::new();
+    /// rle.push("One");
+    /// rle.push("One");
+    ///
+    /// assert_eq!(rle.len(), 2);
+    /// assert_eq!(rle.run_count(), 1);
+    /// ```
+    pub fn push<T>(&mut self, string: T)
+    where
+        T: Deref<Target = str>,
+    {
+        let len = self.len();
+        match self.ends.last_mut() {
+            Some(end) if self.runs.get(self.runs.len() - 1) == Some(&*string) => *end += 1,
+            _ => {
+                self.runs.push(string);
+                self.ends.push(len + 1);
+            }
+        }
+    }
+
+    /// Returns a reference to the string stored in the [`RleCompactStrings`] at that position.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let rle = CompactStrings::from(["One", "One", "Two"]).rle_compress();
+    ///
+    /// assert_eq!(rle.get(1), Some("One"));
+    /// assert_eq!(rle.get(3), None);
+    /// ```
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&str> {
+        if index >= self.len() {
+            return None;
+        }
+
+        self.runs.get(self.run_of(index))
+    }
+
+    /// Returns the index of the run that the string at that position belongs to.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let rle = CompactStrings::from(["One", "One", "Two"]).rle_compress();
+    ///
+    /// assert_eq!(rle.run_index(1), Some(0));
+    /// assert_eq!(rle.run_index(2), Some(1));
+    /// assert_eq!(rle.run_index(3), None);
+    /// ```
+    #[must_use]
+    pub fn run_index(&self, index: usize) -> Option<usize> {
+        if index >= self.len() {
+            return None;
+        }
+
+        Some(self.run_of(index))
+    }
+
+    /// Returns the number of strings in the [`RleCompactStrings`], also referred to as its 'length'.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let rle = CompactStrings::from(["One", "One"]).rle_compress();
+    ///
+    /// assert_eq!(rle.len(), 2);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.ends.last().copied().unwrap_or(0)
+    }
+
+    /// Returns true if the [`RleCompactStrings`] contains no strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::RleCompactStrings;
+    /// let rle = RleCompactStrings::new();
+    ///
+    /// assert!(rle.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    /// Returns the number of runs of equal adjacent strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let rle = CompactStrings::from(["One", "One", "Two", "One"]).rle_compress();
+    ///
+    /// assert_eq!(rle.run_count(), 3);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn run_count(&self) -> usize {
+        self.ends.len()
+    }
+
+    /// Returns an iterator over each run, yielding its string and the number of times it repeats.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let rle = CompactStrings::from(["One", "One", "Two"]).rle_compress();
+    ///
+    /// assert!(rle.runs().eq([("One", 2), ("Two", 1)]));
+    /// ```
+    pub fn runs(&self) -> impl Iterator<Item = (&str, usize)> + '_ {
+        let starts = core::iter::once(0).chain(self.ends.iter().copied());
+        self.runs
+            .iter()
+            .zip(self.ends.iter().zip(starts))
+            .map(|(string, (end, start))| (string, end - start))
+    }
+
+    /// Returns an iterator over the strings, repeating the string of each run.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let rle = CompactStrings::from(["One", "One", "Two"]).rle_compress();
+    ///
+    /// assert!(rle.iter().eq(["One", "One", "Two"]));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            rle: self,
+            front: 0,
+            back: self.len(),
+            front_run: 0,
+            back_run: self.ends.len().saturating_sub(1),
+        }
+    }
+
+    /// Expands the runs back into a [`CompactStrings`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let rle = CompactStrings::from(["One", "One", "Two"]).rle_compress();
+    ///
+    /// assert_eq!(rle.to_compact_strings(), CompactStrings::from(["One", "One", "Two"]));
+    /// ```
+    #[must_use]
+    pub fn to_compact_strings(&self) -> CompactStrings {
+        let data_len = self
+            .runs()
+            .map(|(string, count)| string.len() * count)
+            .sum();
+        let mut cmpstrs = CompactStrings::with_capacity(data_len, self.len());
+        cmpstrs.extend(self);
+        cmpstrs
+    }
+
+    /// Clears the [`RleCompactStrings`], removing all strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let mut rle = CompactStrings::from(["One", "Two"]).rle_compress();
+    /// rle.clear();
+    ///
+    /// assert!(rle.is_empty());
+    /// assert_eq!(rle.run_count(), 0);
+    /// ```
+    pub fn clear(&mut self) {
+        self.runs.clear();
+        self.ends.clear();
+    }
+
+    /// Finds the run holding `index`, which must be in bounds.
+    fn run_of(&self, index: usize) -> usize {
+        self.ends.partition_point(|&end| end <= index)
+    }
+}
+
+/// An iterator over the strings of a [`RleCompactStrings`], created by
+/// [`RleCompactStrings::iter`].
+pub struct Iter<'a> {
+    rle: &'a RleCompactStrings,
+    front: usize,
+    back: usize,
+    front_run: usize,
+    back_run: usize,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        while self.rle.ends[self.front_run] <= self.front {
+            self.front_run += 1;
+        }
+        self.front += 1;
+
+        self.rle.runs.get(self.front_run)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.back - self.front {
+            self.front = self.back;
+            return None;
+        }
+
+        self.front += n;
+        self.front_run = self.rle.run_of(self.front);
+        self.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Iter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.back -= 1;
+        while self.back_run > 0 && self.rle.ends[self.back_run - 1] > self.back {
+            self.back_run -= 1;
+        }
+
+        self.rle.runs.get(self.back_run)
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+impl FusedIterator for Iter<'_> {}
+
+impl Debug for RleCompactStrings {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl Index<usize> for RleCompactStrings {
+    type Output = str;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+
+impl<T> Extend<T> for RleCompactStrings
+where
+    T: Deref<Target = str>,
+{
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for s in iter {
+            self.push(s);
+        }
+    }
+}
+
+impl<T> FromIterator<T> for RleCompactStrings
+where
+    T: Deref<Target = str>,
+{
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut rle = Self::new();
+        rle.extend(iter);
+        rle
+    }
+}
+
+impl<'a> IntoIterator for &'a RleCompactStrings {
+    type Item = &'a str;
+
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl From<&CompactStrings> for RleCompactStrings {
+    #[inline]
+    fn from(value: &CompactStrings) -> Self {
+        value.rle_compress()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{format, vec::Vec};
+
+    use crate::CompactStrings;
+
+    #[test]
+    fn rle_matches_uncompressed() {
+        let cmpstrs: CompactStrings = (0..100).map(|i| format!("{}", i / 7 % 5)).collect();
+
+        let rle = cmpstrs.rle_compress();
+        assert_eq!(rle.run_count(), 15);
+        for (i, string) in cmpstrs.iter().enumerate() {
+            assert_eq!(rle.get(i), Some(string));
+        }
+        assert_eq!(rle.get(100), None);
+
+        assert!(rle.iter().eq(&cmpstrs));
+        assert!(rle.iter().rev().eq(cmpstrs.iter().rev()));
+        assert_eq!(rle.iter().nth(50), cmpstrs.get(50));
+
+        let mut iter = rle.iter();
+        let mut both = Vec::new();
+        while let (Some(front), Some(back)) = (iter.next(), iter.next_back()) {
+            both.push((front, back));
+        }
+        assert_eq!(both.len(), 50);
+        assert_eq!(both[49], (&cmpstrs[49], &cmpstrs[50]));
+
+        assert_eq!(rle.to_compact_strings(), cmpstrs);
+    }
+}
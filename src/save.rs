@@ -0,0 +1,397 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use alloc::vec::Vec;
+
+use crate::{
+    io::{invalid_data, to_meta, write_layout},
+    layout::{self, LayoutError, START_LEN},
+    CompactBytestrings, CompactStrings, FixedCompactBytestrings, FixedCompactStrings,
+};
+
+/// Bytes that every saved file starts with.
+const MAGIC: [u8; 8] = *b"CMPSTRS\0";
+
+/// Format version written by [`write_file`]. Older versions must keep being accepted by
+/// [`parse_file`].
+const VERSION: u16 = 1;
+
+/// Byte order of the integers in the layout, which is always little-endian.
+const LITTLE_ENDIAN: u8 = 0;
+
+/// Size of the magic bytes, version, endianness flag and reserved byte.
+pub(crate) const FILE_HEADER_LEN: usize = 12;
+
+/// Size of the CRC-32 that ends every saved file.
+pub(crate) const CHECKSUM_LEN: usize = 4;
+
+/// Lookup table for the CRC-32 used by zlib and PNG.
+#[allow(clippy::cast_possible_truncation)]
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+/// Feeds `bytes` into a running CRC-32, which starts at `!0` and is inverted once finished.
+fn crc32_update(crc: u32, bytes: &[u8]) -> u32 {
+    bytes.iter().fold(crc, |crc, &byte| {
+        CRC_TABLE[((crc ^ u32::from(byte)) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+/// Writes to the inner writer while keeping a CRC-32 of everything written so far.
+struct ChecksumWriter<W> {
+    inner: W,
+    crc: u32,
+}
+
+impl<W: Write> Write for ChecksumWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.crc = crc32_update(self.crc, &buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Writes `count` elements as a saved file.
+///
+/// The file is the magic bytes, the format version as a little-endian `u16`, a flag that is `0`
+/// for little-endian, a reserved zero byte, then the binary layout documented on
+/// [`CompactBytestrings::to_bytes`] and finally a little-endian CRC-32 of everything before it.
+fn write_file<'a, W, F, I>(writer: W, count: usize, iter: F) -> io::Result<()>
+where
+    W: Write,
+    F: Fn() -> I,
+    I: Iterator<Item = &'a [u8]>,
+{
+    let mut writer = ChecksumWriter {
+        inner: writer,
+        crc: !0,
+    };
+    writer.write_all(&MAGIC)?;
+    writer.write_all(&VERSION.to_le_bytes())?;
+    writer.write_all(&[LITTLE_ENDIAN, 0])?;
+    write_layout(&mut writer, count, iter)?;
+
+    let crc = !writer.crc;
+    writer.inner.write_all(&crc.to_le_bytes())?;
+    writer.inner.flush()
+}
+
+/// Checks a saved file in the format documented on [`write_file`] and returns the part holding
+/// the binary layout, which still has to be checked with [`layout::parse`].
+///
+/// The checksum is verified before the layout is read, so a corrupted file is rejected rather
+/// than producing wrong starting indices.
+pub(crate) fn parse_file(buf: &[u8]) -> Result<&[u8], LayoutError> {
+    if buf.get(..MAGIC.len()) != Some(&MAGIC[..]) {
+        return Err(LayoutError::InvalidHeader);
+    }
+    if buf.len() < FILE_HEADER_LEN + CHECKSUM_LEN {
+        return Err(LayoutError::LengthMismatch);
+    }
+
+    // Every version so far shares the same layout.
+    let version = u16::from_le_bytes([buf[8], buf[9]]);
+    if version == 0 || version > VERSION {
+        return Err(LayoutError::UnsupportedVersion);
+    }
+    if buf[10] != LITTLE_ENDIAN || buf[11] != 0 {
+        return Err(LayoutError::InvalidHeader);
+    }
+
+    let (body, checksum) = buf.split_at(buf.len() - CHECKSUM_LEN);
+    let checksum = [checksum[0], checksum[1], checksum[2], checksum[3]];
+    if !crc32_update(!0, body) != u32::from_le_bytes(checksum) {
+        return Err(LayoutError::ChecksumMismatch);
+    }
+
+    Ok(&body[FILE_HEADER_LEN..])
+}
+
+/// Reads and checks the saved file at `path`, returning its starting indices and data.
+///
+/// The data is returned in the buffer the file was read into, with everything around it removed,
+/// so it is never copied into a second buffer.
+fn read_file(path: &Path) -> io::Result<(Vec<usize>, Vec<u8>)> {
+    let mut buf = fs::read(path)?;
+    let (starts, data) = parse_file(&buf)
+        .and_then(layout::parse)
+        .map_err(invalid_data)?;
+
+    let starts = (0..starts.len() / START_LEN)
+        .map(|index| layout::read_start(starts, index))
+        .collect();
+    let data_start = buf.len() - CHECKSUM_LEN - data.len();
+
+    buf.truncate(buf.len() - CHECKSUM_LEN);
+    buf.drain(..data_start);
+    Ok((starts, buf))
+}
+
+impl CompactBytestrings {
+    /// Writes the bytestrings to a file at `path`, replacing it if it exists, which can be read
+    /// back with [`CompactBytestrings::load`].
+    ///
+    /// The file holds the binary layout documented on [`CompactBytestrings::to_bytes`], preceded
+    /// by magic bytes, a format version and the byte order and followed by a CRC-32 checksum, so
+    /// files written by older versions of this crate or on other platforms keep loading, and
+    /// corrupted ones are rejected. Files written from strings can also be memory-mapped with
+    /// `CompactStrings::open_mmap` when the `mmap` feature is enabled.
+    ///
+    /// # Errors
+    /// Returns any error encountered while creating or writing the file.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([b"One", b"Two"]);
+    ///
+    /// let path = std::env::temp_dir().join("compact_bytestrings_save.bin");
+    /// cmpbytes.save(&path).unwrap();
+    /// assert_eq!(CompactBytestrings::load(&path).unwrap(), cmpbytes);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let file = BufWriter::new(File::create(path)?);
+        write_file(file, self.len(), || self.iter())
+    }
+
+    /// Reads a file written by [`CompactBytestrings::save`].
+    ///
+    /// # Errors
+    /// Returns any error encountered while reading the file, and an error of kind
+    /// [`io::ErrorKind::InvalidData`] wrapping a [`LayoutError`] if the file was not written
+    /// by `save`, was written by a newer version of this crate, or is corrupted.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let path = std::env::temp_dir().join("compact_bytestrings_load.bin");
+    /// CompactBytestrings::from([b"One", b"Two"]).save(&path).unwrap();
+    ///
+    /// let cmpbytes = CompactBytestrings::load(&path).unwrap();
+    /// assert_eq!(cmpbytes.get(1), Some(b"Two".as_slice()));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let (starts, data) = read_file(path.as_ref())?;
+        let meta = to_meta(&starts, data.len());
+        Ok(Self { data, meta })
+    }
+}
+
+impl CompactStrings {
+    /// Writes the strings to a file at `path`, as described in
+    /// [`CompactBytestrings::save`].
+    ///
+    /// # Errors
+    /// Returns any error encountered while creating or writing the file.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let cmpstrs = CompactStrings::from(["One", "Two"]);
+    ///
+    /// let path = std::env::temp_dir().join("compact_strings_save.bin");
+    /// cmpstrs.save(&path).unwrap();
+    /// assert_eq!(CompactStrings::load(&path).unwrap(), cmpstrs);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.0.save(path)
+    }
+
+    /// Reads a file written by [`CompactStrings::save`].
+    ///
+    /// # Errors
+    /// Returns any error encountered while reading the file, and an error of kind
+    /// [`io::ErrorKind::InvalidData`] if the file is not valid, as described in
+    /// [`CompactBytestrings::load`], or if any string is not valid UTF-8.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let path = std::env::temp_dir().join("compact_strings_load.bin");
+    /// CompactStrings::from(["One", "Two"]).save(&path).unwrap();
+    ///
+    /// let cmpstrs = CompactStrings::load(&path).unwrap();
+    /// assert_eq!(cmpstrs.get(1), Some("Two"));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let (starts, data) = read_file(path.as_ref())?;
+        layout::check_utf8(starts.iter().copied(), &data).map_err(invalid_data)?;
+        let meta = to_meta(&starts, data.len());
+        Ok(Self(CompactBytestrings { data, meta }))
+    }
+}
+
+impl FixedCompactBytestrings {
+    /// Writes the bytestrings to a file at `path`, as described in
+    /// [`CompactBytestrings::save`].
+    ///
+    /// # Errors
+    /// Returns any error encountered while creating or writing the file.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let cmpbytes = FixedCompactBytestrings::from([b"One", b"Two"]);
+    ///
+    /// let path = std::env::temp_dir().join("fixed_compact_bytestrings_save.bin");
+    /// cmpbytes.save(&path).unwrap();
+    /// assert_eq!(FixedCompactBytestrings::load(&path).unwrap(), cmpbytes);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let file = BufWriter::new(File::create(path)?);
+        write_file(file, self.len(), || self.iter())
+    }
+
+    /// Reads a file written by [`FixedCompactBytestrings::save`] or
+    /// [`CompactBytestrings::save`].
+    ///
+    /// # Errors
+    /// Returns any error encountered while reading the file, and an error of kind
+    /// [`io::ErrorKind::InvalidData`] if the file is not valid, as described in
+    /// [`CompactBytestrings::load`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactBytestrings;
+    /// let path = std::env::temp_dir().join("fixed_compact_bytestrings_load.bin");
+    /// FixedCompactBytestrings::from([b"One", b"Two"]).save(&path).unwrap();
+    ///
+    /// let cmpbytes = FixedCompactBytestrings::load(&path).unwrap();
+    /// assert_eq!(cmpbytes.get(1), Some(b"Two".as_slice()));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let (starts, data) = read_file(path.as_ref())?;
+        Ok(Self { data, starts })
+    }
+}
+
+impl FixedCompactStrings {
+    /// Writes the strings to a file at `path`, as described in
+    /// [`CompactBytestrings::save`].
+    ///
+    /// # Errors
+    /// Returns any error encountered while creating or writing the file.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let cmpstrs = FixedCompactStrings::from(["One", "Two"]);
+    ///
+    /// let path = std::env::temp_dir().join("fixed_compact_strings_save.bin");
+    /// cmpstrs.save(&path).unwrap();
+    /// assert_eq!(FixedCompactStrings::load(&path).unwrap(), cmpstrs);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.0.save(path)
+    }
+
+    /// Reads a file written by [`FixedCompactStrings::save`] or
+    /// [`CompactStrings::save`].
+    ///
+    /// # Errors
+    /// Returns any error encountered while reading the file, and an error of kind
+    /// [`io::ErrorKind::InvalidData`] if the file is not valid, as described in
+    /// [`CompactBytestrings::load`], or if any string is not valid UTF-8.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::FixedCompactStrings;
+    /// let path = std::env::temp_dir().join("fixed_compact_strings_load.bin");
+    /// FixedCompactStrings::from(["One", "Two"]).save(&path).unwrap();
+    ///
+    /// let cmpstrs = FixedCompactStrings::load(&path).unwrap();
+    /// assert_eq!(cmpstrs.get(1), Some("Two"));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let (starts, data) = read_file(path.as_ref())?;
+        layout::check_utf8(starts.iter().copied(), &data).map_err(invalid_data)?;
+        Ok(Self(FixedCompactBytestrings { data, starts }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::{crc32_update, parse_file, read_file, write_file};
+    use crate::{CompactBytestrings, LayoutError};
+
+    fn save(cmpbytes: &CompactBytestrings) -> Vec<u8> {
+        let mut buf = Vec::new();
+        write_file(&mut buf, cmpbytes.len(), || cmpbytes.iter()).unwrap();
+        buf
+    }
+
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(!crc32_update(!0, b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn saved_file_wraps_the_binary_layout() {
+        let mut cmpbytes = CompactBytestrings::from([b"One", b"Two", b"Six"]);
+        cmpbytes.swap_ignore(0);
+        let buf = save(&cmpbytes);
+
+        assert_eq!(&buf[..12], b"CMPSTRS\0\x01\x00\x00\x00");
+        assert_eq!(parse_file(&buf), Ok(&cmpbytes.to_bytes()[..]));
+
+        let path = std::env::temp_dir().join("compact_bytestrings_saved_file_layout.bin");
+        std::fs::write(&path, &buf).unwrap();
+        let (starts, data) = read_file(&path).unwrap();
+        assert_eq!(starts, [0, 3]);
+        assert_eq!(data, b"SixTwo");
+    }
+
+    #[test]
+    fn saved_file_rejects_corruption() {
+        let buf = save(&CompactBytestrings::from([b"One", b"Two"]));
+
+        for i in 0..buf.len() {
+            let mut corrupted = buf.clone();
+            corrupted[i] ^= 0x10;
+            assert!(parse_file(&corrupted).is_err());
+        }
+
+        let mut future = buf.clone();
+        future[8] = 2;
+        assert_eq!(parse_file(&future), Err(LayoutError::UnsupportedVersion));
+        assert_eq!(
+            parse_file(&buf[..buf.len() - 1]),
+            Err(LayoutError::ChecksumMismatch)
+        );
+        assert_eq!(parse_file(&buf[1..]), Err(LayoutError::InvalidHeader));
+    }
+}
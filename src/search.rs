@@ -0,0 +1,158 @@
+use core::ops::Range;
+
+/// Returns an iterator over the indices of the elements that fully contain `needle`, in order.
+///
+/// The data vector is searched as a whole, and each match is mapped back to an element with
+/// `element_at`, which returns the index and byte range of the only element that could contain
+/// the given position. Matches that span element boundaries or fall into bytes no element refers
+/// to are skipped. An empty `needle` is contained by all `len` elements.
+pub(crate) fn matches<'a, F>(
+    len: usize,
+    data: &'a [u8],
+    needle: &'a [u8],
+    element_at: F,
+) -> impl Iterator<Item = usize> + 'a
+where
+    F: FnMut(usize) -> Option<(usize, Range<usize>)> + 'a,
+{
+    let (all, from) = if needle.is_empty() {
+        (0..len, data.len() + 1)
+    } else {
+        (0..0, 0)
+    };
+
+    all.chain(Matches {
+        data,
+        finder: Finder::new(needle),
+        needle_len: needle.len(),
+        from,
+        element_at,
+    })
+}
+
+struct Matches<'a, F> {
+    data: &'a [u8],
+    finder: Finder<'a>,
+    needle_len: usize,
+    from: usize,
+    element_at: F,
+}
+
+impl<F> Iterator for Matches<'_, F>
+where
+    F: FnMut(usize) -> Option<(usize, Range<usize>)>,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.from <= self.data.len() {
+            let pos = self.from + self.finder.find(&self.data[self.from..])?;
+            match (self.element_at)(pos) {
+                Some((index, range)) if pos + self.needle_len <= range.end => {
+                    // Later elements start at or after the end of this one.
+                    self.from = range.end.max(pos + 1);
+                    return Some(index);
+                }
+                _ => self.from = pos + 1,
+            }
+        }
+
+        None
+    }
+}
+
+/// Returns true if `haystack` contains `needle`.
+pub(crate) fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    find(haystack, needle).is_some()
+}
+
+/// Returns the index of the first occurrence of `needle` in `haystack`.
+pub(crate) fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    Finder::new(needle).find(haystack)
+}
+
+/// Returns the ranges of the pieces of `haystack` separated by `delimiter`, like
+/// [`slice::split`].
+pub(crate) fn split(haystack: &[u8], delimiter: u8) -> impl Iterator<Item = Range<usize>> + '_ {
+    let mut from = Some(0);
+    core::iter::from_fn(move || {
+        let start = from?;
+        let end = find_byte(delimiter, &haystack[start..]).map_or(haystack.len(), |i| start + i);
+        from = (end < haystack.len()).then(|| end + 1);
+
+        Some(start..end)
+    })
+}
+
+/// Returns the index of the first occurrence of `byte` in `haystack`.
+#[cfg(feature = "memchr")]
+pub(crate) fn find_byte(byte: u8, haystack: &[u8]) -> Option<usize> {
+    memchr::memchr(byte, haystack)
+}
+
+/// Returns the index of the first occurrence of `byte` in `haystack`.
+#[cfg(not(feature = "memchr"))]
+pub(crate) fn find_byte(byte: u8, haystack: &[u8]) -> Option<usize> {
+    haystack.iter().position(|&b| b == byte)
+}
+
+#[cfg(feature = "memchr")]
+struct Finder<'n>(memchr::memmem::Finder<'n>);
+
+#[cfg(feature = "memchr")]
+impl<'n> Finder<'n> {
+    fn new(needle: &'n [u8]) -> Self {
+        Self(memchr::memmem::Finder::new(needle))
+    }
+
+    fn find(&self, haystack: &[u8]) -> Option<usize> {
+        self.0.find(haystack)
+    }
+}
+
+#[cfg(not(feature = "memchr"))]
+struct Finder<'n>(&'n [u8]);
+
+#[cfg(not(feature = "memchr"))]
+impl<'n> Finder<'n> {
+    fn new(needle: &'n [u8]) -> Self {
+        Self(needle)
+    }
+
+    fn find(&self, haystack: &[u8]) -> Option<usize> {
+        if self.0.is_empty() {
+            return Some(0);
+        }
+        haystack.windows(self.0.len()).position(|w| w == self.0)
+    }
+}
+
+/// Returns the number of leading indices in `0..len` for which `pred` holds, assuming it holds
+/// for every index before the first one it does not hold for.
+pub(crate) fn partition_point<P>(len: usize, mut pred: P) -> usize
+where
+    P: FnMut(usize) -> bool,
+{
+    let (mut lo, mut hi) = (0, len);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if pred(mid) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+
+    lo
+}
+
+/// Returns the range of indices of the elements starting with `prefix`, assuming the elements
+/// returned by `get` are sorted.
+pub(crate) fn sorted_prefix_range<'a, G>(len: usize, prefix: &[u8], get: G) -> Range<usize>
+where
+    G: Fn(usize) -> &'a [u8],
+{
+    let start = partition_point(len, |i| get(i) < prefix);
+    let end = start + partition_point(len - start, |i| get(start + i).starts_with(prefix));
+    start..end
+}
@@ -0,0 +1,32 @@
+use core::fmt::{self, Display};
+
+/// The reason an element could not be replaced in place by `try_set`.
+///
+/// # Examples
+/// ```
+/// # use compact_strings::{CompactStrings, SetError};
+/// let mut cmpstrs = CompactStrings::from(["One", "Two"]);
+///
+/// assert_eq!(cmpstrs.try_set(0, "Three"), Err(SetError::LengthMismatch));
+/// assert_eq!(cmpstrs.try_set(2, "Six"), Err(SetError::OutOfBounds));
+/// ```
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SetError {
+    /// The index is out of bounds.
+    OutOfBounds,
+    /// The replacement is shorter or longer than the element, in bytes.
+    LengthMismatch,
+}
+
+impl Display for SetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SetError::OutOfBounds => "index is out of bounds",
+            SetError::LengthMismatch => "replacement length does not match the element length",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SetError {}
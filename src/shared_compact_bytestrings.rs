@@ -0,0 +1,232 @@
+use core::{fmt::Debug, ops::Index};
+
+use alloc::{sync::Arc, vec::Vec};
+
+use crate::{compact_bytestrings::Iter, metadata::Metadata, CompactBytestrings};
+
+/// An immutable list of bytestrings whose buffers are shared between clones.
+///
+/// The data and meta buffers of a [`CompactBytestrings`] are moved behind [`Arc`]s, so cloning a
+/// [`SharedCompactBytestrings`] only increments reference counts instead of copying the bytes,
+/// and clones can be sent to other threads.
+///
+/// # Examples
+/// ```
+/// # use compact_strings::{CompactBytestrings, SharedCompactBytestrings};
+/// let mut cmpbytes = CompactBytestrings::new();
+/// cmpbytes.push(b"One");
+/// cmpbytes.push(b"Two");
+///
+/// let shared = cmpbytes.shared();
+/// let cloned = shared.clone();
+///
+/// assert_eq!(cloned.get(0), Some(b"One".as_slice()));
+/// assert_eq!(cloned.get(1), Some(b"Two".as_slice()));
+/// assert_eq!(shared, cloned);
+/// ```
+#[derive(Clone)]
+pub struct SharedCompactBytestrings {
+    pub(crate) data: Arc<[u8]>,
+    pub(crate) meta: Arc<[Metadata]>,
+}
+
+impl SharedCompactBytestrings {
+    /// Returns a reference to the bytestring stored in the [`SharedCompactBytestrings`] at that position.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::SharedCompactBytestrings;
+    /// let shared = SharedCompactBytestrings::from([b"One", b"Two"]);
+    ///
+    /// assert_eq!(shared.get(0), Some(b"One".as_slice()));
+    /// assert_eq!(shared.get(1), Some(b"Two".as_slice()));
+    /// assert_eq!(shared.get(2), None);
+    /// ```
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&[u8]> {
+        let (start, len) = self.meta.get(index)?.as_tuple();
+        if cfg!(feature = "no_unsafe") {
+            self.data.get(start..start + len)
+        } else {
+            unsafe { Some(self.data.get_unchecked(start..start + len)) }
+        }
+    }
+
+    /// Returns a reference to the bytestring stored in the [`SharedCompactBytestrings`] at that position, without
+    /// doing bounds checking.
+    ///
+    /// # Safety
+    /// Calling this method with an out-of-bounds index is undefined behavior even if the resulting reference is not used.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::SharedCompactBytestrings;
+    /// let shared = SharedCompactBytestrings::from([b"One", b"Two"]);
+    ///
+    /// unsafe {
+    ///     assert_eq!(shared.get_unchecked(0), b"One".as_slice());
+    ///     assert_eq!(shared.get_unchecked(1), b"Two".as_slice());
+    /// }
+    /// ```
+    #[must_use]
+    #[cfg(not(feature = "no_unsafe"))]
+    pub unsafe fn get_unchecked(&self, index: usize) -> &[u8] {
+        let (start, len) = self.meta.get_unchecked(index).as_tuple();
+        self.data.get_unchecked(start..start + len)
+    }
+
+    /// Returns the number of bytestrings in the [`SharedCompactBytestrings`], also referred to as its 'length'.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::SharedCompactBytestrings;
+    /// let shared = SharedCompactBytestrings::from([b"One", b"Two"]);
+    ///
+    /// assert_eq!(shared.len(), 2);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.meta.len()
+    }
+
+    /// Returns true if the [`SharedCompactBytestrings`] contains no bytestrings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactBytestrings, SharedCompactBytestrings};
+    /// let shared = SharedCompactBytestrings::from(CompactBytestrings::new());
+    ///
+    /// assert!(shared.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Copies each bytestring into an owned `Vec<u8>`, returning them in a vector.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::SharedCompactBytestrings;
+    /// let shared = SharedCompactBytestrings::from([b"One", b"Two"]);
+    ///
+    /// assert_eq!(shared.to_vec(), [b"One".to_vec(), b"Two".to_vec()]);
+    /// ```
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<Vec<u8>> {
+        self.iter().map(<[u8]>::to_vec).collect()
+    }
+
+    /// Returns an iterator over the bytestrings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::SharedCompactBytestrings;
+    /// let shared = SharedCompactBytestrings::from([b"One", b"Two"]);
+    /// let mut iterator = shared.iter();
+    ///
+    /// assert_eq!(iterator.next(), Some(b"One".as_slice()));
+    /// assert_eq!(iterator.next(), Some(b"Two".as_slice()));
+    /// assert_eq!(iterator.next(), None);
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter::from_parts(&self.data, &self.meta)
+    }
+}
+
+impl CompactBytestrings {
+    /// Copies the [`CompactBytestrings`] into a [`SharedCompactBytestrings`], which can be cloned
+    /// without copying its bytes.
+    ///
+    /// Use [`SharedCompactBytestrings::from`] instead if the [`CompactBytestrings`] is no longer
+    /// needed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactBytestrings;
+    /// let mut cmpbytes = CompactBytestrings::new();
+    /// cmpbytes.push(b"One");
+    ///
+    /// let shared = cmpbytes.shared();
+    /// cmpbytes.push(b"Two");
+    ///
+    /// assert_eq!(shared.len(), 1);
+    /// assert_eq!(shared.get(0), Some(b"One".as_slice()));
+    /// ```
+    #[must_use]
+    pub fn shared(&self) -> SharedCompactBytestrings {
+        SharedCompactBytestrings {
+            data: Arc::from(self.data.as_slice()),
+            meta: Arc::from(self.meta.as_slice()),
+        }
+    }
+}
+
+impl PartialEq for SharedCompactBytestrings {
+    fn eq(&self, other: &Self) -> bool {
+        if Arc::ptr_eq(&self.data, &other.data) && Arc::ptr_eq(&self.meta, &other.meta) {
+            return true;
+        }
+
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl Debug for SharedCompactBytestrings {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl Index<usize> for SharedCompactBytestrings {
+    type Output = [u8];
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+
+impl<'a> IntoIterator for &'a SharedCompactBytestrings {
+    type Item = &'a [u8];
+
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<S> FromIterator<S> for SharedCompactBytestrings
+where
+    S: AsRef<[u8]>,
+{
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        CompactBytestrings::from_iter(iter).into()
+    }
+}
+
+impl<S, I> From<I> for SharedCompactBytestrings
+where
+    S: AsRef<[u8]>,
+    I: IntoIterator<Item = S>,
+{
+    #[inline]
+    fn from(value: I) -> Self {
+        FromIterator::from_iter(value)
+    }
+}
+
+impl From<CompactBytestrings> for SharedCompactBytestrings {
+    fn from(value: CompactBytestrings) -> Self {
+        Self {
+            data: value.data.into(),
+            meta: value.meta.into(),
+        }
+    }
+}
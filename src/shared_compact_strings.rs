@@ -0,0 +1,246 @@
+use core::{
+    fmt::Debug,
+    ops::{Deref, Index},
+};
+
+use alloc::{string::String, vec::Vec};
+
+use crate::{compact_strings::Iter, CompactStrings, SharedCompactBytestrings};
+
+/// An immutable list of strings whose buffers are shared between clones.
+///
+/// The data and meta buffers of a [`CompactStrings`] are moved behind [`Arc`]s, so cloning a
+/// [`SharedCompactStrings`] only increments reference counts instead of copying the bytes,
+/// and clones can be sent to other threads.
+///
+/// [`Arc`]: alloc::sync::Arc
+///
+/// # Examples
+/// ```
+/// # use compact_strings::{CompactStrings, SharedCompactStrings};
+/// let mut cmpstrs = CompactStrings::new();
+/// cmpstrs.push("One");
+/// cmpstrs.push("Two");
+///
+/// let shared = cmpstrs.shared();
+/// let cloned = shared.clone();
+///
+/// assert_eq!(cloned.get(0), Some("One"));
+/// assert_eq!(cloned.get(1), Some("Two"));
+/// assert_eq!(shared, cloned);
+/// ```
+#[repr(transparent)]
+#[derive(Clone, PartialEq)]
+pub struct SharedCompactStrings(pub(crate) SharedCompactBytestrings);
+
+impl SharedCompactStrings {
+    /// Returns a reference to the string stored in the [`SharedCompactStrings`] at that position.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::SharedCompactStrings;
+    /// let shared = SharedCompactStrings::from(["One", "Two"]);
+    ///
+    /// assert_eq!(shared.get(0), Some("One"));
+    /// assert_eq!(shared.get(1), Some("Two"));
+    /// assert_eq!(shared.get(2), None);
+    /// ```
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.0.get(index).and_then(Iter::from_utf8_maybe_checked)
+    }
+
+    /// Returns a reference to the string stored in the [`SharedCompactStrings`] at that position, without
+    /// doing bounds checking.
+    ///
+    /// # Safety
+    /// Calling this method with an out-of-bounds index is undefined behavior even if the resulting reference is not used.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::SharedCompactStrings;
+    /// let shared = SharedCompactStrings::from(["One", "Two"]);
+    ///
+    /// unsafe {
+    ///     assert_eq!(shared.get_unchecked(0), "One");
+    ///     assert_eq!(shared.get_unchecked(1), "Two");
+    /// }
+    /// ```
+    #[must_use]
+    #[cfg(not(feature = "no_unsafe"))]
+    pub unsafe fn get_unchecked(&self, index: usize) -> &str {
+        core::str::from_utf8_unchecked(self.0.get_unchecked(index))
+    }
+
+    /// Returns the number of strings in the [`SharedCompactStrings`], also referred to as its 'length'.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::SharedCompactStrings;
+    /// let shared = SharedCompactStrings::from(["One", "Two"]);
+    ///
+    /// assert_eq!(shared.len(), 2);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if the [`SharedCompactStrings`] contains no strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactStrings, SharedCompactStrings};
+    /// let shared = SharedCompactStrings::from(CompactStrings::new());
+    ///
+    /// assert!(shared.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Copies each string into an owned [`String`], returning them in a vector.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::SharedCompactStrings;
+    /// let shared = SharedCompactStrings::from(["One", "Two"]);
+    ///
+    /// assert_eq!(shared.to_vec(), ["One", "Two"]);
+    /// ```
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<String> {
+        self.iter().map(String::from).collect()
+    }
+
+    /// Returns an iterator over the strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::SharedCompactStrings;
+    /// let shared = SharedCompactStrings::from(["One", "Two"]);
+    /// let mut iterator = shared.iter();
+    ///
+    /// assert_eq!(iterator.next(), Some("One"));
+    /// assert_eq!(iterator.next(), Some("Two"));
+    /// assert_eq!(iterator.next(), None);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter::from_bytes(self.0.iter())
+    }
+}
+
+impl CompactStrings {
+    /// Copies the [`CompactStrings`] into a [`SharedCompactStrings`], which can be cloned
+    /// without copying its bytes.
+    ///
+    /// Use [`SharedCompactStrings::from`] instead if the [`CompactStrings`] is no longer
+    /// needed.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let mut cmpstrs = CompactStrings::new();
+    /// cmpstrs.push("One");
+    ///
+    /// let shared = cmpstrs.shared();
+    /// cmpstrs.push("Two");
+    ///
+    /// assert_eq!(shared.len(), 1);
+    /// assert_eq!(shared.get(0), Some("One"));
+    /// ```
+    #[must_use]
+    pub fn shared(&self) -> SharedCompactStrings {
+        SharedCompactStrings(self.0.shared())
+    }
+}
+
+impl Debug for SharedCompactStrings {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl Index<usize> for SharedCompactStrings {
+    type Output = str;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+
+impl<'a> IntoIterator for &'a SharedCompactStrings {
+    type Item = &'a str;
+
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<S> FromIterator<S> for SharedCompactStrings
+where
+    S: Deref<Target = str>,
+{
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        CompactStrings::from_iter(iter).into()
+    }
+}
+
+impl<S, I> From<I> for SharedCompactStrings
+where
+    S: Deref<Target = str>,
+    I: IntoIterator<Item = S>,
+{
+    #[inline]
+    fn from(value: I) -> Self {
+        FromIterator::from_iter(value)
+    }
+}
+
+impl From<CompactStrings> for SharedCompactStrings {
+    fn from(value: CompactStrings) -> Self {
+        Self(value.0.into())
+    }
+}
+
+impl From<SharedCompactStrings> for SharedCompactBytestrings {
+    fn from(value: SharedCompactStrings) -> Self {
+        value.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CompactStrings, SharedCompactStrings};
+
+    #[test]
+    fn clones_share_buffers() {
+        let mut cmpstrs = CompactStrings::new();
+        cmpstrs.push("One");
+        cmpstrs.push("Two");
+
+        let shared = SharedCompactStrings::from(cmpstrs);
+        let cloned = shared.clone();
+
+        assert!(core::ptr::eq(
+            shared.get(1).unwrap().as_ptr(),
+            cloned.get(1).unwrap().as_ptr(),
+        ));
+    }
+
+    #[test]
+    fn send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<SharedCompactStrings>();
+    }
+}
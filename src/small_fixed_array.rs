@@ -0,0 +1,332 @@
+use core::{fmt::Debug, ops::Index};
+
+use alloc::{string::String, vec::Vec};
+
+use small_fixed_array::{FixedArray, ValidLength};
+
+use crate::{
+    compact_bytestrings, compact_strings, metadata::Metadata, FrozenCompactBytestrings,
+    FrozenCompactStrings,
+};
+
+/// An immutable list of bytestrings backed by [`FixedArray`]s.
+///
+/// This is a [`FrozenCompactBytestrings`] whose buffers store their lengths as `LenT` rather than
+/// `usize`, for crates that have standardized on [`small_fixed_array`]. Converting between the two
+/// never copies the bytes.
+///
+/// # Examples
+/// ```
+/// # use compact_strings::{CompactBytestrings, FixedArrayCompactBytestrings};
+/// let mut cmpbytes = CompactBytestrings::new();
+/// cmpbytes.push(b"One");
+/// cmpbytes.push(b"Two");
+///
+/// let fixed = FixedArrayCompactBytestrings::<u32>::try_from(cmpbytes.freeze()).unwrap();
+///
+/// assert_eq!(fixed.get(0), Some(b"One".as_slice()));
+/// assert_eq!(fixed.get(1), Some(b"Two".as_slice()));
+/// assert_eq!(fixed.get(2), None);
+/// ```
+#[derive(Clone)]
+pub struct FixedArrayCompactBytestrings<LenT: ValidLength = u32> {
+    pub(crate) data: FixedArray<u8, LenT>,
+    pub(crate) meta: FixedArray<Metadata, LenT>,
+}
+
+impl<LenT: ValidLength> FixedArrayCompactBytestrings<LenT> {
+    /// Returns a reference to the bytestring stored in the [`FixedArrayCompactBytestrings`] at that position.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactBytestrings, FixedArrayCompactBytestrings};
+    /// let cmpbytes = CompactBytestrings::from([b"One", b"Two"]);
+    /// let fixed = FixedArrayCompactBytestrings::<u32>::try_from(cmpbytes.freeze()).unwrap();
+    ///
+    /// assert_eq!(fixed.get(0), Some(b"One".as_slice()));
+    /// assert_eq!(fixed.get(2), None);
+    /// ```
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&[u8]> {
+        let (start, len) = self.meta.as_slice().get(index)?.as_tuple();
+        let data = self.data.as_slice();
+        if cfg!(feature = "no_unsafe") {
+            data.get(start..start + len)
+        } else {
+            unsafe { Some(data.get_unchecked(start..start + len)) }
+        }
+    }
+
+    /// Returns the number of bytestrings in the [`FixedArrayCompactBytestrings`], also referred to as its 'length'.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactBytestrings, FixedArrayCompactBytestrings};
+    /// let cmpbytes = CompactBytestrings::from([b"One", b"Two"]);
+    /// let fixed = FixedArrayCompactBytestrings::<u32>::try_from(cmpbytes.freeze()).unwrap();
+    ///
+    /// assert_eq!(fixed.len(), 2);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.meta.len().to_usize()
+    }
+
+    /// Returns true if the [`FixedArrayCompactBytestrings`] contains no bytestrings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactBytestrings, FixedArrayCompactBytestrings};
+    /// let fixed = FixedArrayCompactBytestrings::<u32>::try_from(CompactBytestrings::new().freeze()).unwrap();
+    ///
+    /// assert!(fixed.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.meta.is_empty()
+    }
+
+    /// Copies each bytestring into an owned `Vec<u8>`, returning them in a vector.
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<Vec<u8>> {
+        self.iter().map(<[u8]>::to_vec).collect()
+    }
+
+    /// Returns an iterator over the bytestrings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactBytestrings, FixedArrayCompactBytestrings};
+    /// let cmpbytes = CompactBytestrings::from([b"One", b"Two"]);
+    /// let fixed = FixedArrayCompactBytestrings::<u32>::try_from(cmpbytes.freeze()).unwrap();
+    ///
+    /// assert!(fixed.iter().eq([b"One", b"Two"]));
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> compact_bytestrings::Iter<'_> {
+        compact_bytestrings::Iter::from_parts(self.data.as_slice(), self.meta.as_slice())
+    }
+}
+
+impl<LenT: ValidLength> TryFrom<FrozenCompactBytestrings> for FixedArrayCompactBytestrings<LenT> {
+    /// The [`FrozenCompactBytestrings`] is handed back if either of its buffers is longer than
+    /// `LenT` can represent.
+    type Error = FrozenCompactBytestrings;
+
+    fn try_from(value: FrozenCompactBytestrings) -> Result<Self, Self::Error> {
+        if LenT::from_usize(value.data.len()).is_none()
+            || LenT::from_usize(value.meta.len()).is_none()
+        {
+            return Err(value);
+        }
+
+        match (
+            FixedArray::try_from(value.data),
+            FixedArray::try_from(value.meta),
+        ) {
+            (Ok(data), Ok(meta)) => Ok(Self { data, meta }),
+            _ => unreachable!("lengths were checked to fit"),
+        }
+    }
+}
+
+impl<LenT: ValidLength> From<FixedArrayCompactBytestrings<LenT>> for FrozenCompactBytestrings {
+    fn from(value: FixedArrayCompactBytestrings<LenT>) -> Self {
+        Self {
+            data: value.data.into_boxed_slice(),
+            meta: value.meta.into_boxed_slice(),
+        }
+    }
+}
+
+impl<LenT: ValidLength> PartialEq for FixedArrayCompactBytestrings<LenT> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<LenT: ValidLength> Debug for FixedArrayCompactBytestrings<LenT> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<LenT: ValidLength> Index<usize> for FixedArrayCompactBytestrings<LenT> {
+    type Output = [u8];
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+
+impl<'a, LenT: ValidLength> IntoIterator for &'a FixedArrayCompactBytestrings<LenT> {
+    type Item = &'a [u8];
+
+    type IntoIter = compact_bytestrings::Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An immutable list of strings backed by [`FixedArray`]s.
+///
+/// This is a [`FrozenCompactStrings`] whose buffers store their lengths as `LenT` rather than
+/// `usize`, for crates that have standardized on [`small_fixed_array`]. Converting between the two
+/// never copies the bytes.
+///
+/// # Examples
+/// ```
+/// # use compact_strings::{CompactStrings, FixedArrayCompactStrings};
+/// let mut cmpstrs = CompactStrings::new();
+/// cmpstrs.push("One");
+/// cmpstrs.push("Two");
+///
+/// let fixed = FixedArrayCompactStrings::<u32>::try_from(cmpstrs.freeze()).unwrap();
+///
+/// assert_eq!(fixed.get(0), Some("One"));
+/// assert_eq!(fixed.get(1), Some("Two"));
+/// assert_eq!(fixed.get(2), None);
+/// ```
+#[repr(transparent)]
+#[derive(Clone, PartialEq)]
+pub struct FixedArrayCompactStrings<LenT: ValidLength = u32>(
+    pub(crate) FixedArrayCompactBytestrings<LenT>,
+);
+
+impl<LenT: ValidLength> FixedArrayCompactStrings<LenT> {
+    /// Returns a reference to the string stored in the [`FixedArrayCompactStrings`] at that position.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactStrings, FixedArrayCompactStrings};
+    /// let cmpstrs = CompactStrings::from(["One", "Two"]);
+    /// let fixed = FixedArrayCompactStrings::<u32>::try_from(cmpstrs.freeze()).unwrap();
+    ///
+    /// assert_eq!(fixed.get(0), Some("One"));
+    /// assert_eq!(fixed.get(2), None);
+    /// ```
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.0
+            .get(index)
+            .and_then(compact_strings::Iter::from_utf8_maybe_checked)
+    }
+
+    /// Returns the number of strings in the [`FixedArrayCompactStrings`], also referred to as its 'length'.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactStrings, FixedArrayCompactStrings};
+    /// let cmpstrs = CompactStrings::from(["One", "Two"]);
+    /// let fixed = FixedArrayCompactStrings::<u32>::try_from(cmpstrs.freeze()).unwrap();
+    ///
+    /// assert_eq!(fixed.len(), 2);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if the [`FixedArrayCompactStrings`] contains no strings.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Copies each string into an owned [`String`], returning them in a vector.
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<String> {
+        self.iter().map(String::from).collect()
+    }
+
+    /// Returns an iterator over the strings.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::{CompactStrings, FixedArrayCompactStrings};
+    /// let cmpstrs = CompactStrings::from(["One", "Two"]);
+    /// let fixed = FixedArrayCompactStrings::<u32>::try_from(cmpstrs.freeze()).unwrap();
+    ///
+    /// assert!(fixed.iter().eq(["One", "Two"]));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn iter(&self) -> compact_strings::Iter<'_> {
+        compact_strings::Iter::from_bytes(self.0.iter())
+    }
+}
+
+impl<LenT: ValidLength> TryFrom<FrozenCompactStrings> for FixedArrayCompactStrings<LenT> {
+    /// The [`FrozenCompactStrings`] is handed back if either of its buffers is longer than
+    /// `LenT` can represent.
+    type Error = FrozenCompactStrings;
+
+    fn try_from(value: FrozenCompactStrings) -> Result<Self, Self::Error> {
+        FixedArrayCompactBytestrings::try_from(value.0)
+            .map(Self)
+            .map_err(FrozenCompactStrings)
+    }
+}
+
+impl<LenT: ValidLength> From<FixedArrayCompactStrings<LenT>> for FrozenCompactStrings {
+    fn from(value: FixedArrayCompactStrings<LenT>) -> Self {
+        Self(value.0.into())
+    }
+}
+
+impl<LenT: ValidLength> From<FixedArrayCompactStrings<LenT>>
+    for FixedArrayCompactBytestrings<LenT>
+{
+    fn from(value: FixedArrayCompactStrings<LenT>) -> Self {
+        value.0
+    }
+}
+
+impl<LenT: ValidLength> Debug for FixedArrayCompactStrings<LenT> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<LenT: ValidLength> Index<usize> for FixedArrayCompactStrings<LenT> {
+    type Output = str;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+
+impl<'a, LenT: ValidLength> IntoIterator for &'a FixedArrayCompactStrings<LenT> {
+    type Item = &'a str;
+
+    type IntoIter = compact_strings::Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CompactStrings, FixedArrayCompactStrings, FrozenCompactStrings};
+
+    #[test]
+    fn rejects_oversized() {
+        let cmpstrs = CompactStrings::from(["a"; 256]);
+
+        let frozen = FixedArrayCompactStrings::<u8>::try_from(cmpstrs.freeze()).unwrap_err();
+        assert_eq!(frozen.len(), 256);
+
+        let fixed = FixedArrayCompactStrings::<u16>::try_from(frozen).unwrap();
+        assert_eq!(FrozenCompactStrings::from(fixed).len(), 256);
+    }
+}
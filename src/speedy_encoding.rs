@@ -0,0 +1,138 @@
+use alloc::vec::Vec;
+
+use speedy::{Context, Readable, Reader, Writable, Writer};
+
+use crate::{layout, metadata::Metadata, CompactBytestrings, CompactStrings};
+
+/// Converts a length or starting index into the `u32` that speedy stores it as.
+fn to_u32<E: From<speedy::Error>>(n: usize) -> Result<u32, E> {
+    u32::try_from(n).map_err(|_| speedy::Error::custom("length does not fit in a u32").into())
+}
+
+/// Writes the bytes of every bytestring back to back, followed by the starting index of each of
+/// them, so reading the data back takes one copy rather than one per bytestring.
+///
+/// The layout is the number of bytestrings and the number of bytes as `u32`s, then the bytes, then
+/// one `u32` starting index per bytestring.
+impl<C: Context> Writable<C> for CompactBytestrings {
+    fn write_to<T: ?Sized + Writer<C>>(&self, writer: &mut T) -> Result<(), C::Error> {
+        writer.write_u32(to_u32(self.len())?)?;
+        writer.write_u32(to_u32(self.lengths().sum())?)?;
+
+        for bytestring in self {
+            writer.write_bytes(bytestring)?;
+        }
+
+        let mut start = 0;
+        for len in self.lengths() {
+            writer.write_u32(to_u32(start)?)?;
+            start += len;
+        }
+
+        Ok(())
+    }
+
+    fn bytes_needed(&self) -> Result<usize, C::Error> {
+        Ok(8 + self.lengths().sum::<usize>() + 4 * self.len())
+    }
+}
+
+impl<'a, C: Context> Readable<'a, C> for CompactBytestrings {
+    fn read_from<R: Reader<'a, C>>(reader: &mut R) -> Result<Self, C::Error> {
+        let len = reader.read_u32()? as usize;
+        let data_len = reader.read_u32()? as usize;
+        let data: Vec<u8> = reader.read_vec(data_len)?;
+        let starts: Vec<u32> = reader.read_vec(len)?;
+
+        layout::check_starts(
+            starts.iter().map(|&start| usize::try_from(start).ok()),
+            data.len(),
+        )
+        .map_err(speedy::Error::custom)?;
+
+        let meta = starts
+            .iter()
+            .enumerate()
+            .map(|(i, &start)| {
+                let start = start as usize;
+                let end = starts.get(i + 1).map_or(data.len(), |&end| end as usize);
+                Metadata::new(start, end - start)
+            })
+            .collect();
+
+        Ok(Self { data, meta })
+    }
+
+    #[inline]
+    fn minimum_bytes_needed() -> usize {
+        8
+    }
+}
+
+/// Uses the same layout as [`CompactBytestrings`].
+impl<C: Context> Writable<C> for CompactStrings {
+    #[inline]
+    fn write_to<T: ?Sized + Writer<C>>(&self, writer: &mut T) -> Result<(), C::Error> {
+        Writable::<C>::write_to(&self.0, writer)
+    }
+
+    #[inline]
+    fn bytes_needed(&self) -> Result<usize, C::Error> {
+        Writable::<C>::bytes_needed(&self.0)
+    }
+}
+
+/// Fails if the bytes are not valid UTF-8 or a string does not start on a character boundary.
+impl<'a, C: Context> Readable<'a, C> for CompactStrings {
+    fn read_from<R: Reader<'a, C>>(reader: &mut R) -> Result<Self, C::Error> {
+        let cmpbytes = <CompactBytestrings as Readable<'a, C>>::read_from(reader)?;
+        layout::check_utf8(cmpbytes.meta.iter().map(|meta| meta.start), &cmpbytes.data)
+            .map_err(speedy::Error::custom)?;
+
+        Ok(CompactStrings(cmpbytes))
+    }
+
+    #[inline]
+    fn minimum_bytes_needed() -> usize {
+        <CompactBytestrings as Readable<'a, C>>::minimum_bytes_needed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use speedy::{LittleEndian, Readable, Writable};
+
+    use crate::{CompactBytestrings, CompactStrings};
+
+    #[test]
+    fn speedy_round_trip_after_swap_ignore() {
+        let mut cmpbytes = CompactBytestrings::from([b"One", b"Two", b"Six"]);
+        cmpbytes.swap_ignore(0);
+
+        let buf = cmpbytes.write_to_vec_with_ctx(LittleEndian {}).unwrap();
+        assert_eq!(
+            buf.len(),
+            Writable::<LittleEndian>::bytes_needed(&cmpbytes).unwrap()
+        );
+        assert_eq!(&buf[8..14], b"SixTwo");
+        assert_eq!(
+            CompactBytestrings::read_from_buffer_with_ctx(LittleEndian {}, &buf).unwrap(),
+            cmpbytes
+        );
+
+        let cmpstrs = CompactStrings::from(["One", "Two"]);
+        let mut buf = cmpstrs.write_to_vec_with_ctx(LittleEndian {}).unwrap();
+        assert_eq!(
+            CompactStrings::read_from_buffer_with_ctx(LittleEndian {}, &buf).unwrap(),
+            cmpstrs
+        );
+
+        buf[10] = 0xff;
+        assert!(CompactStrings::read_from_buffer_with_ctx(LittleEndian {}, &buf).is_err());
+        assert!(CompactBytestrings::read_from_buffer_with_ctx(LittleEndian {}, &buf).is_ok());
+
+        // The start of the second bytestring now points past the end of the bytes.
+        buf[18] = 7;
+        assert!(CompactBytestrings::read_from_buffer_with_ctx(LittleEndian {}, &buf).is_err());
+    }
+}
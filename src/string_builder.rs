@@ -0,0 +1,160 @@
+use core::fmt;
+
+use crate::{compact_strings::Iter, metadata::Metadata, CompactStrings};
+
+/// Appends a string to a [`CompactStrings`] piece by piece.
+///
+/// Pieces are written directly into the data vector, and the string is only added to the
+/// [`CompactStrings`] once [`finish`] is called. If the builder is dropped without finishing,
+/// everything written through it is discarded.
+///
+/// [`finish`]: StringBuilder::finish
+///
+/// # Examples
+/// ```
+/// # use compact_strings::CompactStrings;
+/// use core::fmt::Write;
+///
+/// let mut cmpstrs = CompactStrings::new();
+///
+/// let mut builder = cmpstrs.start_string();
+/// builder.push_str("One");
+/// write!(builder, "-{}", 2).unwrap();
+/// builder.finish();
+///
+/// let mut builder = cmpstrs.start_string();
+/// builder.push_str("Discarded");
+/// drop(builder);
+///
+/// assert_eq!(cmpstrs.len(), 1);
+/// assert_eq!(cmpstrs.get(0), Some("One-2"));
+/// ```
+#[must_use = "the string is discarded unless `finish` is called"]
+pub struct StringBuilder<'a> {
+    inner: &'a mut CompactStrings,
+    start: usize,
+    finished: bool,
+}
+
+impl CompactStrings {
+    /// Starts appending a string to the back of the [`CompactStrings`] piece by piece, returning a
+    /// [`StringBuilder`] that commits it on [`StringBuilder::finish`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let mut cmpstrs = CompactStrings::new();
+    ///
+    /// let mut builder = cmpstrs.start_string();
+    /// for chunk in ["On", "e"] {
+    ///     builder.push_str(chunk);
+    /// }
+    /// builder.finish();
+    ///
+    /// assert_eq!(cmpstrs.get(0), Some("One"));
+    /// ```
+    pub fn start_string(&mut self) -> StringBuilder<'_> {
+        let start = self.0.data.len();
+        StringBuilder {
+            inner: self,
+            start,
+            finished: false,
+        }
+    }
+}
+
+impl StringBuilder<'_> {
+    /// Appends a piece to the string being built.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let mut cmpstrs = CompactStrings::new();
+    ///
+    /// let mut builder = cmpstrs.start_string();
+    /// builder.push_str("One");
+    /// builder.push_str("Two");
+    ///
+    /// assert_eq!(builder.as_str(), "OneTwo");
+    /// ```
+    #[inline]
+    pub fn push_str(&mut self, string: &str) {
+        self.inner.0.data.extend_from_slice(string.as_bytes());
+    }
+
+    /// Returns the string built so far.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let mut cmpstrs = CompactStrings::new();
+    ///
+    /// let mut builder = cmpstrs.start_string();
+    /// assert_eq!(builder.as_str(), "");
+    ///
+    /// builder.push_str("One");
+    /// assert_eq!(builder.as_str(), "One");
+    /// ```
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        Iter::from_utf8_maybe_checked(&self.inner.0.data[self.start..]).unwrap_or_default()
+    }
+
+    /// Adds the string built so far to the back of the [`CompactStrings`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_strings::CompactStrings;
+    /// let mut cmpstrs = CompactStrings::new();
+    /// cmpstrs.push("One");
+    ///
+    /// let mut builder = cmpstrs.start_string();
+    /// builder.push_str("Two");
+    /// builder.finish();
+    ///
+    /// assert_eq!(cmpstrs.get(1), Some("Two"));
+    /// ```
+    pub fn finish(mut self) {
+        let len = self.inner.0.data.len() - self.start;
+        self.inner.0.meta.push(Metadata::new(self.start, len));
+        self.finished = true;
+    }
+}
+
+impl fmt::Write for StringBuilder<'_> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+}
+
+impl Drop for StringBuilder<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.inner.0.data.truncate(self.start);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::fmt::Write;
+
+    use crate::CompactStrings;
+
+    #[test]
+    fn rolls_back_on_drop() {
+        let mut cmpstrs = CompactStrings::new();
+        cmpstrs.push("One");
+
+        {
+            let mut builder = cmpstrs.start_string();
+            write!(builder, "Two").unwrap();
+        }
+        cmpstrs.push("Three");
+
+        assert!(cmpstrs.iter().eq(["One", "Three"]));
+        assert_eq!(cmpstrs.0.data, b"OneThree");
+    }
+}
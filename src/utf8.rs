@@ -0,0 +1,96 @@
+use core::str::Utf8Error;
+
+/// Checks that each of `elements`, which are slices of `data` starting or ending only at
+/// `boundaries`, is valid UTF-8.
+///
+/// The whole of `data` is validated in one pass first, after which it is enough to check that no
+/// boundary splits a character. Only if that fails are the elements validated one by one, both
+/// because unreferenced bytes may be invalid and to report the error relative to the element.
+#[cfg(feature = "alloc")]
+pub(crate) fn validate<'a, B, E>(data: &[u8], boundaries: B, elements: E) -> Result<(), Utf8Error>
+where
+    B: IntoIterator<Item = usize>,
+    E: IntoIterator<Item = &'a [u8]>,
+{
+    if from_utf8(data).is_ok()
+        && boundaries
+            .into_iter()
+            .all(|index| is_char_boundary(data, index))
+    {
+        return Ok(());
+    }
+
+    for bytes in elements {
+        from_utf8(bytes)?;
+    }
+
+    Ok(())
+}
+
+/// Converts `bytes` to a string slice like [`core::str::from_utf8`], validating it with SIMD
+/// instructions when the `simdutf8` feature is enabled.
+///
+/// Invalid input is validated again by [`core::str::from_utf8`] to report where the error is.
+#[inline]
+pub(crate) fn from_utf8(bytes: &[u8]) -> Result<&str, Utf8Error> {
+    #[cfg(feature = "simdutf8")]
+    if let Ok(str) = simdutf8::basic::from_utf8(bytes) {
+        return Ok(str);
+    }
+
+    core::str::from_utf8(bytes)
+}
+
+/// Converts `bytes`, which are known to be valid UTF-8, to a string slice without checking them,
+/// or checks them anyway when the `no_unsafe` feature is enabled.
+#[inline]
+pub(crate) fn from_utf8_maybe_checked(bytes: &[u8]) -> Option<&str> {
+    if cfg!(feature = "no_unsafe") {
+        from_utf8(bytes).ok()
+    } else {
+        Some(unsafe { core::str::from_utf8_unchecked(bytes) })
+    }
+}
+
+/// Returns true if `index` is at the start or end of a character in the valid UTF-8 `data`.
+#[cfg(feature = "alloc")]
+#[inline]
+fn is_char_boundary(data: &[u8], index: usize) -> bool {
+    // Continuation bytes are the only ones of the form 0b10xx_xxxx.
+    data.get(index).map_or(true, |&byte| byte & 0xc0 != 0x80)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CompactBytestrings, CompactStrings, FixedCompactBytestrings, FixedCompactStrings};
+
+    #[test]
+    fn split_characters_are_rejected() {
+        let cmpbytes = FixedCompactBytestrings::from([&"é".as_bytes()[..1], &"é".as_bytes()[1..]]);
+        assert!(FixedCompactStrings::try_from(cmpbytes).is_err());
+
+        let mut cmpbytes = CompactBytestrings::from(["aé".as_bytes()]);
+        cmpbytes.push([]);
+        cmpbytes.meta[0].len = 2;
+        cmpbytes.meta[1].start = 2;
+        cmpbytes.meta[1].len = 1;
+        let err = CompactStrings::try_from(cmpbytes).unwrap_err();
+        assert_eq!(err.valid_up_to(), 1);
+    }
+
+    #[test]
+    fn errors_match_core() {
+        for bytes in [&b"One"[..], b"", b"Tw\xffo", b"\xe2\x82", "é".as_bytes()] {
+            assert_eq!(super::from_utf8(bytes), core::str::from_utf8(bytes));
+        }
+    }
+
+    #[test]
+    fn unreferenced_bytes_are_ignored() {
+        let mut cmpbytes = CompactBytestrings::from([&b"\xff"[..], b"One"]);
+        cmpbytes.ignore(0);
+
+        let cmpstrs = CompactStrings::try_from(cmpbytes).unwrap();
+        assert!(cmpstrs.iter().eq(["One"]));
+    }
+}
@@ -0,0 +1,184 @@
+use alloc::vec::Vec;
+
+use js_sys::{wasm_bindgen::JsValue, Array, Uint32Array, Uint8Array};
+
+use crate::{layout, metadata::Metadata, CompactBytestrings, CompactStrings, LayoutError};
+
+impl CompactBytestrings {
+    /// Copies the bytestrings into a pair of JavaScript typed arrays: one holding the bytes of
+    /// every bytestring back to back, and one holding the starting index of each bytestring in it.
+    ///
+    /// The bytestring at `i` ends at the start of the bytestring at `i + 1`, or at the end of the
+    /// bytes for the last bytestring, as in [`to_bytes`](CompactBytestrings::to_bytes). Moving a
+    /// table across the JavaScript boundary this way takes two copies rather than one per
+    /// bytestring.
+    ///
+    /// # Panics
+    /// Panics if the bytestrings hold more than [`u32::MAX`] bytes in total.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use compact_strings::CompactBytestrings;
+    /// let cmpbytes = CompactBytestrings::from([b"One", b"Two"]);
+    ///
+    /// let (data, starts) = cmpbytes.to_typed_arrays();
+    /// assert_eq!(data.to_vec(), b"OneTwo");
+    /// assert_eq!(starts.to_vec(), [0, 3]);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "wasm")))]
+    #[track_caller]
+    #[must_use]
+    pub fn to_typed_arrays(&self) -> (Uint8Array, Uint32Array) {
+        #[cold]
+        #[inline(never)]
+        #[track_caller]
+        fn too_long(len: usize) -> ! {
+            panic!("data length (is {len}) should fit in a u32");
+        }
+
+        let mut data = Vec::with_capacity(self.lengths().sum());
+        let mut starts = Vec::with_capacity(self.len());
+        for bytestring in self {
+            match u32::try_from(data.len()) {
+                Ok(start) => starts.push(start),
+                Err(_) => too_long(data.len()),
+            }
+            data.extend_from_slice(bytestring);
+        }
+        if u32::try_from(data.len()).is_err() {
+            too_long(data.len());
+        }
+
+        (Uint8Array::from(&data[..]), Uint32Array::from(&starts[..]))
+    }
+
+    /// Copies bytestrings out of a pair of JavaScript typed arrays in the form returned by
+    /// [`to_typed_arrays`](CompactBytestrings::to_typed_arrays).
+    ///
+    /// # Errors
+    /// Returns [`LayoutError::InvalidStart`] if the starting indices do not begin at zero, are out
+    /// of order, or point past the end of the bytes.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use compact_strings::CompactBytestrings;
+    /// let (data, starts) = CompactBytestrings::from([b"One", b"Two"]).to_typed_arrays();
+    ///
+    /// let cmpbytes = CompactBytestrings::from_typed_arrays(&data, &starts).unwrap();
+    /// assert!(cmpbytes.iter().eq([b"One", b"Two"]));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "wasm")))]
+    pub fn from_typed_arrays(data: &Uint8Array, starts: &Uint32Array) -> Result<Self, LayoutError> {
+        let data = data.to_vec();
+        let starts = starts.to_vec();
+        layout::check_starts(
+            starts.iter().map(|&start| usize::try_from(start).ok()),
+            data.len(),
+        )?;
+
+        let meta = starts
+            .iter()
+            .enumerate()
+            .map(|(i, &start)| {
+                let start = start as usize;
+                let end = starts.get(i + 1).map_or(data.len(), |&end| end as usize);
+                Metadata::new(start, end - start)
+            })
+            .collect();
+
+        Ok(Self { data, meta })
+    }
+}
+
+impl CompactStrings {
+    /// Copies every element of a JavaScript array of strings into a new [`CompactStrings`].
+    ///
+    /// # Errors
+    /// Returns the first element of `array` that is not a string.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use compact_strings::CompactStrings;
+    /// use js_sys::{wasm_bindgen::JsValue, Array};
+    ///
+    /// let array = Array::of2(&JsValue::from_str("One"), &JsValue::from_str("Two"));
+    ///
+    /// let cmpstrs = CompactStrings::from_js_array(&array).unwrap();
+    /// assert!(cmpstrs.iter().eq(["One", "Two"]));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "wasm")))]
+    pub fn from_js_array(array: &Array) -> Result<Self, JsValue> {
+        let mut cmpstrs = Self::with_capacity(0, array.length() as usize);
+        for value in array.iter() {
+            match value.as_string() {
+                Some(string) => cmpstrs.push(string),
+                None => return Err(value),
+            }
+        }
+
+        Ok(cmpstrs)
+    }
+
+    /// Copies each string into a JavaScript string, returning them in a JavaScript array.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use compact_strings::CompactStrings;
+    /// let array = CompactStrings::from(["One", "Two"]).to_js_array();
+    ///
+    /// assert_eq!(array.get(1).as_string().as_deref(), Some("Two"));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "wasm")))]
+    #[must_use]
+    pub fn to_js_array(&self) -> Array {
+        self.iter().map(JsValue::from_str).collect()
+    }
+
+    /// Copies the strings into a pair of JavaScript typed arrays, as described in
+    /// [`CompactBytestrings::to_typed_arrays`].
+    ///
+    /// On the JavaScript side, each string can be read back with a `TextDecoder` over a
+    /// `subarray` of the bytes.
+    ///
+    /// # Panics
+    /// Panics if the strings hold more than [`u32::MAX`] bytes in total.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use compact_strings::CompactStrings;
+    /// let (data, starts) = CompactStrings::from(["One", "Two"]).to_typed_arrays();
+    ///
+    /// assert_eq!(data.to_vec(), b"OneTwo");
+    /// assert_eq!(starts.to_vec(), [0, 3]);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "wasm")))]
+    #[inline]
+    #[track_caller]
+    #[must_use]
+    pub fn to_typed_arrays(&self) -> (Uint8Array, Uint32Array) {
+        self.0.to_typed_arrays()
+    }
+
+    /// Copies strings out of a pair of JavaScript typed arrays in the form returned by
+    /// [`to_typed_arrays`](CompactStrings::to_typed_arrays).
+    ///
+    /// # Errors
+    /// Returns [`LayoutError::InvalidStart`] if the starting indices are invalid, as described in
+    /// [`CompactBytestrings::from_typed_arrays`], or [`LayoutError::InvalidUtf8`] if the bytes are
+    /// not valid UTF-8 or a string does not start on a character boundary.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use compact_strings::CompactStrings;
+    /// let (data, starts) = CompactStrings::from(["One", "Two"]).to_typed_arrays();
+    ///
+    /// let cmpstrs = CompactStrings::from_typed_arrays(&data, &starts).unwrap();
+    /// assert!(cmpstrs.iter().eq(["One", "Two"]));
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "wasm")))]
+    pub fn from_typed_arrays(data: &Uint8Array, starts: &Uint32Array) -> Result<Self, LayoutError> {
+        let cmpbytes = CompactBytestrings::from_typed_arrays(data, starts)?;
+        layout::check_utf8(cmpbytes.meta.iter().map(|meta| meta.start), &cmpbytes.data)?;
+        Ok(CompactStrings(cmpbytes))
+    }
+}